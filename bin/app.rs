@@ -10,9 +10,14 @@ pub struct App {
     pub quiet: bool,
 
     /// Include definition file. Glob syntax is supported.
+    /// A directory is loaded as a root of partitions.
     #[clap(long)]
     pub def: Option<Vec<String>>,
 
+    /// Directory of plugin partitions, imported using the `//` prefix.
+    #[clap(long)]
+    pub plugins: Option<String>,
+
     /// Files to process. If none specified, a REPL instance is started.
     pub files: Vec<String>,
 }
@@ -35,6 +40,7 @@ mod tests {
             App {
                 quiet: false,
                 def: None,
+                plugins: None,
                 files: vec![]
             }
         );
@@ -45,6 +51,7 @@ mod tests {
             App {
                 quiet: true,
                 def: None,
+                plugins: None,
                 files: vec![]
             }
         );
@@ -55,6 +62,7 @@ mod tests {
             App {
                 quiet: false,
                 def: Some(vec!["foo".to_string()]),
+                plugins: None,
                 files: vec![]
             }
         );
@@ -65,6 +73,7 @@ mod tests {
             App {
                 quiet: false,
                 def: Some(vec!["foo".to_string(), "bar".to_string()]),
+                plugins: None,
                 files: vec![]
             }
         );
@@ -75,6 +84,7 @@ mod tests {
             App {
                 quiet: false,
                 def: None,
+                plugins: None,
                 files: vec!["foo".to_string()]
             }
         );
@@ -85,6 +95,7 @@ mod tests {
             App {
                 quiet: false,
                 def: None,
+                plugins: None,
                 files: vec!["foo".to_string(), "bar".to_string()]
             }
         );
@@ -95,8 +106,20 @@ mod tests {
             App {
                 quiet: false,
                 def: Some(vec!["foo".to_string(), "bar".to_string()]),
+                plugins: None,
                 files: vec!["foo".to_string(), "bar".to_string()]
             }
         );
+
+        let a = App::try_parse_from("ogma --def lib --plugins plugins".split(' ')).unwrap();
+        assert_eq!(
+            a,
+            App {
+                quiet: false,
+                def: Some(vec!["lib".to_string()]),
+                plugins: Some("plugins".to_string()),
+                files: vec![]
+            }
+        );
     }
 }
@@ -7,18 +7,24 @@ use crate::app::App;
 use clap::Parser;
 
 fn main() {
-    let App { quiet, def, files } = App::parse();
+    let App {
+        quiet,
+        def,
+        plugins,
+        files,
+    } = App::parse();
 
     let defs = def
         .map(|x| expand_globs(x, "definition", true))
         .unwrap_or_default();
+    let plugins = plugins.map(PathBuf::from);
     let files = expand_globs(files, "processing", true);
     let verbose = !quiet;
 
     if files.is_empty() {
         // run the ogma-shell/REPL since no processing files were given
-        run_shell(defs)
-    } else if process_files(defs, files, verbose).is_err() {
+        run_shell(defs, plugins)
+    } else if process_files(defs, plugins, files, verbose).is_err() {
         std::process::exit(1); // failed
     }
 }
@@ -49,7 +55,7 @@ fn expand_globs(values: Vec<String>, file_ty: &str, panic_on_empty: bool) -> Vec
     paths
 }
 
-fn run_shell(defs: Vec<PathBuf>) {
+fn run_shell(defs: Vec<PathBuf>, plugins: Option<PathBuf>) {
     let root = Path::new(".")
         .canonicalize()
         .expect("should be able to canonicalize root");
@@ -63,16 +69,29 @@ fn run_shell(defs: Vec<PathBuf>) {
     for def in defs {
         shell = shell.defs_file_path(def);
     }
+    if let Some(plugins) = plugins {
+        shell = shell.plugins_path(plugins);
+    }
 
     shell.run().expect("failed running the ogma shell")
 }
 
-fn process_files(defs: Vec<PathBuf>, files: Vec<PathBuf>, verbose: bool) -> Result<(), ()> {
+fn process_files(
+    defs: Vec<PathBuf>,
+    plugins: Option<PathBuf>,
+    files: Vec<PathBuf>,
+    verbose: bool,
+) -> Result<(), ()> {
     let mut definitions = ogma::lang::Definitions::default();
 
-    // add defs
+    // add defs, directories are loaded as partitions
     for def in defs {
-        if let Err(e) = definitions.add_from_file(&def) {
+        let r = if def.is_dir() {
+            definitions.add_from_dir(&def, plugins.as_deref())
+        } else {
+            definitions.add_from_file(&def)
+        };
+        if let Err(e) = r {
             panic!("failed loading definition file '{}': {}", def.display(), e);
         }
     }
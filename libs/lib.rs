@@ -4,6 +4,7 @@ pub use divvy;
 pub use fastrand;
 pub use parking_lot;
 pub use rayon;
+pub use regex;
 pub use rustc_hash;
 pub use serde;
 pub use serde_json;
@@ -305,6 +305,8 @@ impl Complete for ast::DefinitionImpl {
         }
 
         is_node(&self.name, pos, NT::Command)
+            .or_else(|| self.ty_params.iter().find_map(|p| p.node_at_pos(pos)))
+            .or_else(|| self.out_ty.as_ref().and_then(|t| is_node(t, pos, NT::Type)))
             .or_else(|| self.in_ty.as_ref().and_then(|t| is_node(t, pos, NT::Type)))
             .or_else(|| self.expr.node_at_pos(pos))
             .or_else(|| self.params.iter().find_map(|param| param.node_at_pos(pos)))
@@ -323,6 +325,14 @@ impl Complete for ast::DefinitionImpl {
 
         buf.push(Node::new(self.name.clone(), NT::Command));
 
+        for ty_param in &self.ty_params {
+            buf = ty_param.append_leaves(buf);
+        }
+
+        if let Some(outty) = &self.out_ty {
+            buf.push(Node::new(outty.clone(), NT::Type));
+        }
+
         if let Some(inty) = &self.in_ty {
             buf.push(Node::new(inty.clone(), NT::Type));
         }
@@ -335,6 +345,63 @@ impl Complete for ast::DefinitionImpl {
     }
 }
 
+impl Complete for ast::TypeParameter {
+    fn node_at_pos(&self, pos: usize) -> Option<Node> {
+        is_node(&self.ident, pos, NT::TyParameter)
+            .or_else(|| self.traits.iter().find_map(|t| is_node(t, pos, NT::Type)))
+    }
+
+    fn append_leaves(&self, mut buf: Vec<Node>) -> Vec<Node> {
+        buf.push(Node::new(self.ident.clone(), NT::TyParameter));
+        for trait_ in &self.traits {
+            buf.push(Node::new(trait_.clone(), NT::Type));
+        }
+        buf
+    }
+}
+
+impl Complete for ast::DefinitionTrait {
+    fn node_at_pos(&self, pos: usize) -> Option<Node> {
+        if pos < self.name.start {
+            // use the previous tag to the `def-trait` command
+            let mut tag = self.name.clone();
+            tag.make_mut().start = 0;
+            tag.make_mut().end = self.name.start.saturating_sub(1);
+            return Some(Node::new(tag, NT::Command));
+        }
+
+        is_node(&self.name, pos, NT::Type).or_else(|| {
+            self.ops.iter().find_map(|op| {
+                is_node(&op.name, pos, NT::Command)
+                    .or_else(|| op.params.iter().find_map(|p| p.node_at_pos(pos)))
+            })
+        })
+    }
+
+    fn append_leaves(&self, mut buf: Vec<Node>) -> Vec<Node> {
+        buf.push(Node::new(
+            {
+                let mut tag = self.name.clone();
+                tag.make_mut().start = 0;
+                tag.make_mut().end = self.name.start.saturating_sub(1);
+                tag
+            },
+            NT::Command,
+        ));
+
+        buf.push(Node::new(self.name.clone(), NT::Type));
+
+        for op in &self.ops {
+            buf.push(Node::new(op.name.clone(), NT::Command));
+            for param in &op.params {
+                buf = param.append_leaves(buf);
+            }
+        }
+
+        buf
+    }
+}
+
 impl Complete for ast::DefinitionType {
     fn node_at_pos(&self, pos: usize) -> Option<Node> {
         use ast::TypeVariant::*;
@@ -448,7 +515,7 @@ impl Complete for ast::Argument {
         match self {
             Ident(t) => is_node(t, pos, NT::Ident),
             Num(_, t) => is_node(t, pos, NT::Num),
-            Var(t) => is_node(t, pos, NT::Var),
+            Var(t) | Splat(t) => is_node(t, pos, NT::Var),
             Pound(_, t) => is_node(t, pos, NT::Pound),
             Expr(expr) => expr.node_at_pos(pos),
         }
@@ -459,7 +526,7 @@ impl Complete for ast::Argument {
         let (t, nt) = match self {
             Ident(t) => (t, NT::Ident),
             Num(_, t) => (t, NT::Num),
-            Var(t) => (t, NT::Var),
+            Var(t) | Splat(t) => (t, NT::Var),
             Pound(_, t) => (t, NT::Pound),
             Expr(expr) => return expr.append_leaves(buf),
         };
@@ -471,11 +538,15 @@ impl Complete for ast::Argument {
 impl Complete for ast::Parameter {
     fn node_at_pos(&self, pos: usize) -> Option<Node> {
         is_node(&self.ident, pos, NT::Parameter)
+            .or_else(|| self.default.as_ref().and_then(|d| d.node_at_pos(pos)))
     }
 
     fn append_leaves(&self, mut buf: Vec<Node>) -> Vec<Node> {
         buf.push(Node::new(self.ident.clone(), NT::Parameter));
-        buf
+        match &self.default {
+            Some(d) => d.append_leaves(buf),
+            None => buf,
+        }
     }
 }
 
@@ -97,6 +97,7 @@ impl Workspace {
             .map(|x| match x {
                 Impl(x) => Box::new(x) as Box<_>,
                 Ty(x) => Box::new(x) as Box<_>,
+                Trait(x) => Box::new(x) as Box<_>,
                 Expr(x) => Box::new(x) as Box<_>,
             })
             .map_err(|(err, exp)| Incomplete { err, exp })
@@ -25,6 +25,8 @@ pub struct OgmaShell {
     pub root: PathBuf,
     /// File paths to look for definitions.
     pub defs_file_paths: Vec<PathBuf>,
+    /// Directory of plugin partitions, imported with the `//` prefix.
+    pub plugins_path: Option<PathBuf>,
 }
 
 impl OgmaShell {
@@ -34,6 +36,7 @@ impl OgmaShell {
             title: String::new(),
             root,
             defs_file_paths: Vec::new(),
+            plugins_path: None,
         }
     }
 
@@ -46,15 +49,27 @@ impl OgmaShell {
     /// Add a definition file path.
     ///
     /// Processes the path, if absolute takes verbatim, if not the root is prefixed.
+    /// A directory path is loaded as a root of partitions.
     pub fn defs_file_path<P: AsRef<Path>>(mut self, path: P) -> Self {
-        let path = path.as_ref();
-        let path = if path.is_absolute() {
+        let path = self.rooted(path.as_ref());
+        self.defs_file_paths.push(path);
+        self
+    }
+
+    /// Set the plugins directory, used by directory definition paths.
+    ///
+    /// Processes the path, if absolute takes verbatim, if not the root is prefixed.
+    pub fn plugins_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.plugins_path = Some(self.rooted(path.as_ref()));
+        self
+    }
+
+    fn rooted(&self, path: &Path) -> PathBuf {
+        if path.is_absolute() {
             path.to_path_buf()
         } else {
             self.root.join(path)
-        };
-        self.defs_file_paths.push(path);
-        self
+        }
     }
 
     /// Run the shell.
@@ -65,6 +80,7 @@ impl OgmaShell {
             title,
             root,
             defs_file_paths,
+            plugins_path,
         } = self;
 
         let wsp = Workspace::init();
@@ -73,6 +89,7 @@ impl OgmaShell {
             root: root.clone(),
             tabs: HashMap::default(),
             defs_file_paths,
+            plugins_path,
         };
         let on_enter_press = Box::new(move |i, t, c| state.on_enter(i, t, c));
         let wsp2 = wsp.clone();
@@ -118,6 +135,7 @@ struct RunState {
     wsp: Workspace,
     root: PathBuf,
     defs_file_paths: Vec<PathBuf>,
+    plugins_path: Option<PathBuf>,
     tabs: HashMap<TabId, PathBuf>,
 }
 
@@ -243,7 +261,13 @@ impl RunState {
                 writeln!(buf).ok();
             }
 
-            match defs.add_from_file(path) {
+            let r = if path.is_dir() {
+                defs.add_from_dir(path, self.plugins_path.as_deref())
+            } else {
+                defs.add_from_file(path)
+            };
+
+            match r {
                 Ok(n) => write!(
                     buf,
                     "{}: {}",
@@ -412,6 +436,10 @@ fn write_result<W: Write>(
             Value::Str(s) => write!(wtr, "{}", s),
             Value::Tab(table) => print_table(&table, &mut wtr),
             Value::TabRow(_) => write!(wtr, "<table row>"),
+            Value::Stream(stream) => print_stream(&stream, &mut wtr),
+            Value::DateTime(dt) => write!(wtr, "{}", fmt_datetime(&dt)),
+            Value::Duration(d) => write!(wtr, "{}", fmt_duration(&d)),
+            Value::List(list) => write!(wtr, "{}", fmt_list(&list, &mut Default::default())),
             Value::Ogma(data) => write!(wtr, "{}", print_ogma_data(data)),
         },
         Err(e) => write_err(e, wsp, wtr),
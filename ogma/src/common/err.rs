@@ -193,7 +193,7 @@ impl Error {
         let ty = impls.iter_op(op.str()).collect::<Vec<_>>();
 
         let hlp = if recursion_detected {
-            "recursion is not supported.
+            "recursion is not supported by `def`, use `def-rec` for a recursive definition.
           for alternatives, please see <https://daedalus.report/d/docs/ogma.book/11%20(no)%20recursion.md?pwd-raw=docs>".into()
         } else if ty.is_empty() {
             "view a list of definitions using `def --list`".into()
@@ -237,10 +237,19 @@ impl Error {
 
         if let Some(impl_) = signature {
             let mut params = impl_.params.iter().fold(String::new(), |mut s, param| {
+                if param.flag {
+                    s += "--";
+                }
                 write!(&mut s, "{}", param.ident).ok();
+                if param.rest {
+                    s += "..";
+                }
                 if let Some(ty) = &param.ty {
                     write!(&mut s, ":{}", ty).ok();
                 }
+                if let Some(default) = &param.default {
+                    write!(&mut s, "={}", default.tag()).ok();
+                }
 
                 s += " ";
 
@@ -432,6 +441,15 @@ expected `{}`, found `{}`",
         }
     }
 
+    pub(crate) fn stream_header_changed() -> Self {
+        Error {
+            cat: Category::Evaluation,
+            desc: "a stream chunk has a different header to the first chunk".into(),
+            help_msg: Some("each chunk of a stream must have the same columns".into()),
+            ..Self::default()
+        }
+    }
+
     /// Convert [`Argument`] to `(Tag, &str)` for use with `Error::unexp_arg_variant`.
     pub fn span_arg<'a>(arg: &'a Argument) -> (&'a Tag, &'static str) {
         let tag = arg.tag();
@@ -440,6 +458,7 @@ expected `{}`, found `{}`",
             Argument::Pound(_, _) => "special-literal",
             Argument::Num(_, _) => "number",
             Argument::Var(_) => "variable",
+            Argument::Splat(_) => "splat",
             Argument::Expr(_) => "expression",
         };
         (tag, s)
@@ -454,14 +473,8 @@ expected `{}`, found `{}`",
         // description
         {
             match self.cat {
-                Category::Internal => colour!(wtr, c, bright_red, "Internal Error"),
-                Category::Parsing => colour!(wtr, c, bright_red, "Parsing Error"),
-                Category::UnknownCommand => colour!(wtr, c, bright_red, "Unknown Command"),
-                Category::Semantics => colour!(wtr, c, bright_red, "Semantics Error"),
-                Category::Type => colour!(wtr, c, bright_red, "Typing Error"),
-                Category::Evaluation => colour!(wtr, c, bright_red, "Evaluation Error"),
-                Category::Definitions => colour!(wtr, c, bright_red, "Definition Error"),
-                Category::Help => colour!(wtr, c, bright_yellow, "Help"),
+                Category::Help => colour!(wtr, c, bright_yellow, "{}", self.cat),
+                _ => colour!(wtr, c, bright_red, "{}", self.cat),
             }?;
             colourln!(wtr, c, bright_white, ": {}", self.desc)?;
         }
@@ -560,6 +573,18 @@ Please supply this BACKTRACE:
         self
     }
 
+    pub(crate) fn recursion_limit(op: &Tag, limit: u32) -> Self {
+        Error {
+            cat: Category::Evaluation,
+            desc: format!("recursion limit of {} reached", limit),
+            traces: trace(op, format!("`{}` exceeded the recursion limit", op)),
+            help_msg: Some(
+                "check that the recursive definition has a base case which stops recursing".into(),
+            ),
+            hard: true,
+        }
+    }
+
     /// Use this to bubble an inference depth reached error.
     pub(crate) fn inference_depth() -> Self {
         Self {
@@ -591,6 +616,32 @@ impl Error {
         }
     }
 
+    pub(crate) fn type_params_mismatch<P: AsRef<str>>(
+        ty: &Tag,
+        params: &[P],
+        found: usize,
+    ) -> Self {
+        let help = if params.is_empty() {
+            format!("`{}` is not a generic type", ty)
+        } else {
+            let params = params.iter().map(|p| p.as_ref()).collect::<Vec<_>>();
+            format!("`{}` is defined as `{}<{}>`", ty, ty, params.join(" "))
+        };
+
+        Error {
+            cat: Category::Semantics,
+            desc: format!(
+                "type `{}` expects {} type argument(s), found {}",
+                ty,
+                params.len(),
+                found
+            ),
+            traces: trace(ty, format!("expecting {} type argument(s)", params.len())),
+            help_msg: Some(help),
+            hard: true, // unrecoverable
+        }
+    }
+
     pub(crate) fn unknown_blk_output_type(blk: &Tag) -> Self {
         Error {
             cat: Category::Semantics,
@@ -643,7 +694,7 @@ impl Error {
         }
     }
 
-    pub(crate) fn unexp_arg_output_ty(exp: &Type, found: &Type, arg: &Tag) -> Self {
+    pub(crate) fn unexp_arg_output_ty<E: fmt::Display>(exp: E, found: &Type, arg: &Tag) -> Self {
         Error {
             cat: Category::Semantics,
             desc: format!(
@@ -659,6 +710,118 @@ impl Error {
         }
     }
 
+    pub(crate) fn unexp_rec_def_output_ty(exp: &Type, found: &Type, out_ty: &Tag) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: format!(
+                "expecting recursive definition with output type `{}`, found `{}`",
+                exp, found
+            ),
+            traces: trace(
+                out_ty,
+                format!("the definition body returns type `{}`", found),
+            ),
+            help_msg: Some(
+                "the output type annotation must match the type the definition body returns".into(),
+            ),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn rec_def_expr_param(param: &Tag) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: "recursive definitions do not support `Expr` parameters".into(),
+            traces: trace(param, None),
+            help_msg: Some("annotate the parameter with a concrete type, such as `:Num`".into()),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn rec_def_optional_param(param: &Tag) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: "recursive definitions do not support default, flag, or rest parameters".into(),
+            traces: trace(param, None),
+            help_msg: Some("use a required parameter, supplying the argument at each call".into()),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn splat_not_rest(var: &Tag) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: format!("cannot splat `${}`, it is not a rest parameter", var),
+            traces: trace(var, "splats must reference a rest parameter".to_string()),
+            help_msg: Some(
+                "rest parameters are defined in a def with a trailing `..`, such as `(cols..)`"
+                    .into(),
+            ),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn trait_not_found(name: &Tag) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: format!("trait `{}` not defined", name),
+            traces: trace(name, format!("`{}` not defined", name)),
+            help_msg: Some(
+                "traits are defined with `def-trait`, such as `def-trait Ord { cmp (rhs) }`".into(),
+            ),
+            hard: true, // unrecoverable
+        }
+    }
+
+    pub(crate) fn trait_not_implemented(
+        op: &Tag,
+        ty: &Type,
+        trait_: &Tag,
+        missing: &[&TraitOp],
+    ) -> Self {
+        let delim = ", ";
+        let list = missing.iter().fold(String::new(), |s, op| {
+            let params = op
+                .params
+                .iter()
+                .map(|p| match &p.ty {
+                    Some(ty) => format!("{}:{}", p.ident, ty),
+                    None => p.ident.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+            s + "`" + op.name.str() + " (" + &params + ")`" + delim
+        });
+
+        Error {
+            cat: Category::Semantics,
+            desc: format!("type `{}` does not implement trait `{}`", ty, trait_),
+            traces: vec![
+                Trace::from_tag(op, format!("`{}` does not implement `{}`", ty, trait_)),
+                Trace::from_tag(trait_, "required by this bound".to_string()),
+            ],
+            help_msg: Some(format!(
+                "`{}` is missing implementations for: {}",
+                ty,
+                list.trim_end_matches(delim)
+            )),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn ty_param_mismatch(op: &Tag, param: &Tag, a: &Type, b: &Type) -> Self {
+        Error {
+            cat: Category::Semantics,
+            desc: format!("type parameter `{}` is bound to different types", param),
+            traces: vec![
+                Trace::from_tag(op, format!("`{}` is bound to `{}` and `{}`", param, a, b)),
+                Trace::from_tag(param, None),
+            ],
+            help_msg: Some("each use of a type parameter must be the same type".into()),
+            hard: true,
+        }
+    }
+
     pub(crate) fn field_not_found(field: &Tag, ty: &TypeDef) -> Self {
         fn hlp(ty: &TypeDef) -> Option<String> {
             use types::TypeVariant::*;
@@ -687,6 +850,53 @@ impl Error {
             hard: true,
         }
     }
+
+    pub(crate) fn variant_not_found(variant: &Tag, ty: &TypeDef) -> Self {
+        fn hlp(ty: &TypeDef) -> Option<String> {
+            use types::TypeVariant::*;
+
+            match ty.structure() {
+                Sum(variants) => {
+                    let delim = ", ";
+                    let list = variants
+                        .iter()
+                        .fold(String::new(), |s, v| s + v.name.str() + delim);
+                    Some(format!(
+                        "`{}` has the following variants: {}",
+                        ty.name(),
+                        list.trim_end_matches(delim)
+                    ))
+                }
+                Product(_) => None,
+            }
+        }
+
+        Error {
+            cat: Category::Semantics,
+            desc: format!("`{}` does not contain variant `{}`", ty.name(), variant),
+            traces: trace(variant, format!("`{}` not found", variant)),
+            help_msg: hlp(ty),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn non_exhaustive_match<'a, V>(blk: &Tag, ty: &TypeDef, missing: V) -> Self
+    where
+        V: Iterator<Item = &'a Tag>,
+    {
+        let delim = ", ";
+        let list = missing.fold(String::new(), |s, v| s + v.str() + delim);
+        Error {
+            cat: Category::Semantics,
+            desc: format!("match on `{}` does not cover every variant", ty.name()),
+            traces: trace(
+                blk,
+                format!("missing arms for: {}", list.trim_end_matches(delim)),
+            ),
+            help_msg: Some("add an arm for each missing variant, or use `_` as a catch-all".into()),
+            hard: true,
+        }
+    }
 }
 
 /// Variable Errors
@@ -706,6 +916,55 @@ impl Error {
     }
 }
 
+/// Partition Errors
+impl Error {
+    pub(crate) fn ambiguous_op(op: &Tag, imports: &[(Str, Tag)]) -> Self {
+        let mut traces = trace(op, format!("`{op}` is ambiguous"));
+        traces.extend(
+            imports
+                .iter()
+                .map(|(key, tag)| Trace::from_tag(tag, format!("imports `{key}`"))),
+        );
+
+        Error {
+            cat: Category::Definitions,
+            desc: format!("operation `{op}` is imported from multiple partitions"),
+            traces,
+            help_msg: Some(
+                "qualify the operation with its partition, such as `strings/pad`, \
+or narrow the imports"
+                    .into(),
+            ),
+            hard: true,
+        }
+    }
+
+    pub(crate) fn op_not_exported(op: &Tag, key: &str) -> Self {
+        Error {
+            cat: Category::Definitions,
+            desc: format!("operation `{key}` is not exported from its partition"),
+            traces: trace(op, format!("`{key}` is private")),
+            help_msg: Some(
+                "add the operation to the partition's exports, such as `[export(op-name)]`".into(),
+            ),
+            hard: true,
+        }
+    }
+}
+
+impl Error {
+    /// Print the error traces, without the description or help message.
+    pub fn print_traces(&self) -> String {
+        let mut s = Vec::new();
+        for trace in &self.traces {
+            trace
+                .print(false, &mut s)
+                .expect("writing to vector should not fail");
+        }
+        String::from_utf8(s).expect("print should only print valid characters")
+    }
+}
+
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut s = Vec::new();
@@ -864,6 +1123,21 @@ impl Default for Category {
     }
 }
 
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Category::Internal => write!(f, "Internal Error"),
+            Category::Parsing => write!(f, "Parsing Error"),
+            Category::UnknownCommand => write!(f, "Unknown Command"),
+            Category::Semantics => write!(f, "Semantics Error"),
+            Category::Type => write!(f, "Typing Error"),
+            Category::Evaluation => write!(f, "Evaluation Error"),
+            Category::Definitions => write!(f, "Definition Error"),
+            Category::Help => write!(f, "Help"),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
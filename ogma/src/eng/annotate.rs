@@ -45,6 +45,10 @@ fn fmt(s: &mut String, blk: &Block, node: petgraph::prelude::NodeIndex, v: bool)
             write!(s, "${}", t).unwrap();
             fmt_ty(s, out_ty)
         }
+        Splat { tag, idx: _ } => {
+            write!(s, "${}..", tag).unwrap();
+            fmt_ty(s, out_ty)
+        }
         Expr(_) => {
             write!(s, "{{").unwrap();
 
@@ -123,6 +127,10 @@ impl fmt::Display for Display<'_> {
             Str => "Str",
             Tab => "Table",
             TabRow => "TableRow",
+            Stream => "Stream",
+            DateTime => "DateTime",
+            Duration => "Duration",
+            List(t) => return write!(f, "List<{}>", Display(t)),
             Def(d) => d.name().str(),
         };
 
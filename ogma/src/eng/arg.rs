@@ -253,6 +253,7 @@ impl<'a> ArgBuilder<'a> {
                 let mut stack = eval::Stack::new(vec![Step {
                     out_ty,
                     f: Arc::new(|input, cx| cx.done(input)),
+                    plan: None,
                 }]);
                 #[cfg(debug_assertions)]
                 stack.add_types(&tg[node.idx()]);
@@ -267,6 +268,16 @@ impl<'a> ArgBuilder<'a> {
                         unreachable!("a param argument should shadow the referencer arg node")
                     }
                 }),
+            Splat { tag, idx } => {
+                lg.get(node.idx(), tag.str(), tag)
+                    .and_then(|local| match local {
+                        // each splatted element is checked to exist in the tuple
+                        Local::Var(var) if var.ty().tuple_elem(*idx as usize).is_some() => {
+                            Ok(Hold::Splat(var.clone(), *idx as usize))
+                        }
+                        _ => Err(Error::splat_not_rest(tag)),
+                    })
+            }
             Expr(tag) => compiled_exprs
                 .get(&node.index())
                 .cloned()
@@ -333,6 +344,8 @@ pub struct Argument {
 enum Hold {
     Lit(Value),
     Var(Variable),
+    /// An element of a tuple variable.
+    Splat(Variable, usize),
     Expr(eval::Stack),
 }
 
@@ -342,6 +355,36 @@ pub fn pop(args: &mut Vec<ArgNode>, arg_count: u8, err_tag: &Tag) -> Result<ArgN
 }
 
 impl Argument {
+    /// Gather `items` into a single argument which resolves to a tuple of each item's value.
+    ///
+    /// This is used to bind a def's rest parameter.
+    /// Each item is supplied the same input.
+    pub fn tuple(tag: Tag, in_ty: Type, items: Vec<Argument>) -> Self {
+        let ty = Arc::new(Tuple::ty(
+            items.iter().map(|x| x.out_ty().clone()).collect(),
+        ));
+        let out_ty = Type::Def(ty.clone());
+
+        let stack = eval::Stack::new(vec![Step {
+            out_ty: out_ty.clone(),
+            f: Arc::new(move |input: Value, cx: Context| {
+                let mut data = Vec::with_capacity(items.len());
+                for item in &items {
+                    data.push(item.resolve(|| input.clone(), &cx)?);
+                }
+                cx.done(OgmaData::new(ty.clone(), None, data))
+            }),
+            plan: None,
+        }]);
+
+        Argument {
+            tag,
+            in_ty,
+            out_ty,
+            hold: Box::new(Hold::Expr(stack)),
+        }
+    }
+
     /// The arguments input type.
     pub fn in_ty(&self) -> &Type {
         &self.in_ty
@@ -376,10 +419,16 @@ impl Argument {
                 }
             }
             Hold::Var(_) => Err(Error::unexp_arg_variant(tag, "variable")),
+            Hold::Splat(..) => Err(Error::unexp_arg_variant(tag, "splat")),
             Hold::Expr(_) => Err(Error::unexp_arg_variant(tag, "expression")),
         }
     }
 
+    /// The argument resolves without error, which is the case unless it is an expression.
+    pub fn infallible(&self) -> bool {
+        !matches!(&*self.hold, Hold::Expr(_))
+    }
+
     /// Resolve the argument to its [`Value`]. This means passing a literal through, fetching a
     /// variable (and cloning), or evaluating an expression.
     ///
@@ -391,6 +440,7 @@ impl Argument {
         let r = match &*self.hold {
             Hold::Lit(x) => Ok(x.clone()),
             Hold::Var(v) => Ok(v.fetch(&cx.env).clone()),
+            Hold::Splat(v, idx) => Ok(splat(v.fetch(&cx.env), *idx)),
             Hold::Expr(stack) => stack.eval(input(), cx.clone()).map(|x| x.0),
         };
 
@@ -422,6 +472,7 @@ impl Argument {
         let r = match &*self.hold {
             Hold::Lit(x) => R::V(Cow::Borrowed(x)),
             Hold::Var(x) => R::V(Cow::Owned(x.fetch(&cx.env).clone())),
+            Hold::Splat(x, idx) => R::V(Cow::Owned(splat(x.fetch(&cx.env), *idx))),
             Hold::Expr(e) => R::E(e),
         };
 
@@ -458,11 +509,24 @@ impl Hold {
         match self {
             Hold::Lit(v) => Owned(v.ty()),
             Hold::Var(v) => Borrowed(v.ty()),
+            Hold::Splat(v, idx) => Borrowed(
+                v.ty()
+                    .tuple_elem(*idx)
+                    .expect("splat is checked to be a tuple element"),
+            ),
             Hold::Expr(s) => Borrowed(s.out_ty()),
         }
     }
 }
 
+/// Fetch the element at `idx` of a tuple value.
+fn splat(value: &Value, idx: usize) -> Value {
+    match value {
+        Value::Ogma(x) => x.data()[idx].clone(),
+        _ => unreachable!("splats are type checked to be tuples"),
+    }
+}
+
 impl<'a> Block<'a> {
     /// Get the [`Block`]'s next argument.
     ///
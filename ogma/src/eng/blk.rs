@@ -103,6 +103,28 @@ impl<'a> Block<'a> {
         self.args.get(0).copied()
     }
 
+    /// Detect if the operation `op` is used within any of the remaining arguments, including
+    /// within expanded definitions.
+    pub fn args_use_op(&self, op: &str) -> bool {
+        let ag = &self.compiler.ag;
+        self.args.iter().any(|arg| {
+            ag.detect_within(
+                arg.idx(),
+                |n| matches!(n.op(), Some((x, _)) if x.str() == op),
+            )
+        })
+    }
+
+    /// Detect if the variable `var` is referenced within the next argument.
+    pub fn next_arg_uses_var(&self, var: &str) -> bool {
+        let ag = &self.compiler.ag;
+        self.peek_next_arg_node()
+            .map(|arg| {
+                ag.detect_within(arg.idx(), |n| matches!(n.var(), Some(x) if x.str() == var))
+            })
+            .unwrap_or_default()
+    }
+
     /// Assert that this operation will be adding variables.
     ///
     /// This should be called early on to flag to the compiler that it cannot eagerly seal this
@@ -170,6 +192,7 @@ impl<'a> Block<'a> {
                     Num { .. } => "number",
                     Expr(_) => "expression",
                     Var(_) => "variable",
+                    Splat { .. } => "splat",
                     Pound { .. } => "special-literal",
                     Op { .. } | Intrinsic { .. } | Def { .. } | Flag(_) => unreachable!(),
                 };
@@ -259,6 +282,7 @@ impl<'a> Block<'a> {
         Ok(Step {
             out_ty,
             f: Arc::new(f),
+            plan: None,
         })
     }
 
@@ -129,6 +129,9 @@ impl<'d> Compiler<'d> {
         {
             self.resolve_tg()?;
 
+            // check def type params before their expressions are compiled
+            self.assert_def_bounds()?;
+
             // NOTE turn on for debugging.
             // self._write_debug_report("debug-compiler.md");
 
@@ -144,6 +147,10 @@ impl<'d> Compiler<'d> {
                 continue;
             }
 
+            if self.instantiate_def_in_tys()? {
+                continue;
+            }
+
             if self.link_known_def_path_args() {
                 continue;
             }
@@ -279,12 +286,24 @@ impl<'d> Compiler<'d> {
                 None => continue,
             };
 
-            match local {
-                Local::Var(v) => {
+            match (local, &self.ag[node]) {
+                // a splat element is the type of the tuple's element
+                (Local::Var(v), AstNode::Splat { tag, idx }) => {
+                    let ty = v
+                        .ty()
+                        .tuple_elem(*idx as usize)
+                        .ok_or_else(|| Error::splat_not_rest(tag))?;
+                    chgs.push(KnownOutput(node, ty.clone()));
+                    chgs.push(AnyInput(node));
+                }
+                (Local::Var(v), _) => {
                     chgs.push(KnownOutput(node, v.ty().clone()));
                     chgs.push(AnyInput(node));
                 }
-                Local::Ptr { to, tag: _ } => {
+                (Local::Ptr { .. }, AstNode::Splat { tag, idx: _ }) => {
+                    return Err(Error::splat_not_rest(tag));
+                }
+                (Local::Ptr { to, tag: _ }, _) => {
                     // connect the type flow from the pointed to arg node
                     chgs.push(AddEdge {
                         src: to.idx(),
@@ -447,6 +466,17 @@ impl<'d> Compiler<'d> {
 
     /// Try to compile `opnode` into an evaluation [`Step`] given the input type (`in_ty`).
     pub fn compile_block(&self, opnode: OpNode, in_ty: Type, chgs: &mut Chgs) -> Result<Step> {
+        // a stream falls back to the table implementation if the op has no stream
+        // implementation, the stream is materialised before the step is evaluated
+        if in_ty == Type::Stream
+            && self.ag.get_keyed_impl(opnode, &in_ty).is_none()
+            && self.ag.get_keyed_impl(opnode, &Type::Tab).is_some()
+        {
+            return self
+                .compile_block(opnode, Type::Tab, chgs)
+                .map(Step::materialise);
+        }
+
         let cmd_node = self.ag.get_impl(opnode, &in_ty).ok_or_else(|| {
             Error::op_not_found(
                 self.ag[opnode.idx()].tag(),
@@ -461,7 +491,7 @@ impl<'d> Compiler<'d> {
                 let block = Block::construct(self, cmd_node, in_ty, chgs);
                 intrinsic(block)
             }
-            AstNode::Def { expr: _, params: _ } => {
+            AstNode::Def { .. } => {
                 // check if there exists an entry for the sub-expression,
                 // if so, wrap that in a Step and call it done!
                 let defnode = DefNode(cmd_node.idx());
@@ -542,15 +572,32 @@ impl<'d> Compiler<'d> {
                      param,
                      var,
                      arg_idx,
+                     len,
                  }| {
-                    let arg = args[*arg_idx as usize]; // indexing should be safe since it was built against the args
-                    let arg = arg::ArgBuilder::new(arg, self, chgs, Some(in_ty.clone()))
-                        .supplied(in_ty.clone());
-                    let arg = match param.ty().cloned() {
-                        Some(ty) => arg.and_then(|a| a.returns(ty)),
-                        None => arg,
+                    let mut build = |node| {
+                        let arg = arg::ArgBuilder::new(node, self, chgs, Some(in_ty.clone()))
+                            .supplied(in_ty.clone());
+                        let arg = match param.ty().cloned() {
+                            Some(ty) => arg.and_then(|a| a.returns(ty)),
+                            None => arg,
+                        };
+                        arg.and_then(|a| a.concrete())
                     };
-                    arg.and_then(|a| a.concrete()).map(|arg| (var.clone(), arg))
+
+                    // indexing should be safe since it was built against the args
+                    let i = *arg_idx as usize;
+                    let arg = if param.rest {
+                        // the rest arguments are gathered into a tuple
+                        args[i..i + *len as usize]
+                            .iter()
+                            .map(|&a| build(a))
+                            .collect::<Result<Vec<_>>>()
+                            .map(|items| Argument::tuple(param.name.clone(), in_ty.clone(), items))
+                    } else {
+                        build(args[i])
+                    };
+
+                    arg.map(|arg| (var.clone(), arg))
                 },
             )
             .collect()
@@ -671,7 +718,7 @@ impl eval::Stack {
         steps.reverse();
 
         #[allow(unused_mut)]
-        let mut stack = Self::new(steps);
+        let mut stack = Self::new(optimise::optimise(steps));
 
         #[cfg(debug_assertions)]
         stack.add_types(&compiler.tg[expr_node]);
@@ -15,8 +15,28 @@
 //! support the `$row` variable being available for the predicate.
 //! With the locals graph, this is possible to inject the variable locally, and even environment
 //! capturing seems to fall out this graph implementation (testing to be done...).
+//!
+//! ## Defaults and Flags
+//! Default arguments and flag parameters are resolved when the def is expanded in the AST graph.
+//! An unsupplied parameter has its default added as an argument, and a flag parameter is
+//! supplied as a `#t` or `#f` argument. This means the def's arguments always line up with its
+//! parameters here, and any flags which remain are not supported.
+//!
+//! ## Rest Params
+//! A rest parameter takes the arguments that are not taken by the other parameters, and is bound
+//! to a _tuple_ variable of the arguments' values. Since defs are expanded at each callsite, the
+//! number of arguments is known, and splats of the rest parameter (`$x..`) are expanded into an
+//! argument for each element when the def's expression is flattened.
+//!
+//! ## Type Params
+//! A def's type parameters are bound by unifying the types over them (`T`, `Option<T>`) with the
+//! def's input type and the types of its callsite arguments. The bound types are checked against
+//! the parameter's traits each compilation loop, so that a missing implementation is reported at
+//! the def rather than when compiling the def's expression. If the input type is not known yet,
+//! the type parameters bound by the arguments instantiate it, which the previous block can then
+//! infer its output from.
 use super::*;
-use astgraph::Parameter;
+use astgraph::{Parameter, ParameterTy};
 
 impl<'d> Compiler<'d> {
     /// Insert the locals for a definition into the `callsite_params`.
@@ -64,6 +84,154 @@ impl<'d> Compiler<'d> {
     }
 }
 
+impl<'d> Compiler<'d> {
+    /// Assert that the types bound to each def's type parameters are consistent and implement the
+    /// parameter's traits.
+    pub fn assert_def_bounds(&self) -> Result<()> {
+        let Compiler { ag, tg, defs, .. } = self;
+
+        for op in ag.op_nodes() {
+            // only defs with a known path
+            let in_ty = match tg[op.idx()].input.ty() {
+                Some(ty) => ty,
+                None => continue,
+            };
+            let def = match ag.get_impl(op, in_ty).and_then(|cmd| cmd.def(ag)) {
+                Some(def) => def,
+                None => continue,
+            };
+            let ty_params = def.ty_params(ag);
+            if ty_params.is_empty() {
+                continue;
+            }
+
+            let bound = bind_ty_params(self, def, Some(in_ty))?;
+
+            let optag = ag[op.idx()].tag();
+            for (ty_param, ty) in ty_params.iter().zip(&bound) {
+                let ty = match ty {
+                    Some(ty) => ty,
+                    None => continue,
+                };
+
+                for trait_ in &ty_param.traits {
+                    let tr = defs.traits().get_using_tag(trait_)?;
+                    let missing = lang::traits::missing_ops(tr, ty, defs.impls());
+                    if !missing.is_empty() {
+                        return Err(Error::trait_not_implemented(optag, ty, trait_, &missing));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Oblige the input of defs over type parameters (`def unwrap-or<T> Option<T> (default:T)`)
+    /// once the callsite arguments bind the parameters, so the previous block can infer its output
+    /// from it, such as `Option::None | unwrap-or 3`.
+    pub fn instantiate_def_in_tys(&mut self) -> Result<bool> {
+        let mut chgs = Vec::new();
+
+        for def in self
+            .def_nodes_with_known_path()
+            .filter(|d| self.callsite_params.contains_key(&d.index()))
+        {
+            let op = def.parent(&self.ag);
+            if self.tg[op.idx()].input.ty().is_some() {
+                continue;
+            }
+            let in_ty = match def.generic_in_ty(&self.ag) {
+                Some(t) => t,
+                None => continue,
+            };
+
+            let names = ty_param_names(&self.ag, def);
+            let bound = bind_ty_params(self, def, None)?;
+            if let Some(ty) = self.defs.types().resolve_params(in_ty, &names, &bound)? {
+                chgs.push(tygraph::Chg::AnonTy(ty.clone()).into());
+                chgs.push(tygraph::Chg::ObligeInput(op.idx(), ty).into());
+            }
+        }
+
+        self.apply_graph_chgs(chgs.into_iter())
+    }
+}
+
+fn ty_param_names(ag: &AstGraph, def: DefNode) -> Vec<&str> {
+    def.ty_params(ag).iter().map(|p| p.name.str()).collect()
+}
+
+/// Bind the def's type parameters by unifying the types over them with the def's input type and
+/// the types of the callsite arguments, if these are known.
+fn bind_ty_params(
+    compiler: &Compiler,
+    def: DefNode,
+    in_ty: Option<&Type>,
+) -> Result<Vec<Option<Type>>> {
+    let Compiler {
+        ag,
+        defs,
+        callsite_params,
+        ..
+    } = compiler;
+
+    let names = ty_param_names(ag, def);
+    let optag = ag[def.parent(ag).idx()].tag();
+    let args = ag.get_args(def);
+    let arg_tag = |i: usize| args.get(i).map(|a| ag[a.idx()].tag());
+
+    // each type over the type parameters, with the type it takes, and the argument it is from
+    let mut sites = Vec::new();
+    if let (Some(pat), Some(ty)) = (def.generic_in_ty(ag), in_ty) {
+        sites.push((pat, ty.clone(), None));
+    }
+    for cp in callsite_params.get(&def.index()).into_iter().flatten() {
+        if let ParameterTy::Generic(pat) = &cp.param.ty {
+            let ty = cp.var.ty();
+            let idx = cp.arg_idx as usize;
+            if cp.param.rest {
+                sites.extend(
+                    (0..cp.len as usize)
+                        .filter_map(|i| ty.tuple_elem(i).map(|ty| (i, ty)))
+                        .map(|(i, ty)| (pat, ty.clone(), arg_tag(idx + i))),
+                );
+            } else {
+                sites.push((pat, ty.clone(), arg_tag(idx)));
+            }
+        }
+    }
+
+    let mut bound = vec![None; names.len()];
+    for (pat, ty, arg) in sites {
+        let mut found = vec![None; names.len()];
+        let unified = types::unify_params(pat, &names, &ty, &mut found)
+            && defs.types().resolve_params(pat, &names, &found)?.as_ref() == Some(&ty);
+        if !unified {
+            return Err(match arg {
+                Some(arg) => Error::unexp_arg_output_ty(pat, &ty, arg),
+                None => Error::wrong_op_input_type(&ty, optag),
+            });
+        }
+
+        for ((b, x), p) in bound.iter_mut().zip(found).zip(def.ty_params(ag)) {
+            let x = match x {
+                Some(x) => x,
+                None => continue,
+            };
+            match b {
+                Some(b) if *b != x => {
+                    return Err(Error::ty_param_mismatch(optag, &p.name, b, &x));
+                }
+                Some(_) => (),
+                None => *b = Some(x),
+            }
+        }
+    }
+
+    Ok(bound)
+}
+
 pub enum LocalInjection {
     Success { callsite_params: Vec<CallsiteParam> },
     LgChange,
@@ -75,6 +243,8 @@ pub struct CallsiteParam {
     pub param: astgraph::Parameter,
     pub var: Variable,
     pub arg_idx: u8,
+    /// The number of arguments the parameter takes, which is only variable for a rest parameter.
+    pub len: u8,
 }
 
 pub(super) fn map_def_params_into_variables(
@@ -84,7 +254,7 @@ pub(super) fn map_def_params_into_variables(
 ) -> Result<LocalInjection> {
     let Compiler { ag, tg, defs, .. } = compiler;
 
-    // flags are not supported:
+    // flag parameters consume their flags, so any remaining are not supported:
     let flags = ag.get_flags(defnode);
     if !flags.is_empty() {
         return Err(Error::unused_flags(flags.iter()));
@@ -100,27 +270,45 @@ pub(super) fn map_def_params_into_variables(
     let blk_tag = defnode.parent(ag).blk_tag(ag);
 
     let mut lg_chg = false;
+    let mut arg_idx = 0;
 
     for (idx, param) in params.iter().enumerate() {
+        if param.rest {
+            // the rest parameter takes the arguments not taken by the trailing (flag) parameters
+            let len = args.len().saturating_sub(params.len() - idx - 1);
+            let mut argnodes = args.split_off(args.len() - len);
+            argnodes.reverse();
+
+            match map_rest_param(compiler, &argnodes, defnode, arg_idx, param, chgs)? {
+                Ok(Some(cp)) => callsite_params.push(cp),
+                Ok(None) => lg_chg = true,
+                Err(li) => return Ok(li),
+            }
+
+            arg_idx += len as u8;
+            continue;
+        }
+
         let idx = idx as u8;
         // point of failure
         let argnode = arg::pop(&mut args, idx, blk_tag).map_err(|_| {
             let op = defnode.parent(ag);
             let optag = op.op_tag(ag);
+            let impls = defs.iter_op(optag).unwrap_or_default();
             let impl_ = tg[op.idx()]
                 .input
                 .ty()
-                .and_then(|inty| defs.impls().get_impl_with_err(optag, inty).ok())
-                .or_else(|| {
-                    defs.impls()
-                        .iter_op(optag.str())
-                        .map(|x| x.impl_)
-                        // TODO: this returns on the first match, but I don't think that is a reasonable
-                        // option???
-                        // Maybe it should fail if not typed???, dunno...
-                        .next()
+                .and_then(|inty| {
+                    impls
+                        .iter()
+                        .find(|x| x.ty == Some(inty))
+                        .or_else(|| impls.iter().find(|x| x.ty.is_none()))
                 })
-                .and_then(|i| match i {
+                // TODO: this returns on the first match, but I don't think that is a reasonable
+                // option???
+                // Maybe it should fail if not typed???, dunno...
+                .or_else(|| impls.first())
+                .and_then(|x| match x.impl_ {
                     Implementation::Definition(x) => Some(x.as_ref()),
                     _ => None,
                 });
@@ -136,12 +324,14 @@ pub(super) fn map_def_params_into_variables(
             }
         } else {
             // the parameter is to be resolved at the call site
-            match map_callsite_param(compiler, argnode, defnode, idx, param, chgs)? {
+            match map_callsite_param(compiler, argnode, defnode, arg_idx, param, chgs)? {
                 Ok(Some(cp)) => callsite_params.push(cp),
                 Ok(None) => lg_chg = true,
                 Err(li) => return Ok(li),
             }
         }
+
+        arg_idx += 1;
     }
 
     finalise_args(&args, ag)?;
@@ -181,8 +371,50 @@ fn map_callsite_param(
     param: &Parameter,
     chgs: &mut Chgs,
 ) -> Result<std::result::Result<Option<CallsiteParam>, LocalInjection>> {
-    let Compiler { ag, lg, .. } = compiler;
+    let ty = match callsite_arg_ty(compiler, argnode, param, chgs)? {
+        Ok(ty) => ty,
+        Err(li) => return Ok(Err(li)),
+    };
+
+    Ok(Ok(new_callsite_var(
+        compiler, defnode, arg_idx, 1, param, ty, chgs,
+    )))
+}
+
+/// A rest parameter is a callsite variable of the tuple of its arguments.
+fn map_rest_param(
+    compiler: &Compiler,
+    argnodes: &[ArgNode],
+    defnode: DefNode,
+    arg_idx: u8,
+    param: &Parameter,
+    chgs: &mut Chgs,
+) -> Result<std::result::Result<Option<CallsiteParam>, LocalInjection>> {
+    let mut tys = Vec::with_capacity(argnodes.len());
+
+    for &argnode in argnodes {
+        match callsite_arg_ty(compiler, argnode, param, chgs)? {
+            Ok(ty) => tys.push(ty),
+            Err(li) => return Ok(Err(li)),
+        }
+    }
 
+    let ty = Type::Def(Arc::new(Tuple::ty(tys)));
+    chgs.chgs.push(tygraph::Chg::AnonTy(ty.clone()).into());
+
+    let len = argnodes.len() as u8;
+    Ok(Ok(new_callsite_var(
+        compiler, defnode, arg_idx, len, param, ty, chgs,
+    )))
+}
+
+/// Get the return type of a callsite argument, constraining it to the parameter's type.
+fn callsite_arg_ty(
+    compiler: &Compiler,
+    argnode: ArgNode,
+    param: &Parameter,
+    chgs: &mut Chgs,
+) -> Result<std::result::Result<Type, LocalInjection>> {
     let arg = arg::ArgBuilder::new(argnode, compiler, chgs, None);
 
     let arg = match param.ty() {
@@ -206,30 +438,39 @@ fn map_callsite_param(
     // we do not need to .concrete the arg, since we don't really want to get the Argument
     // that it returns. Instead, all we really want to know about this argument is it's
     // output type.
-    let ty = match arg.return_ty() {
-        Some(ty) => ty,
-        None => {
-            return Ok(Err(LocalInjection::UnknownReturnTy(argnode)));
-        }
-    };
+    match arg.return_ty() {
+        Some(ty) => Ok(Ok(ty.clone())),
+        None => Ok(Err(LocalInjection::UnknownReturnTy(argnode))),
+    }
+}
+
+/// This callsite param would create a new variable available at the expression node of the def.
+fn new_callsite_var(
+    compiler: &Compiler,
+    defnode: DefNode,
+    arg_idx: u8,
+    len: u8,
+    param: &Parameter,
+    ty: Type,
+    chgs: &mut Chgs,
+) -> Option<CallsiteParam> {
+    let Compiler { ag, lg, .. } = compiler;
 
-    // this callsite param would create a new variable available at the expression node of
-    // the def
     let expr = defnode.expr(ag);
-    Ok(Ok(lg
-        .new_var(
-            expr.idx(),
-            Str::new(param.name.str()),
-            ty.clone(),
-            param.name.clone(),
-        )
-        .map(|var| CallsiteParam {
-            param: param.clone(),
-            var,
-            arg_idx,
-        })
-        .map_err(|chg| chgs.chgs.push(chg.into()))
-        .ok()))
+    lg.new_var(
+        expr.idx(),
+        Str::new(param.name.str()),
+        ty,
+        param.name.clone(),
+    )
+    .map(|var| CallsiteParam {
+        param: param.clone(),
+        var,
+        arg_idx,
+        len,
+    })
+    .map_err(|chg| chgs.chgs.push(chg.into()))
+    .ok()
 }
 
 fn finalise_args(args: &[ArgNode], ag: &AstGraph) -> Result<()> {
@@ -1,6 +1,10 @@
 //! Evaluation.
 use super::*;
 use graphs::tygraph::AnonTypes;
+use std::{
+    cell::RefCell,
+    sync::{OnceLock, Weak},
+};
 
 // ###### CONTEXT ##############################################################
 #[derive(Clone)]
@@ -8,6 +12,8 @@ pub struct Context<'a> {
     pub env: Environment,
     pub root: &'a std::path::Path,
     pub wd: &'a std::path::Path,
+    /// The depth of nested recursive definition calls.
+    pub depth: u32,
 }
 
 impl<'a> Context<'a> {
@@ -52,6 +58,11 @@ impl Stack {
         &self.steps.last().expect("at least one step").out_ty
     }
 
+    #[cfg(test)]
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
     pub fn eval(&self, value: Value, cx: Context) -> Result<(Value, Environment)> {
         #[cfg(debug_assertions)]
         debug_assert_eq!(
@@ -60,11 +71,21 @@ impl Stack {
             "expecting input value into Stack to match Stack's input type"
         );
 
-        let Context { mut env, root, wd } = cx;
+        let Context {
+            mut env,
+            root,
+            wd,
+            depth,
+        } = cx;
 
         let mut input = value;
         for step in &self.steps {
-            let cx = Context { env, root, wd };
+            let cx = Context {
+                env,
+                root,
+                wd,
+                depth,
+            };
             let (output, new_env) = step.invoke(input, cx)?;
             input = output;
             env = new_env;
@@ -105,7 +126,11 @@ impl From<Stack> for Step {
             .clone();
         let f = Arc::new(move |value: Value, cx: Context| stack.eval(value, cx));
 
-        Step { out_ty, f }
+        Step {
+            out_ty,
+            f,
+            plan: None,
+        }
     }
 }
 
@@ -238,6 +263,164 @@ impl CodeInjector<Eval> {
                     env,
                     root: cx.root,
                     wd: cx.wd,
+                    depth: cx.depth,
+                },
+            )
+            .map(|x| x.0)
+    }
+}
+
+// ###### RECURSIVE CALL #######################################################
+/// A compiled recursive definition body is keyed by the definition source, the input type, and
+/// the argument types.
+type RecKey = (Arc<str>, Type, Vec<Type>);
+
+thread_local! {
+    /// The recursive definition bodies which are currently compiling.
+    /// A recursive call site _within_ a body finds its key here and references the body, rather
+    /// than compiling it again.
+    static COMPILING: RefCell<Vec<(RecKey, Arc<OnceLock<Eval>>)>> = RefCell::new(Vec::new());
+}
+
+/// A call to a recursive definition (`def-rec`), which evaluates the definition body at runtime.
+pub struct RecursiveCall {
+    tag: Tag,
+    limit: u32,
+    /// Map of block argument to a parameter variable in the body.
+    args: Vec<(Argument, Variable)>,
+    body: RecBody,
+}
+
+enum RecBody {
+    /// The outermost call site compiles, and owns, the body.
+    Owned(Arc<OnceLock<Eval>>),
+    /// Call sites within the body only reference it, which avoids a reference cycle.
+    Inner(Weak<OnceLock<Eval>>),
+}
+
+impl RecursiveCall {
+    /// Compile a call to the recursive definition `def`.
+    ///
+    /// `params` are the definition's parameter names, along with the annotated type if there is
+    /// one. The **next** block arguments are mapped to these parameters.
+    /// The body is checked to return `out_ty`.
+    pub fn compile<I>(
+        blk: &mut Block,
+        def: &ast::DefinitionImpl,
+        params: I,
+        out_ty: &Type,
+    ) -> Result<Self>
+    where
+        I: IntoIterator<Item = (Str, Option<Type>)>,
+    {
+        let mut locals = var::SeedVars::default();
+        let mut args = Vec::new();
+        for (name, ty) in params {
+            let arg = blk.next_arg()?.supplied(None)?;
+            let arg = match ty {
+                Some(ty) => arg.returns(ty)?,
+                None => arg,
+            }
+            .concrete()?;
+            let var = locals.add(name, arg.out_ty().clone(), arg.tag.clone());
+            args.push((arg, var));
+        }
+
+        let tag = blk.op_tag().clone();
+        let limit = blk.defs().recursion_limit();
+        let in_ty = blk.in_ty().clone();
+        let key: RecKey = (
+            def.src.clone(),
+            in_ty.clone(),
+            args.iter().map(|(arg, _)| arg.out_ty().clone()).collect(),
+        );
+
+        let inner = COMPILING.with(|c| {
+            c.borrow()
+                .iter()
+                .find(|(k, _)| k == &key)
+                .map(|(_, body)| Arc::downgrade(body))
+        });
+
+        let body = match inner {
+            Some(body) => RecBody::Inner(body),
+            None => {
+                let body = Arc::new(OnceLock::new());
+                let nesting = COMPILING.with(|c| {
+                    let mut c = c.borrow_mut();
+                    c.push((key, body.clone()));
+                    c.len()
+                });
+
+                let compiled = if nesting > limit as usize {
+                    Err(Error::recursion_limit(&tag, limit))
+                } else {
+                    comp::compile_with_seed_vars(
+                        def.expr.clone(),
+                        blk.defs(),
+                        in_ty,
+                        blk.compiler().tg().anon_tys(),
+                        locals,
+                    )
+                };
+                COMPILING.with(|c| c.borrow_mut().pop());
+
+                let FullCompilation {
+                    eval_stack: stack,
+                    env,
+                } = compiled?;
+
+                if stack.out_ty() != out_ty {
+                    let tag = def.out_ty.as_ref().unwrap_or(&def.name);
+                    return Err(Error::unexp_rec_def_output_ty(out_ty, stack.out_ty(), tag));
+                }
+
+                if body.set(Eval { stack, env }).is_err() {
+                    unreachable!("body is only compiled once");
+                }
+
+                RecBody::Owned(body)
+            }
+        };
+
+        Ok(Self {
+            tag,
+            limit,
+            args,
+            body,
+        })
+    }
+
+    /// Evaluate the definition body, erroring if the recursion limit is reached.
+    pub fn eval(&self, input: Value, cx: &Context) -> Result<Value> {
+        if cx.depth >= self.limit {
+            return Err(Error::recursion_limit(&self.tag, self.limit));
+        }
+
+        let body = match &self.body {
+            RecBody::Owned(body) => body.clone(),
+            RecBody::Inner(body) => body
+                .upgrade()
+                .expect("inner call sites are only evaluated while the body is alive"),
+        };
+        let Eval { stack, env } = body.get().expect("body is compiled before evaluation");
+
+        // evalulate the args and set the parameters
+        // NOTE: this uses the caller's cx since it needs the caller's env
+        let mut env = env.clone();
+        for (arg, var) in &self.args {
+            let v = arg.resolve(|| input.clone(), cx)?;
+            var.set_data(&mut env, v);
+        }
+
+        stack
+            .eval(
+                input,
+                Context {
+                    env,
+                    root: cx.root,
+                    wd: cx.wd,
+                    depth: cx.depth + 1,
                 },
             )
             .map(|x| x.0)
@@ -31,6 +31,9 @@ pub enum AstNode {
     Def {
         expr: Tag,
         params: Vec<Parameter>,
+        ty_params: Vec<TypeParameter>,
+        /// The def's input type when it is over the type parameters, such as `Option<T>`.
+        in_ty: Option<Tag>,
     },
     Flag(Tag),
     Ident(Tag),
@@ -43,6 +46,11 @@ pub enum AstNode {
         tag: Tag,
     },
     Var(Tag),
+    /// An element of a splatted rest parameter, `$x..`.
+    Splat {
+        tag: Tag,
+        idx: u8,
+    },
     Expr(Tag),
 }
 
@@ -81,6 +89,8 @@ pub enum Relation {
 pub struct Parameter {
     pub name: Tag,
     pub ty: ParameterTy,
+    /// A rest parameter, where `ty` is the type of _each_ argument.
+    pub rest: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -88,6 +98,17 @@ pub enum ParameterTy {
     Unspecified,
     Specified(Type),
     Expr,
+    /// The parameter's type is over the def's type parameters, such as `T` or `Option<T>`, which
+    /// are bound at each callsite.
+    Generic(Tag),
+}
+
+/// Engine equivalent of [`ast::TypeParameter`].
+#[derive(Debug, Clone)]
+pub struct TypeParameter {
+    pub name: Tag,
+    /// The traits that the bound type must implement.
+    pub traits: Vec<Tag>,
 }
 
 const LOOPLIM: u32 = 128;
@@ -107,7 +128,7 @@ pub fn init(expr: ast::Expression, defs: &Definitions) -> Result<(AstGraph, Chgs
 
     let expr_tag = expr.tag.clone();
 
-    graph.flatten_expr(expr, &mut chgs, defs)?;
+    graph.flatten_expr(expr, None, &mut chgs, defs)?;
 
     let recursion_detector = &mut RecursionDetection::default();
 
@@ -125,9 +146,13 @@ pub fn init(expr: ast::Expression, defs: &Definitions) -> Result<(AstGraph, Chgs
 /// Initialisation functions.
 impl AstGraph {
     /// Returns the NodeIndex that the `expr` becomes.
+    ///
+    /// `rest` is the name and argument count of a def's rest parameter, which is used to expand
+    /// splats (`$x..`) into an argument for each element.
     fn flatten_expr(
         &mut self,
         expr: ast::Expression,
+        rest: Option<(&str, usize)>,
         chgs: &mut Chgs,
         defs: &Definitions,
     ) -> Result<NodeIndex> {
@@ -157,7 +182,7 @@ impl AstGraph {
         q.push_back(Qi {
             root,
             blocks,
-            out_ty: map_ty_tag(out_ty, defs)?,
+            out_ty: map_ty_tag(out_ty, defs, chgs)?,
         });
 
         // FIFO -- breadth-first
@@ -180,53 +205,47 @@ impl AstGraph {
                     out_ty,
                 } = blk.parts();
 
-                let op = match op.is_op() {
-                    Some(t) => t.clone(),
-                    None => {
-                        todo!("paths are not supported yet")
-                    }
-                };
+                // a partition path is kept whole, it is resolved when the op is expanded
+                let op = op.tag().into_owned();
 
                 let op = g.add_node(AstNode::Op { op, blk: blk_tag });
                 g.add_edge(root, op, Relation::Normal); // edge from the expression root to the op
 
-                if let Some(t) = map_ty_tag(in_ty, defs)? {
+                if let Some(t) = map_ty_tag(in_ty, defs, chgs)? {
                     chgs.push(Chg::ObligeInput(op, t));
                 }
-                if let Some(t) = map_ty_tag(out_ty, defs)? {
+                if let Some(t) = map_ty_tag(out_ty, defs, chgs)? {
                     chgs.push(Chg::ObligeOutput(op, t));
                 }
 
                 for term in terms {
-                    use ast::Argument::*;
-                    use ast::Term::*;
-                    use AstNode as An;
-                    use PoundTy as Pt;
+                    if let ast::Term::Arg(ast::Argument::Splat(tag)) = term {
+                        let len = match rest {
+                            Some((name, len)) if name == tag.str() => len,
+                            _ => return Err(Error::splat_not_rest(&tag)),
+                        };
+                        for idx in 0..len {
+                            let idx = idx as u8;
+                            let tag = tag.clone();
+                            let term = g.add_node(AstNode::Splat { tag, idx });
+                            g.add_edge(op, term, Relation::Normal);
+                        }
+                        continue;
+                    }
 
                     let mut next = None;
 
                     let node = match term {
-                        Flag(f) => An::Flag(f),
-                        Arg(Ident(x)) => An::Ident(x),
-                        Arg(Num(val, tag)) => An::Num { val, tag },
-                        Arg(Pound('n', tag)) => An::Pound { ty: Pt::Nil, tag },
-                        Arg(Pound('t', tag)) => An::Pound { ty: Pt::True, tag },
-                        Arg(Pound('f', tag)) => An::Pound { ty: Pt::False, tag },
-                        Arg(Pound('b', tag)) => An::Pound {
-                            ty: Pt::Newline,
-                            tag,
-                        },
-                        Arg(Pound('i', tag)) => An::Pound { ty: Pt::Input, tag },
-                        Arg(Pound(ch, tag)) => return Err(Error::unknown_spec_literal(ch, &tag)),
-                        Arg(Var(x)) => An::Var(x),
-                        Arg(Expr(ast::Expression {
+                        ast::Term::Flag(f) => AstNode::Flag(f),
+                        ast::Term::Arg(ast::Argument::Expr(ast::Expression {
                             tag,
                             blocks,
                             out_ty,
                         })) => {
                             next = Some((blocks, out_ty));
-                            An::Expr(tag)
+                            AstNode::Expr(tag)
                         }
+                        ast::Term::Arg(arg) => arg_node(arg)?,
                     };
 
                     let term = g.add_node(node);
@@ -236,7 +255,7 @@ impl AstGraph {
                         q.push_back(Qi {
                             root: term,
                             blocks,
-                            out_ty: map_ty_tag(out_ty, defs)?,
+                            out_ty: map_ty_tag(out_ty, defs, chgs)?,
                         });
                     }
                 }
@@ -290,15 +309,25 @@ impl AstGraph {
             .clone();
 
         let impls = defs.impls();
+        let op_impls = defs.iter_op(&op)?;
 
-        if !impls.contains_op(op.str()) {
+        if op_impls.is_empty() {
             return Err(Error::op_not_found(&op, None, false, impls));
         }
 
-        let op_impls = impls.iter_op(op.str());
-
         recursion_detector.clear_cache();
 
+        // the terms supplied at the callsite, in positional order
+        // REVERSED since the neighbors are returned in reverse add order
+        let (flags, args): (Vec<_>, Vec<_>) = self
+            .edges(opnode_)
+            .filter(|e| e.weight().is_normal())
+            .map(|e| e.target())
+            .collect::<Vec<_>>()
+            .into_iter()
+            .rev()
+            .partition(|&n| self[n].flag().is_some());
+
         let mut expanded = false;
 
         for lang::impls::ImplEntry {
@@ -316,6 +345,17 @@ impl AstGraph {
                     op: op.clone(),
                     intrinsic: f.clone(),
                 }),
+                // recursive definitions are called at runtime rather than expanded inline
+                Implementation::Definition(def) if def.recursive => {
+                    if let Some(ty) = ty {
+                        insert_anon_ty(ty, chgs);
+                    }
+
+                    self.0.add_node(AstNode::Intrinsic {
+                        op: op.clone(),
+                        intrinsic: lang::impls::recursive_def(def),
+                    })
+                }
                 Implementation::Definition(def) => {
                     // first, test that this def is not already being used in the call chain,
                     // detecting recursion.
@@ -335,14 +375,45 @@ impl AstGraph {
                     let params = def
                         .params
                         .iter()
-                        .map(|p| Parameter::from_ast(p, tys))
+                        .map(|p| Parameter::from_ast(p, &def.ty_params, tys))
                         .collect::<Result<Vec<Parameter>>>()?;
+                    let ty_params = def
+                        .ty_params
+                        .iter()
+                        .map(|p| TypeParameter {
+                            name: p.ident.clone(),
+                            traits: p.traits.clone(),
+                        })
+                        .collect::<Vec<_>>();
+                    let names = ty_params.iter().map(|p| p.name.str()).collect::<Vec<_>>();
+                    let in_ty = def
+                        .in_ty
+                        .clone()
+                        .filter(|t| types::mentions_params(t, &names));
+                    for p in &params {
+                        if let ParameterTy::Specified(ty) = &p.ty {
+                            insert_anon_ty(ty, chgs);
+                        }
+                    }
+                    if let Some(ty) = ty {
+                        insert_anon_ty(ty, chgs);
+                    }
+
+                    // a rest parameter takes the arguments remaining after the positional ones
+                    let positional = def.params.iter().filter(|p| !p.flag && !p.rest).count();
+                    let rest = def
+                        .params
+                        .iter()
+                        .find(|p| p.rest)
+                        .map(|p| (p.ident.str(), args.len().saturating_sub(positional)));
 
                     let cmd = self.0.add_node(AstNode::Def {
                         expr: def.expr.tag.clone(),
                         params,
+                        ty_params,
+                        in_ty,
                     });
-                    let expr = self.flatten_expr(def.expr.clone(), chgs, defs)?;
+                    let expr = self.flatten_expr(def.expr.clone(), rest, chgs, defs)?;
                     // link cmd to expr
                     self.0.add_edge(cmd, expr, Relation::Normal);
                     // add the id into the recursion detector
@@ -354,37 +425,26 @@ impl AstGraph {
                 }
             };
 
+            // definitions map the terms onto the parameters, filling in defaults and flags
+            let (flags, args) = match impl_ {
+                Implementation::Definition(def) if !def.recursive => {
+                    self.def_terms(opnode_, def, &flags, &args, chgs, defs)?
+                }
+                _ => (flags.clone(), args.clone()),
+            };
+
             let g = &mut self.0;
 
             // link the op with this subroot, keyed by the key!
             g.add_edge(opnode_, cmd, Relation::Keyed(ty.cloned()));
 
             // link the op's terms to this subroot
-            // REVERSED since the neighbors are returned in reverse add order
             // flags first
-            for (i, flag) in g
-                .edges(opnode_)
-                .filter(|e| e.weight().is_normal())
-                .filter(|e| g[e.target()].flag().is_some())
-                .map(|e| e.target())
-                .collect::<Vec<_>>()
-                .into_iter()
-                .rev()
-                .enumerate()
-            {
+            for (i, flag) in flags.into_iter().enumerate() {
                 g.add_edge(flag, cmd, Relation::Term(i as u8));
             }
             // now args, normal connections that aren't flags
-            for (i, arg) in g
-                .edges(opnode_)
-                .filter(|e| e.weight().is_normal())
-                .filter(|e| g[e.target()].flag().is_none())
-                .map(|e| e.target())
-                .collect::<Vec<_>>()
-                .into_iter()
-                .rev()
-                .enumerate()
-            {
+            for (i, arg) in args.into_iter().enumerate() {
                 g.add_edge(arg, cmd, Relation::Term(i as u8));
             }
 
@@ -395,6 +455,73 @@ impl AstGraph {
         Ok(expanded)
     }
 
+    /// Map the callsite terms onto a definition's parameters, returning the flags and arguments to
+    /// link to the definition.
+    ///
+    /// Flag parameters consume the matching flag and are supplied as a boolean argument.
+    /// Parameters which are not supplied use their default, which is added as an argument of the
+    /// op, so it is evaluated as if supplied at the callsite.
+    /// A rest parameter takes the remaining supplied arguments.
+    fn def_terms(
+        &mut self,
+        opnode: NodeIndex,
+        def: &ast::DefinitionImpl,
+        flags: &[NodeIndex],
+        args: &[NodeIndex],
+        chgs: &mut Chgs,
+        defs: &Definitions,
+    ) -> Result<(Vec<NodeIndex>, Vec<NodeIndex>)> {
+        let mut flags = flags.to_vec();
+        let mut supplied = args.iter().copied();
+        let mut args = Vec::with_capacity(def.params.len());
+
+        for param in &def.params {
+            if param.rest {
+                args.extend(supplied.by_ref());
+                continue;
+            }
+
+            let arg = if param.flag {
+                let flag = flags
+                    .iter()
+                    .position(|&f| self[f].flag().map(|t| t.str()) == Some(param.ident.str()))
+                    .map(|i| flags.remove(i));
+                let node = match flag {
+                    Some(f) => AstNode::Pound {
+                        ty: PoundTy::True,
+                        tag: self[f].tag().clone(),
+                    },
+                    None => AstNode::Pound {
+                        ty: PoundTy::False,
+                        tag: param.ident.clone(),
+                    },
+                };
+                let node = self.0.add_node(node);
+                self.0.add_edge(opnode, node, Relation::Normal);
+                node
+            } else if let Some(arg) = supplied.next() {
+                arg
+            } else if let Some(default) = &param.default {
+                let node = match default.clone() {
+                    ast::Argument::Expr(expr) => self.flatten_expr(expr, None, chgs, defs)?,
+                    arg => self.0.add_node(arg_node(arg)?),
+                };
+                self.0.add_edge(opnode, node, Relation::Normal);
+                node
+            } else {
+                // the missing argument is reported when mapping the parameters
+                break;
+            };
+
+            args.push(arg);
+        }
+
+        // any extra arguments are reported as unused
+        args.extend(supplied);
+
+        Ok((flags, args))
+    }
+
     /// Returns if the op has been expanded already, that is, it has children with keyed types.
     fn op_expanded(&self, opnode: OpNode) -> bool {
         self.edges(opnode.into())
@@ -402,9 +529,44 @@ impl AstGraph {
     }
 }
 
-fn map_ty_tag(tag: Option<Tag>, defs: &Definitions) -> Result<Option<Type>> {
-    tag.map(|t| defs.types().get_using_tag(&t).map(|x| x.clone()))
-        .transpose()
+/// Map a non-expression argument into its node.
+fn arg_node(arg: ast::Argument) -> Result<AstNode> {
+    use ast::Argument::*;
+    use AstNode as An;
+    use PoundTy as Pt;
+
+    Ok(match arg {
+        Ident(x) => An::Ident(x),
+        Num(val, tag) => An::Num { val, tag },
+        Pound('n', tag) => An::Pound { ty: Pt::Nil, tag },
+        Pound('t', tag) => An::Pound { ty: Pt::True, tag },
+        Pound('f', tag) => An::Pound { ty: Pt::False, tag },
+        Pound('b', tag) => An::Pound {
+            ty: Pt::Newline,
+            tag,
+        },
+        Pound('i', tag) => An::Pound { ty: Pt::Input, tag },
+        Pound(ch, tag) => return Err(Error::unknown_spec_literal(ch, &tag)),
+        Var(x) => An::Var(x),
+        Splat(_) => unreachable!("splats are expanded"),
+        Expr(_) => unreachable!("expressions are flattened"),
+    })
+}
+
+fn map_ty_tag(tag: Option<Tag>, defs: &Definitions, chgs: &mut Chgs) -> Result<Option<Type>> {
+    let ty = tag.map(|t| defs.types().get_using_tag(&t)).transpose()?;
+    if let Some(ty) = &ty {
+        insert_anon_ty(ty, chgs);
+    }
+    Ok(ty)
+}
+
+/// Instantiated generic types and list types are not part of the type graph's initial types set,
+/// so they need to be inserted as anonymous types.
+fn insert_anon_ty(ty: &Type, chgs: &mut Chgs) {
+    if matches!(ty, Type::List(_)) || matches!(ty, Type::Def(x) if x.generic().is_some()) {
+        chgs.push(Chg::AnonTy(ty.clone()));
+    }
 }
 
 #[derive(Default)]
@@ -536,6 +698,17 @@ impl AstGraph {
         fallback
     }
 
+    /// Matches the implementation of a command (op) keyed on exactly `in_ty`.
+    ///
+    /// Unlike [`AstGraph::get_impl`], an input agnostic implementation is not matched.
+    pub fn get_keyed_impl(&self, opnode: OpNode, in_ty: &Type) -> Option<CmdNode> {
+        opnode.debug_assert_is_op_node(self);
+
+        self.edges(NodeIndex::from(opnode))
+            .find(|e| matches!(e.weight(), Relation::Keyed(Some(t)) if t == in_ty))
+            .map(|e| CmdNode(e.target()))
+    }
+
     /// Get the arguments into a command node _in positional order_.
     pub fn get_args<N: Into<CmdNode>>(&self, node: N) -> Vec<ArgNode> {
         self.get_terms(node.into(), false).map(ArgNode).collect()
@@ -588,7 +761,7 @@ impl AstGraph {
 
         match self[node] {
             // these are always in argument positions
-            Ident(_) | Num { .. } | Pound { .. } | Var(_) => true,
+            Ident(_) | Num { .. } | Pound { .. } | Var(_) | Splat { .. } => true,
             // expr might be a root or under a Def
             Expr(_) => self
                 // get incoming node
@@ -652,6 +825,25 @@ impl AstGraph {
             .into_iter()
             .any(|var| petgraph::algo::has_path_connecting(&self.0, op.idx(), var, None))
     }
+
+    /// Detect if `f` holds for any node within `node`, including within expanded definitions.
+    pub fn detect_within<F>(&self, node: NodeIndex, f: F) -> bool
+    where
+        F: Fn(&AstNode) -> bool,
+    {
+        // term edges link back to the parent op's implementations
+        let g = petgraph::visit::EdgeFiltered::from_fn(&self.0, |e| {
+            !matches!(e.weight(), Relation::Term(_))
+        });
+        let mut dfs = Dfs::new(&g, node);
+        while let Some(n) = dfs.next(&g) {
+            if f(&self[n]) {
+                return true;
+            }
+        }
+
+        false
+    }
 }
 
 #[cfg(debug_assertions)]
@@ -719,7 +911,7 @@ impl AstNode {
     /// If this is a def node, returns the params as `Some`.
     pub fn def(&self) -> Option<&[Parameter]> {
         match self {
-            AstNode::Def { expr: _, params } => Some(params.as_slice()),
+            AstNode::Def { params, .. } => Some(params.as_slice()),
             _ => None,
         }
     }
@@ -733,9 +925,11 @@ impl AstNode {
     }
 
     /// If this is a variable node, returns the tag as `Some`.
+    ///
+    /// Splat elements also reference a variable.
     pub fn var(&self) -> Option<&Tag> {
         match self {
-            AstNode::Var(x) => Some(x),
+            AstNode::Var(x) | AstNode::Splat { tag: x, idx: _ } => Some(x),
             _ => None,
         }
     }
@@ -754,12 +948,13 @@ impl AstNode {
         match self {
             Op { op, blk: _ } => op,
             Intrinsic { op, intrinsic: _ } => op,
-            Def { expr, params: _ } => expr,
+            Def { expr, .. } => expr,
             Flag(f) => f,
             Ident(s) => s,
             Num { val: _, tag } => tag,
             Pound { ty: _, tag } => tag,
             Var(v) => v,
+            Splat { tag, idx: _ } => tag,
             Expr(e) => e,
         }
     }
@@ -775,12 +970,13 @@ impl fmt::Display for AstNode {
                 op: _,
                 intrinsic: _,
             } => write!(f, "Intrinsic"),
-            Def { expr: _, params: _ } => write!(f, "Def"),
+            Def { .. } => write!(f, "Def"),
             Flag(t) => write!(f, "Flag(--{})", t.str()),
             Ident(x) => write!(f, "Ident({})", x.str()),
             Num { val, tag: _ } => write!(f, "Num({})", val),
             Pound { ty: _, tag } => write!(f, "Pound({})", tag),
             Var(t) => write!(f, "Var(${})", t.str()),
+            Splat { tag, idx } => write!(f, "Splat(${}..{})", tag.str(), idx),
             Expr(t) => write!(f, "Expr({})", t.str()),
         }
     }
@@ -847,28 +1043,48 @@ impl fmt::Display for Relation {
 }
 
 impl Parameter {
-    fn from_ast(param: &ast::Parameter, tys: &types::Types) -> Result<Self> {
-        let ast::Parameter { ident, ty } = param;
+    fn from_ast(
+        param: &ast::Parameter,
+        ty_params: &[ast::TypeParameter],
+        tys: &types::Types,
+    ) -> Result<Self> {
+        let ast::Parameter {
+            ident,
+            ty,
+            flag,
+            rest,
+            ..
+        } = param;
 
         let name = ident.clone();
         let ty = ty.as_ref();
-        let ty = if ty.map(|t| t.str() == "Expr").unwrap_or(false) {
+        let ty = if *flag {
+            ParameterTy::Specified(Type::Bool)
+        } else if ty.map(|t| t.str() == "Expr").unwrap_or(false) {
             ParameterTy::Expr
+        } else if let Some(t) = ty.filter(|t| {
+            let names = ty_params.iter().map(|p| p.ident.str()).collect::<Vec<_>>();
+            types::mentions_params(t, &names)
+        }) {
+            ParameterTy::Generic(t.clone())
         } else {
             ty.map(|t| tys.get_using_tag(t))
                 .transpose()?
-                .map(Clone::clone)
                 .map(ParameterTy::Specified)
                 .unwrap_or(ParameterTy::Unspecified)
         };
 
-        Ok(Self { name, ty })
+        Ok(Self {
+            name,
+            ty,
+            rest: *rest,
+        })
     }
 
     /// This parameter should be evaluated at the call site.
     pub fn is_callsite_eval(&self) -> bool {
         match self.ty {
-            ParameterTy::Unspecified | ParameterTy::Specified(_) => true,
+            ParameterTy::Unspecified | ParameterTy::Specified(_) | ParameterTy::Generic(_) => true,
             ParameterTy::Expr => false,
         }
     }
@@ -882,11 +1098,28 @@ impl Parameter {
     pub fn ty(&self) -> Option<&Type> {
         match &self.ty {
             ParameterTy::Specified(ty) => Some(ty),
-            ParameterTy::Unspecified | ParameterTy::Expr => None,
+            ParameterTy::Unspecified | ParameterTy::Expr | ParameterTy::Generic(_) => None,
         }
     }
 }
 
+/// Get the parameter which the argument at `idx` maps to.
+///
+/// A rest parameter maps to all the arguments that are not taken by the other parameters.
+pub fn arg_param(params: &[Parameter], idx: usize, args_len: usize) -> Option<&Parameter> {
+    match params.iter().position(|p| p.rest) {
+        Some(rest) if idx >= rest => {
+            let end = args_len.saturating_sub(params.len() - rest - 1);
+            if idx < end {
+                params.get(rest)
+            } else {
+                params.get(rest + 1 + idx - end)
+            }
+        }
+        _ => params.get(idx),
+    }
+}
+
 impl ParameterTy {
     pub fn is_expr(&self) -> bool {
         matches!(self, ParameterTy::Expr)
@@ -1038,6 +1271,23 @@ impl DefNode {
         self.debug_assert_is_def_node(g);
         g[self.idx()].def().expect("filtered to def node")
     }
+
+    pub fn ty_params(self, g: &AstGraph) -> &[TypeParameter] {
+        self.debug_assert_is_def_node(g);
+        match &g[self.idx()] {
+            AstNode::Def { ty_params, .. } => ty_params,
+            _ => unreachable!("filtered to def node"),
+        }
+    }
+
+    /// The def's input type, if it is over the type parameters.
+    pub fn generic_in_ty(self, g: &AstGraph) -> Option<&Tag> {
+        self.debug_assert_is_def_node(g);
+        match &g[self.idx()] {
+            AstNode::Def { in_ty, .. } => in_ty.as_ref(),
+            _ => unreachable!("filtered to def node"),
+        }
+    }
 }
 
 impl ExprNode {
@@ -155,20 +155,22 @@ mod tests {
         assert!(matches!(ag.node_weight(6.into()), Some(Num { .. }))); // 3
         assert!(matches!(ag.node_weight(7.into()), Some(Intrinsic { .. }))); // filter intrinsic
         assert!(matches!(ag.node_weight(8.into()), Some(Intrinsic { .. }))); // filter intrinsic
-        assert!(matches!(ag.node_weight(9.into()), Some(Intrinsic { .. }))); // len intrinsic
+        assert!(matches!(ag.node_weight(9.into()), Some(Intrinsic { .. }))); // filter intrinsic
         assert!(matches!(ag.node_weight(10.into()), Some(Intrinsic { .. }))); // len intrinsic
-        assert!(matches!(ag.node_weight(11.into()), Some(Intrinsic { .. }))); // eq intrinsic
-        assert!(matches!(ag.node_weight(12.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(11.into()), Some(Intrinsic { .. }))); // len intrinsic
+        assert!(matches!(ag.node_weight(12.into()), Some(Intrinsic { .. }))); // len intrinsic
         assert!(matches!(ag.node_weight(13.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(14.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(15.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(16.into()), Some(Intrinsic { .. }))); // eq intrinsic
-        assert!(matches!(ag.node_weight(17.into()), None));
+        assert!(matches!(ag.node_weight(17.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(18.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(19.into()), None));
 
-        assert_eq!(ag.node_count(), 17);
-        assert_eq!(ag.edge_count(), 26);
+        assert_eq!(ag.node_count(), 19);
+        assert_eq!(ag.edge_count(), 30);
 
-        assert_eq!(tg.node_count(), 17);
+        assert_eq!(tg.node_count(), 19);
         assert_eq!(tg.edge_count(), 0); // zeroed initially
 
         check_relation(&ag, 0, 1, 0, Normal); // root -> filter
@@ -179,14 +181,18 @@ mod tests {
         check_relation(&ag, 5, 6, 5, Normal); // eq -> 3
         check_relation(&ag, 1, 7, 6, Keyed(Some(Type::Tab))); // filter -> intrinsic
         check_relation(&ag, 1, 8, 9, Keyed(Some(Type::Str))); // filter -> intrinsic
+        check_relation(&ag, 1, 9, 12, Keyed(None)); // filter -> intrinsic
         check_relation(&ag, 2, 7, 7, Term(0)); // foo -> intrinsic
         check_relation(&ag, 3, 7, 8, Term(1)); // eq 3 -> intrinsic
-        check_relation(&ag, 4, 9, 12, Keyed(Some(Type::Tab))); // len -> intrinsic
-        check_relation(&ag, 4, 10, 13, Keyed(Some(Type::Str))); // len -> intrinsic
-        check_relation(&ag, 5, 11, 14, Keyed(Some(Type::Nil))); // eq -> intrinsic
-        check_relation(&ag, 6, 11, 15, Term(0)); // 3 -> intrinsic
-        check_relation(&ag, 5, 12, 16, Keyed(Some(Type::Num))); // eq -> intrinsic
-        check_relation(&ag, 5, 13, 18, Keyed(Some(Type::Bool))); // eq -> intrinsic
+        check_relation(&ag, 2, 9, 13, Term(0)); // foo -> intrinsic
+        check_relation(&ag, 3, 9, 14, Term(1)); // eq 3 -> intrinsic
+        check_relation(&ag, 4, 10, 15, Keyed(Some(Type::Tab))); // len -> intrinsic
+        check_relation(&ag, 4, 11, 16, Keyed(Some(Type::Str))); // len -> intrinsic
+        check_relation(&ag, 4, 12, 17, Keyed(None)); // len -> intrinsic
+        check_relation(&ag, 5, 13, 18, Keyed(Some(Type::Nil))); // eq -> intrinsic
+        check_relation(&ag, 6, 13, 19, Term(0)); // 3 -> intrinsic
+        check_relation(&ag, 5, 14, 20, Keyed(Some(Type::Num))); // eq -> intrinsic
+        check_relation(&ag, 5, 15, 22, Keyed(Some(Type::Bool))); // eq -> intrinsic
     }
 
     #[test]
@@ -195,7 +201,7 @@ mod tests {
 
         tg.apply_ast_types(&ag);
 
-        assert_eq!(tg.node_count(), 25);
+        assert_eq!(tg.node_count(), 27);
 
         use tygraph::{Knowledge, Node};
         let def = || Node {
@@ -242,10 +248,10 @@ mod tests {
         tg.apply_ast_types(&ag);
         tg.apply_ast_edges(&ag);
 
-        assert_eq!(ag.node_count(), 20);
-        assert_eq!(ag.edge_count(), 29);
+        assert_eq!(ag.node_count(), 22);
+        assert_eq!(ag.edge_count(), 33);
 
-        assert_eq!(tg.node_count(), 20);
+        assert_eq!(tg.node_count(), 22);
 
         // Check AST graph edges
         assert!(matches!(ag.node_weight(0.into()), Some(Expr(_)))); // root
@@ -260,15 +266,17 @@ mod tests {
         assert!(matches!(ag.node_weight(9.into()), Some(Intrinsic { .. }))); // ls intrinsic
         assert!(matches!(ag.node_weight(10.into()), Some(Intrinsic { .. }))); // filter intrinsic
         assert!(matches!(ag.node_weight(11.into()), Some(Intrinsic { .. }))); // filter intrinsic
-        assert!(matches!(ag.node_weight(12.into()), Some(Intrinsic { .. }))); // len intrinsic
+        assert!(matches!(ag.node_weight(12.into()), Some(Intrinsic { .. }))); // filter intrinsic
         assert!(matches!(ag.node_weight(13.into()), Some(Intrinsic { .. }))); // len intrinsic
-        assert!(matches!(ag.node_weight(14.into()), Some(Intrinsic { .. }))); // eq intrinsic
-        assert!(matches!(ag.node_weight(15.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(14.into()), Some(Intrinsic { .. }))); // len intrinsic
+        assert!(matches!(ag.node_weight(15.into()), Some(Intrinsic { .. }))); // len intrinsic
         assert!(matches!(ag.node_weight(16.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(17.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(18.into()), Some(Intrinsic { .. }))); // eq intrinsic
         assert!(matches!(ag.node_weight(19.into()), Some(Intrinsic { .. }))); // eq intrinsic
-        assert!(matches!(ag.node_weight(20.into()), None));
+        assert!(matches!(ag.node_weight(20.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(21.into()), Some(Intrinsic { .. }))); // eq intrinsic
+        assert!(matches!(ag.node_weight(22.into()), None));
 
         check_relation(&ag, 0, 1, 0, Normal); // root -> ls
         check_relation(&ag, 0, 2, 1, Normal); // root -> filter
@@ -284,16 +292,20 @@ mod tests {
         check_relation(&ag, 1, 9, 8, Keyed(None)); // ls -> intrinsic
         check_relation(&ag, 2, 10, 9, Keyed(Some(Type::Tab))); // filter -> intrinsic
         check_relation(&ag, 2, 11, 12, Keyed(Some(Type::Str))); // filter -> intrinsic
-        check_relation(&ag, 5, 12, 15, Keyed(Some(Type::Tab))); // len -> intrinsic
-        check_relation(&ag, 5, 13, 16, Keyed(Some(Type::Str))); // len -> intrinsic
-        check_relation(&ag, 6, 14, 17, Keyed(Some(Type::Nil))); // eq -> intrinsic
+        check_relation(&ag, 2, 12, 15, Keyed(None)); // filter -> intrinsic
+        check_relation(&ag, 5, 13, 18, Keyed(Some(Type::Tab))); // len -> intrinsic
+        check_relation(&ag, 5, 14, 19, Keyed(Some(Type::Str))); // len -> intrinsic
+        check_relation(&ag, 5, 15, 20, Keyed(None)); // len -> intrinsic
+        check_relation(&ag, 6, 16, 21, Keyed(Some(Type::Nil))); // eq -> intrinsic
 
         check_relation(&ag, 3, 10, 10, Term(0)); // foo -> filter intrinsic
         check_relation(&ag, 4, 10, 11, Term(1)); // eq 0 -> filter intrinsic
         check_relation(&ag, 3, 11, 13, Term(0)); // foo -> filter intrinsic
         check_relation(&ag, 4, 11, 14, Term(1)); // eq 0 -> filter intrinsic
+        check_relation(&ag, 3, 12, 16, Term(0)); // foo -> filter intrinsic
+        check_relation(&ag, 4, 12, 17, Term(1)); // eq 0 -> filter intrinsic
 
-        check_relation(&ag, 7, 14, 18, Term(0)); // 0 -> eq intrinsic
+        check_relation(&ag, 7, 16, 22, Term(0)); // 0 -> eq intrinsic
 
         // Type graph nodes
         use tygraph::{Flow, Knowledge, Node};
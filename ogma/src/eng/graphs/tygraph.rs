@@ -163,7 +163,7 @@ impl TypeGraph {
                 Ident(_) => Some(Type::Str),
                 Num { .. } => Some(Type::Num),
                 Pound { ty, tag: _ } => ty.ty(),
-                Var(_) => None,
+                Var(_) | Splat { .. } => None,
                 Expr(_) => None,
             };
 
@@ -320,23 +320,19 @@ impl TypeGraph {
             .iter()
             .map(|&n| ag[n].def().expect("all def nodes"))
             .collect::<Vec<_>>();
-        let min = params
-            .iter()
-            .map(|v| v.len())
-            .min()
-            .unwrap_or(0)
-            .min(args.len());
 
         let mut chgd = false;
 
-        for i in 0..min {
-            let all_callsite = params.iter().all(|p| p[i].is_callsite_eval());
+        for (i, &arg) in args.iter().enumerate() {
+            let all_callsite = params.iter().all(|p| {
+                arg_param(p, i, args.len())
+                    .map(|p| p.is_callsite_eval())
+                    .unwrap_or(false)
+            });
             if !all_callsite {
                 continue;
             }
 
-            let arg = args[i];
-
             if !self.contains_edge(op.idx(), arg.idx()) {
                 chgd = true;
                 self.g.add_edge(op.idx(), arg.idx(), Flow::II);
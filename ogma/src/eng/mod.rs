@@ -8,6 +8,7 @@ mod blk;
 mod comp;
 mod eval;
 mod graphs;
+mod optimise;
 mod step;
 mod var;
 
@@ -17,8 +18,9 @@ type IndexMap<V> = crate::HashMap<usize, V>;
 pub(crate) use self::{
     annotate::types as annotate_types,
     arg::Argument,
-    eval::{CodeInjector, Context, Eval},
+    eval::{CodeInjector, Context, Eval, RecursiveCall},
     graphs::tygraph::AnonTypes,
+    optimise::{MapEntry, Plan},
     var::{Environment, Local, Variable},
 };
 
@@ -124,6 +126,8 @@ impl<T, O> Func<O> for T where T: Fn(Value, Context) -> O + Send + Sync + 'stati
 pub struct Step {
     out_ty: Type,
     f: Arc<dyn Func<StepR>>,
+    /// A description of the step for the optimiser.
+    plan: Option<Arc<Plan>>,
 }
 
 type StepR = Result<(Value, Environment)>;
@@ -144,6 +148,6 @@ mod tests {
         // refactoring somewhat outweighs any benefit, without doing any proper profiling to
         // support it.
         assert_eq!(size_of::<Block>(), 88 + size_of::<Option<Type>>()); // `output_ty` is only on debug builds
-        assert_eq!(size_of::<Step>(), 32);
+        assert_eq!(size_of::<Step>(), 40);
     }
 }
@@ -0,0 +1,292 @@
+//! Optimisation of compiled evaluation stacks.
+//!
+//! Table steps describe themselves with a [`Plan`], which is used to rewrite a stack where it is
+//! provably safe to do so:
+//! - a `filter` moves ahead of the infallible `append`s and `map`s, and the `sort`s, its
+//!   predicate does not depend on,
+//! - a `pick` moves ahead of a `sort` on picked columns, and removes the infallible `append`s of
+//!   columns it does not pick,
+//! - consecutive `map`s fuse into a single pass over the rows,
+//! - `sort | take n` becomes a top-k selection.
+//!
+//! A step is only run on fewer rows, or removed, if its plan marks it as infallible, so an
+//! expression which errors still errors once optimised.
+use super::*;
+use ::libs::rayon::prelude::*;
+
+/// Limit of rewrites. Each rewrite moves a step forward or removes one, so this is only a guard.
+const REWRITELIM: usize = 128;
+
+/// A description of a compiled [`Step`], which the optimiser uses to rewrite a stack.
+///
+/// A plan is only attached to a `Table -> Table` step which does not alter the environment, and
+/// the step must uphold its description.
+#[derive(Clone)]
+pub enum Plan {
+    /// Keeps the rows where the entry of `col` passes a predicate, which only reads the entry.
+    Filter { col: Str },
+    /// Appends the columns `cols`, where each entry only reads its own row.
+    ///
+    /// If `infallible`, no entry errors.
+    Append { cols: Vec<Str>, infallible: bool },
+    /// Replaces the entries of columns, where each entry only reads itself.
+    ///
+    /// The maps are applied in order.
+    Map(Vec<Arc<dyn MapEntry>>),
+    /// Sorts the rows by columns, which are `cols` if known before evaluation.
+    Sort { cols: Option<Vec<Str>>, top: TopK },
+    /// Takes the first `n` rows.
+    Take(usize),
+    /// Keeps only the columns `cols`.
+    Pick { cols: Vec<Str> },
+}
+
+/// Select the first `n` rows of a table once sorted, without sorting the whole table.
+pub type TopK = Arc<dyn Fn(Table, usize, &Context) -> Result<Table> + Send + Sync>;
+
+/// The replacement of a column entry, which can be fused with other maps.
+pub trait MapEntry: Send + Sync {
+    /// The column header.
+    fn col(&self) -> &Str;
+
+    /// The tag to use if the column is not found.
+    fn tag(&self) -> &Tag;
+
+    /// The map does not error on any entry.
+    fn infallible(&self) -> bool;
+
+    /// Map the `entry` of the row at `idx`.
+    fn map(&self, entry: &Entry<Value>, idx: usize, cx: &Context) -> Result<Value>;
+}
+
+impl Plan {
+    /// The step does not error on any row, so it can be run on fewer rows.
+    ///
+    /// The step can still error on the table as a whole, such as a `map` of a missing column.
+    fn infallible(&self) -> bool {
+        match self {
+            Plan::Append { infallible, .. } => *infallible,
+            Plan::Map(maps) => maps.iter().all(|m| m.infallible()),
+            Plan::Sort { .. } | Plan::Take(_) | Plan::Pick { .. } => true,
+            Plan::Filter { .. } => false,
+        }
+    }
+}
+
+/// Rewrite the `steps` of a stack.
+pub fn optimise(mut steps: Vec<Step>) -> Vec<Step> {
+    for _ in 0..REWRITELIM {
+        if !rewrite(&mut steps) {
+            break;
+        }
+    }
+
+    steps
+}
+
+/// Apply the first rewrite found, returning if there was one.
+fn rewrite(steps: &mut Vec<Step>) -> bool {
+    use Plan::*;
+
+    for i in 1..steps.len() {
+        let (a, b) = match (&steps[i - 1].plan, &steps[i].plan) {
+            (Some(a), Some(b)) => (Arc::clone(a), Arc::clone(b)),
+            _ => continue,
+        };
+
+        match (&*a, &*b) {
+            // the filtered out rows are not run by the earlier step
+            (_, Filter { .. }) if !a.infallible() => continue,
+            (Append { cols, .. }, Filter { col }) if !cols.contains(col) => steps.swap(i - 1, i),
+            (Map(maps), Filter { col }) if maps.iter().all(|m| m.col() != col) => {
+                steps.swap(i - 1, i)
+            }
+            (Sort { .. }, Filter { .. }) => steps.swap(i - 1, i),
+            (
+                Sort {
+                    cols: Some(cols), ..
+                },
+                Pick { cols: picked },
+            ) if cols.iter().all(|c| picked.contains(c)) => steps.swap(i - 1, i),
+            // an infallible append does not error on the table either, so it can be removed
+            (
+                Append {
+                    cols,
+                    infallible: true,
+                },
+                Pick { cols: picked },
+            ) if !cols.iter().any(|c| picked.contains(c)) => {
+                steps.remove(i - 1);
+            }
+            (Map(fst), Map(snd)) => {
+                let maps = fst.iter().chain(snd).cloned().collect();
+                steps.splice(i - 1..=i, [fused_maps(maps)]);
+            }
+            (Sort { top, .. }, Take(n)) => {
+                steps.splice(i - 1..=i, [top_k(Arc::clone(top), *n)]);
+            }
+            _ => continue,
+        }
+
+        return true;
+    }
+
+    false
+}
+
+/// A step which applies each map in a single pass over the rows.
+fn fused_maps(maps: Vec<Arc<dyn MapEntry>>) -> Step {
+    let plan = Some(Arc::new(Plan::Map(maps.clone())));
+
+    let f = Arc::new(move |input: Value, cx: Context| {
+        let mut table = Table::try_from(input)?;
+        let cols = maps
+            .iter()
+            .map(|m| TableRow::col_idx(&table, m.col(), m.tag()))
+            .collect::<Result<Vec<_>>>()?;
+
+        // the mapped entries of each row, a later map reads the entry of an earlier one
+        let rows = (1..table.rows_len())
+            .into_par_iter()
+            .map(|idx| {
+                let mut entries: Vec<(usize, Entry<Value>)> = Vec::with_capacity(cols.len());
+                for (m, &col) in maps.iter().zip(&cols) {
+                    let i = match entries.iter().position(|x| x.0 == col) {
+                        Some(i) => i,
+                        None => {
+                            let e = TableRow::entry_at(&table, idx, col).clone();
+                            entries.push((col, e));
+                            entries.len() - 1
+                        }
+                    };
+                    entries[i].1 = m.map(&entries[i].1, idx, &cx)?.into();
+                }
+                Ok(entries)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let t = table.make_mut();
+        for (idx, entries) in (1..).zip(rows) {
+            for (col, e) in entries {
+                if let Some(x) = t.row_mut(idx).and_then(|mut r| r.nth(col)) {
+                    *x = e;
+                }
+            }
+        }
+
+        cx.done(table)
+    });
+
+    Step {
+        out_ty: Type::Tab,
+        f,
+        plan,
+    }
+}
+
+/// A step which selects the sorted first `n` rows.
+fn top_k(top: TopK, n: usize) -> Step {
+    let f = Arc::new(move |input: Value, cx: Context| {
+        let table = top(Table::try_from(input)?, n, &cx)?;
+        cx.done(table)
+    });
+
+    Step {
+        out_ty: Type::Tab,
+        f,
+        plan: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The plan of each step, compiled with a `Table` input.
+    fn plans(expr: &str) -> Vec<&'static str> {
+        let defs = &Definitions::default();
+        let expr = lang::parse::expression(expr, Default::default(), defs).unwrap();
+        compile(expr, defs, Type::Tab)
+            .unwrap()
+            .eval_stack
+            .steps()
+            .iter()
+            .map(|x| match x.plan.as_deref() {
+                Some(Plan::Filter { .. }) => "filter",
+                Some(Plan::Append { .. }) => "append",
+                Some(Plan::Map(_)) => "map",
+                Some(Plan::Sort { .. }) => "sort",
+                Some(Plan::Take(_)) => "take",
+                Some(Plan::Pick { .. }) => "pick",
+                None => "_",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn filter_pushdown() {
+        assert_eq!(plans("append --x 1 | filter a > 1"), ["filter", "append"]);
+        assert_eq!(
+            plans("map b --force 0 | sort c | filter a > 1"),
+            ["filter", "map", "sort"]
+        );
+
+        // the earlier step can error on the filtered out rows
+        assert_eq!(
+            plans("append --x { get b --Num } | filter a > 1"),
+            ["append", "filter"]
+        );
+        assert_eq!(
+            plans("map b + 1 | sort c | filter a > 1"),
+            ["map", "filter", "sort"]
+        );
+
+        // the predicate reads the column
+        assert_eq!(plans("append --a 1 | filter a > 1"), ["append", "filter"]);
+        assert_eq!(plans("map a + 1 | filter a > 1"), ["map", "filter"]);
+        // the appended entries read other rows
+        assert_eq!(
+            plans("append --x { prev 2 | len } | filter a > 1"),
+            ["_", "filter"]
+        );
+        // the predicate reads the whole row
+        assert_eq!(
+            plans("append --x 1 | filter { get a --Num | > 1 }"),
+            ["append", "_"]
+        );
+        assert_eq!(
+            plans("append --x 1 | filter --skip-errors a > 1"),
+            ["append", "_"]
+        );
+    }
+
+    #[test]
+    fn pick_pruning() {
+        assert_eq!(plans("append --x 1 | append --y 2 | pick a"), ["pick"]);
+        assert_eq!(plans("sort b a | pick a b c"), ["pick", "sort"]);
+
+        // the removed step could error
+        assert_eq!(
+            plans("append --x { get b --Num } | pick a"),
+            ["append", "pick"]
+        );
+        assert_eq!(plans("map b --force 0 | pick a"), ["map", "pick"]);
+
+        assert_eq!(plans("append --x 1 | pick a x"), ["append", "pick"]);
+        assert_eq!(plans("sort b | pick a"), ["sort", "pick"]);
+        assert_eq!(plans("append --x 1 | pick --trail a"), ["append", "_"]);
+    }
+
+    #[test]
+    fn map_fusion() {
+        assert_eq!(plans("map a + 1 | map a * 2 | map b - 1"), ["map"]);
+        assert_eq!(plans("map a + 1 | map a $row.b:Num"), ["map", "_"]);
+    }
+
+    #[test]
+    fn sort_take_top_k() {
+        assert_eq!(plans("sort a | take 3"), ["_"]);
+        assert_eq!(plans("filter a > 1 | sort a | take 3"), ["filter", "_"]);
+        assert_eq!(plans("sort a | take { len | - 1 }"), ["sort", "_"]);
+    }
+}
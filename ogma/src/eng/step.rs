@@ -17,7 +17,36 @@ impl Step {
             subexpr.eval(input, cx)
         });
 
-        Self { out_ty, f }
+        Self {
+            out_ty,
+            f,
+            plan: None,
+        }
+    }
+
+    /// Wrap this step so a [`Stream`] input is materialised into a table before evaluation.
+    ///
+    /// The plan is dropped since the step no longer takes a table.
+    pub fn materialise(self) -> Self {
+        let Step { out_ty, f, .. } = self;
+        let f = Arc::new(move |input: Value, cx: Context| {
+            let table = Stream::try_from(input)?.collect()?;
+            f(Value::Tab(table), cx)
+        });
+
+        Self {
+            out_ty,
+            f,
+            plan: None,
+        }
+    }
+
+    /// Describe this step for the optimiser.
+    ///
+    /// The step must uphold the description of the [`Plan`].
+    pub fn with_plan(mut self, plan: Option<Plan>) -> Self {
+        self.plan = plan.map(Arc::new);
+        self
     }
 
     /// Evaluate this step, invoking the stored closure with the given value and context.
@@ -48,6 +77,7 @@ impl Clone for Step {
         Step {
             out_ty: self.out_ty.clone(),
             f: Arc::clone(&self.f),
+            plan: self.plan.clone(),
         }
     }
 }
@@ -1,20 +1,49 @@
 //! This handles definitions (fns, structs, enums)
 use crate::prelude::*;
+use crate::Mutex;
 use ::libs::divvy::Str;
 use ast::*;
 use err::Trace;
-use lang::help::*;
+use lang::{
+    defs2::{self, DefItems},
+    help::*,
+    impls::ImplEntry,
+};
+use std::{
+    path::Path,
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 /// The working set of `ogma` definitions.
 ///
-/// [`Definitions`] contains the implementations (commands) and the types in the current `ogma`
-/// session.
+/// [`Definitions`] contains the implementations (commands), the types, and the traits in the
+/// current `ogma` session.
 #[derive(Clone)]
 pub struct Definitions {
     impls: lang::impls::Implementations,
     types: types::Types,
+    traits: lang::traits::Traits,
+    parts: defs2::Definitions,
+    recursion_limit: u32,
+    /// Changes whenever the definitions change, invalidating the compiled expressions.
+    generation: u64,
+    compiled: Arc<Mutex<CompiledExprs>>,
 }
 
+/// The default maximum depth of nested recursive definition (`def-rec`) calls.
+const RECURSION_LIMIT: u32 = 64;
+
+/// Compiled expressions, keyed on the expression source, location, input type, and definitions
+/// generation.
+type CompiledExprs = HashMap<(Arc<str>, Location, Type, u64), Arc<eng::FullCompilation>>;
+
+/// The number of compiled expressions kept before the cache is cleared.
+const COMPILEDLIM: usize = 256;
+
+/// The source of generations. It is shared by all [`Definitions`] so that clones which change
+/// separately never share a generation.
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+
 impl Default for Definitions {
     fn default() -> Self {
         Self::new()
@@ -32,10 +61,21 @@ impl Definitions {
 
         // add in the initialisation impls for tydefs
         for x in tydefs {
-            lang::impls::add_typedef_init_impls(&mut impls, x);
+            match x {
+                types::NewType::Def(x) => lang::impls::add_typedef_init_impls(&mut impls, x),
+                types::NewType::Generic(x) => lang::impls::add_generic_init_impls(&mut impls, x),
+            }
         }
 
-        let mut definitions = Self { impls, types };
+        let mut definitions = Self {
+            impls,
+            types,
+            traits: Default::default(),
+            parts: Default::default(),
+            recursion_limit: RECURSION_LIMIT,
+            generation: GENERATION.fetch_add(1, Ordering::Relaxed),
+            compiled: Default::default(),
+        };
 
         let defs = &mut definitions;
 
@@ -186,21 +226,27 @@ the row is assumed to be populated with strings, if not an error occurs",
     }
 
     /// Add definitions by reading a file.
-    pub fn add_from_file<P: AsRef<std::path::Path>>(&mut self, file: P) -> Result<usize> {
+    pub fn add_from_file<P: AsRef<Path>>(&mut self, file: P) -> Result<usize> {
         let file = file.as_ref();
         let s = std::fs::read_to_string(file).map_err(|e| Error::io(&ast::Tag::default(), e))?;
         self.add_from_str(&s, file)
     }
 
     /// Add definitions from a string.
-    pub fn add_from_str(&mut self, s: &str, file: &std::path::Path) -> Result<usize> {
+    pub fn add_from_str(&mut self, s: &str, file: &Path) -> Result<usize> {
         let file = Arc::from(file);
         let loc = Location::File(Arc::clone(&file), 0);
         let items = lang::parse::file(s, loc)?;
 
-        // parse and add each def
+        // parse and add each def, traits are added before the impls which are bound by them
         let mut count = 0;
-        for item in items.types.into_iter().chain(items.impls).map(|(_, x)| x) {
+        for item in items
+            .types
+            .into_iter()
+            .chain(items.traits)
+            .chain(items.impls)
+            .map(|(_, x)| x)
+        {
             dbg!(&item.doc);
             let lang::parse::Item { doc, code, line } = item;
             process_definition(&code, Location::File(file.clone(), line), doc, self)?;
@@ -210,11 +256,79 @@ the row is assumed to be populated with strings, if not an error occurs",
         Ok(count)
     }
 
+    /// Add definitions by reading the partitions within the `root` directory, and optionally a
+    /// `plugins` directory.
+    ///
+    /// Each `.ogma` file, or directory of `.ogma` files, is a partition. Implementations are held
+    /// by their partition node and named by their qualified path (such as `/utils/strings/pad`, or
+    /// `//tools/pad` for plugins). The operations used within a file are resolved through its
+    /// partition and `import` directives. Type names are not partitioned.
+    ///
+    /// Any previously loaded partitions are replaced.
+    pub fn add_from_dir<P: AsRef<Path>>(
+        &mut self,
+        root: P,
+        plugins: Option<&Path>,
+    ) -> Result<usize> {
+        self.changed();
+        let files = self.parts.load(root.as_ref(), plugins)?;
+        let x = add_partition_files(self, files);
+        if x.is_err() {
+            // do not leave partially loaded partitions
+            self.parts = Default::default();
+        }
+        x
+    }
+
+    /// The implementations of the operation `op`.
+    ///
+    /// `op` is resolved through the partitions, using the scope of the file it is used in. If it
+    /// is not a partition implementation, the unqualified (shell or `ogma` defined)
+    /// implementations are used.
+    pub fn iter_op(&self, op: &Tag) -> Result<Vec<ImplEntry>> {
+        let nodes = self.parts.resolve_impls(op)?;
+        if nodes.is_empty() {
+            Ok(self.impls.iter_op(op.str()).collect())
+        } else {
+            Ok(self.parts.entries(nodes))
+        }
+    }
+
+    /// Is there an operation `op`? See [`Self::iter_op`] for how `op` is resolved.
+    ///
+    /// Partition implementations are known once the partitions are built, before their
+    /// definitions are parsed.
+    pub fn contains_op(&self, op: &Tag) -> Result<bool> {
+        let nodes = self.parts.resolve_impls(op)?;
+        Ok(!nodes.is_empty() || self.impls.contains_op(op.str()))
+    }
+
+    /// **Builds** the help message of the operation `op`, which concatenates all applicable input
+    /// types. If `op` is not found, returns an error.
+    pub fn get_help_with_err(&self, op: &Tag) -> Result<Error> {
+        lang::impls::help_all(self.iter_op(op)?)
+            .ok_or_else(|| Error::op_not_found(op, None, false, &self.impls))
+    }
+
+    /// Add the `import` directives of a `file` which sits outside the partitions, such as a batch
+    /// file. The imports are resolved from the root partition.
+    pub(crate) fn add_imports(
+        &mut self,
+        file: Arc<Path>,
+        imports: &[lang::parse::Import],
+    ) -> Result<()> {
+        self.changed();
+        self.parts.add_file(file, imports)
+    }
+
     /// Clears user-defined commands. If `only_files` is `true`, shell defined definitions are left
     /// in.
     pub fn clear(&mut self, only_files: bool) {
+        self.changed();
         self.impls.clear(only_files);
         self.types.clear(only_files);
+        self.traits.clear(only_files);
+        self.parts = Default::default();
     }
 
     /// Provide access to the defined implementations.
@@ -226,6 +340,125 @@ the row is assumed to be populated with strings, if not an error occurs",
     pub fn types(&self) -> &types::Types {
         &self.types
     }
+
+    /// Provide access to the defined traits.
+    pub fn traits(&self) -> &lang::traits::Traits {
+        &self.traits
+    }
+
+    /// The maximum depth of nested recursive definition (`def-rec`) calls.
+    pub fn recursion_limit(&self) -> u32 {
+        self.recursion_limit
+    }
+
+    /// Set the maximum depth of nested recursive definition (`def-rec`) calls.
+    /// Reaching the limit returns an evaluation error.
+    ///
+    /// Each call uses stack space, so a large limit can overflow the stack of the evaluating
+    /// thread.
+    pub fn set_recursion_limit(&mut self, limit: u32) {
+        self.changed();
+        self.recursion_limit = limit;
+    }
+
+    /// Get the compilation of the expression `expr` with the `input` type, compiling it if it is
+    /// not cached.
+    ///
+    /// Compilations are cached until the definitions change.
+    pub(crate) fn compiled<F>(
+        &self,
+        expr: &Arc<str>,
+        loc: &Location,
+        input: &Type,
+        compile: F,
+    ) -> Result<Arc<eng::FullCompilation>>
+    where
+        F: FnOnce() -> Result<eng::FullCompilation>,
+    {
+        let key = (
+            Arc::clone(expr),
+            loc.clone(),
+            input.clone(),
+            self.generation,
+        );
+        let cached = self.compiled.lock().get(&key).cloned();
+        if let Some(x) = cached {
+            return Ok(x);
+        }
+
+        // the lock is not held while compiling
+        let x = Arc::new(compile()?);
+        let mut compiled = self.compiled.lock();
+        if compiled.len() >= COMPILEDLIM {
+            compiled.clear();
+        }
+        compiled.insert(key, Arc::clone(&x));
+
+        Ok(x)
+    }
+
+    /// Flag that the definitions have changed, so expressions are compiled again.
+    fn changed(&mut self) {
+        self.generation = GENERATION.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Add the definitions of the files loaded from the partitions.
+fn add_partition_files(defs: &mut Definitions, files: Vec<defs2::PartFile>) -> Result<usize> {
+    // types and traits are added first, the impls are collected with their node
+    let mut count = 0;
+    let mut impls = Vec::new();
+    for defs2::PartFile {
+        file,
+        types,
+        impls: nodes,
+    } in files
+    {
+        let file_ = match &file.loc {
+            Location::File(f, _) => f.clone(),
+            _ => unreachable!("partition files have a file location"),
+        };
+
+        for ((name, item), node) in file.types.into_iter().zip(types) {
+            let lang::parse::Item { doc, code, line } = item;
+            process_definition(&code, Location::File(file_.clone(), line), doc, defs)?;
+            if let Some(ty) = defs.types.get_using_str(&name) {
+                defs.parts.insert_type(node, ty.clone());
+            }
+            count += 1;
+        }
+
+        for (_, item) in file.traits {
+            let lang::parse::Item { doc, code, line } = item;
+            process_definition(&code, Location::File(file_.clone(), line), doc, defs)?;
+            count += 1;
+        }
+
+        impls.extend(
+            file.impls
+                .into_iter()
+                .zip(nodes)
+                .map(|((_, item), node)| (file_.clone(), item, node)),
+        );
+    }
+
+    // the impl nodes are known before parsing, so the operations defined in other partitions are
+    // recognised when parsing arguments
+    let mut parsed = Vec::with_capacity(impls.len());
+    for (file, item, node) in impls {
+        let lang::parse::Item { doc, code, line } = item;
+        let loc = Location::File(file, line);
+        let def = lang::syntax::parse::definition_impl(&code, loc, defs).map_err(|e| e.0)?;
+        insert_impl(defs, Some(node), def.clone(), doc)?;
+        parsed.push(def);
+    }
+
+    // partitions can reference one another, so ops are checked once all impls are inserted
+    for def in &parsed {
+        assert_all_ops_defined(def, defs)?;
+    }
+
+    Ok(count + parsed.len())
 }
 
 fn add_derived_impl(
@@ -237,7 +470,7 @@ fn add_derived_impl(
 ) {
     let im = lang::syntax::parse::definition_impl(s, Location::Ogma, defs).unwrap();
     let in_ty = if let Some(in_ty) = &im.in_ty {
-        Some(defs.types.get_using_tag(in_ty).unwrap().clone())
+        Some(defs.types.get_using_tag(in_ty).unwrap())
     } else {
         None
     };
@@ -254,11 +487,16 @@ fn add_derived_impl(
 /// ```rust
 /// # use ogma::lang::recognise_definition;
 /// assert!(recognise_definition("def foo-bar { }"));
+/// assert!(recognise_definition("def-rec foo-bar:Num { }"));
 /// assert!(recognise_definition("def-ty Point { x:Num y:Num }"));
+/// assert!(recognise_definition("def-trait Ord { cmp (rhs) }"));
 /// assert!(!recognise_definition("foo-bar zog"));
 /// ```
 pub fn recognise_definition(s: &str) -> bool {
-    s.starts_with("def ") || s.starts_with("def-ty ")
+    s.starts_with("def ")
+        || s.starts_with("def-rec ")
+        || s.starts_with("def-ty ")
+        || s.starts_with("def-trait ")
 }
 
 type DefResult<'a> = Result<(Value, Option<&'a str>)>;
@@ -279,14 +517,18 @@ pub fn process_definition<'a>(
     help: Option<String>,
     defs: &mut Definitions,
 ) -> DefResult<'a> {
-    if def.starts_with("def ") {
+    defs.changed();
+
+    if def.starts_with("def ") || def.starts_with("def-rec ") {
         process_impl(def, loc, help, defs)
     } else if def.starts_with("def-ty ") {
         process_ty(def, loc, help, defs)
+    } else if def.starts_with("def-trait ") {
+        process_trait(def, loc, defs)
     } else {
         Err(Error {
             cat: err::Category::Parsing,
-            desc: "a definition must start with `def` or `def-ty`".into(),
+            desc: "a definition must start with `def`, `def-ty`, or `def-trait`".into(),
             traces: vec![Trace {
                 loc,
                 source: def.to_string(),
@@ -312,9 +554,10 @@ def has specialised syntax which takes variable params: ( )"
                 .into(),
             params: vec![
                 HelpParameter::Required("name".into()),
+                HelpParameter::Optional("<T: Trait ...>".into()),
                 HelpParameter::Optional("Ty".into()),
                 HelpParameter::Custom(
-                    "([param1[:Ty|Expr]] [param2[:Ty|Expr]] ...) { expr }".into(),
+                    "([param1[:Ty|Expr][=default]] [--flag] ... [rest..[:Ty]]) { expr }".into(),
                 ),
             ],
             flags: vec![
@@ -338,6 +581,22 @@ def has specialised syntax which takes variable params: ( )"
                     desc: "use types to better define use",
                     code: "def sum Table (seed:Num value:Expr) { fold $seed + $acc $value }",
                 },
+                HelpExample {
+                    desc: "parameters can have default values, and flags are read as booleans",
+                    code: "def add Num (x:Num=1 --neg) { if $neg { - $x } { + $x } }",
+                },
+                HelpExample {
+                    desc: "rest parameters take the remaining arguments and can be splatted",
+                    code: "def keep Table (cols..:Str) { pick --trail $cols.. }",
+                },
+                HelpExample {
+                    desc: "type parameters can be bound by traits, see `def-trait --help`",
+                    code: "def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }",
+                },
+                HelpExample {
+                    desc: "recursive commands use `def-rec` and annotate the output type",
+                    code: "def-rec fact:Num Num () { if {<= 1} 1 { * {- 1 | fact} } }",
+                },
             ],
             ..HelpMessage::new("def")
         };
@@ -351,26 +610,61 @@ def has specialised syntax which takes variable params: ( )"
     } else {
         let def = lang::syntax::parse::definition_impl(s, loc, defs).map_err(|e| e.0)?;
 
-        assert_all_ops_defined(&def, defs.impls())?;
-
-        let in_ty = if let Some(in_ty) = &def.in_ty {
-            Some(defs.types.get_using_tag(in_ty)?.clone())
-        } else {
-            None
-        };
+        assert_all_ops_defined(&def, defs)?;
+        insert_impl(defs, None, def, help)?;
+        Ok((Value::Nil, None))
+    }
+}
 
-        let mut helpmsg = lang::impls::usr_impl_help(&def);
-        if let Some(help) = help {
-            helpmsg.desc = format!("{}\n\n{}", helpmsg.desc, help).into();
+/// Insert a user defined implementation, held by its impl `node` if it is defined in a partition.
+fn insert_impl(
+    defs: &mut Definitions,
+    node: Option<defs2::ImplNode>,
+    def: ast::DefinitionImpl,
+    help: Option<String>,
+) -> Result<()> {
+    if def.recursive {
+        assert_rec_def_params(&def)?;
+    }
+    // the bounding traits must be defined
+    for ty_param in &def.ty_params {
+        for trait_ in &ty_param.traits {
+            defs.traits.get_using_tag(trait_)?;
         }
+    }
+
+    let ty_params = def
+        .ty_params
+        .iter()
+        .map(|p| p.ident.str())
+        .collect::<Vec<_>>();
+    let in_ty = match &def.in_ty {
+        // an input over the type parameters (`T`, `Option<T>`) is not keyed, the type parameters
+        // are bound and checked when compiled
+        Some(in_ty) if types::mentions_params(in_ty, &ty_params) => None,
+        // a type defined in a partition is found through the impl's partition and imports first
+        Some(in_ty) => match node {
+            Some(node) if defs.parts.types().contains(in_ty.str(), node) => {
+                Some(defs.parts.types().get(in_ty, node)?.clone())
+            }
+            _ => Some(defs.types.get_using_tag(in_ty)?),
+        },
+        None => None,
+    };
+
+    let mut helpmsg = lang::impls::usr_impl_help(&def);
+    if let Some(help) = help {
+        helpmsg.desc = format!("{}\n\n{}", helpmsg.desc, help).into();
+    }
 
-        defs.impls.insert_impl(
+    match node {
+        Some(node) => defs.parts.insert_impl(node, in_ty, def, helpmsg),
+        None => defs.impls.insert_impl(
             in_ty,
             def,
             lang::impls::OperationCategory::UserDefined,
             helpmsg,
-        )?;
-        Ok((Value::Nil, None))
+        ),
     }
 }
 
@@ -428,16 +722,58 @@ fn process_ty<'a>(
     }
 }
 
-fn assert_all_ops_defined(def: &ast::DefinitionImpl, defs: &Implementations) -> Result<()> {
+fn process_trait<'a>(s: &'a str, loc: Location, defs: &mut Definitions) -> DefResult<'a> {
+    if s.contains("--help") {
+        let help = HelpMessage {
+            desc: "define a trait, a named set of commands that a type must implement
+a def's type parameters can be bound by traits, which are checked when the def is used"
+                .into(),
+            params: vec![
+                HelpParameter::Required("name".into()),
+                HelpParameter::Custom("{ op1 ([param1 ...]) op2 ([param1 ...]) ... }".into()),
+            ],
+            examples: vec![
+                HelpExample {
+                    desc: "define a trait for types that can be ordered",
+                    code: "def-trait Ord { cmp (rhs) }",
+                },
+                HelpExample {
+                    desc: "bound the input type of a def",
+                    code: "def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }",
+                },
+            ],
+            ..HelpMessage::new("def-trait")
+        };
+        Err(err::help_as_error(&help, None))
+    } else {
+        let def = lang::syntax::parse::definition_trait(s, loc, defs).map_err(|e| e.0)?;
+        defs.traits.insert(def);
+        Ok((Value::Nil, None))
+    }
+}
+
+fn assert_all_ops_defined(def: &ast::DefinitionImpl, defs: &Definitions) -> Result<()> {
     let name = &def.name;
+    let impls = defs.impls();
 
-    let mut check = vec![def.expr.clone()];
+    // default arguments are checked too
+    let mut check = def
+        .params
+        .iter()
+        .filter_map(|p| match &p.default {
+            Some(ast::Argument::Expr(expr)) => Some(expr.clone()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    check.push(def.expr.clone());
     while let Some(expr) = check.pop() {
         for block in &expr.blocks {
             let op = block.op();
 
-            if !defs.contains_op(op.str()) {
-                return Err(Error::op_not_found(&op, None, &*op == name, defs));
+            // a recursive definition can reference itself
+            let recursion = &*op == name;
+            if !defs.contains_op(&op)? && !(recursion && def.recursive) {
+                return Err(Error::op_not_found(&op, None, recursion, impls));
             }
             check.extend(block.terms().iter().filter_map(|term| match term {
                 ast::Term::Arg(ast::Argument::Expr(expr)) => Some(expr.clone()),
@@ -448,6 +784,21 @@ fn assert_all_ops_defined(def: &ast::DefinitionImpl, defs: &Implementations) ->
     Ok(())
 }
 
+/// Recursive definitions evaluate arguments before the call, so `Expr` parameters are not
+/// supported. The arguments are also compiled with the call, so each must be supplied.
+fn assert_rec_def_params(def: &ast::DefinitionImpl) -> Result<()> {
+    for p in &def.params {
+        if let Some(ty) = p.ty.as_ref().filter(|t| t.str() == "Expr") {
+            return Err(Error::rec_def_expr_param(ty));
+        }
+        if p.flag || p.default.is_some() || p.rest {
+            return Err(Error::rec_def_optional_param(&p.ident));
+        }
+    }
+
+    Ok(())
+}
+
 /// Construct a table comprising of the various definitions in `defs`.
 pub fn construct_def_table(defs: &Definitions) -> Table {
     use ::table::Entry::*;
@@ -467,10 +818,12 @@ pub fn construct_def_table(defs: &Definitions) -> Table {
 
     fn code(im: &Implementation) -> Entry<Str> {
         // we remove the `def ` at the start of a src line. This is an implementation detail which
-        // could change if `def` keyword ever changes.
+        // could change if `def` keyword ever changes. `def-rec` is kept to show recursion.
         match im {
             Implementation::Intrinsic { .. } => Nil,
-            Implementation::Definition(d) => Obj(Str::new(&d.src[4..])),
+            Implementation::Definition(d) => {
+                Obj(Str::new(d.src.strip_prefix("def ").unwrap_or(&d.src)))
+            }
         }
     }
 
@@ -484,6 +837,7 @@ pub fn construct_def_table(defs: &Definitions) -> Table {
     let mut rows = defs
         .impls
         .iter()
+        .chain(defs.parts.impls().iter())
         .map(
             |super::impls::ImplEntry {
                  name,
@@ -1,24 +1,62 @@
 //! This handles definitions (fns, structs, enums)
 use crate::prelude::*;
 use ast::Location;
-use lang::parse::File;
+use lang::{
+    help::HelpMessage,
+    impls::ImplEntry,
+    parse::{File, Import},
+};
 use std::{
     collections::BTreeMap,
     path::{Path, PathBuf},
 };
 
 mod partitions;
+mod scope;
 #[cfg(test)]
 mod tests;
 
 use partitions::*;
+pub use partitions::{ImplNode, TypeNode};
+use scope::Scope;
 
 pub const ROOT: BoundaryNode = BoundaryNode(0);
 
+/// The definitions loaded from partitions.
+///
+/// Implementations and types are keyed by their partition node. Operations are resolved through
+/// the partition lookup, using the scope of the file they are used in (see
+/// [`Definitions::resolve_impls`]).
+#[derive(Clone)]
 pub struct Definitions {
     partitions: Partitions,
-    impls: HashMap<ImplNode, (Option<Type>, Implementation)>,
+    impls: HashMap<ImplNode, PartImpl>,
     types: HashMap<TypeNode, Type>,
+    files: HashMap<Arc<Path>, Scope>,
+    loaded: bool,
+}
+
+/// An implementation defined in a partition.
+#[derive(Clone)]
+struct PartImpl {
+    /// The qualified name, such as `/utils/strings/pad`.
+    name: Str,
+    ty: Option<Type>,
+    impl_: Implementation,
+    help: HelpMessage,
+}
+
+/// A file loaded from the partitions, with the nodes of its types and impls (in item order).
+pub struct PartFile {
+    pub file: File,
+    pub types: Vec<TypeNode>,
+    pub impls: Vec<ImplNode>,
+}
+
+impl Default for Definitions {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Definitions {
@@ -27,6 +65,8 @@ impl Definitions {
             partitions: Partitions::new(),
             impls: HashMap::default(),
             types: HashMap::default(),
+            files: HashMap::default(),
+            loaded: false,
         }
     }
 
@@ -37,6 +77,163 @@ impl Definitions {
     pub fn types(&self) -> Types {
         Types(self)
     }
+
+    /// Build the partitions from the `.ogma` files within `root`, and optionally `plugins`,
+    /// returning the files to load.
+    ///
+    /// This replaces any previously loaded partitions.
+    pub fn load(&mut self, root: &Path, plugins: Option<&Path>) -> Result<Vec<PartFile>> {
+        let rootmap = build_fs_map(root)?;
+        let pluginsmap = plugins.map(build_fs_map).transpose()?.unwrap_or_default();
+
+        // plugins are built first so the root partitions can import from them
+        let partitions = Partitions::new()
+            .extend_plugins(pluginsmap.clone())?
+            .extend_root(rootmap.clone())?;
+
+        let mut files = HashMap::default();
+        let mut loaded = Vec::new();
+        let maps = [
+            (partitions.plugins().0, pluginsmap),
+            (partitions.root().0, rootmap),
+        ];
+        for (top, fsmap) in maps {
+            for (path, fs) in fsmap {
+                let bnd = partitions
+                    .boundary_path(&path, top)
+                    .expect("partition created from fsmap");
+
+                // impls can share a name, their nodes are added in the order of the files
+                let mut nth = HashMap::<String, usize>::default();
+
+                for file in fs {
+                    if let Location::File(f, _) = &file.loc {
+                        let scope = Scope::build(&partitions, bnd, file.iter_imports())?;
+                        files.insert(f.clone(), scope);
+                    }
+
+                    let types = file
+                        .types
+                        .iter()
+                        .map(|(name, _)| {
+                            partitions
+                                .type_within(bnd, name)
+                                .expect("type node created from file")
+                        })
+                        .collect();
+                    let impls = file
+                        .impls
+                        .iter()
+                        .map(|(name, _)| {
+                            let n = nth.entry(name.clone()).or_default();
+                            *n += 1;
+                            partitions
+                                .impls_within(bnd, name)
+                                .nth(*n - 1)
+                                .expect("impl node created from file")
+                        })
+                        .collect();
+
+                    loaded.push(PartFile { file, types, impls });
+                }
+            }
+        }
+
+        *self = Self {
+            partitions,
+            files,
+            loaded: true,
+            ..Self::new()
+        };
+
+        Ok(loaded)
+    }
+
+    /// Add the scope of a `file` which sits outside the partitions, such as a batch file.
+    ///
+    /// The `imports` are resolved from the root partition.
+    pub fn add_file(&mut self, file: Arc<Path>, imports: &[Import]) -> Result<()> {
+        if !self.loaded {
+            return Err(Error {
+                cat: err::Category::Definitions,
+                desc: "imports require partitions to be loaded".to_string(),
+                traces: imports
+                    .first()
+                    .map(|i| err::trace(&i.tag(), "this import".to_string()))
+                    .unwrap_or_default(),
+                help_msg: Some("load a definitions directory to import from".into()),
+                hard: true,
+            });
+        }
+
+        let scope = Scope::build(&self.partitions, ROOT, imports)?;
+        self.files.insert(file, scope);
+
+        Ok(())
+    }
+
+    /// Insert the definition of the impl `node`, keyed on the input type `ty`.
+    ///
+    /// A partition can define an impl multiple times, but only once for each input type.
+    pub fn insert_impl(
+        &mut self,
+        node: ImplNode,
+        ty: Option<Type>,
+        def: ast::DefinitionImpl,
+        help: HelpMessage,
+    ) -> Result<()> {
+        let name = Str::from(self.partitions.qualified(node));
+
+        if self.impls.values().any(|x| x.name == name && x.ty == ty) {
+            let ty = ty.map(|t| t.to_string()).unwrap_or_else(|| "<any>".into());
+            return Err(Error {
+                cat: err::Category::Definitions,
+                desc: format!("the implementation `{name}` is already defined for input `{ty}`"),
+                traces: err::trace(&def.name, "defined again here".to_string()),
+                help_msg: Some("remove or rename one of the definitions".into()),
+                hard: true,
+            });
+        }
+
+        let impl_ = Implementation::Definition(Box::new(def));
+        self.impls.insert(
+            node,
+            PartImpl {
+                name,
+                ty,
+                impl_,
+                help,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Insert the type defined by the type `node`.
+    pub fn insert_type(&mut self, node: TypeNode, ty: Type) {
+        self.types.insert(node, ty);
+    }
+
+    /// The implementation entries of the impl `nodes`.
+    pub fn entries(&self, nodes: Vec<ImplNode>) -> Vec<ImplEntry> {
+        nodes
+            .into_iter()
+            .filter_map(|n| self.impls.get(&n))
+            .map(PartImpl::entry)
+            .collect()
+    }
+}
+
+impl PartImpl {
+    fn entry(&self) -> ImplEntry {
+        ImplEntry {
+            name: &self.name,
+            ty: self.ty.as_ref(),
+            cat: lang::impls::OperationCategory::UserDefined,
+            help: &self.help,
+            impl_: &self.impl_,
+        }
+    }
 }
 
 type FsMap = BTreeMap<PathBuf, Vec<File>>;
@@ -183,7 +380,7 @@ pub struct Types<'a>(&'a Definitions);
 
 impl<'a> DefItems<Type> for Impls<'a> {
     type Item = Implementation;
-    type Iter = ImplsIter;
+    type Iter = ImplsIter<'a>;
 
     fn contains<N: Into<Id>>(&self, key: &str, within: N) -> bool {
         let (bnd, imports) = self.0.partitions.bnd_and_imports(within);
@@ -205,7 +402,7 @@ impl<'a> DefItems<Type> for Impls<'a> {
         let chk_ty = key.meta();
         let key_ = key.key();
         for n in self.0.partitions.find_impls(bnd, imports, key_) {
-            let (ty, impl_) = self
+            let PartImpl { ty, impl_, .. } = self
                 .0
                 .impls
                 .get(&n)
@@ -257,20 +454,21 @@ impl<'a> DefItems<Type> for Impls<'a> {
     }
 
     fn iter(&self) -> Self::Iter {
-        todo!()
+        ImplsIter(self.0.impls.values())
     }
 }
 
+// generic types are not held by the partitions, so only the nodes with a type are found
 impl<'a> DefItems<()> for Types<'a> {
     type Item = Type;
-    type Iter = TypesIter;
+    type Iter = TypesIter<'a>;
 
     fn contains<N: Into<Id>>(&self, key: &str, within: N) -> bool {
         let (bnd, imports) = self.0.partitions.bnd_and_imports(within);
         self.0
             .partitions
             .find_types(bnd, imports, key)
-            .any(|_| true)
+            .any(|n| self.0.types.contains_key(&n))
     }
 
     fn get<'b, K, N: Into<Id>>(&'b self, key: &K, within: N) -> K::Output
@@ -280,7 +478,11 @@ impl<'a> DefItems<()> for Types<'a> {
     {
         let (bnd, imports) = self.0.partitions.bnd_and_imports(within);
 
-        let mut x = self.0.partitions.find_types(bnd, imports, key.key());
+        let mut x = self
+            .0
+            .partitions
+            .find_types(bnd, imports, key.key())
+            .filter(|n| self.0.types.contains_key(n));
         let y = x.next();
 
         if x.next().is_some() {
@@ -315,27 +517,27 @@ impl<'a> DefItems<()> for Types<'a> {
     }
 
     fn iter(&self) -> Self::Iter {
-        todo!()
+        TypesIter(self.0.types.values())
     }
 }
 
-pub struct ImplsIter {}
+pub struct ImplsIter<'a>(std::collections::hash_map::Values<'a, ImplNode, PartImpl>);
 
-pub struct TypesIter {}
+pub struct TypesIter<'a>(std::collections::hash_map::Values<'a, TypeNode, Type>);
 
-impl Iterator for ImplsIter {
-    type Item = ();
+impl<'a> Iterator for ImplsIter<'a> {
+    type Item = ImplEntry<'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        todo!()
+        self.0.next().map(PartImpl::entry)
     }
 }
 
-impl Iterator for TypesIter {
-    type Item = ();
+impl<'a> Iterator for TypesIter<'a> {
+    type Item = &'a Type;
 
     fn next(&mut self) -> Option<Self::Item> {
-        todo!()
+        self.0.next()
     }
 }
 
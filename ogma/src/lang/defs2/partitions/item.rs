@@ -29,6 +29,7 @@ impl Item {
         }
     }
 
+    #[cfg(test)]
     pub fn imports(&self) -> Option<&PartSet> {
         match self {
             Boundary { .. } => None,
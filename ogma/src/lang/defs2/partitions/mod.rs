@@ -8,7 +8,7 @@ mod partset;
 #[cfg(test)]
 mod tests;
 
-use partset::PartSet;
+pub use partset::PartSet;
 
 pub type Id = u32;
 
@@ -31,7 +31,7 @@ enum Item {
 // There is no way to remove nodes, so node indices are stable
 // There are 3 root nodes, [root, shell, plugins]
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Partitions(Vec<Node>);
 
 macro_rules! node_wrapper {
@@ -85,6 +85,7 @@ impl Partitions {
         (BoundaryNode(0), &self.0[0])
     }
 
+    #[cfg(test)]
     pub fn shell(&self) -> (BoundaryNode, &Node) {
         (BoundaryNode(1), &self.0[1])
     }
@@ -93,7 +94,17 @@ impl Partitions {
         (BoundaryNode(2), &self.0[2])
     }
 
-    pub fn extend_root(mut self, fsmap: super::FsMap) -> Result<Self> {
+    pub fn extend_root(self, fsmap: super::FsMap) -> Result<Self> {
+        let root = self.root().0;
+        self.extend(root, fsmap)
+    }
+
+    pub fn extend_plugins(self, fsmap: super::FsMap) -> Result<Self> {
+        let plugins = self.plugins().0;
+        self.extend(plugins, fsmap)
+    }
+
+    fn extend(mut self, root: BoundaryNode, fsmap: super::FsMap) -> Result<Self> {
         // Phase 1: Create the structure of the graph given the map
         //          - we avoid the looking at the imports/exports for now
         //          - since we destruct the File, the remaining parts need to be stored for later
@@ -101,16 +112,17 @@ impl Partitions {
         let mut exports_map = <HashMap<_, Vec<_>>>::default();
         let mut imports_col = Vec::new();
 
-        let root = self.root().0;
         for (p, files) in fsmap {
             let bnd = self.get_or_create_boundary_path(&p, root)?;
 
             for file in files {
                 // deconstruct the file
                 let File {
+                    loc: _,
                     doc: _,
                     directives,
                     types,
+                    traits: _,
                     impls,
                     exprs: _,
                 } = file;
@@ -219,6 +231,56 @@ impl Partitions {
         Ok(a)
     }
 
+    /// Find the boundary node which matches the file system `path`, starting at `root`.
+    pub fn boundary_path(&self, path: &Path, root: BoundaryNode) -> Option<BoundaryNode> {
+        path.iter().try_fold(root, |a, p| {
+            self.children(a)
+                .find(|&n| self[n].eq_boundary(p))
+                .map(BoundaryNode)
+        })
+    }
+
+    /// The qualified name of a node, such as `/foo/bar/zog`.
+    ///
+    /// Nodes under the plugins partition are prefixed with `//`.
+    pub fn qualified<I: Into<Id>>(&self, id: I) -> String {
+        let id: Id = id.into();
+        let mut names = Vec::new();
+        let mut n = &self[id];
+        while let Some(parent) = n.parent {
+            names.push(n.name().as_str());
+            n = &self[parent];
+        }
+
+        let prefix = if n.name() == self.plugins().1.name() {
+            "//"
+        } else {
+            "/"
+        };
+
+        names.reverse();
+        format!("{prefix}{}", names.join("/"))
+    }
+
+    /// The type node named `name` which is directly within the `bnd` partition.
+    pub fn type_within(&self, bnd: BoundaryNode, name: &str) -> Option<TypeNode> {
+        self.children(bnd)
+            .find(|&n| self[n].eq_type(name))
+            .map(TypeNode)
+    }
+
+    /// The impl nodes named `name` which are directly within the `bnd` partition, in the order they
+    /// were added.
+    pub fn impls_within<'a>(
+        &'a self,
+        bnd: BoundaryNode,
+        name: &'a str,
+    ) -> impl Iterator<Item = ImplNode> + 'a {
+        self.children(bnd)
+            .filter(move |&n| self[n].eq_impl(name))
+            .map(ImplNode)
+    }
+
     fn children(&self, bnd: BoundaryNode) -> impl ExactSizeIterator<Item = Id> + '_ {
         match &self[bnd].item {
             Item::Boundary { children, .. } => children.iter().copied(),
@@ -317,7 +379,7 @@ impl Partitions {
         Ok(())
     }
 
-    fn resolve_imports<'a, I>(&self, from: BoundaryNode, imports: I) -> Result<Vec<Id>>
+    pub fn resolve_imports<'a, I>(&self, from: BoundaryNode, imports: I) -> Result<Vec<Id>>
     where
         I: IntoIterator<Item = &'a Import>,
     {
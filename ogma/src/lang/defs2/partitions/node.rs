@@ -5,6 +5,10 @@ impl Node {
         &self.name
     }
 
+    pub fn parent(&self) -> Option<BoundaryNode> {
+        self.parent
+    }
+
     pub fn is_boundary(&self) -> bool {
         self.item.is_boundary()
     }
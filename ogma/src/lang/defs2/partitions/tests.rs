@@ -122,6 +122,39 @@ fn extending_partition_graph_with_fsmap() {
     };
 }
 
+#[test]
+fn qualified_names_and_boundary_paths() {
+    let p = Partitions::new()
+        .extend_plugins(mkmap([("tools", vec![Err("wrap")])]))
+        .unwrap()
+        .extend_root(mkmap([("foo/bar", vec![Err("impl-a")])]))
+        .unwrap();
+
+    let root = p.root().0;
+    let plugins = p.plugins().0;
+
+    let bar = p.boundary_path(Path::new("foo/bar"), root).unwrap();
+    assert_eq!(p.qualified(bar), "/foo/bar");
+    assert_eq!(p.qualified(root), "/");
+    assert!(p.boundary_path(Path::new("foo/zog"), root).is_none());
+    assert!(p.boundary_path(Path::new("tools"), root).is_none());
+
+    let tools = p.boundary_path(Path::new("tools"), plugins).unwrap();
+    assert_eq!(p.qualified(tools), "//tools");
+    assert_eq!(p.qualified(plugins), "//");
+
+    let x = p
+        .find_impls(bar, PartSet::empty(), "impl-a")
+        .next()
+        .unwrap();
+    assert_eq!(p.qualified(x), "/foo/bar/impl-a");
+    let x = p
+        .find_impls(tools, PartSet::empty(), "wrap")
+        .next()
+        .unwrap();
+    assert_eq!(p.qualified(x), "//tools/wrap");
+}
+
 #[test]
 fn extending_fails() {
     let x = Partitions::new()
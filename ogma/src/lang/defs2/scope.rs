@@ -0,0 +1,99 @@
+//! Partition scopes, resolving operations through the partitions from the file they are used in.
+use super::*;
+
+/// The partition scope of a file.
+#[derive(Debug, Clone)]
+pub(super) struct Scope {
+    /// The partition the file is within.
+    bnd: BoundaryNode,
+    /// The imported nodes.
+    imports: PartSet,
+    /// The import directive of each imported node, referenced in error messages.
+    tags: Vec<(Id, Tag)>,
+}
+
+impl Scope {
+    pub(super) fn build<'a, I>(parts: &Partitions, bnd: BoundaryNode, imports: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = &'a Import>,
+    {
+        let mut tags = Vec::new();
+        for import in imports {
+            let tag = import.tag();
+            for id in parts.resolve_imports(bnd, std::iter::once(import))? {
+                tags.push((id, tag.clone()));
+            }
+        }
+
+        let imports = PartSet::from_vec(tags.iter().map(|(id, _)| *id).collect(), parts);
+
+        Ok(Scope { bnd, imports, tags })
+    }
+}
+
+impl Definitions {
+    /// Resolve the impl nodes of the operation `op`, using the partition scope of the file `op` is
+    /// used in. Operations used outside a file are resolved from the root partition.
+    ///
+    /// Names are found in the file's partition and imports, names containing a partition delimiter
+    /// (`strings/pad`) are also found from the root partition. Impls in the file's partition are
+    /// used first, otherwise the impls must be exported from a single partition.
+    ///
+    /// No nodes are returned if `op` is not a partition impl, it then names an unqualified (shell
+    /// or `ogma` defined) operation.
+    pub fn resolve_impls(&self, op: &Tag) -> Result<Vec<ImplNode>> {
+        if !self.loaded {
+            return Ok(Vec::new());
+        }
+
+        let parts = &self.partitions;
+        let scope = match &op.anchor {
+            Location::File(f, _) => self.files.get(f),
+            _ => None,
+        };
+        let (bnd, imports) = scope
+            .map(|s| (s.bnd, &s.imports))
+            .unwrap_or((ROOT, PartSet::empty()));
+
+        let name = op.str();
+        let mut nodes = parts.find_impls(bnd, imports, name).collect::<Vec<_>>();
+        if nodes.is_empty() && bnd != ROOT && name.contains('/') {
+            nodes.extend(parts.find_impls(ROOT, PartSet::empty(), name));
+        }
+
+        let parent = |n: &ImplNode| parts[*n].parent();
+
+        if nodes.iter().any(|n| parent(n) == Some(bnd)) {
+            nodes.retain(|n| parent(n) == Some(bnd));
+            return Ok(nodes);
+        }
+
+        let (nodes, private): (Vec<_>, Vec<_>) = nodes.into_iter().partition(|n| {
+            parent(n)
+                .map(|p| parts.exports(p).contains_id(n.id()))
+                .unwrap_or_default()
+        });
+
+        if let (None, Some(n)) = (nodes.first(), private.first()) {
+            return Err(Error::op_not_exported(op, &parts.qualified(*n)));
+        }
+
+        let first = nodes.first().and_then(parent);
+        if nodes.iter().any(|n| parent(n) != first) {
+            let mut xs = Vec::<(Str, Tag)>::new();
+            for n in &nodes {
+                let key = Str::from(parts.qualified(*n));
+                let tag = scope
+                    .and_then(|s| s.tags.iter().find(|(id, _)| *id == n.id()))
+                    .map(|(_, tag)| tag.clone())
+                    .unwrap_or_else(|| op.clone());
+                if !xs.iter().any(|(k, _)| k == &key) {
+                    xs.push((key, tag));
+                }
+            }
+            return Err(Error::ambiguous_op(op, &xs));
+        }
+
+        Ok(nodes)
+    }
+}
@@ -1,4 +1,5 @@
 use super::*;
+use ::time::{Duration, OffsetDateTime};
 use std::cmp;
 
 pub fn add_intrinsics(impls: &mut Implementations) {
@@ -6,19 +7,42 @@ pub fn add_intrinsics(impls: &mut Implementations) {
         ("+", Number, add_num, Arithmetic)
         ("+", Str, add_str, Arithmetic)
         ("+", Table, add_table, Arithmetic)
+        ("+", OffsetDateTime, add_datetime, Arithmetic)
+        ("+", Duration, add_duration, Arithmetic)
 
         ("*", Number, mul_num, Arithmetic)
         ("×", Number, mul_num, Arithmetic)
 
         ("-", Number, sub_num, Arithmetic)
+        ("-", OffsetDateTime, sub_datetime, Arithmetic)
+        ("-", Duration, sub_duration, Arithmetic)
 
         ("/", Number, div_num, Arithmetic)
         ("÷", Number, div_num, Arithmetic)
 
         ("ceil", Number, ceil_num, Arithmetic)
+        ("clamp", Number, clamp_num, Arithmetic)
+        ("exp", Number, exp_num, Arithmetic)
         ("floor", Number, floor_num, Arithmetic)
+        ("hypot", Number, hypot_num, Arithmetic)
         ("is-finite", Number, isfinite_num, Arithmetic)
+        ("ln", Number, ln_num, Arithmetic)
+        ("log", Number, log_num, Arithmetic)
+        ("pow", Number, pow_num, Arithmetic)
         ("root", Number, root_num, Arithmetic)
+        ("round", Number, round_num, Arithmetic)
+        ("sign", Number, sign_num, Arithmetic)
+        ("trunc", Number, trunc_num, Arithmetic)
+
+        ("sin", Number, sin_num, Arithmetic)
+        ("cos", Number, cos_num, Arithmetic)
+        ("tan", Number, tan_num, Arithmetic)
+        ("asin", Number, asin_num, Arithmetic)
+        ("acos", Number, acos_num, Arithmetic)
+        ("atan", Number, atan_num, Arithmetic)
+
+        (e, Arithmetic)
+        (pi, Arithmetic)
     };
 }
 
@@ -32,6 +56,96 @@ where
     })
 }
 
+/// Successively applies each duration argument to the input.
+/// Errors if the result falls out of range.
+fn variadic_intrinsic_duration<T, F>(mut blk: Block, f: F) -> Result<Step>
+where
+    T: AsType + Into<Value> + 'static,
+    T: TryFrom<Value, Error = Error>,
+    F: Fn(T, Duration) -> Option<T> + Send + Sync + 'static,
+{
+    let ty = T::as_type();
+
+    blk.assert_input(&ty)?;
+    blk.assert_output(ty);
+
+    let len = blk.args_len();
+
+    if len == 0 {
+        let err_tag = blk.blk_tag().clone();
+        return Err(Error::insufficient_args(&err_tag, 0, None));
+    }
+
+    let mut args = Vec::with_capacity(len);
+    for _ in 0..len {
+        let arg = blk
+            .next_arg()?
+            .supplied(None)?
+            .returns(Ty::Duration)?
+            .concrete()?;
+        args.push(arg);
+    }
+
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |input, cx| {
+        let mut prev: T = input.clone().try_into()?;
+
+        for arg in &args {
+            let d: Duration = arg.resolve(|| input.clone(), &cx)?.try_into()?;
+            prev = f(prev, d).ok_or_else(|| out_of_range_err(&blktag))?;
+        }
+
+        cx.done_o(prev)
+    })
+}
+
+/// Applies `f` to the input number.
+fn unary_intrinsic_num<F>(blk: Block, f: F) -> Result<Step>
+where
+    F: Fn(f64) -> f64 + Send + Sync + 'static,
+{
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    blk.eval_o(move |n, cx| {
+        Number::try_from(n)
+            .map(|n| f(n.as_f64()))
+            .and_then(|n| cx.done_o(Number::from(n)))
+    })
+}
+
+/// Applies `f` to the input number and a number argument.
+fn binary_intrinsic_num<F>(mut blk: Block, f: F) -> Result<Step>
+where
+    F: Fn(f64, f64) -> f64 + Send + Sync + 'static,
+{
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    blk.eval_o(move |lhs, cx| {
+        let rhs = rhs
+            .resolve(|| lhs.clone(), &cx)
+            .and_then(Number::try_from)?
+            .as_f64();
+        let lhs = Number::try_from(lhs)?.as_f64();
+        cx.done_o(Number::from(f(lhs, rhs)))
+    })
+}
+
+fn out_of_range_err(blktag: &Tag) -> Error {
+    Error::eval(
+        blktag,
+        "result is out of range",
+        String::from("this overflows"),
+        None,
+    )
+}
+
 // ------ Add ------------------------------------------------------------------
 fn add_num_help() -> HelpMessage {
     variadic_help(
@@ -185,6 +299,42 @@ fn add_table_by_col(mut prev: Table, next: Table, intersect: bool) -> Table {
     prev
 }
 
+fn add_datetime_help() -> HelpMessage {
+    variadic_help(
+        "+",
+        "add durations to a datetime",
+        vec![
+            HelpExample {
+                desc: "the time in an hour",
+                code: "now | + {\\ 1 | to-duration --hours}",
+            },
+            HelpExample {
+                desc: "add multiple durations",
+                code: "now | + {\\ 1 | to-duration --days} {\\ 30 | to-duration --mins}",
+            },
+        ],
+    )
+}
+
+fn add_datetime_intrinsic(blk: Block) -> Result<Step> {
+    variadic_intrinsic_duration(blk, OffsetDateTime::checked_add)
+}
+
+fn add_duration_help() -> HelpMessage {
+    variadic_help(
+        "+",
+        "add durations together",
+        vec![HelpExample {
+            desc: "add 30 minutes to an hour",
+            code: "\\ 1 | to-duration --hours | + {\\ 30 | to-duration --mins}",
+        }],
+    )
+}
+
+fn add_duration_intrinsic(blk: Block) -> Result<Step> {
+    variadic_intrinsic_duration(blk, Duration::checked_add)
+}
+
 // ------ Ceil -----------------------------------------------------------------
 fn ceil_num_help() -> HelpMessage {
     HelpMessage {
@@ -204,6 +354,85 @@ fn ceil_num_intrinsic(blk: Block) -> Result<Step> {
     })
 }
 
+// ------ Clamp ----------------------------------------------------------------
+fn clamp_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "restrict a number to be within the minimum and maximum (inclusive)".into(),
+        params: vec![
+            HelpParameter::Required("min".into()),
+            HelpParameter::Required("max".into()),
+        ],
+        examples: vec![HelpExample {
+            desc: "clamp a percentage between 0 and 1",
+            code: "\\ 1.2 | clamp 0 1",
+        }],
+        ..HelpMessage::new("clamp")
+    }
+}
+
+fn clamp_num_intrinsic(mut blk: Block) -> Result<Step> {
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    let mut arg = || blk.next_arg()?.supplied(None)?.returns(Ty::Num)?.concrete();
+    let min = arg()?;
+    let max = arg()?;
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |n, cx| {
+        let min = min.resolve(|| n.clone(), &cx).and_then(Number::try_from)?;
+        let max = max.resolve(|| n.clone(), &cx).and_then(Number::try_from)?;
+        let n = Number::try_from(n)?;
+
+        // NaN bounds are also invalid
+        let valid = min.as_f64() <= max.as_f64();
+        if !valid {
+            return Err(Error::eval(
+                &blktag,
+                "clamp minimum is greater than the maximum",
+                format!("min is {} and max is {}", min, max),
+                None,
+            ));
+        }
+
+        let x = match (n.as_i128(), min.as_i128(), max.as_i128()) {
+            (Ok(n), Ok(min), Ok(max)) => Number::from(n.clamp(min, max)),
+            _ => Number::from(n.as_f64().clamp(min.as_f64(), max.as_f64())),
+        };
+        cx.done_o(x)
+    })
+}
+
+// ------ Constants ------------------------------------------------------------
+fn e_help() -> HelpMessage {
+    HelpMessage {
+        desc: "Euler's number (e ≈ 2.71828)".into(),
+        examples: vec![HelpExample {
+            desc: "the natural log of e is 1",
+            code: "e | ln",
+        }],
+        ..HelpMessage::new("e")
+    }
+}
+
+fn e_intrinsic(blk: Block) -> Result<Step> {
+    blk.eval_o(|_, cx| cx.done_o(Number::from(std::f64::consts::E)))
+}
+
+fn pi_help() -> HelpMessage {
+    HelpMessage {
+        desc: "the ratio of a circle's circumference to its diameter (π ≈ 3.14159)".into(),
+        examples: vec![HelpExample {
+            desc: "the area of a circle with a radius of 2",
+            code: "pi | * 2 2",
+        }],
+        ..HelpMessage::new("pi")
+    }
+}
+
+fn pi_intrinsic(blk: Block) -> Result<Step> {
+    blk.eval_o(|_, cx| cx.done_o(Number::from(std::f64::consts::PI)))
+}
+
 // ------ Div ------------------------------------------------------------------
 fn div_num_help() -> HelpMessage {
     variadic_help(
@@ -227,6 +456,22 @@ fn div_num_intrinsic(blk: Block) -> Result<Step> {
     variadic_intrinsic_num(blk, std::ops::Div::div)
 }
 
+// ------ Exp ------------------------------------------------------------------
+fn exp_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return e raised to the power of a number".into(),
+        examples: vec![HelpExample {
+            desc: "e squared",
+            code: "\\ 2 | exp",
+        }],
+        ..HelpMessage::new("exp")
+    }
+}
+
+fn exp_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::exp)
+}
+
 // ------ Floor ----------------------------------------------------------------
 fn floor_num_help() -> HelpMessage {
     HelpMessage {
@@ -246,6 +491,25 @@ fn floor_num_intrinsic(blk: Block) -> Result<Step> {
     })
 }
 
+// ------ Hypot ----------------------------------------------------------------
+fn hypot_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "calculate the length of the hypotenuse of a right-angle triangle
+the input and argument are the lengths of the other two sides"
+            .into(),
+        params: vec![HelpParameter::Required("y".into())],
+        examples: vec![HelpExample {
+            desc: "the hypotenuse of a 3-4-5 triangle",
+            code: "\\ 3 | hypot 4",
+        }],
+        ..HelpMessage::new("hypot")
+    }
+}
+
+fn hypot_num_intrinsic(blk: Block) -> Result<Step> {
+    binary_intrinsic_num(blk, f64::hypot)
+}
+
 // ------ Is Finite ------------------------------------------------------------
 fn isfinite_num_help() -> HelpMessage {
     HelpMessage {
@@ -277,6 +541,53 @@ fn isfinite_num_intrinsic(blk: Block) -> Result<Step> {
     }
 }
 
+// ------ Logarithms -----------------------------------------------------------
+fn ln_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the natural logarithm of a number".into(),
+        examples: vec![HelpExample {
+            desc: "the natural log of 10",
+            code: "\\ 10 | ln",
+        }],
+        ..HelpMessage::new("ln")
+    }
+}
+
+fn ln_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::ln)
+}
+
+fn log_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the logarithm of a number with respect to a base".into(),
+        params: vec![HelpParameter::Required("base".into())],
+        examples: vec![
+            HelpExample {
+                desc: "log base 10 of 1000",
+                code: "\\ 1000 | log 10",
+            },
+            HelpExample {
+                desc: "log base 2 of 8",
+                code: "\\ 8 | log 2",
+            },
+        ],
+        ..HelpMessage::new("log")
+    }
+}
+
+fn log_num_intrinsic(blk: Block) -> Result<Step> {
+    binary_intrinsic_num(blk, |x, base| {
+        // use the more accurate specialisations where possible
+        if base == 10.0 {
+            x.log10()
+        } else if base == 2.0 {
+            x.log2()
+        } else {
+            x.log(base)
+        }
+    })
+}
+
 // ------ Mul ------------------------------------------------------------------
 fn mul_num_help() -> HelpMessage {
     variadic_help(
@@ -299,6 +610,54 @@ fn mul_num_intrinsic(blk: Block) -> Result<Step> {
     variadic_intrinsic_num(blk, std::ops::Mul::mul)
 }
 
+// ------ Pow ------------------------------------------------------------------
+fn pow_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "raise a number to a power
+integers raised to non-negative integer powers are exact"
+            .into(),
+        params: vec![HelpParameter::Required("exponent".into())],
+        examples: vec![
+            HelpExample {
+                desc: "2 to the power of 10",
+                code: "\\ 2 | pow 10",
+            },
+            HelpExample {
+                desc: "the square root of 2",
+                code: "\\ 2 | pow 0.5",
+            },
+        ],
+        ..HelpMessage::new("pow")
+    }
+}
+
+fn pow_num_intrinsic(mut blk: Block) -> Result<Step> {
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    let exp = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    blk.eval_o(move |base, cx| {
+        let exp = exp
+            .resolve(|| base.clone(), &cx)
+            .and_then(Number::try_from)?;
+        let base = Number::try_from(base)?;
+        let int = base
+            .as_i128()
+            .ok()
+            .zip(exp.as_u128().ok().and_then(|e| u32::try_from(e).ok()))
+            .and_then(|(b, e)| b.checked_pow(e));
+        let x = match int {
+            Some(x) => Number::from(x),
+            None => Number::from(base.as_f64().powf(exp.as_f64())),
+        };
+        cx.done_o(x)
+    })
+}
+
 // ------ Root -----------------------------------------------------------------
 fn root_num_help() -> HelpMessage {
     HelpMessage {
@@ -344,6 +703,120 @@ fn root_num_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+// ------ Round ----------------------------------------------------------------
+fn round_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "round a number to a number of decimal places, defaulting to 0
+halfway values are rounded away from zero
+negative places round to the left of the decimal point"
+            .into(),
+        params: vec![HelpParameter::Optional("places".into())],
+        examples: vec![
+            HelpExample {
+                desc: "round to the nearest integer",
+                code: "\\ 2.5 | round",
+            },
+            HelpExample {
+                desc: "round to 2 decimal places",
+                code: "\\ 3.14159 | round 2",
+            },
+            HelpExample {
+                desc: "round to the nearest hundred",
+                code: "\\ 1234 | round -2",
+            },
+        ],
+        ..HelpMessage::new("round")
+    }
+}
+
+fn round_num_intrinsic(mut blk: Block) -> Result<Step> {
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    let places = match blk.args_len() {
+        0 => None,
+        _ => Some(
+            blk.next_arg()?
+                .supplied(None)?
+                .returns(Ty::Num)?
+                .concrete()?,
+        ),
+    };
+    blk.eval_o(move |n, cx| {
+        let places = match &places {
+            Some(arg) => {
+                let p = arg.resolve(|| n.clone(), &cx).and_then(Number::try_from)?;
+                p.as_i128()
+                    .ok()
+                    .and_then(|p| i32::try_from(p).ok())
+                    .ok_or_else(|| {
+                        Error::eval(
+                            &arg.tag,
+                            "decimal places must be an integer",
+                            format!("this resolves to {}", p),
+                            None,
+                        )
+                    })?
+            }
+            None => 0,
+        };
+
+        let n = Number::try_from(n)?;
+        let x = match n.as_i128() {
+            Ok(i) => round_int(i, places).map(Number::from),
+            Err(_) => None,
+        };
+        let x = x.unwrap_or_else(|| {
+            let m = 10f64.powi(places);
+            Number::from((n.as_f64() * m).round() / m)
+        });
+        cx.done_o(x)
+    })
+}
+
+/// Round an integer to `places`, returning `None` if it would overflow.
+fn round_int(i: i128, places: i32) -> Option<i128> {
+    if places >= 0 {
+        return Some(i);
+    }
+
+    let m = 10i128.checked_pow(places.unsigned_abs())?;
+    let r = i.unsigned_abs().checked_add(m.unsigned_abs() / 2)? / m.unsigned_abs();
+    let r = i128::try_from(r).ok()?.checked_mul(m)?;
+    Some(if i < 0 { -r } else { r })
+}
+
+// ------ Sign -----------------------------------------------------------------
+fn sign_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the sign of a number: -1 if negative, 0 if zero, and 1 if positive".into(),
+        examples: vec![HelpExample {
+            desc: "the sign of -5",
+            code: "\\ -5 | sign",
+        }],
+        ..HelpMessage::new("sign")
+    }
+}
+
+fn sign_num_intrinsic(blk: Block) -> Result<Step> {
+    if blk.in_ty() != &Ty::Num {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    blk.eval_o(|n, cx| {
+        let n = Number::try_from(n)?.as_f64();
+        let x = if n.is_nan() {
+            Number::from(n)
+        } else if n > 0.0 {
+            Number::from(1)
+        } else if n < 0.0 {
+            Number::from(-1)
+        } else {
+            Number::from(0)
+        };
+        cx.done_o(x)
+    })
+}
+
 // ------ Sub ------------------------------------------------------------------
 fn sub_num_help() -> HelpMessage {
     variadic_help(
@@ -365,3 +838,172 @@ fn sub_num_help() -> HelpMessage {
 fn sub_num_intrinsic(blk: Block) -> Result<Step> {
     variadic_intrinsic_num(blk, std::ops::Sub::sub)
 }
+
+fn sub_datetime_help() -> HelpMessage {
+    variadic_help(
+        "-",
+        "subtract durations from a datetime
+if a single datetime argument is given, the duration between the datetimes is returned",
+        vec![
+            HelpExample {
+                desc: "the time an hour ago",
+                code: "now | - {\\ 1 | to-duration --hours}",
+            },
+            HelpExample {
+                desc: "the time since the start of 2000",
+                code: "now | - {\\ '2000-01-01' | to-datetime}",
+            },
+        ],
+    )
+}
+
+fn sub_datetime_intrinsic(mut blk: Block) -> Result<Step> {
+    if blk.args_len() != 1 {
+        return variadic_intrinsic_duration(blk, OffsetDateTime::checked_sub);
+    }
+
+    blk.assert_input(&Ty::DateTime)?;
+
+    // the single argument can be a datetime or a duration, which changes the output type
+    let rhs = blk.next_arg()?.supplied(None)?.concrete()?;
+    match rhs.out_ty() {
+        Ty::DateTime => {
+            blk.assert_output(Ty::Duration);
+            blk.eval_o(move |lhs, cx| {
+                let lhs: OffsetDateTime = lhs.try_into()?;
+                let rhs: OffsetDateTime = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+                cx.done_o(lhs - rhs)
+            })
+        }
+        Ty::Duration => {
+            blk.assert_output(Ty::DateTime);
+            let blktag = blk.blk_tag().clone();
+            blk.eval_o(move |lhs, cx| {
+                let lhs: OffsetDateTime = lhs.try_into()?;
+                let rhs: Duration = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+                lhs.checked_sub(rhs)
+                    .ok_or_else(|| out_of_range_err(&blktag))
+                    .and_then(|x| cx.done_o(x))
+            })
+        }
+        x => Err(Error::unexp_arg_output_ty(&Ty::Duration, x, &rhs.tag)),
+    }
+}
+
+fn sub_duration_help() -> HelpMessage {
+    variadic_help(
+        "-",
+        "subtract durations from one another",
+        vec![HelpExample {
+            desc: "subtract 30 minutes from an hour",
+            code: "\\ 1 | to-duration --hours | - {\\ 30 | to-duration --mins}",
+        }],
+    )
+}
+
+fn sub_duration_intrinsic(blk: Block) -> Result<Step> {
+    variadic_intrinsic_duration(blk, Duration::checked_sub)
+}
+
+// ------ Trigonometry ---------------------------------------------------------
+fn trig_help(cmd: &'static str, desc: &str, example: (&'static str, &'static str)) -> HelpMessage {
+    HelpMessage {
+        desc: desc.into(),
+        examples: vec![HelpExample {
+            desc: example.0,
+            code: example.1,
+        }],
+        ..HelpMessage::new(cmd)
+    }
+}
+
+fn sin_num_help() -> HelpMessage {
+    trig_help(
+        "sin",
+        "return the sine of an angle in radians",
+        ("the sine of π/2", "pi | / 2 | sin"),
+    )
+}
+
+fn sin_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::sin)
+}
+
+fn cos_num_help() -> HelpMessage {
+    trig_help(
+        "cos",
+        "return the cosine of an angle in radians",
+        ("the cosine of π", "pi | cos"),
+    )
+}
+
+fn cos_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::cos)
+}
+
+fn tan_num_help() -> HelpMessage {
+    trig_help(
+        "tan",
+        "return the tangent of an angle in radians",
+        ("the tangent of π/4", "pi | / 4 | tan"),
+    )
+}
+
+fn tan_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::tan)
+}
+
+fn asin_num_help() -> HelpMessage {
+    trig_help(
+        "asin",
+        "return the arcsine of a number, in radians
+the result is between -π/2 and π/2, or NaN if the number is outside -1 to 1",
+        ("the arcsine of 1 is π/2", "\\ 1 | asin"),
+    )
+}
+
+fn asin_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::asin)
+}
+
+fn acos_num_help() -> HelpMessage {
+    trig_help(
+        "acos",
+        "return the arccosine of a number, in radians
+the result is between 0 and π, or NaN if the number is outside -1 to 1",
+        ("the arccosine of -1 is π", "\\ -1 | acos"),
+    )
+}
+
+fn acos_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::acos)
+}
+
+fn atan_num_help() -> HelpMessage {
+    trig_help(
+        "atan",
+        "return the arctangent of a number, in radians
+the result is between -π/2 and π/2",
+        ("the arctangent of 1 is π/4", "\\ 1 | atan"),
+    )
+}
+
+fn atan_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::atan)
+}
+
+// ------ Trunc ----------------------------------------------------------------
+fn trunc_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the integer part of a number, rounding towards zero".into(),
+        examples: vec![HelpExample {
+            desc: "truncate a negative number",
+            code: "\\ -3.7 | trunc",
+        }],
+        ..HelpMessage::new("trunc")
+    }
+}
+
+fn trunc_num_intrinsic(blk: Block) -> Result<Step> {
+    unary_intrinsic_num(blk, f64::trunc)
+}
@@ -1,4 +1,5 @@
 use super::*;
+use ::time::{Duration, OffsetDateTime};
 use std::cmp;
 
 pub fn add_intrinsics(impls: &mut Implementations) {
@@ -7,6 +8,8 @@ pub fn add_intrinsics(impls: &mut Implementations) {
         ("cmp", bool, cmp_bool, Cmp)
         ("cmp", Number, cmp_num, Cmp)
         ("cmp", Str, cmp_str, Cmp)
+        ("cmp", OffsetDateTime, cmp_datetime, Cmp)
+        ("cmp", Duration, cmp_duration, Cmp)
         ("cmp", cmp::Ordering, cmp_ord, Cmp)
         (cmp, Cmp)
 
@@ -14,6 +17,8 @@ pub fn add_intrinsics(impls: &mut Implementations) {
         ("eq", bool, eq_bool, Cmp)
         ("eq", Number, eq_num, Cmp)
         ("eq", Str, eq_str, Cmp)
+        ("eq", OffsetDateTime, eq_datetime, Cmp)
+        ("eq", Duration, eq_duration, Cmp)
         ("eq", cmp::Ordering, eq_ord, Cmp)
         (eq, Cmp)
 
@@ -120,6 +125,64 @@ fn cmp_str_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+fn cmp_datetime_help() -> HelpMessage {
+    HelpMessage {
+        desc: "compare <rhs> to input.
+datetimes are compared by their instant in time, regardless of UTC offset"
+            .into(),
+        params: vec![HelpParameter::Required("rhs:DateTime".into())],
+        examples: vec![HelpExample {
+            desc: "compare now to the start of 2000",
+            code: "now | cmp {\\ '2000-01-01' | to-datetime}",
+        }],
+        ..HelpMessage::new("cmp")
+    }
+}
+
+fn cmp_datetime_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Type::DateTime)?;
+    blk.assert_output(cmp::Ordering::as_type()); // all 'cmp's return an Ord
+
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::DateTime)?
+        .concrete()?;
+    blk.eval_o(move |lhs, cx| {
+        let lhs: OffsetDateTime = lhs.try_into()?;
+        let rhs: OffsetDateTime = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+        cx.done_o(lhs.cmp(&rhs))
+    })
+}
+
+fn cmp_duration_help() -> HelpMessage {
+    HelpMessage {
+        desc: "compare <rhs> to input.".into(),
+        params: vec![HelpParameter::Required("rhs:Duration".into())],
+        examples: vec![HelpExample {
+            desc: "compare 90 minutes to 1 hour",
+            code: "\\ 90 | to-duration --mins | cmp {\\ 1 | to-duration --hours}",
+        }],
+        ..HelpMessage::new("cmp")
+    }
+}
+
+fn cmp_duration_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Type::Duration)?;
+    blk.assert_output(cmp::Ordering::as_type()); // all 'cmp's return an Ord
+
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Duration)?
+        .concrete()?;
+    blk.eval_o(move |lhs, cx| {
+        let lhs: Duration = lhs.try_into()?;
+        let rhs: Duration = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+        cx.done_o(lhs.cmp(&rhs))
+    })
+}
+
 fn cmp_ord_help() -> HelpMessage {
     HelpMessage {
         desc: "compare <rhs> to input.".into(),
@@ -336,6 +399,61 @@ fn eq_str_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+fn eq_datetime_help() -> HelpMessage {
+    HelpMessage {
+        desc: "returns if <rhs> is equal to input
+datetimes are equal if they are the same instant in time, regardless of UTC offset"
+            .into(),
+        params: vec![HelpParameter::Required("rhs:DateTime".into())],
+        examples: vec![HelpExample {
+            desc: "the same instant in different offsets",
+            code:
+                "\\ '2022-01-01T10:00:00+10:00' | to-datetime | eq {\\ '2022-01-01' | to-datetime}",
+        }],
+        ..HelpMessage::new("eq")
+    }
+}
+
+fn eq_datetime_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::DateTime)?;
+    blk.assert_output(Ty::Bool); // equals always returns a boolean value (at least our intrinsic does)
+
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::DateTime)?
+        .concrete()?;
+    blk.eval_o(move |lhs, cx| {
+        let lhs: OffsetDateTime = lhs.try_into()?;
+        let rhs: OffsetDateTime = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+        cx.done_o(lhs.eq(&rhs))
+    })
+}
+
+fn eq_duration_help() -> HelpMessage {
+    HelpMessage {
+        desc: "returns if <rhs> is equal to input".into(),
+        params: vec![HelpParameter::Required("rhs:Duration".into())],
+        ..HelpMessage::new("eq")
+    }
+}
+
+fn eq_duration_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Duration)?;
+    blk.assert_output(Ty::Bool); // equals always returns a boolean value (at least our intrinsic does)
+
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Duration)?
+        .concrete()?;
+    blk.eval_o(move |lhs, cx| {
+        let lhs: Duration = lhs.try_into()?;
+        let rhs: Duration = rhs.resolve(|| lhs.into(), &cx)?.try_into()?;
+        cx.done_o(lhs.eq(&rhs))
+    })
+}
+
 fn eq_ord_help() -> HelpMessage {
     HelpMessage {
         desc: "returns if <rhs> is equal to input".into(),
@@ -0,0 +1,397 @@
+use super::*;
+use ::time::{
+    format_description::{self, well_known::Rfc3339, FormatItem},
+    macros::format_description as fmt_desc,
+    Date, Duration, OffsetDateTime, PrimitiveDateTime,
+};
+
+pub fn add_intrinsics(impls: &mut Implementations) {
+    add! { impls,
+        (now, Pipeline)
+
+        ("to-datetime", Str, to_datetime_str, Pipeline)
+
+        ("to-duration", Number, to_duration_num, Pipeline)
+        ("to-num", Duration, to_num_duration, Pipeline)
+
+        ("year", OffsetDateTime, year_datetime, Pipeline)
+        ("month", OffsetDateTime, month_datetime, Pipeline)
+        ("day", OffsetDateTime, day_datetime, Pipeline)
+        ("hour", OffsetDateTime, hour_datetime, Pipeline)
+        ("minute", OffsetDateTime, minute_datetime, Pipeline)
+        ("second", OffsetDateTime, second_datetime, Pipeline)
+        ("weekday", OffsetDateTime, weekday_datetime, Pipeline)
+    };
+}
+
+// ------ Helpers --------------------------------------------------------------
+/// The ISO 8601 formats tried, in order, after RFC 3339. These have no offset and are assumed UTC.
+const ISO_FMTS: &[&[FormatItem<'static>]] = &[
+    fmt_desc!("[year]-[month]-[day]T[hour]:[minute]:[second].[subsecond]"),
+    fmt_desc!("[year]-[month]-[day]T[hour]:[minute]:[second]"),
+    fmt_desc!("[year]-[month]-[day]T[hour]:[minute]"),
+    fmt_desc!("[year]-[month]-[day] [hour]:[minute]:[second].[subsecond]"),
+    fmt_desc!("[year]-[month]-[day] [hour]:[minute]:[second]"),
+    fmt_desc!("[year]-[month]-[day] [hour]:[minute]"),
+    fmt_desc!("[year]-[month]-[day]"),
+];
+
+/// Duration unit flags, with the number of seconds in each unit.
+const DURATION_UNITS: &[(&str, f64)] = &[
+    ("weeks", 604_800.0),
+    ("days", 86_400.0),
+    ("hours", 3_600.0),
+    ("mins", 60.0),
+    ("secs", 1.0),
+    ("ms", 0.001),
+];
+
+/// Gets the first duration unit flag, defaulting to seconds.
+///
+/// > The flag is **removed** from the block's flags.
+fn duration_unit_flag(blk: &mut Block) -> f64 {
+    DURATION_UNITS
+        .iter()
+        .find(|(flag, _)| blk.get_flag(*flag).is_some())
+        .map(|x| x.1)
+        .unwrap_or(1.0)
+}
+
+fn duration_unit_help_flags() -> Vec<(&'static str, &'static str)> {
+    vec![
+        ("weeks", "number of weeks"),
+        ("days", "number of days"),
+        ("hours", "number of hours"),
+        ("mins", "number of minutes"),
+        ("secs", "number of seconds (default)"),
+        ("ms", "number of milliseconds"),
+    ]
+}
+
+/// Parses a format description argument, such as `'[year]-[month]-[day]'`.
+///
+/// The argument must be a literal so the description can be checked at compile time.
+pub(super) fn datetime_fmt_arg(blk: &mut Block) -> Result<Str> {
+    let f = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Str)?
+        .concrete()?;
+    let s = f.extract_literal::<Str>()?.clone();
+    format_description::parse(&s).map_err(|e| Error {
+        cat: err::Category::Parsing,
+        desc: format!("invalid datetime format string: {}", e),
+        traces: err::trace(&f.tag, Some("invalid format string".into())),
+        help_msg: Some(
+            "datetime formatting syntax can be found at
+          <https://time-rs.github.io/book/api/format-description.html>"
+                .into(),
+        ),
+        hard: true,
+    })?;
+    Ok(s)
+}
+
+/// Parse using the format items. Formats without an offset are assumed UTC, and formats without a
+/// time are assumed midnight.
+fn parse_datetime(s: &str, items: &[FormatItem]) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s, items)
+        .ok()
+        .or_else(|| {
+            PrimitiveDateTime::parse(s, items)
+                .ok()
+                .map(PrimitiveDateTime::assume_utc)
+        })
+        .or_else(|| {
+            Date::parse(s, items)
+                .ok()
+                .map(|d| d.midnight().assume_utc())
+        })
+}
+
+/// Parse using RFC 3339, falling back to common ISO 8601 formats.
+fn parse_datetime_iso(s: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(s, &Rfc3339)
+        .ok()
+        .or_else(|| ISO_FMTS.iter().find_map(|items| parse_datetime(s, items)))
+}
+
+/// Builds an accessor returning a component of the input datetime as a number.
+fn datetime_component<F>(blk: Block, f: F) -> Result<Step>
+where
+    F: Fn(OffsetDateTime) -> i64 + Send + Sync + 'static,
+{
+    if blk.in_ty() != &Ty::DateTime {
+        return Err(Error::wrong_op_input_type(blk.in_ty(), blk.op_tag()));
+    }
+    blk.eval_o(move |dt, cx| {
+        OffsetDateTime::try_from(dt)
+            .map(&f)
+            .and_then(|x| cx.done_o(Number::from(x)))
+    })
+}
+
+fn component_help(cmd: &'static str, desc: &str) -> HelpMessage {
+    HelpMessage {
+        desc: desc.into(),
+        ..HelpMessage::new(cmd)
+    }
+}
+
+// ------ Now ------------------------------------------------------------------
+fn now_help() -> HelpMessage {
+    HelpMessage {
+        desc: "the current datetime, in UTC".into(),
+        examples: vec![HelpExample {
+            desc: "the current year",
+            code: "now | year",
+        }],
+        ..HelpMessage::new("now")
+    }
+}
+
+fn now_intrinsic(blk: Block) -> Result<Step> {
+    blk.eval_o(|_, cx| cx.done_o(OffsetDateTime::now_utc()))
+}
+
+// ------ To DateTime ----------------------------------------------------------
+fn to_datetime_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "parse a string into a datetime
+by default the string is expected in ISO 8601 format, such as `2022-03-04T12:30:00+10:00`
+a datetime without an offset is assumed to be UTC, and a date is assumed to be at midnight
+a custom format can be specified, see <https://time-rs.github.io/book/api/format-description.html>"
+            .into(),
+        params: vec![HelpParameter::Optional("fmt".into())],
+        examples: vec![
+            HelpExample {
+                desc: "parse an ISO 8601 datetime",
+                code: "\\ '2022-03-04T12:30:00Z' | to-datetime",
+            },
+            HelpExample {
+                desc: "parse a date",
+                code: "\\ '2022-03-04' | to-datetime",
+            },
+            HelpExample {
+                desc: "parse a date with a custom format",
+                code: "\\ '04/03/2022' | to-datetime '[day]/[month]/[year]'",
+            },
+        ],
+        ..HelpMessage::new("to-datetime")
+    }
+}
+
+fn to_datetime_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::DateTime);
+
+    let fmt = if blk.args_len() == 0 {
+        None
+    } else {
+        Some(datetime_fmt_arg(&mut blk)?)
+    };
+
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |s, cx| {
+        let s = Str::try_from(s)?;
+        let dt = match &fmt {
+            Some(fmt) => {
+                let items = format_description::parse(fmt).expect("checked at compile time");
+                parse_datetime(s.trim(), &items)
+            }
+            None => parse_datetime_iso(s.trim()),
+        };
+
+        match dt {
+            Some(dt) => cx.done_o(dt),
+            None => Err(Error::eval(
+                &blktag,
+                format!("failed to parse `{}` as a datetime", s),
+                String::from("within this block"),
+                match &fmt {
+                    Some(fmt) => format!("the string does not match the format `{}`", fmt),
+                    None => "expecting an ISO 8601 format such as `2022-03-04T12:30:00Z`
+          a custom format can be specified, for example `to-datetime '[day]/[month]/[year]'`"
+                        .to_string(),
+                },
+            )),
+        }
+    })
+}
+
+// ------ To Duration ----------------------------------------------------------
+fn to_duration_num_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a number into a duration
+the unit is specified with a flag and defaults to seconds"
+            .into(),
+        flags: duration_unit_help_flags(),
+        examples: vec![
+            HelpExample {
+                desc: "a duration of 90 seconds",
+                code: "\\ 90 | to-duration",
+            },
+            HelpExample {
+                desc: "a duration of 2.5 days",
+                code: "\\ 2.5 | to-duration --days",
+            },
+        ],
+        ..HelpMessage::new("to-duration")
+    }
+}
+
+fn to_duration_num_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Num)?;
+    blk.assert_output(Ty::Duration);
+
+    let unit = duration_unit_flag(&mut blk);
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |n, cx| {
+        let secs = Number::try_from(n)?.as_f64() * unit;
+        // guard the bounds of a duration, seconds are stored in an i64
+        if secs.is_finite() && secs.abs() < i64::MAX as f64 {
+            cx.done_o(Duration::seconds_f64(secs))
+        } else {
+            Err(Error::eval(
+                &blktag,
+                format!("`{}` seconds cannot be represented as a duration", secs),
+                String::from("this is out of range"),
+                None,
+            ))
+        }
+    })
+}
+
+fn to_num_duration_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a duration into a number
+the unit is specified with a flag and defaults to seconds"
+            .into(),
+        flags: duration_unit_help_flags(),
+        examples: vec![HelpExample {
+            desc: "the number of days since the start of 2000",
+            code: "now | - {\\ '2000-01-01' | to-datetime} | to-num --days",
+        }],
+        ..HelpMessage::new("to-num")
+    }
+}
+
+fn to_num_duration_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Duration)?;
+    blk.assert_output(Ty::Num);
+
+    let unit = duration_unit_flag(&mut blk);
+    blk.eval_o(move |d, cx| {
+        let d = Duration::try_from(d)?;
+        cx.done_o(Number::from(d.as_seconds_f64() / unit))
+    })
+}
+
+// ------ Components -----------------------------------------------------------
+fn year_datetime_help() -> HelpMessage {
+    component_help("year", "the year of the datetime")
+}
+
+fn year_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.year().into())
+}
+
+fn month_datetime_help() -> HelpMessage {
+    component_help("month", "the month of the datetime, from 1 to 12")
+}
+
+fn month_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| u8::from(dt.month()).into())
+}
+
+fn day_datetime_help() -> HelpMessage {
+    component_help("day", "the day of the month of the datetime, from 1 to 31")
+}
+
+fn day_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.day().into())
+}
+
+fn hour_datetime_help() -> HelpMessage {
+    component_help("hour", "the hour of the datetime, from 0 to 23")
+}
+
+fn hour_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.hour().into())
+}
+
+fn minute_datetime_help() -> HelpMessage {
+    component_help("minute", "the minute of the datetime, from 0 to 59")
+}
+
+fn minute_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.minute().into())
+}
+
+fn second_datetime_help() -> HelpMessage {
+    component_help("second", "the second of the datetime, from 0 to 59")
+}
+
+fn second_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.second().into())
+}
+
+fn weekday_datetime_help() -> HelpMessage {
+    HelpMessage {
+        desc: "the day of the week of the datetime, from 1 (Monday) to 7 (Sunday)
+use `to-str '[weekday]'` to get the name of the day"
+            .into(),
+        ..HelpMessage::new("weekday")
+    }
+}
+
+fn weekday_datetime_intrinsic(blk: Block) -> Result<Step> {
+    datetime_component(blk, |dt| dt.weekday().number_from_monday().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::time::macros::datetime;
+
+    #[test]
+    fn parse_datetime_iso_testing() {
+        let f = parse_datetime_iso;
+
+        assert_eq!(
+            f("2022-03-04T12:30:00Z"),
+            Some(datetime!(2022-03-04 12:30:00 UTC))
+        );
+        assert_eq!(
+            f("2022-03-04T12:30:00.25+10:00"),
+            Some(datetime!(2022-03-04 12:30:00.25 +10:00))
+        );
+        assert_eq!(
+            f("2022-03-04T12:30:00"),
+            Some(datetime!(2022-03-04 12:30:00 UTC))
+        );
+        assert_eq!(
+            f("2022-03-04 12:30"),
+            Some(datetime!(2022-03-04 12:30:00 UTC))
+        );
+        assert_eq!(f("2022-03-04"), Some(datetime!(2022-03-04 00:00:00 UTC)));
+        assert_eq!(f("04/03/2022"), None);
+        assert_eq!(f("2022-13-04"), None);
+    }
+
+    #[test]
+    fn parse_datetime_custom_testing() {
+        let items = format_description::parse("[day]/[month]/[year]").unwrap();
+        assert_eq!(
+            parse_datetime("04/03/2022", &items),
+            Some(datetime!(2022-03-04 00:00:00 UTC))
+        );
+
+        let items = format_description::parse("[day]/[month]/[year] [hour]:[minute]").unwrap();
+        assert_eq!(
+            parse_datetime("04/03/2022 13:45", &items),
+            Some(datetime!(2022-03-04 13:45:00 UTC))
+        );
+        assert_eq!(parse_datetime("2022-03-04", &items), None);
+    }
+}
@@ -1,6 +1,7 @@
 use super::*;
 use rt::fscache::FSCACHE;
-use std::io::{self, Write};
+use std::io::{self, BufRead, Write};
+use types::Chunks;
 
 pub fn add_intrinsics(impls: &mut Implementations) {
     add! { impls,
@@ -113,9 +114,9 @@ fn make_dir_table<P: AsRef<std::path::Path>>(dir: P, blk_tag: &Tag) -> Result<Ta
     use std::iter::*;
     use Entry::*;
 
-    let o = |s: &str| Obj(Str::new(s));
+    let o = |s: &str| Obj(Value::Str(Str::new(s)));
 
-    let mut table = ::table::DataTable::from(vec![vec![
+    let mut table = InnerTable::from(vec![vec![
         o("name"),
         o("type"),
         o("size"),
@@ -150,7 +151,11 @@ fn make_dir_table<P: AsRef<std::path::Path>>(dir: P, blk_tag: &Tag) -> Result<Ta
                         .unwrap_or(Nil),
                 ))
                 .chain(once(
-                    meta.modified().map(fmt_systime).map(Obj).unwrap_or(Nil),
+                    meta.modified()
+                        .map(systime_to_datetime)
+                        .map(Value::DateTime)
+                        .map(Obj)
+                        .unwrap_or(Nil),
                 ));
 
             table.add_row(row);
@@ -159,15 +164,19 @@ fn make_dir_table<P: AsRef<std::path::Path>>(dir: P, blk_tag: &Tag) -> Result<Ta
         }
     }
 
-    table.sort(0, |x, y| x.partial_cmp(y).unwrap());
+    // names are always strings
+    table.sort(0, |x, y| match (x, y) {
+        (Obj(Value::Str(x)), Obj(Value::Str(y))) => x.cmp(y),
+        _ => std::cmp::Ordering::Equal,
+    });
 
-    Ok(table.map_obj(Value::Str).into()) // convert to Table<Value>
+    Ok(table.into())
 }
 
-fn fmt_systime(systime: std::time::SystemTime) -> Str {
-    let fmt = &time::macros::format_description!("[year].[month].[day] [hour][minute]");
+/// Converts to UTC, truncating to whole seconds.
+fn systime_to_datetime(systime: std::time::SystemTime) -> time::OffsetDateTime {
     let dt = time::OffsetDateTime::from(systime);
-    dt.format(fmt).unwrap_or_default().into()
+    dt.replace_nanosecond(0).unwrap_or(dt)
 }
 
 // ------ Open -----------------------------------------------------------------
@@ -175,13 +184,27 @@ fn open_help() -> HelpMessage {
     HelpMessage {
         desc: "open something
 Table (default): parse file as a table
-String: reads file as string"
+Stream: lazily read the table in chunks, for files larger than memory
+String: reads file as string
+the table format is chosen by the file extension, defaulting to csv
+recognised extensions: csv, tsv, psv, json, ndjson/jsonl"
             .into(),
         params: vec![HelpParameter::Required("file".into())],
-        flags: vec![(
-            "<type>",
-            "open file as type. defaults to Table if not specified",
-        )],
+        flags: vec![
+            (
+                "<type>",
+                "open file as type. defaults to Table if not specified",
+            ),
+            ("csv", "parse table as comma separated values"),
+            ("tsv", "parse table as tab separated values"),
+            ("psv", "parse table as pipe separated values"),
+            ("json", "parse table as a JSON array of objects"),
+            ("ndjson", "parse table as newline delimited JSON objects"),
+            (
+                "infer",
+                "infer column types, parsing number and boolean columns",
+            ),
+        ],
         examples: vec![
             HelpExample {
                 desc: "open a csv as a table",
@@ -195,6 +218,18 @@ String: reads file as string"
                 desc: "open a file as a string",
                 code: "open --Str foo.txt",
             },
+            HelpExample {
+                desc: "open a tab separated file which has a txt extension",
+                code: "open --tsv export.txt",
+            },
+            HelpExample {
+                desc: "open a csv, parsing formatted numbers such as `$1,234`",
+                code: "open --infer sales.csv",
+            },
+            HelpExample {
+                desc: "stream a large csv, saving the filtered rows",
+                code: "open --Stream logs.csv | filter status > 499 | save errors.csv",
+            },
         ],
         ..HelpMessage::new("open")
     }
@@ -207,34 +242,63 @@ fn open_intrinsic(mut blk: Block) -> Result<Step> {
         .supplied(None)?
         .returns(Ty::Str)?
         .concrete()?;
+    // format flags need to be popped before the type flag, which takes the first flag
+    let fmt_flag = TableFmt::flag(&mut blk, &TableFmt::READ);
+    // formats which are only written are not expected
+    if let Some((_, flag)) = TableFmt::flag(&mut blk, &TableFmt::WRITE) {
+        return Err(Error::unused_flags(once(&flag)));
+    }
+    let infer = blk.get_flag("infer");
     // TODO make this output inferred / default to Table?
-    // AND make this to guessing of types based on file extension
     let as_ty = type_flag(&mut blk)?.unwrap_or(Ty::Tab);
 
     match as_ty {
         Ty::Tab => blk.eval_o(move |val, cx| {
-            // TODO make this better at reading in tables:
-            // 1. Recognise extension to choosing deserializing method?
-            // 2. Use a flag to choose deserializing method?
-            // 3. Support more than just comma for dsv types
-
             let p: Str = arg.resolve(|| val, &cx)?.try_into()?;
             let path = scrub_filepath(&p, &cx).map_err(|e| Error::io(&blktag, e))?;
-            let table = match FSCACHE.get::<Table>(&path) {
+            let fmt = fmt_flag
+                .as_ref()
+                .map(|x| x.0)
+                .unwrap_or_else(|| TableFmt::from_path(&path, &TableFmt::READ));
+            let table = match FSCACHE.get_fmt::<Table>(&path, fmt.name()) {
                 Some(table) => table,
                 None => {
                     let s: Str = read_file(&path).map_err(|e| Error::io(&blktag, e))?.into();
-
-                    let table = Table::from(
-                        ::table::parse_dsv(',', &s).map_obj(|s| Value::Str(Str::new(s))),
-                    );
-                    FSCACHE.insert(&path, table.clone());
+                    let table = fmt.parse(&s).map(Table::from).map_err(|e| {
+                        Error::eval(
+                            &blktag,
+                            format!("failed to parse `{}` as {}: {}", p, fmt.name(), e),
+                            String::from("within this block"),
+                            "the table format can be specified with a flag, such as `--csv`"
+                                .to_string(),
+                        )
+                    })?;
+                    FSCACHE.insert_fmt(&path, fmt.name(), table.clone());
                     table
                 }
             };
 
+            let table = match infer {
+                Some(_) => infer_col_types(table),
+                None => table,
+            };
+
             cx.done_o(table)
         }),
+        Ty::Stream => blk.eval_o(move |val, cx| {
+            let p: Str = arg.resolve(|| val, &cx)?.try_into()?;
+            let path = scrub_filepath(&p, &cx).map_err(|e| Error::io(&blktag, e))?;
+            let fmt = fmt_flag
+                .as_ref()
+                .map(|x| x.0)
+                .unwrap_or_else(|| TableFmt::from_path(&path, &TableFmt::READ));
+            let stream = open_stream(path, fmt, infer.is_some(), blktag.clone())?;
+            cx.done_o(stream)
+        }),
+        Ty::Str if fmt_flag.is_some() || infer.is_some() => {
+            let flags = fmt_flag.map(|x| x.1).into_iter().chain(infer);
+            Err(Error::unused_flags(flags.collect::<Vec<_>>().iter()))
+        }
         Ty::Str => blk.eval_o(move |val, cx| {
             let p: Str = arg.resolve(|| val, &cx)?.try_into()?;
             let path = scrub_filepath(&p, &cx).map_err(|e| Error::io(&blktag, e))?;
@@ -256,6 +320,458 @@ fn open_intrinsic(mut blk: Block) -> Result<Step> {
     }
 }
 
+/// Parse the string columns of a table where every string entry is a number or boolean.
+///
+/// Columns with no string entries are left as is.
+fn infer_col_types(table: Table) -> Table {
+    let cols = infer_cols(&table);
+    parse_cols(table, &cols)
+}
+
+/// The type a column is inferred as.
+#[derive(Debug, Clone, Copy)]
+enum Infer {
+    Num,
+    Bool,
+}
+
+/// Infer the type of each column, `None` if the column is left as is.
+fn infer_cols(table: &Table) -> Vec<Option<Infer>> {
+    use Entry::*;
+
+    (0..table.cols_len())
+        .map(|c| {
+            let col = || table.col(c).expect("inside table").skip(1);
+            let strs = col().filter(|e| matches!(e, Obj(Value::Str(_)))).count();
+            if strs == 0 {
+                return None;
+            }
+
+            let is_num = col().all(|e| match e {
+                Nil | Num(_) => true,
+                Obj(Value::Str(s)) => super::pipeline::parse_num(s, false).is_some(),
+                _ => false,
+            });
+            let is_bool = !is_num
+                && col().all(|e| match e {
+                    Nil | Obj(Value::Bool(_)) => true,
+                    Obj(Value::Str(s)) => super::pipeline::parse_bool(s).is_some(),
+                    _ => false,
+                });
+
+            match (is_num, is_bool) {
+                (true, _) => Some(Infer::Num),
+                (_, true) => Some(Infer::Bool),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/// Parse the string entries of the inferred columns. Entries which do not parse are left as is.
+fn parse_cols(mut table: Table, cols: &[Option<Infer>]) -> Table {
+    use Entry::*;
+
+    for (c, ty) in cols.iter().enumerate() {
+        let ty = match ty {
+            Some(ty) => *ty,
+            None => continue,
+        };
+
+        let col = match table.make_mut().col_mut(c) {
+            Some(col) => col.skip(1),
+            None => continue,
+        };
+        for e in col {
+            let x = match (&*e, ty) {
+                (Obj(Value::Str(s)), Infer::Num) => super::pipeline::parse_num(s, false).map(Num),
+                (Obj(Value::Str(s)), Infer::Bool) => {
+                    super::pipeline::parse_bool(s).map(|x| Obj(Value::Bool(x)))
+                }
+                _ => None,
+            };
+            if let Some(x) = x {
+                *e = x;
+            }
+        }
+    }
+
+    table
+}
+
+/// The serialisation formats of tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TableFmt {
+    /// Comma separated values.
+    Csv,
+    /// Tab separated values.
+    Tsv,
+    /// Pipe separated values.
+    Psv,
+    /// A JSON array of objects, each object is a row.
+    Json,
+    /// Newline delimited JSON objects, each line is a row.
+    Ndjson,
+    /// A markdown table, only supported for saving.
+    Md,
+}
+
+impl TableFmt {
+    /// The formats which can be read, markdown tables are only written.
+    const READ: [TableFmt; 5] = [
+        TableFmt::Csv,
+        TableFmt::Tsv,
+        TableFmt::Psv,
+        TableFmt::Json,
+        TableFmt::Ndjson,
+    ];
+
+    /// The formats which can be written.
+    const WRITE: [TableFmt; 6] = [
+        TableFmt::Csv,
+        TableFmt::Tsv,
+        TableFmt::Psv,
+        TableFmt::Json,
+        TableFmt::Ndjson,
+        TableFmt::Md,
+    ];
+
+    /// The format name, which is also the flag name.
+    fn name(self) -> &'static str {
+        match self {
+            TableFmt::Csv => "csv",
+            TableFmt::Tsv => "tsv",
+            TableFmt::Psv => "psv",
+            TableFmt::Json => "json",
+            TableFmt::Ndjson => "ndjson",
+            TableFmt::Md => "md",
+        }
+    }
+
+    /// Gets a format flag of `fmts`, such as `--tsv`, if one is specified.
+    ///
+    /// > The flag is **removed** from the block's flags.
+    fn flag(blk: &mut Block, fmts: &[Self]) -> Option<(Self, Tag)> {
+        fmts.iter()
+            .find_map(|&f| blk.get_flag(f.name()).map(|t| (f, t)))
+    }
+
+    /// Guess the format using the path's extension, defaulting to [`TableFmt::Csv`] if the
+    /// extension is not known or is not one of `fmts`.
+    fn from_path(path: &std::path::Path, fmts: &[Self]) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|x| x.to_str())
+            .map(|x| x.to_lowercase());
+
+        let fmt = match ext.as_deref() {
+            Some("tsv" | "tab") => TableFmt::Tsv,
+            Some("psv") => TableFmt::Psv,
+            Some("json") => TableFmt::Json,
+            Some("ndjson" | "jsonl") => TableFmt::Ndjson,
+            Some("md" | "markdown") => TableFmt::Md,
+            _ => TableFmt::Csv,
+        };
+
+        if fmts.contains(&fmt) {
+            fmt
+        } else {
+            TableFmt::Csv
+        }
+    }
+
+    /// The format can be read and written in chunks.
+    fn streams(self) -> bool {
+        !matches!(self, TableFmt::Json | TableFmt::Md)
+    }
+
+    /// Parse `s` into a table. The first row is the header row.
+    fn parse(self, s: &str) -> std::result::Result<InnerTable, String> {
+        let dsv = |delim| ::table::parse_dsv(delim, s).map_obj(|s| Value::Str(Str::new(s)));
+        match self {
+            TableFmt::Csv => Ok(dsv(',')),
+            TableFmt::Tsv => Ok(dsv('\t')),
+            TableFmt::Psv => Ok(dsv('|')),
+            TableFmt::Json => parse_json(s),
+            TableFmt::Ndjson => parse_ndjson(s),
+            TableFmt::Md => Err("markdown tables can only be saved".to_string()),
+        }
+    }
+}
+
+/// Parse a JSON array of objects into a table.
+fn parse_json(s: &str) -> std::result::Result<InnerTable, String> {
+    use ::libs::serde_json::{from_str, Value as J};
+
+    match from_str::<J>(s).map_err(|e| e.to_string())? {
+        J::Array(rows) => json_rows_into_table(rows),
+        _ => Err("expecting an array of objects".to_string()),
+    }
+}
+
+/// Parse newline delimited JSON objects into a table. Blank lines are skipped.
+fn parse_ndjson(s: &str) -> std::result::Result<InnerTable, String> {
+    let mut rows = Vec::new();
+    for (i, line) in s.lines().enumerate().filter(|x| !x.1.trim().is_empty()) {
+        let row =
+            ::libs::serde_json::from_str(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        rows.push(row);
+    }
+
+    json_rows_into_table(rows)
+}
+
+/// Each object is a row. The header is built from the object keys, in order of appearance.
+/// Missing keys are filled with nils.
+fn json_rows_into_table(
+    rows: Vec<::libs::serde_json::Value>,
+) -> std::result::Result<InnerTable, String> {
+    json_rows_into_table_with(rows, &mut Vec::new())
+}
+
+/// See [`json_rows_into_table`]. The header is seeded with `hdrs`, new keys are pushed onto it.
+fn json_rows_into_table_with(
+    rows: Vec<::libs::serde_json::Value>,
+    hdrs: &mut Vec<String>,
+) -> std::result::Result<InnerTable, String> {
+    use ::libs::serde_json::Value as J;
+
+    let mut idxs = hdrs
+        .iter()
+        .enumerate()
+        .map(|(i, k)| (k.clone(), i))
+        .collect::<HashMap<_, _>>();
+    let mut table = vec![Vec::new()]; // header row
+
+    for (i, row) in rows.into_iter().enumerate() {
+        let obj = match row {
+            J::Object(x) => x,
+            _ => return Err(format!("element {} is not an object", i)),
+        };
+
+        let mut r = Vec::new();
+        for (k, v) in obj {
+            let idx = *idxs.entry(k.clone()).or_insert_with(|| {
+                hdrs.push(k);
+                hdrs.len() - 1
+            });
+            if r.len() <= idx {
+                r.resize(idx + 1, Entry::Nil);
+            }
+            r[idx] = json_into_entry(v);
+        }
+        table.push(r);
+    }
+
+    let len = hdrs.len();
+    table[0] = hdrs.iter().cloned().map(o).collect();
+    table
+        .iter_mut()
+        .skip(1)
+        .for_each(|r| r.resize(len, Entry::Nil));
+
+    Ok(InnerTable::from(table))
+}
+
+fn json_into_entry(v: ::libs::serde_json::Value) -> Entry<Value> {
+    use ::libs::serde_json::Value as J;
+
+    match v {
+        J::Null => Entry::Nil,
+        J::Bool(x) => Entry::Obj(Value::Bool(x)),
+        J::Number(x) => x
+            .as_u64()
+            .map(Number::from)
+            .or_else(|| x.as_i64().map(Number::from))
+            .or_else(|| x.as_f64().map(Number::from))
+            .map(Entry::Num)
+            .unwrap_or(Entry::Nil),
+        J::String(x) => o(x),
+        // nested arrays and objects are kept as their JSON text
+        x => o(x.to_string()),
+    }
+}
+
+// ------ Streams --------------------------------------------------------------
+/// The number of rows in each chunk of a streamed table.
+const CHUNK_ROWS: usize = 10_000;
+
+/// Stream the table at `path`, reading [`CHUNK_ROWS`] rows at a time.
+///
+/// If inferring, the column types are inferred from the first chunk.
+fn open_stream(path: std::path::PathBuf, fmt: TableFmt, infer: bool, tag: Tag) -> Result<Stream> {
+    if !fmt.streams() {
+        return Err(Error::eval(
+            &tag,
+            format!("{} tables cannot be streamed", fmt.name()),
+            String::from("within this block"),
+            "streams support the csv, tsv, psv, and ndjson formats".to_string(),
+        ));
+    }
+
+    Ok(Stream::new(move || {
+        let file = std::fs::File::open(&path).map_err(|e| Error::io(&tag, e))?;
+        let rdr = ChunkReader::new(io::BufReader::new(file), fmt, CHUNK_ROWS, tag.clone());
+        let chunks: Chunks = if infer {
+            let mut cols = None;
+            Box::new(rdr.map(move |x| {
+                x.map(|table| {
+                    let cols = cols.get_or_insert_with(|| infer_cols(&table));
+                    parse_cols(table, cols)
+                })
+            }))
+        } else {
+            Box::new(rdr)
+        };
+        Ok(chunks)
+    }))
+}
+
+/// Reads a table in chunks of `rows`, each chunk is parsed with the header.
+struct ChunkReader<R> {
+    rdr: R,
+    fmt: TableFmt,
+    rows: usize,
+    tag: Tag,
+    /// The header record (delimited formats) or keys (ndjson), read by the first chunk.
+    hdr: Option<Vec<String>>,
+    /// The line number, used in ndjson errors.
+    line: usize,
+    done: bool,
+}
+
+impl<R: BufRead> ChunkReader<R> {
+    fn new(rdr: R, fmt: TableFmt, rows: usize, tag: Tag) -> Self {
+        Self {
+            rdr,
+            fmt,
+            rows,
+            tag,
+            hdr: None,
+            line: 0,
+            done: false,
+        }
+    }
+
+    fn read_dsv(&mut self) -> Result<Option<Table>> {
+        let first = self.hdr.is_none();
+        let mut buf = match self.hdr.as_ref().and_then(|x| x.first()) {
+            Some(hdr) => hdr.clone(),
+            None => {
+                let mut hdr = String::new();
+                self.read_record(&mut hdr)?;
+                self.hdr = Some(vec![hdr.clone()]);
+                hdr
+            }
+        };
+
+        let mut n = 0;
+        while n < self.rows && self.read_record(&mut buf)? > 0 {
+            n += 1;
+        }
+
+        if n == 0 && !first {
+            return Ok(None);
+        }
+
+        self.fmt
+            .parse(&buf)
+            .map(|x| Some(Table::from(x)))
+            .map_err(|e| self.parse_err(e))
+    }
+
+    /// Read a whole record onto `buf`, which spans lines if a quoted field contains new lines.
+    fn read_record(&mut self, buf: &mut String) -> Result<usize> {
+        let mut n = 0;
+        let mut quotes = 0;
+        loop {
+            let start = buf.len();
+            let x = self
+                .rdr
+                .read_line(buf)
+                .map_err(|e| Error::io(&self.tag, e))?;
+            n += x;
+            quotes += buf[start..].matches('"').count();
+            if x == 0 || quotes % 2 == 0 {
+                return Ok(n);
+            }
+        }
+    }
+
+    fn read_ndjson(&mut self) -> Result<Option<Table>> {
+        let first = self.hdr.is_none();
+        let mut hdrs = self.hdr.take().unwrap_or_default();
+        let mut rows = Vec::new();
+        let mut buf = String::new();
+
+        while rows.len() < self.rows {
+            buf.clear();
+            let x = self
+                .rdr
+                .read_line(&mut buf)
+                .map_err(|e| Error::io(&self.tag, e))?;
+            if x == 0 {
+                break;
+            }
+            self.line += 1;
+            if buf.trim().is_empty() {
+                continue;
+            }
+
+            let row: ::libs::serde_json::Value = ::libs::serde_json::from_str(&buf)
+                .map_err(|e| self.parse_err(format!("line {}: {}", self.line, e)))?;
+            // the header is fixed by the first chunk, since earlier chunks have been passed on
+            if !first {
+                let key = row
+                    .as_object()
+                    .and_then(|x| x.keys().find(|k| !hdrs.contains(*k)));
+                if let Some(key) = key {
+                    return Err(self.parse_err(format!(
+                        "line {}: key `{}` is not in the header of the first {} rows",
+                        self.line, key, self.rows
+                    )));
+                }
+            }
+            rows.push(row);
+        }
+
+        if rows.is_empty() && !first {
+            return Ok(None);
+        }
+
+        let table = json_rows_into_table_with(rows, &mut hdrs).map_err(|e| self.parse_err(e))?;
+        self.hdr = Some(hdrs);
+        Ok(Some(table.into()))
+    }
+
+    fn parse_err(&self, e: String) -> Error {
+        Error::eval(
+            &self.tag,
+            format!("failed to stream table as {}: {}", self.fmt.name(), e),
+            String::from("within this block"),
+            None,
+        )
+    }
+}
+
+impl<R: BufRead> Iterator for ChunkReader<R> {
+    type Item = Result<Table>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let x = match self.fmt {
+            TableFmt::Ndjson => self.read_ndjson(),
+            _ => self.read_dsv(),
+        };
+
+        self.done = !matches!(x, Ok(Some(_)));
+        x.transpose()
+    }
+}
+
 /// Read a file to a String, but not necessarily from UTF-8
 fn read_file(path: impl AsRef<std::path::Path>) -> io::Result<String> {
     use ::encoding::{all::UTF_8, decode, DecoderTrap};
@@ -269,14 +785,34 @@ fn read_file(path: impl AsRef<std::path::Path>) -> io::Result<String> {
 fn save_help() -> HelpMessage {
     HelpMessage {
         desc: "save the input to a file
-table input is saved as comma separated values"
+table input is saved in the format matching the file extension:
+  .csv (and unknown extensions) -> comma separated values
+  .tsv .tab                     -> tab separated values
+  .psv                          -> pipe separated values
+  .json                         -> JSON array of objects, one object per row
+  .ndjson .jsonl                -> newline delimited JSON objects
+  .md .markdown                 -> markdown table
+a format flag overrides the extension
+a Stream input is written chunk by chunk"
             .into(),
         params: vec![HelpParameter::Required("file".into())],
+        flags: vec![
+            ("csv", "save as comma separated values"),
+            ("tsv", "save as tab separated values"),
+            ("psv", "save as pipe separated values"),
+            ("json", "save as a JSON array of objects"),
+            ("ndjson", "save as newline delimited JSON objects"),
+            ("md", "save as a markdown table"),
+        ],
         examples: vec![
             HelpExample {
                 desc: "save table as a csv",
                 code: "open file1.csv | save file2.csv",
             },
+            HelpExample {
+                desc: "convert a csv file into json",
+                code: "open file1.csv | save file1.json",
+            },
             HelpExample {
                 desc: "save text as a string",
                 code: "\\ 'Hello, world!' | save hello-world.txt",
@@ -298,13 +834,15 @@ fn save_intrinsic(mut blk: Block) -> Result<Step> {
         .supplied(None)?
         .returns(Ty::Str)?
         .concrete()?;
+    let fmt_flag = TableFmt::flag(&mut blk, &TableFmt::WRITE).map(|x| x.0);
     let blktag = blk.blk_tag().clone();
     blk.eval(ty, move |val, cx| {
         let p: Str = filepath.resolve(|| val.clone(), &cx)?.try_into()?;
         let p = cx.root.join(cx.wd).join(p.as_str());
+        let fmt = fmt_flag.unwrap_or_else(|| TableFmt::from_path(&p, &TableFmt::WRITE));
         // check path doesn't go beyond root. can't use scrub_filepath as this uses
         // canonicalization for checking
-        {
+        let mut file = {
             use std::path::Component::*;
             let x = p.components().fold(0i32, |x, c| {
                 x + match c {
@@ -325,8 +863,12 @@ fn save_intrinsic(mut blk: Block) -> Result<Step> {
         }
         .and_then(|_| mkdirs(&p).and_then(|_| std::fs::File::create(p)))
         .map(std::io::BufWriter::new)
-        .and_then(|mut file| write_file(&mut file, val.clone()))
         .map_err(|e| Error::io(&blktag, e))?;
+
+        match &val {
+            Value::Stream(stream) => write_stream(&mut file, stream, fmt, &blktag)?,
+            x => write_file(&mut file, x.clone(), fmt).map_err(|e| Error::io(&blktag, e))?,
+        }
         cx.done(val)
     })
 }
@@ -341,53 +883,309 @@ fn mkdirs<P: AsRef<std::path::Path>>(p: P) -> io::Result<()> {
     Ok(())
 }
 
-fn write_file<W: Write>(file: &mut W, value: Value) -> io::Result<()> {
-    use ::numfmt::*;
-    fn fmt_cell<WW: Write>(
-        wtr: &mut WW,
-        entry: &Entry<Value>,
-        fmtr: &mut Formatter,
-    ) -> io::Result<()> {
-        match entry {
-            Entry::Obj(Value::Nil) | Entry::Nil => Ok(()), // don't write anything
-            e => {
-                let s = print::fmt_cell(e, fmtr);
-                // if s contains commas or new lines need to escape it.
-                if s.contains(&[',', '"', '\r', '\n'] as &[_]) {
-                    write!(wtr, "\"{}\"", s.escape_debug())
-                } else {
-                    write!(wtr, "{}", s)
+fn write_file<W: Write>(file: &mut W, value: Value, fmt: TableFmt) -> io::Result<()> {
+    let fmtr = &mut ::numfmt::Formatter::new();
+    match (value, fmt) {
+        (Value::Tab(table), TableFmt::Csv) => write_dsv(file, &table, ',', fmtr)?,
+        (Value::Tab(table), TableFmt::Tsv) => write_dsv(file, &table, '\t', fmtr)?,
+        (Value::Tab(table), TableFmt::Psv) => write_dsv(file, &table, '|', fmtr)?,
+        (Value::Tab(table), TableFmt::Json) => write_json(file, &table, fmtr)?,
+        (Value::Tab(table), TableFmt::Ndjson) => write_ndjson(file, &table, fmtr)?,
+        (Value::Tab(table), TableFmt::Md) => write_md(file, &table, fmtr)?,
+        (x, TableFmt::Json | TableFmt::Ndjson) => write!(file, "{}", value_into_json(&x, fmtr))?,
+        (x, _) => {
+            let x = print::fmt_cell(&Entry::from(x), fmtr);
+            write!(file, "{}", x)?;
+        }
+    }
+
+    file.flush()
+}
+
+/// Write the stream chunk by chunk. The header is written from the first chunk.
+fn write_stream<W: Write>(wtr: &mut W, stream: &Stream, fmt: TableFmt, tag: &Tag) -> Result<()> {
+    let fmtr = &mut ::numfmt::Formatter::new();
+    let mut hdrs = Vec::new();
+    let mut written = 0; // json rows written
+
+    let mut write_chunk = |wtr: &mut W, chunk: Table, first: bool| -> io::Result<()> {
+        let rows = || chunk.rows().skip(usize::from(!first));
+        match fmt {
+            TableFmt::Csv => write_dsv_rows(wtr, rows(), ',', fmtr, !first),
+            TableFmt::Tsv => write_dsv_rows(wtr, rows(), '\t', fmtr, !first),
+            TableFmt::Psv => write_dsv_rows(wtr, rows(), '|', fmtr, !first),
+            TableFmt::Json => {
+                if first {
+                    hdrs = json_hdrs(&chunk, fmtr);
+                    write!(wtr, "[")?;
                 }
+                write_json_rows(wtr, &hdrs, chunk.rows().skip(1), fmtr, &mut written)
             }
+            TableFmt::Ndjson => write_ndjson(wtr, &chunk, fmtr),
+            TableFmt::Md if first => write_md(wtr, &chunk, fmtr),
+            TableFmt::Md => rows().try_for_each(|row| write_md_row(wtr, row, fmtr)),
         }
+    };
+
+    let mut first = true;
+    for chunk in stream.chunks()? {
+        write_chunk(wtr, chunk?, first).map_err(|e| Error::io(tag, e))?;
+        first = false;
     }
 
-    let fmtr = &mut Formatter::new();
-    match value {
-        Value::Tab(table) => {
-            let mut add_newline = false;
-            for row in table.rows() {
-                if add_newline {
-                    writeln!(file)?;
-                }
-                add_newline |= true;
-                let mut add_comma = false;
-                for entry in row {
-                    if add_comma {
-                        write!(file, ",")?;
+    match fmt {
+        TableFmt::Json if first => write!(wtr, "[]"),
+        TableFmt::Json if written > 0 => writeln!(wtr).and_then(|_| write!(wtr, "]")),
+        TableFmt::Json => write!(wtr, "]"),
+        _ => Ok(()),
+    }
+    .and_then(|_| wtr.flush())
+    .map_err(|e| Error::io(tag, e))
+}
+
+/// Write delimiter separated values, quoting fields as per RFC 4180.
+fn write_dsv<W: Write>(
+    wtr: &mut W,
+    table: &Table,
+    delim: char,
+    fmtr: &mut ::numfmt::Formatter,
+) -> io::Result<()> {
+    write_dsv_rows(wtr, table.rows(), delim, fmtr, false)
+}
+
+/// See [`write_dsv`]. If `add_newline` is set, a new line is written before the first row.
+fn write_dsv_rows<'a, W, R, I>(
+    wtr: &mut W,
+    rows: R,
+    delim: char,
+    fmtr: &mut ::numfmt::Formatter,
+    mut add_newline: bool,
+) -> io::Result<()>
+where
+    W: Write,
+    R: Iterator<Item = I>,
+    I: Iterator<Item = &'a Entry<Value>>,
+{
+    for row in rows {
+        if add_newline {
+            writeln!(wtr)?;
+        }
+        add_newline |= true;
+        let mut add_delim = false;
+        for entry in row {
+            if add_delim {
+                write!(wtr, "{}", delim)?;
+            }
+            add_delim |= true;
+            match entry {
+                Entry::Obj(Value::Nil) | Entry::Nil => (), // don't write anything
+                e => {
+                    let s = print::fmt_cell(e, fmtr);
+                    // fields with delimiters, quotes, or new lines are quoted, with quotes doubled
+                    if s.contains(|c| c == delim || matches!(c, '"' | '\r' | '\n')) {
+                        write!(wtr, "\"{}\"", s.replace('"', "\"\""))?;
+                    } else {
+                        write!(wtr, "{}", s)?;
                     }
-                    add_comma |= true;
-                    fmt_cell(file, entry, fmtr)?;
                 }
             }
         }
-        x => {
-            let x = print::fmt_cell(&Entry::from(x), fmtr);
-            write!(file, "{}", x)?;
+    }
+
+    Ok(())
+}
+
+/// Write a JSON array of objects, each row is an object keyed by the header.
+fn write_json<W: Write>(
+    wtr: &mut W,
+    table: &Table,
+    fmtr: &mut ::numfmt::Formatter,
+) -> io::Result<()> {
+    let hdrs = json_hdrs(table, fmtr);
+    write!(wtr, "[")?;
+    write_json_rows(wtr, &hdrs, table.rows().skip(1), fmtr, &mut 0)?;
+    if table.rows_len() > 1 {
+        writeln!(wtr)?;
+    }
+    write!(wtr, "]")
+}
+
+/// Write the rows of a JSON array, `written` counts the rows written to the array.
+fn write_json_rows<'a, W, R, I>(
+    wtr: &mut W,
+    hdrs: &[String],
+    rows: R,
+    fmtr: &mut ::numfmt::Formatter,
+    written: &mut usize,
+) -> io::Result<()>
+where
+    W: Write,
+    R: Iterator<Item = I>,
+    I: Iterator<Item = &'a Entry<Value>>,
+{
+    for row in rows {
+        if *written > 0 {
+            write!(wtr, ",")?;
+        }
+        writeln!(wtr)?;
+        write_json_obj(wtr, hdrs, row, fmtr)?;
+        *written += 1;
+    }
+    Ok(())
+}
+
+/// Write each row as a JSON object on its own line.
+fn write_ndjson<W: Write>(
+    wtr: &mut W,
+    table: &Table,
+    fmtr: &mut ::numfmt::Formatter,
+) -> io::Result<()> {
+    let hdrs = json_hdrs(table, fmtr);
+    for row in table.rows().skip(1) {
+        write_json_obj(wtr, &hdrs, row, fmtr)?;
+        writeln!(wtr)?;
+    }
+    Ok(())
+}
+
+/// The header row as JSON keys.
+fn json_hdrs(table: &Table, fmtr: &mut ::numfmt::Formatter) -> Vec<String> {
+    table
+        .row(0)
+        .map(|r| r.map(|e| print::fmt_cell(e, fmtr).to_string()).collect())
+        .unwrap_or_default()
+}
+
+/// Writes the object by hand to keep the keys in column order.
+fn write_json_obj<'a, W, I>(
+    wtr: &mut W,
+    hdrs: &[String],
+    row: I,
+    fmtr: &mut ::numfmt::Formatter,
+) -> io::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = &'a Entry<Value>>,
+{
+    use ::libs::serde_json::Value as J;
+
+    write!(wtr, "{{")?;
+    for (i, (hdr, entry)) in hdrs.iter().zip(row).enumerate() {
+        if i > 0 {
+            write!(wtr, ",")?;
         }
+        let v = match entry {
+            Entry::Nil => J::Null,
+            Entry::Num(n) => num_into_json(n),
+            Entry::Obj(v) => value_into_json(v, fmtr),
+        };
+        write!(wtr, "{}:{}", J::from(hdr.as_str()), v)?;
     }
+    write!(wtr, "}}")
+}
 
-    file.flush()
+fn num_into_json(n: &Number) -> ::libs::serde_json::Value {
+    use ::libs::serde_json::{Number as N, Value as J};
+
+    let f = n.as_f64();
+    // keep whole numbers as integers, 2^53 is the limit of exact integers in a f64
+    if f.fract() == 0.0 && f.abs() < 9_007_199_254_740_992.0 {
+        J::from(f as i64)
+    } else {
+        N::from_f64(f).map(J::Number).unwrap_or(J::Null)
+    }
+}
+
+/// User types and tuples are serialised as objects keyed by field name, and lists as arrays.
+/// Sum type variants follow the externally tagged representation: unit variants are strings
+/// and variants with fields are `{ "Variant": { fields.. } }`.
+fn value_into_json(v: &Value, fmtr: &mut ::numfmt::Formatter) -> ::libs::serde_json::Value {
+    use ::libs::serde_json::{Map, Value as J};
+
+    fn fields_into_json(
+        fields: &[types::Field],
+        data: &[Value],
+        fmtr: &mut ::numfmt::Formatter,
+    ) -> J {
+        fields
+            .iter()
+            .zip(data)
+            .map(|(f, v)| (f.name().to_string(), value_into_json(v, fmtr)))
+            .collect::<Map<_, _>>()
+            .into()
+    }
+
+    match v {
+        Value::Nil => J::Null,
+        Value::Bool(b) => J::Bool(*b),
+        Value::Num(n) => num_into_json(n),
+        Value::Str(s) => J::from(s.as_str()),
+        Value::Tab(t) => {
+            let mut buf = Vec::new();
+            write_json(&mut buf, t, fmtr).ok();
+            ::libs::serde_json::from_slice(&buf).unwrap_or(J::Null)
+        }
+        Value::TabRow(_) => J::from("<table row>"), // this should not be reachable.
+        Value::Stream(_) => J::from("<stream>"),
+        Value::DateTime(dt) => J::from(print::fmt_datetime(dt)),
+        Value::Duration(d) => J::from(print::fmt_duration(d)),
+        Value::List(x) => J::Array(x.iter().map(|v| value_into_json(v, fmtr)).collect()),
+        Value::Ogma(x) => match x.ty().structure() {
+            types::TypeVariant::Sum(vars) => {
+                let var = &vars[x.variant_idx()];
+                match &var.fields {
+                    Some(fields) => {
+                        let mut map = Map::new();
+                        map.insert(
+                            var.name.to_string(),
+                            fields_into_json(fields, x.data(), fmtr),
+                        );
+                        J::Object(map)
+                    }
+                    None => J::from(var.name.str()),
+                }
+            }
+            types::TypeVariant::Product(fields) => fields_into_json(fields, x.data(), fmtr),
+        },
+    }
+}
+
+/// Write a markdown table. The first row is the header row.
+fn write_md<W: Write>(
+    wtr: &mut W,
+    table: &Table,
+    fmtr: &mut ::numfmt::Formatter,
+) -> io::Result<()> {
+    for (i, row) in table.rows().enumerate() {
+        write_md_row(wtr, row, fmtr)?;
+        if i == 0 {
+            write!(wtr, "|")?;
+            for _ in 0..table.cols_len() {
+                write!(wtr, " --- |")?;
+            }
+            writeln!(wtr)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_md_row<'a, W, I>(wtr: &mut W, row: I, fmtr: &mut ::numfmt::Formatter) -> io::Result<()>
+where
+    W: Write,
+    I: Iterator<Item = &'a Entry<Value>>,
+{
+    write!(wtr, "|")?;
+    for e in row {
+        let cell = match e {
+            Entry::Nil | Entry::Obj(Value::Nil) => String::new(),
+            e => print::fmt_cell(e, fmtr)
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace('\n', "<br>"),
+        };
+        write!(wtr, " {} |", cell)?;
+    }
+    writeln!(wtr)
 }
 
 #[cfg(test)]
@@ -398,7 +1196,7 @@ mod tests {
     fn write_file_testing() {
         let f = |v| {
             let mut wtr = Vec::new();
-            write_file(&mut wtr, v).unwrap();
+            write_file(&mut wtr, v, TableFmt::Csv).unwrap();
             String::from_utf8(wtr).unwrap()
         };
         let s = |s| Entry::Obj(Value::Str(Str::new(s)));
@@ -416,8 +1214,212 @@ mod tests {
                 vec![n(3.6e9), s("foo,bar"), s("foo \"zog\" bar")]
             ])),
             r#"1500000.0,2.5,
-foo,,"foo\nbar"
-3600000000.0,"foo,bar","foo \"zog\" bar""#
+foo,,"foo
+bar"
+3600000000.0,"foo,bar","foo ""zog"" bar""#
+        );
+    }
+
+    #[test]
+    fn write_file_fmts_testing() {
+        let f = |v, fmt| {
+            let mut wtr = Vec::new();
+            write_file(&mut wtr, v, fmt).unwrap();
+            String::from_utf8(wtr).unwrap()
+        };
+        let s = |s| Entry::Obj(Value::Str(Str::new(s)));
+        let n = |n: f64| Entry::Num(n.into());
+
+        let t = || {
+            Value::Tab(Table::from(::table::Table::from(vec![
+                vec![s("a"), s("b"), s("c")],
+                vec![n(1.0), s("x\ty"), Entry::Nil],
+                vec![n(2.5), s("p|q"), Entry::Obj(Value::Bool(true))],
+            ])))
+        };
+
+        assert_eq!(
+            &f(t(), TableFmt::Tsv),
+            "a\tb\tc\n1.0\t\"x\ty\"\t\n2.5\tp|q\ttrue"
+        );
+        assert_eq!(
+            &f(t(), TableFmt::Json),
+            r#"[
+{"a":1,"b":"x\ty","c":null},
+{"a":2.5,"b":"p|q","c":true}
+]"#
+        );
+        assert_eq!(
+            &f(t(), TableFmt::Ndjson),
+            r#"{"a":1,"b":"x\ty","c":null}
+{"a":2.5,"b":"p|q","c":true}
+"#
+        );
+        assert_eq!(
+            &f(t(), TableFmt::Md),
+            "| a | b | c |
+| --- | --- | --- |
+| 1.0 | x\ty |  |
+| 2.5 | p\\|q | true |
+"
+        );
+
+        let empty = Value::Tab(Table::from(::table::Table::from(vec![vec![s("a")]])));
+        assert_eq!(&f(empty, TableFmt::Json), "[]");
+
+        assert_eq!(
+            &f(Value::Str("a \"b\"".into()), TableFmt::Json),
+            r#""a \"b\"""#
+        );
+        assert_eq!(&f(Value::Num(3.into()), TableFmt::Ndjson), "3");
+    }
+
+    #[test]
+    fn table_fmt_from_path_testing() {
+        let f = |p: &str| TableFmt::from_path(std::path::Path::new(p), &TableFmt::WRITE);
+
+        assert_eq!(f("foo.csv"), TableFmt::Csv);
+        assert_eq!(f("foo.CSV"), TableFmt::Csv);
+        assert_eq!(f("foo.txt"), TableFmt::Csv);
+        assert_eq!(f("foo"), TableFmt::Csv);
+        assert_eq!(f("foo.tsv"), TableFmt::Tsv);
+        assert_eq!(f("foo.tab"), TableFmt::Tsv);
+        assert_eq!(f("path/to/foo.psv"), TableFmt::Psv);
+        assert_eq!(f("foo.json"), TableFmt::Json);
+        assert_eq!(f("foo.ndjson"), TableFmt::Ndjson);
+        assert_eq!(f("foo.jsonl"), TableFmt::Ndjson);
+        assert_eq!(f("foo.md"), TableFmt::Md);
+        assert_eq!(f("foo.markdown"), TableFmt::Md);
+
+        // markdown tables are only written, they are read as csv
+        let f = |p: &str| TableFmt::from_path(std::path::Path::new(p), &TableFmt::READ);
+        assert_eq!(f("foo.md"), TableFmt::Csv);
+        assert_eq!(f("foo.json"), TableFmt::Json);
+    }
+
+    #[test]
+    fn json_parsing_testing() {
+        let s = |s| Entry::Obj(Value::Str(Str::new(s)));
+        let n = |n: f64| Entry::Num(n.into());
+
+        let exp = InnerTable::from(vec![
+            vec![s("a"), s("b"), s("c")],
+            vec![n(1.0), s("foo"), Entry::Nil],
+            vec![n(2.5), Entry::Nil, Entry::Obj(Value::Bool(true))],
+        ]);
+
+        let t = TableFmt::Json
+            .parse(r#"[{"a":1,"b":"foo"},{"a":2.5,"b":null,"c":true}]"#)
+            .unwrap();
+        assert_eq!(t, exp);
+
+        let t = TableFmt::Ndjson
+            .parse("{\"a\":1,\"b\":\"foo\"}\n\n{\"a\":2.5,\"c\":true}\n")
+            .unwrap();
+        assert_eq!(t, exp);
+
+        assert_eq!(
+            TableFmt::Json.parse(r#"{"a":1}"#),
+            Err("expecting an array of objects".to_string())
+        );
+        assert_eq!(
+            TableFmt::Json.parse("[1, 2]"),
+            Err("element 0 is not an object".to_string())
+        );
+        assert!(TableFmt::Ndjson.parse("{\"a\":1}\n{").is_err());
+    }
+
+    #[test]
+    fn chunk_reader_testing() {
+        let s = |s| Entry::Obj(Value::Str(Str::new(s)));
+        let n = |n: f64| Entry::Num(n.into());
+        let chunks = |src: &'static str, fmt| {
+            ChunkReader::new(src.as_bytes(), fmt, 2, Tag::default())
+                .map(|x| x.map(|t| InnerTable::clone(&t)))
+                .collect::<Result<Vec<_>>>()
+        };
+
+        // each chunk carries the header
+        let x = chunks("a,b\n1,\"x, y\"\n2,z\n3,w\n", TableFmt::Csv).unwrap();
+        assert_eq!(
+            x,
+            vec![
+                InnerTable::from(vec![
+                    vec![s("a"), s("b")],
+                    vec![n(1.0), s("x, y")],
+                    vec![n(2.0), s("z")],
+                ]),
+                InnerTable::from(vec![vec![s("a"), s("b")], vec![n(3.0), s("w")]]),
+            ]
+        );
+
+        // quoted fields can span lines
+        let mut rdr = ChunkReader::new(
+            "1,\"x\ny\"\n2\n".as_bytes(),
+            TableFmt::Csv,
+            2,
+            Tag::default(),
         );
+        let mut buf = String::new();
+        assert_eq!(rdr.read_record(&mut buf).unwrap(), 8);
+        assert_eq!(&buf, "1,\"x\ny\"\n");
+
+        // header only
+        let x = chunks("a,b\n", TableFmt::Csv).unwrap();
+        assert_eq!(x, vec![InnerTable::from(vec![vec![s("a"), s("b")]])]);
+
+        let x = chunks("{\"a\":1}\n{\"b\":2}\n\n{\"a\":3}\n", TableFmt::Ndjson).unwrap();
+        assert_eq!(
+            x,
+            vec![
+                InnerTable::from(vec![
+                    vec![s("a"), s("b")],
+                    vec![n(1.0), Entry::Nil],
+                    vec![Entry::Nil, n(2.0)],
+                ]),
+                InnerTable::from(vec![vec![s("a"), s("b")], vec![n(3.0), Entry::Nil]]),
+            ]
+        );
+
+        let x = chunks("{\"a\":1}\n{\"a\":2}\n{\"c\":3}\n", TableFmt::Ndjson)
+            .unwrap_err()
+            .to_string();
+        assert!(x.contains("line 3: key `c` is not in the header of the first 2 rows"));
+    }
+
+    #[test]
+    fn write_stream_testing() {
+        let stream = |src: &'static str| {
+            Stream::new(move || {
+                let rdr = ChunkReader::new(src.as_bytes(), TableFmt::Ndjson, 2, Tag::default());
+                Ok(Box::new(rdr) as Chunks)
+            })
+        };
+        let write = |src, fmt| {
+            let mut buf = Vec::new();
+            write_stream(&mut buf, &stream(src), fmt, &Tag::default())
+                .map(|_| String::from_utf8(buf).unwrap())
+        };
+
+        // later chunks are written with the first chunk's keys, missing keys are nil
+        let src = "{\"a\":1,\"b\":2}\n{\"a\":3}\n{\"b\":4}\n{\"a\":5}\n";
+        assert_eq!(
+            write(src, TableFmt::Csv).unwrap(),
+            "a,b\n1.0,2.0\n3.0,\n,4.0\n5.0,"
+        );
+        assert_eq!(
+            write(src, TableFmt::Json).unwrap(),
+            "[\n{\"a\":1,\"b\":2},\n{\"a\":3,\"b\":null},\n{\"a\":null,\"b\":4},\n{\"a\":5,\"b\":null}\n]"
+        );
+        let t = stream(src).collect().unwrap();
+        assert!(t.rows().all(|r| r.count() == 2));
+
+        // keys unseen by the first chunk are an error, rather than widening the output
+        let src = "{\"a\":1}\n{\"a\":2}\n{\"a\":3,\"c\":4}\n";
+        let x = write(src, TableFmt::Csv).unwrap_err().to_string();
+        assert!(x.contains("line 3: key `c` is not in the header of the first 2 rows"));
+        let x = write(src, TableFmt::Json).unwrap_err().to_string();
+        assert!(x.contains("line 3: key `c` is not in the header of the first 2 rows"));
+        assert!(stream(src).collect().is_err());
     }
 }
@@ -4,8 +4,10 @@ pub fn add_intrinsics(impls: &mut Implementations) {
     add! { impls,
         (and, Logic)
         (if, Logic)
+        ("match", match_sum, Logic)
         ("not", bool, not_bool, Logic)
         (or, Logic)
+        ("try", try_expr, Logic)
     };
 }
 
@@ -109,6 +111,141 @@ fn if_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+// ------ Match ----------------------------------------------------------------
+fn match_sum_help() -> HelpMessage {
+    HelpMessage {
+        desc: "evaluate the expression of the arm matching the variant of a sum type
+each arm is a variant name followed by an expression
+the variant's fields are bound as variables within the arm's expression
+input is carried through to each of the expressions
+every variant must be matched, use `_` as a catch-all arm for the remaining variants
+all arm expressions must evaluate to the same type"
+            .into(),
+        params: vec![
+            HelpParameter::Required("variant-1".into()),
+            HelpParameter::Required("expr-1".into()),
+            HelpParameter::Optional("variant-2".into()),
+            HelpParameter::Optional("expr-2".into()),
+            HelpParameter::Required("...".into()),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "describe the ordering of 3 compared to 5",
+                code: "\\ 3 | cmp 5 | match Lt 'less' Eq 'equal' Gt 'greater'",
+            },
+            HelpExample {
+                desc: "use a catch-all arm",
+                code: "\\ 3 | cmp 5 | match Lt 'less' _ 'not less'",
+            },
+            HelpExample {
+                desc: "bind the fields of a variant",
+                code: "Weather::Rainy 5 | match Sunny 0 Rainy $mm",
+            },
+        ],
+        ..HelpMessage::new("match")
+    }
+}
+
+fn match_sum_intrinsic(mut blk: Block) -> Result<Step> {
+    let tydef = match blk.in_ty() {
+        Ty::Def(x) if matches!(x.structure(), types::TypeVariant::Sum(_)) => x.clone(),
+        x => {
+            let mut err = Error::wrong_op_input_type(x, blk.op_tag());
+            err.help_msg = Some("`match` works on the variants of sum types".into());
+            return Err(err);
+        }
+    };
+    let variants = match tydef.structure() {
+        types::TypeVariant::Sum(variants) => variants,
+        _ => unreachable!("just checked that we are on Sum type"),
+    };
+
+    let args = blk.args_len();
+    if args == 0 || args % 2 != 0 {
+        let mut e = Error::insufficient_args(blk.blk_tag(), args as u8, None);
+        e.help_msg = Some("`match` requires pairs of variant names and expressions".into());
+        return Err(e);
+    }
+
+    struct Arm {
+        vars: Vec<eng::Variable>,
+        expr: eng::Argument,
+    }
+
+    let mut arms: Vec<Option<Arm>> = repeat_with(|| None).take(variants.len()).collect();
+    let mut catchall: Option<Arm> = None;
+    let mut out_ty: Option<Type> = None;
+
+    while blk.args_len() > 1 {
+        let variant = blk.next_arg()?.supplied(Ty::Nil)?.concrete()?;
+        let name = variant.extract_literal::<Str>()?.as_str();
+
+        let (idx, fields) = if name == "_" {
+            (None, &[] as &[types::Field])
+        } else {
+            let idx = variants
+                .iter()
+                .position(|v| v.name.str() == name)
+                .ok_or_else(|| Error::variant_not_found(&variant.tag, &tydef))?;
+            let fields = variants[idx].fields.as_deref().unwrap_or_default();
+            (Some(idx), fields)
+        };
+
+        let vars = fields
+            .iter()
+            .map(|f| blk.inject_manual_var_next_arg(f.name().str(), f.ty().clone()))
+            .collect::<Result<Vec<_>>>()?;
+        let expr = blk.next_arg()?.supplied(None)?.concrete()?;
+
+        let ty = out_ty.get_or_insert_with(|| expr.out_ty().clone());
+        if *ty != *expr.out_ty() {
+            return Err(Error::eval(
+                &expr.tag,
+                "match arms do not have matching output types",
+                "this arm has a different output type".to_string(),
+                "branching impls require consistent output types".to_string(),
+            ));
+        }
+
+        let arm = match idx {
+            Some(idx) => &mut arms[idx],
+            None => &mut catchall,
+        };
+        if arm.is_some() {
+            return Err(Error::eval(
+                &variant.tag,
+                format!("`{}` is matched more than once", variant.tag),
+                "duplicate arm".to_string(),
+                "each variant can only have one arm".to_string(),
+            ));
+        }
+        *arm = Some(Arm { vars, expr });
+    }
+
+    if catchall.is_none() && arms.iter().any(Option::is_none) {
+        let missing = variants
+            .iter()
+            .zip(&arms)
+            .filter(|(_, arm)| arm.is_none())
+            .map(|(v, _)| &v.name);
+        return Err(Error::non_exhaustive_match(blk.blk_tag(), &tydef, missing));
+    }
+
+    let out_ty = out_ty.expect("at least one arm");
+
+    blk.eval(out_ty, move |input, mut cx| {
+        let data = OgmaData::try_from(input.clone())?;
+        let Arm { vars, expr } = arms[data.variant_idx()]
+            .as_ref()
+            .or(catchall.as_ref())
+            .expect("match is exhaustive");
+        for (var, value) in vars.iter().zip(data.data()) {
+            var.set_data(&mut cx.env, value.clone());
+        }
+        expr.resolve(|| input, &cx).and_then(|x| cx.done(x))
+    })
+}
+
 // ------ Not ------------------------------------------------------------------
 fn not_bool_help() -> HelpMessage {
     HelpMessage {
@@ -146,3 +283,47 @@ fn or_intrinsic(blk: Block) -> Result<Step> {
         (x, x) // short-circuit if true!
     })
 }
+
+// ------ Try ------------------------------------------------------------------
+fn try_expr_help() -> HelpMessage {
+    HelpMessage {
+        desc: "evaluate an expression, evaluating the fallback if the expression errors
+input is carried through to the expression
+the fallback's input is the `Error`, which has the fields `cat`, `desc`, and `trace`
+`expr` and `fallback` must evaluate to the same type"
+            .into(),
+        params: vec![
+            HelpParameter::Required("expr".into()),
+            HelpParameter::Required("fallback".into()),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "return -1 if a string is not a number",
+                code: "\\ 'abc' | try {to-num} -1",
+            },
+            HelpExample {
+                desc: "return the error description",
+                code: "\\ 'abc' | try {to-num | to-str} {get desc}",
+            },
+        ],
+        ..HelpMessage::new("try")
+    }
+}
+
+fn try_expr_intrinsic(mut blk: Block) -> Result<Step> {
+    let expr = blk.next_arg()?.supplied(None)?.concrete()?;
+    let out_ty = expr.out_ty().clone();
+    let fallback = blk
+        .next_arg()?
+        .supplied(Ty::Def(types::ERROR.get()))?
+        .returns(out_ty.clone())?
+        .concrete()?;
+
+    blk.eval(out_ty, move |input, cx| {
+        match expr.resolve(|| input, &cx) {
+            Ok(x) => Ok(x),
+            Err(e) => fallback.resolve(|| Value::from(&e), &cx),
+        }
+        .and_then(|x| cx.done(x))
+    })
+}
@@ -4,7 +4,7 @@ use ::libs::{divvy::Str, rayon::prelude::*};
 use ::paste::paste;
 use ::table::Entry;
 use ast::{Location, Tag};
-use eng::{AnonTypes, Block, Context, Step};
+use eng::{AnonTypes, Block, Context, Plan, Step};
 use lang::{help::*, impls::OperationCategory};
 use std::{
     convert::{TryFrom, TryInto},
@@ -42,20 +42,26 @@ macro_rules! add {
 
 mod arithmetic;
 mod cmp;
+mod datetime;
 mod diagnostics;
 mod io;
 mod logic;
 mod morphism;
 mod pipeline;
+mod stats;
+mod string;
 
 pub fn add_intrinsics(impls: &mut Implementations) {
     arithmetic::add_intrinsics(impls);
     cmp::add_intrinsics(impls);
+    datetime::add_intrinsics(impls);
     diagnostics::add_intrinsics(impls);
     io::add_intrinsics(impls);
     logic::add_intrinsics(impls);
     morphism::add_intrinsics(impls);
     pipeline::add_intrinsics(impls);
+    stats::add_intrinsics(impls);
+    string::add_intrinsics(impls);
 }
 
 // ------ Helpers --------------------------------------------------------------
@@ -203,12 +209,27 @@ fn type_flag(blk: &mut Block) -> Result<Option<Type>> {
             };
             match x {
                 Some(x) => Ok(x),
-                None => blk.defs().types().get_using_tag(&ty).map(|x| x.clone()),
+                None => blk.defs().types().get_using_tag(&ty),
             }
         })
         .transpose()
 }
 
+/// The element type of the block's list input.
+///
+/// The list implementations are agnostic to the input type, so this errors if the input is not a
+/// list.
+fn list_elem_ty(blk: &Block) -> Result<Type> {
+    blk.in_ty().list_elem().cloned().ok_or_else(|| {
+        let mut err = Error::wrong_op_input_type(blk.in_ty(), blk.op_tag());
+        err.help_msg = Some(format!(
+            "`{0}` supports `List` input, use `{0} --help` to view the supported input types",
+            blk.op_tag()
+        ));
+        err
+    })
+}
+
 /// Iterator over buf in a parallel fashion, invoking the callback `f` on each item of `buf`.
 ///
 /// This function is meant for iterating over table rows and updating-in-place `T`. The pattern to
@@ -240,6 +261,39 @@ where
         .map(|_| ())
 }
 
+/// Like [`par_over_tablerows`], but if `skip` is `true` the errors do not abort, instead each
+/// error is returned with the index of the row which failed. This supports `--skip-errors`.
+fn par_over_tablerows_skip<T, F>(
+    buf: &mut [T],
+    table: &Table,
+    cx: &Context,
+    skip: bool,
+    f: F,
+) -> Result<Vec<(usize, Error)>>
+where
+    F: Fn(&mut T, &mut Context, TableRow) -> Result<()> + Sync,
+    T: Send,
+{
+    if !skip {
+        return par_over_tablerows(buf, table, cx, f).map(|_| Vec::new());
+    }
+
+    let errs = crate::Mutex::new(Vec::new());
+    buf.par_iter_mut().enumerate().skip(1).for_each_init(
+        || (Default::default(), cx.clone()),
+        |(colmap, cx): &mut (types::TableRowColMap, _), (row, x)| {
+            let trow = TableRow::new(table.clone(), colmap.clone(), row);
+            if let Err(e) = f(x, cx, trow) {
+                errs.lock().push((row, e));
+            }
+        },
+    );
+
+    let mut errs = errs.into_inner();
+    errs.sort_by_key(|x| x.0);
+    Ok(errs)
+}
+
 /// Create an argument list where each argument expects Nil input and resolves to a Str.
 /// This is intended for cmds that take a variadic column names.
 struct ColNameArgs {
@@ -265,6 +319,14 @@ impl ColNameArgs {
         Ok(Self { names: x })
     }
 
+    /// The column names, if each is a literal.
+    fn literals(&self) -> Option<Vec<Str>> {
+        self.names
+            .iter()
+            .map(|x| x.extract_literal::<Str>().ok().cloned())
+            .collect()
+    }
+
     /// Resolves the column index, which **must** exist in the table.
     /// Returns the _name_ of the column that it was resolved to as well.
     fn resolve_indices(&self, table: &Table, cx: &Context) -> Result<Vec<(usize, Str)>> {
@@ -4,19 +4,41 @@ use std::{cell::RefCell, cmp, collections::BTreeMap, mem, rc::Rc};
 pub fn add_intrinsics(impls: &mut Implementations) {
     add! { impls,
     ("append", Table, append_table, Morphism)
+    ("append", Stream, append_stream, Morphism)
     ("append-row", Table, append_row, Morphism)
 
+    ("collect", Stream, collect_stream, Morphism)
+
     ("dedup", Str, dedup_str, Morphism)
     ("dedup", Table, dedup_table, Morphism)
 
     ("filter", Str, filter_str, Morphism)
     ("filter", Table, filter_table, Morphism)
+    ("filter", Stream, filter_stream, Morphism)
+    ("filter", filter_list, Morphism)
 
     ("fold", Table, fold_table, Morphism)
+    ("fold", Stream, fold_stream, Morphism)
+    ("fold", fold_list, Morphism)
     ("fold-while", Table, fold_while_table, Morphism)
+    ("reduce", Table, reduce_table, Morphism)
+    ("reduce", Stream, reduce_stream, Morphism)
     ("grp", Table, grp_table, Morphism)
     ("grp-by", Table, grpby_table, Morphism)
+    ("pivot", Table, pivot_table, Morphism)
+    ("unpivot", Table, unpivot_table, Morphism)
+    ("melt", Table, melt_table, Morphism)
+    ("join", Table, join_table, Morphism)
+    ("join-by", Table, joinby_table, Morphism)
     ("map", Table, map_table, Morphism)
+    ("map", Stream, map_stream, Morphism)
+    ("map", map_list, Morphism)
+    ("lag", Table, lag_table, Morphism)
+    ("lead", Table, lead_table, Morphism)
+    ("rolling", Table, rolling_table, Morphism)
+    ("cumsum", Table, cumsum_table, Morphism)
+    ("rank", Table, rank_table, Morphism)
+    ("row-number", Table, rownumber_table, Morphism)
     ("pick", Table, pick_table, Morphism)
     ("ren", Table, ren_table, Morphism)
     ("ren-with", Table, ren_with_table, Morphism)
@@ -26,15 +48,187 @@ pub fn add_intrinsics(impls: &mut Implementations) {
 
     ("skip", Str, skip_str, Morphism)
     ("skip", Table, skip_table, Morphism)
+    ("skip", Stream, skip_stream, Morphism)
 
     ("sort", Table, sort_table, Morphism)
+    ("sort", sort_list, Morphism)
     ("sort-by", Table, sortby_table, Morphism)
 
     ("take", Str, take_str, Morphism)
     ("take", Table, take_table, Morphism)
+    ("take", Stream, take_stream, Morphism)
     };
 }
 
+// ------ Skip Errors Flag -----------------------------------------------------
+const SKIP_ERRORS_FLAG: (&str, &str) = (
+    "skip-errors",
+    "remove rows which error, outputting a Tuple of the table and a table of the errors",
+);
+
+/// The `--skip-errors` flag, which collects row errors rather than failing the whole table.
+///
+/// Holds the `Tuple` output type if the flag was specified.
+struct SkipErrors(Option<Arc<types::TypeDef>>);
+
+impl SkipErrors {
+    /// Gets the `--skip-errors` flag and asserts the output type of the block.
+    fn flag(blk: &mut Block) -> Self {
+        let skip = blk
+            .get_flag("skip-errors")
+            .map(|_| Arc::new(Tuple::ty(vec![Ty::Tab, Ty::Tab])));
+
+        match &skip {
+            Some(ty) => {
+                let ty = Ty::Def(ty.clone());
+                blk.insert_anon_type_into_compiler(ty.clone());
+                blk.assert_output(ty);
+            }
+            None => blk.assert_output(Ty::Tab),
+        }
+
+        Self(skip)
+    }
+
+    fn skip(&self) -> bool {
+        self.0.is_some()
+    }
+
+    fn out_ty(&self) -> Type {
+        self.0.clone().map(Ty::Def).unwrap_or(Ty::Tab)
+    }
+
+    /// Build the output value.
+    ///
+    /// Rows which errored are removed from `table`, along with any rows not marked to `keep`.
+    /// If skipping, the output is a `Tuple` of the table and a table of the errors.
+    fn output(&self, table: Table, errs: Vec<(usize, Error)>, keep: Option<Vec<bool>>) -> Value {
+        let table = match (keep, errs.is_empty()) {
+            (None, true) => table,
+            (keep, _) => {
+                let mut keep = keep.unwrap_or_else(|| vec![true; table.rows_len()]);
+                for (row, _) in &errs {
+                    keep[*row] = false;
+                }
+                FilterTable::retain_keep_rows(table, &keep)
+            }
+        };
+
+        let ty = match &self.0 {
+            Some(ty) => ty.clone(),
+            None => return Value::Tab(table),
+        };
+
+        let mut rows = Vec::with_capacity(errs.len() + 1);
+        rows.push(
+            ["row", "cat", "desc", "trace"]
+                .iter()
+                .map(|x| Entry::Obj(Value::Str(Str::from(*x))))
+                .collect::<Vec<_>>(),
+        );
+        rows.extend(errs.into_iter().map(|(row, e)| {
+            vec![
+                Entry::Num(row.into()),
+                Entry::Obj(Value::Str(e.cat.to_string().into())),
+                Entry::Obj(Value::Str(Str::from(e.desc.as_str()))),
+                Entry::Obj(Value::Str(e.print_traces().into())),
+            ]
+        }));
+        let errs = Table::from(table::Table::from(rows));
+
+        OgmaData::new(ty, None, vec![Value::Tab(table), Value::Tab(errs)]).into()
+    }
+}
+
+// ------ Streams --------------------------------------------------------------
+/// An owned evaluation [`Context`], so stream chunks can be evaluated after the step has returned.
+struct StreamCx {
+    env: eng::Environment,
+    root: std::path::PathBuf,
+    wd: std::path::PathBuf,
+    depth: u32,
+}
+
+impl StreamCx {
+    fn new(cx: &Context) -> Self {
+        Self {
+            env: cx.env.clone(),
+            root: cx.root.to_path_buf(),
+            wd: cx.wd.to_path_buf(),
+            depth: cx.depth,
+        }
+    }
+
+    fn cx(&self) -> Context {
+        Context {
+            env: self.env.clone(),
+            root: &self.root,
+            wd: &self.wd,
+            depth: self.depth,
+        }
+    }
+}
+
+/// Streams do not support `--skip-errors`, the errors are only found as the stream is consumed.
+fn no_skip_errors(blk: &mut Block) -> Result<()> {
+    match blk.get_flag("skip-errors") {
+        Some(flag) => Err(Error::unused_flags(once(&flag))),
+        None => Ok(()),
+    }
+}
+
+/// Streams are processed chunk by chunk, so `prev` would not reach rows in earlier chunks.
+/// If the arguments use `prev`, the stream is collected into a single chunk.
+fn chunked_stream(blk: &Block) -> fn(Stream) -> Stream {
+    if blk.args_use_op("prev") {
+        |s| s.whole()
+    } else {
+        |s| s
+    }
+}
+
+/// The help of a table operation, altered for the stream implementation.
+fn stream_help(help: HelpMessage, desc: &str, examples: Vec<HelpExample>) -> HelpMessage {
+    HelpMessage {
+        desc: format!(
+            "{}
+the stream is processed chunk by chunk, if `prev` is used the stream is collected first",
+            desc
+        )
+        .into(),
+        flags: help
+            .flags
+            .into_iter()
+            .filter(|x| x != &SKIP_ERRORS_FLAG)
+            .collect(),
+        examples,
+        ..help
+    }
+}
+
+fn collect_stream_help() -> HelpMessage {
+    HelpMessage {
+        desc: "materialise a stream into a table
+operations without a Stream implementation collect the stream automatically"
+            .into(),
+        examples: vec![HelpExample {
+            desc: "collect a filtered stream",
+            code: "open --Stream logs.csv | filter status > 499 | collect",
+        }],
+        ..HelpMessage::new("collect")
+    }
+}
+
+fn collect_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Tab);
+    blk.eval_o(|stream, cx| {
+        Stream::try_from(stream)?
+            .collect()
+            .and_then(|x| cx.done_o(x))
+    })
+}
+
 // ------ Append ---------------------------------------------------------------
 fn append_table_help() -> HelpMessage {
     HelpMessage{
@@ -42,7 +236,10 @@ fn append_table_help() -> HelpMessage {
 each expression adds a new column, populated by row with the result of the expression
 tags can be optionally specified to name the columns".into(),
         params: vec![HelpParameter::Required("args..".into())],
-        flags: vec![("<col-names>", "name each column in order of expression")],
+        flags: vec![
+            ("<col-names>", "name each column in order of expression"),
+            SKIP_ERRORS_FLAG,
+        ],
         examples: vec![
             HelpExample {
                 desc: "flag if a filesystem item is a file",
@@ -59,8 +256,51 @@ tags can be optionally specified to name the columns".into(),
 
 fn append_table_intrinsic(mut blk: Block) -> Result<Step> {
     blk.assert_input(&Ty::Tab)?;
-    blk.assert_output(Ty::Tab);
+    // without `prev`, each entry only reads its own row
+    let row_local = !blk.args_use_op("prev");
+    let skip = SkipErrors::flag(&mut blk); // read before the column names
+    let cols = append_cols(&mut blk)?;
+    let plan = (row_local && !skip.skip()).then(|| Plan::Append {
+        cols: cols.iter().map(|x| x.1.clone()).collect(),
+        infallible: cols.iter().all(|x| x.0.infallible()),
+    });
+
+    blk.eval(skip.out_ty(), move |table, cx| {
+        let (table, errs) = append_table(table.try_into()?, &cx, &cols, skip.skip())?;
+        cx.done(skip.output(table, errs, None))
+    })
+    .map(|x| x.with_plan(plan))
+}
+
+fn append_stream_help() -> HelpMessage {
+    stream_help(
+        append_table_help(),
+        "add new columns onto a stream using one or more expressions",
+        vec![HelpExample {
+            desc: "flag slow requests in a large log file",
+            code: "open --Stream logs.csv | append { get ms | > 1000 } --slow | save flagged.csv",
+        }],
+    )
+}
 
+fn append_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Stream);
+    no_skip_errors(&mut blk)?; // read before the column names
+    let chunked = chunked_stream(&blk);
+    let cols = Arc::new(append_cols(&mut blk)?);
+
+    blk.eval_o(move |stream, cx| {
+        let scx = StreamCx::new(&cx);
+        let cols = Arc::clone(&cols);
+        let stream = chunked(Stream::try_from(stream)?)
+            .map_chunks(move |table| append_table(table, &scx.cx(), &cols, false).map(|x| x.0));
+        cx.done_o(stream)
+    })
+}
+
+/// The expressions to append, with the column names.
+fn append_cols(blk: &mut Block) -> Result<Vec<(eng::Argument, Str)>> {
     let len = blk.args_len();
     if len == 0 {
         return Err(Error::insufficient_args(blk.blk_tag(), 0, None));
@@ -78,12 +318,15 @@ fn append_table_intrinsic(mut blk: Block) -> Result<Step> {
         cols.push((arg, col));
     }
 
-    blk.eval_o(move |table, cx| {
-        append_table(table.try_into()?, &cx, &cols).and_then(|x| cx.done_o(x))
-    })
+    Ok(cols)
 }
 
-fn append_table(mut table: Table, cx: &Context, with: &[(eng::Argument, Str)]) -> Result<Table> {
+fn append_table(
+    mut table: Table,
+    cx: &Context,
+    with: &[(eng::Argument, Str)],
+    skip: bool,
+) -> Result<(Table, Vec<(usize, Error)>)> {
     let rows = table.rows_len();
     // seed column buffers
     let mut to_append = with
@@ -101,16 +344,20 @@ fn append_table(mut table: Table, cx: &Context, with: &[(eng::Argument, Str)]) -
     // calculate appending values
     // parallelised over the expressions _and_ the rows
     let err = crate::Mutex::new(None);
+    let skipped = crate::Mutex::new(Vec::new());
     to_append
         .par_iter_mut()
         .enumerate()
         .for_each(|(withidx, av)| {
             let predicate = with[withidx].0.resolver_sync(cx);
-            if let Err(e) = par_over_tablerows(av, &table, cx, |a, _, trow| {
+            match par_over_tablerows_skip(av, &table, cx, skip, |a, _, trow| {
                 *a = predicate(trow.into())?.into();
                 Ok(())
             }) {
-                *err.lock() = Some(e);
+                Ok(errs) => skipped
+                    .lock()
+                    .extend(errs.into_iter().map(|(row, e)| (row, withidx, e))),
+                Err(e) => *err.lock() = Some(e),
             }
         });
 
@@ -119,6 +366,12 @@ fn append_table(mut table: Table, cx: &Context, with: &[(eng::Argument, Str)]) -
         return Err(e);
     }
 
+    // a row can error in more than one expression, only the first expression's error is kept
+    let mut skipped = skipped.into_inner();
+    skipped.sort_by_key(|x| (x.0, x.1));
+    skipped.dedup_by_key(|x| x.0);
+    let skipped = skipped.into_iter().map(|(row, _, e)| (row, e)).collect();
+
     // append to the column, if ref shared, clone with known expansion of cols
     let to_append = to_append.into_iter().map(|x| x.into_iter());
     if let Some(t) = table.get_mut() {
@@ -129,7 +382,7 @@ fn append_table(mut table: Table, cx: &Context, with: &[(eng::Argument, Str)]) -
         table = t.into();
     }
 
-    Ok(table)
+    Ok((table, skipped))
 }
 
 // ------ Append-Row -----------------------------------------------------------
@@ -329,6 +582,7 @@ filtering columns is achievable with the --cols flag"
                 "cols",
                 "filter columns with predicate. predicate is String -> Bool",
             ),
+            SKIP_ERRORS_FLAG,
         ],
         examples: vec![
             HelpExample {
@@ -354,14 +608,55 @@ filtering columns is achievable with the --cols flag"
 
 fn filter_table_intrinsic(mut blk: Block) -> Result<Step> {
     blk.assert_input(&Ty::Tab)?;
-    blk.assert_output(Ty::Tab); // filtering Table -> Table
 
     let cols = blk.get_flag("cols").is_some();
     if cols {
+        blk.assert_output(Ty::Tab); // filtering Table -> Table
         filter_table_columns(blk)
     } else {
-        FilterTable::filter(blk)
+        let skip = SkipErrors::flag(&mut blk);
+        FilterTable::filter(blk, skip)
+    }
+}
+
+fn filter_stream_help() -> HelpMessage {
+    stream_help(
+        filter_table_help(),
+        "filter a stream using a predicate",
+        vec![HelpExample {
+            desc: "save the server errors of a large log file",
+            code: "open --Stream logs.csv | filter status > 499 | save errors.csv",
+        }],
+    )
+}
+
+fn filter_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Stream);
+
+    if blk.get_flag("cols").is_some() {
+        let predicate = Arc::new(filter_cols_predicate(&mut blk)?);
+        return blk.eval_o(move |stream, cx| {
+            let scx = StreamCx::new(&cx);
+            let predicate = Arc::clone(&predicate);
+            let stream = Stream::try_from(stream)?
+                .map_chunks(move |table| retain_cols(table, &predicate, &scx.cx()));
+            cx.done_o(stream)
+        });
     }
+
+    no_skip_errors(&mut blk)?;
+    let chunked = chunked_stream(&blk);
+    let ft = Arc::new(FilterTable::new(&mut blk)?);
+    blk.eval_o(move |stream, cx| {
+        let scx = StreamCx::new(&cx);
+        let ft = Arc::clone(&ft);
+        let stream = chunked(Stream::try_from(stream)?).map_chunks(move |table| {
+            let (keep, _) = ft.keep(&table, &scx.cx(), false)?;
+            Ok(FilterTable::retain_keep_rows(table, &keep))
+        });
+        cx.done_o(stream)
+    })
 }
 
 struct FilterTable {
@@ -371,9 +666,9 @@ struct FilterTable {
 }
 
 impl FilterTable {
-    fn filter(mut blk: Block) -> Result<Step> {
+    fn new(blk: &mut Block) -> Result<Self> {
         if blk.args_len() == 1 {
-            let ft = Box::new(Self {
+            Ok(Self {
                 expr_predicate: blk
                     .next_arg()?
                     .supplied(Ty::TabRow)?
@@ -381,12 +676,6 @@ impl FilterTable {
                     .concrete()?,
                 col: None,
                 exp_ty: Ty::Nil,
-            });
-
-            blk.eval_o(move |table, cx| {
-                let table: Table = table.try_into()?;
-                let keep = ft.gen_keep_arr_with_table_row_input(&table, &cx)?;
-                cx.done_o(FilterTable::retain_keep_rows(table, &keep))
             })
         } else {
             let col = blk
@@ -395,7 +684,7 @@ impl FilterTable {
                 .returns(Ty::Str)?
                 .concrete()?;
 
-            let ty_flag = type_flag(&mut blk)?;
+            let ty_flag = type_flag(blk)?;
 
             let expr_predicate = blk.next_arg()?;
             let expr_predicate = match ty_flag {
@@ -407,20 +696,45 @@ impl FilterTable {
 
             let exp_ty = expr_predicate.in_ty().clone();
 
-            let ft = Box::new(Self {
+            Ok(Self {
                 expr_predicate,
                 col: Some(col),
                 exp_ty,
-            });
-
-            blk.eval_o(move |table, cx| {
-                let table: Table = table.try_into()?;
-                let keep = ft.gen_keep_arr_with_known_col(&table, &cx)?;
-                cx.done_o(FilterTable::retain_keep_rows(table, &keep))
             })
         }
     }
 
+    fn filter(mut blk: Block, skip: SkipErrors) -> Result<Step> {
+        let ft = Box::new(Self::new(&mut blk)?);
+        let plan = ft.plan(&skip);
+        blk.eval(skip.out_ty(), move |table, cx| {
+            let table: Table = table.try_into()?;
+            let (keep, errs) = ft.keep(&table, &cx, skip.skip())?;
+            cx.done(skip.output(table, errs, Some(keep)))
+        })
+        .map(|x| x.with_plan(plan))
+    }
+
+    /// The predicate of a filter on a named column is only supplied the column's entry.
+    fn plan(&self, skip: &SkipErrors) -> Option<Plan> {
+        let col = self.col.as_ref().filter(|_| !skip.skip())?;
+        let col = col.extract_literal::<Str>().ok()?.clone();
+        Some(Plan::Filter { col })
+    }
+
+    /// Mark the rows to keep, with the errors of rows if skipping.
+    fn keep(
+        &self,
+        table: &Table,
+        cx: &Context,
+        skip: bool,
+    ) -> Result<(Vec<bool>, Vec<(usize, Error)>)> {
+        match &self.col {
+            Some(_) => self.gen_keep_arr_with_known_col(table, cx, skip),
+            None => self.gen_keep_arr_with_table_row_input(table, cx, skip),
+        }
+    }
+
     // filtering works by applying the predicate to each row and marking rows for
     // keeping if it passes the predicate.
     // Rows cannot be removed in the loop as the indexing will break,
@@ -428,19 +742,29 @@ impl FilterTable {
     // The specialised syntax which has the col-name out the front can leverage parallelisation
     // since the col-idx can be cached.
     // The normal filter can still utilise parallelisation by using for_each_init
-    fn gen_keep_arr_with_table_row_input(&self, table: &Table, cx: &Context) -> Result<Vec<bool>> {
+    fn gen_keep_arr_with_table_row_input(
+        &self,
+        table: &Table,
+        cx: &Context,
+        skip: bool,
+    ) -> Result<(Vec<bool>, Vec<(usize, Error)>)> {
         let mut keep = vec![true; table.rows_len()];
         let predicate = self.expr_predicate.resolver_sync(cx);
-        par_over_tablerows(&mut keep, table, cx, |k, _, trow| {
+        let errs = par_over_tablerows_skip(&mut keep, table, cx, skip, |k, _, trow| {
             predicate(trow.into())
                 .and_then(TryInto::try_into)
                 .map(|x| *k = x)
         })?;
 
-        Ok(keep)
+        Ok((keep, errs))
     }
 
-    fn gen_keep_arr_with_known_col(&self, table: &Table, cx: &Context) -> Result<Vec<bool>> {
+    fn gen_keep_arr_with_known_col(
+        &self,
+        table: &Table,
+        cx: &Context,
+        skip: bool,
+    ) -> Result<(Vec<bool>, Vec<(usize, Error)>)> {
         let Self {
             col, exp_ty: ty, ..
         } = self;
@@ -451,7 +775,14 @@ impl FilterTable {
         let mut keep = vec![true; table.rows_len()];
         let predicate = self.expr_predicate.resolver_sync(cx);
 
-        par_over_tablerows(&mut keep, table, cx, |k, _, trow| {
+        if let Some(mut errs) = Self::keep_typed_col(&mut keep, table, name, tag, ty, &predicate) {
+            if !skip && !errs.is_empty() {
+                return Err(errs.swap_remove(0).1);
+            }
+            return Ok((keep, errs));
+        }
+
+        let errs = par_over_tablerows_skip(&mut keep, table, cx, skip, |k, _, trow| {
             let i = trow.idx;
             let e = trow.entry(name, tag)?;
             match TableRow::cnv_value(e, ty, i, name, tag) {
@@ -460,7 +791,63 @@ impl FilterTable {
             }
         })?;
 
-        Ok(keep)
+        Ok((keep, errs))
+    }
+
+    /// Mark the rows to keep by scanning a typed column, reading the column view rather than the
+    /// table entries.
+    ///
+    /// Nil entries, and entries of a column not of type `ty`, fail the conversion and are not kept.
+    /// Returns `None` if the column is not found or is not typed, where the rows are used instead.
+    fn keep_typed_col<P>(
+        keep: &mut [bool],
+        table: &Table,
+        name: &str,
+        tag: &Tag,
+        ty: &Type,
+        predicate: P,
+    ) -> Option<Vec<(usize, Error)>>
+    where
+        P: Fn(Value) -> Result<Value> + Sync,
+    {
+        if !matches!(ty, Ty::Num | Ty::Str) {
+            return None;
+        }
+
+        use types::ColumnView as C;
+
+        let c = TableRow::col_idx(table, name, tag).ok()?;
+        let column = table.column_view(c);
+        let nils = column.nils()?;
+        let hit = matches!(
+            (&*column, ty),
+            (C::Num(..), Ty::Num) | (C::Str(..), Ty::Str)
+        );
+
+        let errs = crate::Mutex::new(Vec::new());
+        keep.par_iter_mut()
+            .enumerate()
+            .skip(1)
+            .for_each(|(idx, k)| {
+                if !hit || nils.is_nil(idx) {
+                    *k = false;
+                    return;
+                }
+
+                let e = match &*column {
+                    C::Num(xs, _) => Value::Num(xs[idx].into()),
+                    C::Str(xs, _) => Value::Str(xs[idx].clone()),
+                    C::Mixed => unreachable!("only typed columns are scanned"),
+                };
+                match predicate(e).and_then(TryInto::try_into) {
+                    Ok(x) => *k = x,
+                    Err(e) => errs.lock().push((idx, e)),
+                }
+            });
+
+        let mut errs = errs.into_inner();
+        errs.sort_by_key(|x| x.0);
+        Some(errs)
     }
 
     fn retain_keep_rows(mut table: Table, keep: &[bool]) -> Table {
@@ -488,34 +875,85 @@ impl FilterTable {
 }
 
 fn filter_table_columns(mut blk: Block) -> Result<Step> {
+    let predicate = filter_cols_predicate(&mut blk)?;
+    blk.eval_o(move |input, cx| {
+        let table = retain_cols(Table::try_from(input)?, &predicate, &cx)?;
+        cx.done_o(table)
+    })
+}
+
+fn filter_cols_predicate(blk: &mut Block) -> Result<eng::Argument> {
+    blk.next_arg()?
+        .supplied(Ty::Str)?
+        .returns(Ty::Bool)?
+        .concrete()
+}
+
+/// Retain the columns where the header passes the `predicate`.
+fn retain_cols(mut table: Table, predicate: &eng::Argument, cx: &Context) -> Result<Table> {
+    // work backwards on cols to remove
+    for idx in (0..table.cols_len()).rev() {
+        let hdr = table
+            .col(idx)
+            .and_then(|mut x| x.next())
+            .map(|v| match v {
+                Entry::Obj(Value::Str(h)) => h.clone(),
+                _ => Str::default(),
+            })
+            .unwrap_or_default();
+        let rm = predicate
+            .resolve(|| hdr.into(), cx)
+            .and_then(bool::try_from)?;
+        if !rm {
+            // delay cloning (if have to) to last moment
+            // should only clone _once_ (if have to)
+            table.make_mut().remove_col_par(idx);
+        }
+    }
+
+    Ok(table)
+}
+
+fn filter_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "filter a list, keeping the items which match a predicate".into(),
+        params: vec![HelpParameter::Required("<predicate>".into())],
+        examples: vec![HelpExample {
+            desc: "keep the numbers greater than 2",
+            code: "\\ [1 2 3 4] | filter > 2",
+        }],
+        ..HelpMessage::new("filter")
+    }
+}
+
+fn filter_list_intrinsic(mut blk: Block) -> Result<Step> {
+    let elem = list_elem_ty(&blk)?;
+    let ty = blk.in_ty().clone();
+    blk.assert_output(ty.clone());
+
     let predicate = blk
         .next_arg()?
-        .supplied(Ty::Str)?
+        .supplied(elem)?
         .returns(Ty::Bool)?
         .concrete()?;
-    blk.eval_o(move |input, cx| {
-        let mut table = Table::try_from(input)?;
-        // work backwards on cols to remove
-        for idx in (0..table.cols_len()).rev() {
-            let hdr = table
-                .col(idx)
-                .and_then(|mut x| x.next())
-                .map(|v| match v {
-                    Entry::Obj(Value::Str(h)) => h.clone(),
-                    _ => Str::default(),
-                })
-                .unwrap_or_default();
-            let rm = predicate
-                .resolve(|| hdr.into(), &cx)
-                .and_then(bool::try_from)?;
-            if !rm {
-                // delay cloning (if have to) to last moment
-                // should only clone _once_ (if have to)
-                table.make_mut().remove_col_par(idx);
-            }
-        }
 
-        cx.done_o(table)
+    blk.eval(ty, move |input, cx| {
+        let mut list = List::try_from(input)?;
+        let r = predicate.resolver_sync(&cx);
+        let mut e = None;
+        list.make_mut().retain(|x| {
+            r(x.clone()).and_then(bool::try_from).unwrap_or_else(|err| {
+                e = Some(err);
+                true
+            })
+        });
+
+        drop(r);
+
+        match e {
+            Some(e) => Err(e),
+            None => cx.done(list),
+        }
     })
 }
 
@@ -545,8 +983,114 @@ the variable $row is available to query the table row"
 }
 
 fn fold_table_intrinsic(mut blk: Block) -> Result<Step> {
-    blk.assert_adds_vars(true);
     blk.assert_input(&Ty::Tab)?;
+    let fold = FoldTable::new(&mut blk)?;
+
+    blk.eval(fold.out_ty.clone(), move |table, mut cx| {
+        let table: Table = table.try_into()?;
+        let x = fold.seed.resolve(|| Value::Nil, &cx)?;
+        let x = fold.fold(x, table, &mut cx)?;
+        cx.done(x)
+    })
+}
+
+fn fold_stream_help() -> HelpMessage {
+    stream_help(
+        fold_table_help(),
+        "fold (reduce) a stream into single value
+fold takes a seed value and an accumulator expression
+the variable $row is available to query the table row",
+        vec![HelpExample {
+            desc: "count the rows of a large file",
+            code: "open --Stream logs.csv | fold 0 + 1",
+        }],
+    )
+}
+
+fn fold_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    let chunked = chunked_stream(&blk);
+    let fold = FoldTable::new(&mut blk)?;
+
+    blk.eval(fold.out_ty.clone(), move |stream, mut cx| {
+        let stream = chunked(Stream::try_from(stream)?);
+        let mut x = fold.seed.resolve(|| Value::Nil, &cx)?;
+        for chunk in stream.chunks()? {
+            x = fold.fold(x, chunk?, &mut cx)?;
+        }
+        cx.done(x)
+    })
+}
+
+struct FoldTable {
+    seed: eng::Argument,
+    row_var: eng::Variable,
+    acc_expr: eng::Argument,
+    out_ty: Type,
+}
+
+impl FoldTable {
+    fn new(blk: &mut Block) -> Result<Self> {
+        blk.assert_adds_vars(true);
+
+        let seed = blk
+            .next_arg()?
+            .decouple_op_seal()
+            .supplied(Type::Nil)?
+            .concrete()?;
+        let out_ty = seed.out_ty().clone();
+        blk.assert_output(out_ty.clone());
+
+        let row_var = blk.inject_manual_var_next_arg("row", Ty::TabRow)?;
+        blk.assert_vars_added();
+        let acc_expr = blk
+            .next_arg()?
+            .supplied(out_ty.clone())? // accumulator supplies seed type
+            .returns(out_ty.clone())? // and must return seed type!
+            .concrete()?;
+
+        Ok(Self {
+            seed,
+            row_var,
+            acc_expr,
+            out_ty,
+        })
+    }
+
+    /// Fold the rows of `table` onto `x`.
+    fn fold(&self, mut x: Value, table: Table, cx: &mut Context) -> Result<Value> {
+        let colmap = types::TableRowColMap::default();
+        for idx in 1..table.rows_len() {
+            let trow = TableRow::new(table.clone(), colmap.clone(), idx);
+            self.row_var.set_data(&mut cx.env, trow.into());
+            x = self.acc_expr.resolve(|| x, cx)?;
+        }
+
+        Ok(x)
+    }
+}
+
+fn fold_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "fold (reduce) a list into single value
+fold takes a seed value and an accumulator expression
+the variable $item is available to query the list item"
+            .into(),
+        params: vec![
+            HelpParameter::Required("seed".into()),
+            HelpParameter::Required("accumulator".into()),
+        ],
+        examples: vec![HelpExample {
+            desc: "sum a list of numbers",
+            code: "\\ [1 2 3] | fold 0 + $item",
+        }],
+        ..HelpMessage::new("fold")
+    }
+}
+
+fn fold_list_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_adds_vars(true);
+    let elem = list_elem_ty(&blk)?;
 
     let seed = blk
         .next_arg()?
@@ -556,21 +1100,19 @@ fn fold_table_intrinsic(mut blk: Block) -> Result<Step> {
     let out_ty = seed.out_ty().clone();
     blk.assert_output(out_ty.clone());
 
-    let row_var = blk.inject_manual_var_next_arg("row", Ty::TabRow)?;
+    let item_var = blk.inject_manual_var_next_arg("item", elem)?;
     blk.assert_vars_added();
     let acc_expr = blk
         .next_arg()?
-        .supplied(out_ty.clone())? // accumulator supplies seed type
-        .returns(out_ty.clone())? // and must return seed type!
+        .supplied(out_ty.clone())?
+        .returns(out_ty.clone())?
         .concrete()?;
 
-    blk.eval(out_ty, move |table, mut cx| {
-        let table: Table = table.try_into()?;
-        let colmap = types::TableRowColMap::default();
+    blk.eval(out_ty, move |list, mut cx| {
+        let list = List::try_from(list)?;
         let mut x = seed.resolve(|| Value::Nil, &cx)?;
-        for idx in 1..table.rows_len() {
-            let trow = TableRow::new(table.clone(), colmap.clone(), idx);
-            row_var.set_data(&mut cx.env, trow.into());
+        for item in list.iter() {
+            item_var.set_data(&mut cx.env, item.clone());
             x = acc_expr.resolve(|| x, &cx)?;
         }
 
@@ -645,56 +1187,216 @@ fn fold_while_table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
-// ------ Grouping -------------------------------------------------------------
-fn grp_table_help() -> HelpMessage {
+// ------ Reduce ---------------------------------------------------------------
+fn reduce_table_help() -> HelpMessage {
     HelpMessage {
-        desc: "group a table by column headers
-each value under the header is stringified and
-concatenated together separated by a hyphen
-to group on a derived value see `grp-by`"
+        desc: "reduce table into single value, folding the rows in parallel
+reduce takes a seed value, an accumulator expression, and a combine expression
+the rows are split into chunks, each chunk is folded from the seed using the accumulator,
+and the chunk results are merged using the combine expression
+the variable $row is available to query the table row in the accumulator
+the variable $rhs is the result being merged in the combine expression
+the seed must not alter a result it is combined with, and the combine expression must be
+associative, otherwise the result depends on how the rows are split"
             .into(),
-        params: vec![HelpParameter::Required("col-name..".into())],
+        params: vec![
+            HelpParameter::Required("seed".into()),
+            HelpParameter::Required("accumulator".into()),
+            HelpParameter::Required("combine".into()),
+        ],
         examples: vec![
             HelpExample {
-                desc: "group by file extension",
-                code: "ls | grp ext",
+                desc: "sum numbers (0,10)",
+                code: "range 0 11 | reduce 0 { + $row.i } { + $rhs }",
             },
             HelpExample {
-                desc: "group by file extension and modified",
-                code: "ls | grp ext modified",
+                desc: "the maximum price of the diamonds",
+                code: "open diamonds.csv | reduce -inf { max $row.price } { max $rhs }",
             },
         ],
-        ..HelpMessage::new("grp")
+        ..HelpMessage::new("reduce")
     }
 }
 
-fn grp_table_intrinsic(mut blk: Block) -> Result<Step> {
+fn reduce_table_intrinsic(mut blk: Block) -> Result<Step> {
     blk.assert_input(&Ty::Tab)?;
-    blk.assert_output(Ty::Tab);
+    let reduce = ReduceTable::new(&mut blk)?;
 
-    let colnames = ColNameArgs::build(&mut blk)?;
-    let blktag = blk.blk_tag().clone();
-    blk.eval_o(move |table, cx| {
+    blk.eval(reduce.out_ty.clone(), move |table, cx| {
         let table: Table = table.try_into()?;
-        if table.is_empty() {
-            return Err(Error::eval(&blktag, "table is empty", None, None));
-        }
+        let seed = reduce.seed.resolve(|| Value::Nil, &cx)?;
+        let x = reduce.reduce(seed, &table, &cx)?;
+        cx.done(x)
+    })
+}
 
-        let cols = colnames.resolve_indices(&table, &cx)?;
+fn reduce_stream_help() -> HelpMessage {
+    stream_help(
+        reduce_table_help(),
+        "reduce a stream into single value, folding the rows of each chunk in parallel
+reduce takes a seed value, an accumulator expression, and a combine expression
+the variable $row is available to query the table row in the accumulator
+the variable $rhs is the result being merged in the combine expression",
+        vec![HelpExample {
+            desc: "count the rows of a large file",
+            code: "open --Stream logs.csv | reduce 0 { + 1 } { + $rhs }",
+        }],
+    )
+}
+
+fn reduce_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    let chunked = chunked_stream(&blk);
+    let reduce = ReduceTable::new(&mut blk)?;
+
+    blk.eval(reduce.out_ty.clone(), move |stream, cx| {
+        let stream = chunked(Stream::try_from(stream)?);
+        let seed = reduce.seed.resolve(|| Value::Nil, &cx)?;
+        let mut x = seed.clone();
+        for chunk in stream.chunks()? {
+            let y = reduce.reduce(seed.clone(), &chunk?, &cx)?;
+            x = reduce.combine(x, y, &cx)?;
+        }
+        cx.done(x)
+    })
+}
+
+struct ReduceTable {
+    seed: eng::Argument,
+    row_var: eng::Variable,
+    acc_expr: eng::Argument,
+    rhs_var: eng::Variable,
+    combine_expr: eng::Argument,
+    out_ty: Type,
+}
+
+impl ReduceTable {
+    fn new(blk: &mut Block) -> Result<Self> {
+        let args = blk.args_len();
+        if args < 3 {
+            return Err(Error::insufficient_args(blk.blk_tag(), args as u8, None));
+        }
+
+        blk.assert_adds_vars(true);
+
+        let seed = blk
+            .next_arg()?
+            .decouple_op_seal()
+            .supplied(Type::Nil)?
+            .concrete()?;
+        let out_ty = seed.out_ty().clone();
+        blk.assert_output(out_ty.clone());
+
+        let row_var = blk.inject_manual_var_next_arg("row", Ty::TabRow)?;
+        // the combine expression is the final argument
+        let combine = blk.peek_last_arg_node().expect("3 or more args");
+        let rhs_var = blk.inject_manual_var_into_arg_locals(combine, "rhs", out_ty.clone())?;
+        blk.assert_vars_added();
+
+        let acc_expr = blk
+            .next_arg()?
+            .supplied(out_ty.clone())? // accumulator supplies seed type
+            .returns(out_ty.clone())? // and must return seed type!
+            .concrete()?;
+        let combine_expr = blk
+            .next_arg()?
+            .supplied(out_ty.clone())?
+            .returns(out_ty.clone())?
+            .concrete()?;
+
+        Ok(Self {
+            seed,
+            row_var,
+            acc_expr,
+            rhs_var,
+            combine_expr,
+            out_ty,
+        })
+    }
+
+    /// Fold chunks of the rows of `table` in parallel, each starting from `seed`, and combine the
+    /// chunk results. A table without rows reduces to `seed`.
+    fn reduce(&self, seed: Value, table: &Table, cx: &Context) -> Result<Value> {
+        let x = (1..table.rows_len())
+            .into_par_iter()
+            .try_fold(
+                || (seed.clone(), cx.clone(), types::TableRowColMap::default()),
+                |(x, mut cx, colmap), idx| {
+                    let trow = TableRow::new(table.clone(), colmap.clone(), idx);
+                    self.row_var.set_data(&mut cx.env, trow.into());
+                    let x = self.acc_expr.resolve(|| x, &cx)?;
+                    Ok((x, cx, colmap))
+                },
+            )
+            .map(|x| x.map(|(x, ..)| x))
+            .try_reduce_with(|a, b| self.combine(a, b, cx))
+            .transpose()?;
+
+        Ok(x.unwrap_or(seed))
+    }
+
+    /// Combine `rhs` into `lhs`.
+    fn combine(&self, lhs: Value, rhs: Value, cx: &Context) -> Result<Value> {
+        let mut cx = cx.clone();
+        self.rhs_var.set_data(&mut cx.env, rhs);
+        self.combine_expr.resolve(|| lhs, &cx)
+    }
+}
+
+// ------ Grouping -------------------------------------------------------------
+fn grp_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "group a table by column headers
+each value under the header is stringified and
+concatenated together separated by a hyphen
+to group on a derived value see `grp-by`"
+            .into(),
+        params: vec![HelpParameter::Required("col-name..".into())],
+        examples: vec![
+            HelpExample {
+                desc: "group by file extension",
+                code: "ls | grp ext",
+            },
+            HelpExample {
+                desc: "group by file extension and modified",
+                code: "ls | grp ext modified",
+            },
+        ],
+        ..HelpMessage::new("grp")
+    }
+}
+
+fn grp_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let colnames = ColNameArgs::build(&mut blk)?;
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |table, cx| {
+        let table: Table = table.try_into()?;
+        if table.is_empty() {
+            return Err(Error::eval(&blktag, "table is empty", None, None));
+        }
+
+        let cols = colnames.resolve_indices(&table, &cx)?;
+        let keys = cols
+            .iter()
+            .map(|&(c, _)| (c, table.column_view(c)))
+            .collect::<Vec<_>>();
 
         let mut map = BTreeMap::default();
         let row = |i| table.row(i).expect("should exist");
         let fmtr = &mut numfmt::Formatter::new();
 
         for rowidx in 1..table.rows_len() {
-            let key = cols
+            let key = keys
                 .iter()
-                .map(|(c, _)| row(rowidx).nth(*c).expect("index inside table"))
+                .map(|(c, col)| grp_key(&table, rowidx, *c, col, fmtr))
                 .fold(String::new(), |mut k, e| {
                     if !k.is_empty() {
                         k.push('-');
                     }
-                    k.push_str(&print::fmt_cell(e, fmtr));
+                    k.push_str(&e);
                     k
                 });
             let t = map.entry(key).or_insert_with(|| {
@@ -709,6 +1411,22 @@ fn grp_table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+/// The stringified entry of a `grp` key, typed columns are read without matching the entry.
+fn grp_key(
+    table: &Table,
+    row: usize,
+    col: usize,
+    typed: &types::ColumnView,
+    fmtr: &mut numfmt::Formatter,
+) -> Str {
+    use types::ColumnView as C;
+    match typed {
+        C::Str(xs, nils) if !nils.is_nil(row) => xs[row].clone(),
+        C::Num(xs, nils) if !nils.is_nil(row) => Str::new(fmtr.fmt(xs[row])),
+        _ => print::fmt_cell(TableRow::entry_at(table, row, col), fmtr),
+    }
+}
+
 fn build_table_from_map<K, F>(map: BTreeMap<K, InnerTable>, mut key_f: F) -> Table
 where
     F: FnMut(K) -> Value,
@@ -812,144 +1530,1261 @@ where
         t.add_row(table.row(0).expect("has hdr").cloned());
         t
     };
-    let mut map = BTreeMap::new();
+    let mut map = BTreeMap::new();
+
+    let binop = Rc::new(RefCell::new(binop));
+    for (idx, value) in values.into_iter().skip(1) {
+        // skip header row
+        let key = GrpByKey {
+            value,
+            cx,
+            binop: Rc::clone(&binop),
+        };
+        let t = map.entry(key).or_insert_with(newtable);
+        t.add_row(table.row(idx).expect("row inside table").cloned());
+    }
+
+    build_table_from_map(map, |k| k.value)
+}
+
+// ------ Pivot ----------------------------------------------------------------
+fn pivot_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "reshape a table into wide form, aggregating the values of each row and column key
+a row is made for each distinct row key entry and a column for each distinct column key entry
+the aggregation expression is supplied a table of the value columns for the matching rows
+cells without any matching rows are nil
+to reshape into long form see `unpivot`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("row-key".into()),
+            HelpParameter::Required("col-key".into()),
+            HelpParameter::Required("value-col..".into()),
+            HelpParameter::Required("<agg>".into()),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "total sales by region (rows) and month (columns)",
+                code: "open sales.csv | pivot region month sales { fold 0 + $row.sales }",
+            },
+            HelpExample {
+                desc: "price per carat by clarity and cut",
+                code: "open diamonds.csv | pivot clarity cut price carat { let { fold 0 + $row.carat } $c | fold 0 + $row.price | / $c }",
+            },
+        ],
+        ..HelpMessage::new("pivot")
+    }
+}
+
+fn pivot_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    // row key, column key, then value columns, leaving the aggregation expression
+    let mut names = Vec::with_capacity(blk.args_len());
+    loop {
+        names.push(
+            blk.next_arg()?
+                .supplied(Ty::Nil)?
+                .returns(Ty::Str)?
+                .concrete()?,
+        );
+        if names.len() >= 3 && blk.args_len() <= 1 {
+            break;
+        }
+    }
+    let colnames = ColNameArgs { names };
+    let agg = blk.next_arg()?.supplied(Ty::Tab)?.concrete()?;
+    let blktag = blk.blk_tag().clone();
+
+    blk.eval_o(move |table, cx| {
+        let table: Table = table.try_into()?;
+        if table.is_empty() {
+            return Err(Error::eval(&blktag, "table is empty", None, None));
+        }
+
+        let cols = colnames.resolve_indices(&table, &cx)?;
+        let (rcol, ccol, vcols) = (&cols[0], cols[1].0, &cols[2..]);
+        let entry = |row, col| TableRow::entry_at(&table, row, col);
+
+        // split the value columns into a table for each cell
+        let fmtr = &mut numfmt::Formatter::new();
+        let (mut rkeys, mut ckeys) = (PivotKeys::default(), PivotKeys::default());
+        let mut cells: HashMap<(usize, usize), InnerTable> = HashMap::default();
+        for row in 1..table.rows_len() {
+            let r = rkeys.insert(entry(row, rcol.0), fmtr);
+            let c = ckeys.insert(entry(row, ccol), fmtr);
+            cells
+                .entry((r, c))
+                .or_insert_with(|| {
+                    let mut t = InnerTable::new();
+                    t.add_row(vcols.iter().map(|(_, n)| o(n.clone())));
+                    t
+                })
+                .add_row(vcols.iter().map(|&(c, _)| entry(row, c).clone()));
+        }
+
+        // aggregate each cell, in parallel
+        let mut cells = {
+            let agg = agg.resolver_sync(&cx);
+            cells
+                .into_par_iter()
+                .map(|(k, t)| agg(Value::Tab(t.into())).map(|v| (k, v)))
+                .collect::<Result<HashMap<_, _>>>()?
+        };
+
+        let (rorder, corder) = (rkeys.sorted(), ckeys.sorted());
+        let mut t = InnerTable::new();
+        t.add_row(once(o(rcol.1.clone())).chain(corder.iter().map(|&c| o(ckeys.name(c)))));
+        for r in rorder {
+            let aggs = corder
+                .iter()
+                .map(|&c| cells.remove(&(r, c)).map(Entry::from).unwrap_or(Entry::Nil));
+            t.add_row(once(rkeys.entry(r).clone()).chain(aggs));
+        }
+
+        cx.done_o(Table::from(t))
+    })
+}
+
+/// The distinct entries of a key column, identified by their stringified value.
+#[derive(Default)]
+struct PivotKeys {
+    map: HashMap<Str, usize>,
+    keys: Vec<(Entry<Value>, Str)>,
+}
+
+impl PivotKeys {
+    /// Insert the entry, returning the key's index.
+    fn insert(&mut self, entry: &Entry<Value>, fmtr: &mut numfmt::Formatter) -> usize {
+        let name = print::fmt_cell(entry, fmtr);
+        let idx = self.keys.len();
+        *self.map.entry(name.clone()).or_insert_with(|| {
+            self.keys.push((entry.clone(), name));
+            idx
+        })
+    }
+
+    fn entry(&self, idx: usize) -> &Entry<Value> {
+        &self.keys[idx].0
+    }
+
+    fn name(&self, idx: usize) -> Str {
+        self.keys[idx].1.clone()
+    }
+
+    /// The key indices, sorted canonically on the entries.
+    fn sorted(&self) -> Vec<usize> {
+        let mut x = (0..self.keys.len()).collect::<Vec<_>>();
+        x.sort_by(|&a, &b| cmp_table_entries(self.entry(a), self.entry(b)));
+        x
+    }
+}
+
+// ------ Unpivot --------------------------------------------------------------
+fn unpivot_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "reshape a table into long form, turning the specified columns into key-value rows
+a row is made for each specified column of each row, with the column name in `key`
+and the entry in `value`. the unspecified columns are repeated on each row
+to reshape into wide form see `pivot`"
+            .into(),
+        params: vec![HelpParameter::Required("col-name..".into())],
+        examples: vec![
+            HelpExample {
+                desc: "turn monthly sales columns into rows",
+                code: "open sales.csv | unpivot Jan Feb Mar",
+            },
+            HelpExample {
+                desc: "unpivot and then rename the key-value columns",
+                code: "open sales.csv | unpivot Jan Feb Mar | ren month sales",
+            },
+        ],
+        ..HelpMessage::new("unpivot")
+    }
+}
+
+fn unpivot_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let colnames = ColNameArgs::build(&mut blk)?;
+    let blktag = blk.blk_tag().clone();
+
+    blk.eval_o(move |table, cx| {
+        let table: Table = table.try_into()?;
+        if table.is_empty() {
+            return Err(Error::eval(&blktag, "table is empty", None, None));
+        }
+
+        let cols = colnames.resolve_indices(&table, &cx)?;
+        let ids = (0..table.cols_len())
+            .filter(|c| !cols.iter().any(|(x, _)| x == c))
+            .collect::<Vec<_>>();
+        let entry = |row, col| TableRow::entry_at(&table, row, col).clone();
+
+        let mut t = InnerTable::new();
+        t.add_row(
+            ids.iter()
+                .map(|&c| entry(0, c))
+                .chain(once(o("key")))
+                .chain(once(o("value"))),
+        );
+        for row in 1..table.rows_len() {
+            for (col, name) in &cols {
+                t.add_row(
+                    ids.iter()
+                        .map(|&c| entry(row, c))
+                        .chain(once(o(name.clone())))
+                        .chain(once(entry(row, *col))),
+                );
+            }
+        }
+
+        cx.done_o(Table::from(t))
+    })
+}
+
+fn melt_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "reshape a table into long form, turning the specified columns into key-value rows
+this is an alias of `unpivot`"
+            .into(),
+        params: vec![HelpParameter::Required("col-name..".into())],
+        examples: vec![HelpExample {
+            desc: "turn monthly sales columns into rows",
+            code: "open sales.csv | melt Jan Feb Mar",
+        }],
+        ..HelpMessage::new("melt")
+    }
+}
+
+fn melt_table_intrinsic(blk: Block) -> Result<Step> {
+    unpivot_table_intrinsic(blk)
+}
+
+// ------ Join -----------------------------------------------------------------
+fn join_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "join the input table with another table where the key columns match
+the key columns must exist in both tables and only appear once in the result
+clashing column names from the other table are suffixed with `_rhs`
+to join on a derived value see `join-by`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("table".into()),
+            HelpParameter::Required("col-name..".into()),
+        ],
+        flags: JoinKind::help_flags(),
+        examples: vec![
+            HelpExample {
+                desc: "join the files of two directories by name",
+                code: "ls | join { ls other-dir } name",
+            },
+            HelpExample {
+                desc: "keep all sales, joining on region and country",
+                code: "open sales.csv | join --left { open regions.csv } region country",
+            },
+        ],
+        ..HelpMessage::new("join")
+    }
+}
+
+fn join_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let kind = JoinKind::flag(&mut blk);
+    // the other table is supplied the input table
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Tab)?
+        .concrete()?;
+    let colnames = ColNameArgs::build(&mut blk)?;
+    let blktag = blk.blk_tag().clone();
+
+    blk.eval_o(move |input, cx| {
+        let rhs: Table = rhs.resolve(|| input.clone(), &cx)?.try_into()?;
+        let lhs: Table = input.try_into()?;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Err(Error::eval(&blktag, "table is empty", None, None));
+        }
+
+        let lcols = colnames.resolve_indices(&lhs, &cx)?;
+        let rcols = colnames.resolve_indices(&rhs, &cx)?;
+        let (lkeys, rkeys) = ::libs::rayon::join(
+            || JoinKey::from_cols(&lhs, &lcols, &blktag),
+            || JoinKey::from_cols(&rhs, &rcols, &blktag),
+        );
+        let shared = lcols
+            .into_iter()
+            .zip(rcols)
+            .map(|((l, _), (r, _))| (l, r))
+            .collect::<Vec<_>>();
+
+        cx.done_o(join_tables(&lhs, &rhs, &lkeys?, &rkeys?, &shared, kind))
+    })
+}
+
+// join-by
+// whilst join matches on column entries, join-by converts each row of both tables into a value
+// using an expression, then matches on the resulting values
+fn joinby_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "join the input table with another table where the result of an expression matches
+the expression is evaluated on the rows of both tables and cannot return a table
+all columns of both tables are kept, clashing column names from the other table
+are suffixed with `_rhs`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("table".into()),
+            HelpParameter::Required("<expr>".into()),
+        ],
+        flags: JoinKind::help_flags(),
+        examples: vec![
+            HelpExample {
+                desc: "join files modified on the same day",
+                code: "ls | join-by { ls other-dir } { get:DateTime modified | to-str '[year]-[month]-[day]' }",
+            },
+            HelpExample {
+                desc: "join using a user-defined type",
+                code: "ls | join-by --outer { ls other-dir } { Point { get size } }",
+            },
+        ],
+        ..HelpMessage::new("join-by")
+    }
+}
+
+fn joinby_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let kind = JoinKind::flag(&mut blk);
+    // the other table is supplied the input table
+    let rhs = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Tab)?
+        .concrete()?;
+    // the expression which will be joined on
+    let key = blk.next_arg()?.supplied(Ty::TabRow)?.concrete()?;
+    if matches!(key.out_ty(), Ty::Tab | Ty::TabRow) {
+        return Err(Error {
+            cat: err::Category::Semantics,
+            desc: format!("join-by key expression cannot return a `{}`", key.out_ty()),
+            traces: err::trace(
+                &key.tag,
+                format!("this argument returns type `{}`", key.out_ty()),
+            ),
+            help_msg: Some("join keys are matched by value, try returning a table entry".into()),
+            ..Error::default()
+        });
+    }
+
+    let blktag = blk.blk_tag().clone();
+
+    blk.eval_o(move |input, cx| {
+        let rhs: Table = rhs.resolve(|| input.clone(), &cx)?.try_into()?;
+        let lhs: Table = input.try_into()?;
+        if lhs.is_empty() || rhs.is_empty() {
+            return Err(Error::eval(&blktag, "table is empty", None, None));
+        }
+
+        let keys = |table: &Table| {
+            resolve_trow_expr_par(table, &key, &cx).and_then(|values| {
+                values
+                    .par_iter()
+                    .skip(1) // skip header row
+                    .map(|v| JoinKey::from_value(v, &key.tag))
+                    .collect::<Result<Vec<_>>>()
+            })
+        };
+        let (lkeys, rkeys) = (keys(&lhs)?, keys(&rhs)?);
+
+        cx.done_o(join_tables(&lhs, &rhs, &lkeys, &rkeys, &[], kind))
+    })
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum JoinKind {
+    Inner,
+    Left,
+    Right,
+    Outer,
+}
+
+impl JoinKind {
+    /// Get the join kind from the block's flags, defaulting to [`JoinKind::Inner`].
+    ///
+    /// > The flag is **removed** from the block's flags.
+    fn flag(blk: &mut Block) -> Self {
+        use JoinKind::*;
+        [
+            ("inner", Inner),
+            ("left", Left),
+            ("right", Right),
+            ("outer", Outer),
+        ]
+        .into_iter()
+        .find_map(|(f, k)| blk.get_flag(f).map(|_| k))
+        .unwrap_or(Inner)
+    }
+
+    fn help_flags() -> Vec<(&'static str, &'static str)> {
+        vec![
+            (
+                "inner",
+                "only keep rows which match in both tables (default)",
+            ),
+            ("left", "keep all rows of the input table"),
+            ("right", "keep all rows of the other table"),
+            ("outer", "keep all rows of both tables"),
+        ]
+    }
+
+    fn keep_lhs(self) -> bool {
+        matches!(self, JoinKind::Left | JoinKind::Outer)
+    }
+
+    fn keep_rhs(self) -> bool {
+        matches!(self, JoinKind::Right | JoinKind::Outer)
+    }
+}
+
+/// A hashable representation of a value, used to match rows in a hash join.
+#[derive(PartialEq, Eq, Hash, Debug)]
+enum JoinKey {
+    Nil,
+    Bool(bool),
+    Num(u64),
+    Str(Str),
+    DateTime(::time::OffsetDateTime),
+    Duration(::time::Duration),
+    Ogma(Str, usize, Vec<JoinKey>),
+    List(Vec<JoinKey>),
+    Multi(Vec<JoinKey>),
+}
+
+impl JoinKey {
+    fn from_value(value: &Value, tag: &Tag) -> Result<Self> {
+        Ok(match value {
+            Value::Nil => JoinKey::Nil,
+            Value::Bool(x) => JoinKey::Bool(*x),
+            Value::Num(x) => JoinKey::num(x),
+            Value::Str(x) => JoinKey::Str(x.clone()),
+            // datetimes are matched on their instant in time
+            Value::DateTime(x) => JoinKey::DateTime(x.to_offset(::time::UtcOffset::UTC)),
+            Value::Duration(x) => JoinKey::Duration(*x),
+            Value::Ogma(x) => JoinKey::Ogma(
+                Str::new(x.ty().name().str()),
+                x.variant_idx(),
+                x.data()
+                    .iter()
+                    .map(|v| JoinKey::from_value(v, tag))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::List(x) => JoinKey::List(
+                x.iter()
+                    .map(|v| JoinKey::from_value(v, tag))
+                    .collect::<Result<_>>()?,
+            ),
+            Value::Tab(_) | Value::TabRow(_) | Value::Stream(_) => {
+                return Err(Error::eval(
+                    tag,
+                    "tables cannot be used as join keys",
+                    None,
+                    None,
+                ))
+            }
+        })
+    }
+
+    fn from_entry(entry: &Entry<Value>, tag: &Tag) -> Result<Self> {
+        match entry {
+            Entry::Nil => Ok(JoinKey::Nil),
+            Entry::Num(x) => Ok(JoinKey::num(x)),
+            Entry::Obj(x) => JoinKey::from_value(x, tag),
+        }
+    }
+
+    /// Build the keys for each row of the table (**excluding the header**), in parallel.
+    fn from_cols(table: &Table, cols: &[(usize, Str)], tag: &Tag) -> Result<Vec<Self>> {
+        (1..table.rows_len())
+            .into_par_iter()
+            .map(|row| {
+                let mut k = cols
+                    .iter()
+                    .map(|(c, _)| JoinKey::from_entry(TableRow::entry_at(table, row, *c), tag))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(match k.len() {
+                    1 => k.pop().expect("one key"),
+                    _ => JoinKey::Multi(k),
+                })
+            })
+            .collect()
+    }
+
+    fn num(n: &Number) -> Self {
+        let x = n.as_f64();
+        // -0 and 0 should match
+        JoinKey::Num(if x == 0.0 { 0f64 } else { x }.to_bits())
+    }
+}
+
+/// Hash join two tables using the row keys (which **exclude the header**).
+///
+/// `shared` are the key column indices `(lhs, rhs)` which only appear once in the result. The
+/// entries are taken from `lhs`, or from `rhs` if the row only exists in `rhs`.
+fn join_tables(
+    lhs: &Table,
+    rhs: &Table,
+    lkeys: &[JoinKey],
+    rkeys: &[JoinKey],
+    shared: &[(usize, usize)],
+    kind: JoinKind,
+) -> Table {
+    // build the hash map on the rhs, storing row indices
+    let mut map: HashMap<&JoinKey, Vec<usize>> = HashMap::default();
+    for (i, k) in rkeys.iter().enumerate() {
+        map.entry(k).or_default().push(i + 1);
+    }
+
+    // probe with the lhs, in parallel
+    let matches: Vec<&[usize]> = lkeys
+        .par_iter()
+        .map(|k| map.get(k).map(Vec::as_slice).unwrap_or_default())
+        .collect();
+
+    let rcols = (0..rhs.cols_len())
+        .filter(|c| !shared.iter().any(|(_, r)| r == c))
+        .collect::<Vec<_>>();
+    let entry = |t: &Table, row, col| TableRow::entry_at(t, row, col).clone();
+    let lrow = |row| lhs.row(row).expect("row inside table").cloned();
+    let rrow = |row| rcols.iter().map(move |&c| entry(rhs, row, c));
+    let nils = || repeat(Entry::Nil).take(rcols.len());
+
+    let mut table = InnerTable::new();
+    table.add_row(join_hdr(lhs, rhs, &rcols));
+
+    let mut rmatched = vec![false; rhs.rows_len()];
+    for (row, rows) in matches.into_iter().enumerate().map(|(i, x)| (i + 1, x)) {
+        for &r in rows {
+            rmatched[r] = true;
+            table.add_row(lrow(row).chain(rrow(r)));
+        }
+
+        if rows.is_empty() && kind.keep_lhs() {
+            table.add_row(lrow(row).chain(nils()));
+        }
+    }
+
+    if kind.keep_rhs() {
+        for r in (1..rhs.rows_len()).filter(|&r| !rmatched[r]) {
+            let l = (0..lhs.cols_len()).map(|c| {
+                shared
+                    .iter()
+                    .find(|(l, _)| *l == c)
+                    .map(|&(_, rc)| entry(rhs, r, rc))
+                    .unwrap_or(Entry::Nil)
+            });
+            table.add_row(l.chain(rrow(r)));
+        }
+    }
+
+    table.into()
+}
+
+/// The header for a joined table, suffixing any clashing rhs column names with `_rhs`.
+fn join_hdr(lhs: &Table, rhs: &Table, rcols: &[usize]) -> Vec<Entry<Value>> {
+    let fmtr = &mut numfmt::Formatter::new();
+    let mut names = lhs
+        .row(0)
+        .expect("has hdr")
+        .map(|e| print::fmt_cell(e, fmtr).to_string())
+        .collect::<Vec<_>>();
+    let mut hdr = lhs.row(0).expect("has hdr").cloned().collect::<Vec<_>>();
+
+    for &c in rcols {
+        let e = TableRow::entry_at(rhs, 0, c);
+        let mut name = print::fmt_cell(e, fmtr).to_string();
+        if names.contains(&name) {
+            while names.contains(&name) {
+                name.push_str("_rhs");
+            }
+            hdr.push(o(name.as_str()));
+        } else {
+            hdr.push(e.clone());
+        }
+        names.push(name);
+    }
+
+    hdr
+}
+
+// ------ Map ------------------------------------------------------------------
+fn map_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "replace entry in column with result of an expression
+`map` provides the variable `$row` which is the TableRow
+the input into the expression is the value of the entry"
+            .into(),
+        params: vec![
+            HelpParameter::Required("col-name".into()),
+            HelpParameter::Required("value".into()),
+        ],
+        flags: vec![
+            ("<type>", "the type that entry has"),
+            ("force", "ignore entry types"),
+            SKIP_ERRORS_FLAG,
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "scale 'size' by dividing by one million",
+                code: "ls | map size / 1e6",
+            },
+            HelpExample {
+                desc: "use a different column and result type",
+                code: "ls | map type --Str { \\$row.size | + 100 }",
+            },
+        ],
+        ..HelpMessage::new("map")
+    }
+}
+
+fn map_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    let skip = SkipErrors::flag(&mut blk); // read before the type flag
+    let mp = Arc::new(MapTable::new(&mut blk)?);
+    let plan = MapCol::plan(&mp, &skip);
+
+    blk.eval(skip.out_ty(), move |table, cx| {
+        let (table, errs) = mp.doit(table.try_into()?, &cx, skip.skip())?;
+        cx.done(skip.output(table, errs, None))
+    })
+    .map(|x| x.with_plan(plan))
+}
+
+fn map_stream_help() -> HelpMessage {
+    stream_help(
+        map_table_help(),
+        "replace entry in column with result of an expression
+`map` provides the variable `$row` which is the TableRow
+the input into the expression is the value of the entry",
+        vec![HelpExample {
+            desc: "convert milliseconds to seconds in a large log file",
+            code: "open --Stream logs.csv | map ms / 1000 | save secs.csv",
+        }],
+    )
+}
+
+fn map_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Stream);
+    no_skip_errors(&mut blk)?; // read before the type flag
+    let chunked = chunked_stream(&blk);
+    let mp = Arc::new(MapTable::new(&mut blk)?);
+
+    blk.eval_o(move |stream, cx| {
+        let scx = StreamCx::new(&cx);
+        let mp = Arc::clone(&mp);
+        let stream = chunked(Stream::try_from(stream)?)
+            .map_chunks(move |table| mp.doit(table, &scx.cx(), false).map(|x| x.0));
+        cx.done_o(stream)
+    })
+}
+
+struct MapTable {
+    /// Expecting TableRow.
+    transformation: eng::Argument,
+    colarg: eng::Argument,
+    force: bool,
+    row_var: eng::Variable,
+    /// The transformation references `$row`.
+    uses_row: bool,
+}
+
+impl MapTable {
+    fn new(blk: &mut Block) -> Result<Self> {
+        blk.assert_adds_vars(true);
+
+        let colarg = blk
+            .next_arg()?
+            .decouple_op_seal()
+            .supplied(Ty::Nil)?
+            .returns(Ty::Str)?
+            .concrete()?;
+        let force_flag = blk.get_flag("force").is_some();
+        let ty_flag = (!force_flag).then(|| type_flag(blk)).transpose()?.flatten();
+
+        let row_var = blk.inject_manual_var_next_arg("row", Ty::TabRow)?;
+        blk.assert_vars_added();
+
+        let uses_row = blk.next_arg_uses_var("row");
+        let transformation = blk.next_arg()?;
+        let transformation = match (force_flag, ty_flag) {
+            (true, _) => transformation.supplied(Ty::Nil)?,
+            (false, Some(tyflag)) => transformation.supplied(tyflag)?,
+            _ => transformation,
+        }
+        .concrete()?;
+
+        Ok(Self {
+            transformation,
+            colarg,
+            force: force_flag,
+            row_var,
+            uses_row,
+        })
+    }
+
+    fn doit(
+        &self,
+        mut table: Table,
+        cx: &Context,
+        skip: bool,
+    ) -> Result<(Table, Vec<(usize, Error)>)> {
+        let colname: Str = self.colarg.resolve(|| Value::Nil, cx)?.try_into()?;
+        let ctag = &self.colarg.tag;
+        let colidx = TableRow::col_idx(&table, &colname, ctag)?;
+
+        let mut replace_with: Vec<Value> =
+            repeat_with(|| Value::Nil).take(table.rows_len()).collect();
+        let var = &self.row_var;
+
+        let errs = par_over_tablerows_skip(&mut replace_with, &table, cx, skip, |v, cx, trow| {
+            let trowidx = trow.idx;
+            var.set_data(&mut cx.env, trow.into());
+            let e = TableRow::entry_at(&table, trowidx, colidx);
+            *v = self.map_entry(e, trowidx, &colname, cx)?;
+            Ok(())
+        })?;
+
+        table
+            .make_mut()
+            .col_mut(colidx)
+            .expect("col should exist")
+            .zip(replace_with.into_iter())
+            .skip(1) // for header
+            .for_each(|(e1, e2)| *e1 = e2.into());
+
+        Ok((table, errs))
+    }
+
+    /// Map the entry of column `colname` at row `idx`.
+    ///
+    /// If the transformation uses `$row`, the variable must be set.
+    fn map_entry(
+        &self,
+        e: &Entry<Value>,
+        idx: usize,
+        colname: &str,
+        cx: &Context,
+    ) -> Result<Value> {
+        let tf = &self.transformation;
+        if self.force {
+            tf.resolve(|| Value::Nil, cx)
+        } else {
+            let e = TableRow::cnv_value(e, tf.in_ty(), idx, colname, &self.colarg.tag)?;
+            tf.resolve(|| e, cx)
+        }
+    }
+}
+
+/// A `map` of a named column which does not use `$row`, so each entry only reads itself.
+struct MapCol {
+    col: Str,
+    mp: Arc<MapTable>,
+}
+
+impl MapCol {
+    fn plan(mp: &Arc<MapTable>, skip: &SkipErrors) -> Option<Plan> {
+        if skip.skip() || mp.uses_row {
+            return None;
+        }
+
+        let col = mp.colarg.extract_literal::<Str>().ok()?.clone();
+        let mp = Arc::clone(mp);
+        Some(Plan::Map(vec![Arc::new(MapCol { col, mp })]))
+    }
+}
+
+impl eng::MapEntry for MapCol {
+    fn col(&self) -> &Str {
+        &self.col
+    }
+
+    fn tag(&self) -> &Tag {
+        &self.mp.colarg.tag
+    }
+
+    fn infallible(&self) -> bool {
+        // the entry is not converted when forced
+        self.mp.force && self.mp.transformation.infallible()
+    }
+
+    fn map(&self, entry: &Entry<Value>, idx: usize, cx: &Context) -> Result<Value> {
+        self.mp.map_entry(entry, idx, &self.col, cx)
+    }
+}
+
+fn map_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "map each item in a list using an expression
+the output list has the type returned by the expression"
+            .into(),
+        params: vec![HelpParameter::Required("<expr>".into())],
+        examples: vec![
+            HelpExample {
+                desc: "double each number",
+                code: "\\ [1 2 3] | map * 2",
+            },
+            HelpExample {
+                desc: "convert numbers to strings",
+                code: "\\ [1 2 3] | map to-str",
+            },
+        ],
+        ..HelpMessage::new("map")
+    }
+}
+
+fn map_list_intrinsic(mut blk: Block) -> Result<Step> {
+    let elem = list_elem_ty(&blk)?;
+
+    let f = blk.next_arg()?.supplied(elem)?.concrete()?;
+    let out = f.out_ty().clone();
+    let ty = Type::list(out.clone());
+    blk.insert_anon_type_into_compiler(ty.clone());
+    blk.assert_output(ty.clone());
+
+    blk.eval(ty, move |list, cx| {
+        let list = List::try_from(list)?;
+        let r = f.resolver_sync(&cx);
+        let items = list
+            .into_items()
+            .into_iter()
+            .map(&r)
+            .collect::<Result<_>>()?;
+        drop(r);
+        cx.done(List::new(out.clone(), items))
+    })
+}
+
+// ------ Windows --------------------------------------------------------------
+/// The `--partition` flag, which uses the first argument as the partitioning column.
+const PARTITION_FLAG: (&str, &str) = (
+    "partition",
+    "partition the rows by the first argument column, each partition is windowed separately",
+);
+
+/// If the `--partition` flag is specified, the first argument is the partitioning column name.
+fn partition_arg(blk: &mut Block) -> Result<Option<eng::Argument>> {
+    match blk.get_flag(PARTITION_FLAG.0) {
+        Some(_) => blk
+            .next_arg()?
+            .supplied(Ty::Nil)?
+            .returns(Ty::Str)?
+            .concrete()
+            .map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Split the table rows **(excluding the header)** into partitions, keeping the table order.
+/// Without a partitioning column, all the rows are a single partition.
+fn partitions(table: &Table, col: Option<&eng::Argument>, cx: &Context) -> Result<Vec<Vec<usize>>> {
+    let col = match col {
+        Some(col) => col,
+        None => return Ok(vec![(1..table.rows_len()).collect()]),
+    };
+
+    let name: Str = col.resolve(|| Value::Nil, cx)?.try_into()?;
+    let c = TableRow::col_idx(table, &name, &col.tag)?;
+    let keys = JoinKey::from_cols(table, &[(c, name)], &col.tag)?;
+
+    let mut map: HashMap<&JoinKey, usize> = HashMap::default();
+    let mut parts: Vec<Vec<usize>> = Vec::new();
+    for (i, k) in keys.iter().enumerate() {
+        let p = *map.entry(k).or_insert_with(|| {
+            parts.push(Vec::new());
+            parts.len() - 1
+        });
+        parts[p].push(i + 1); // +1 for header
+    }
+
+    Ok(parts)
+}
+
+/// Resolve a window column name, returning the column index and the name.
+fn window_col(table: &Table, col: &eng::Argument, cx: &Context) -> Result<(usize, Str)> {
+    let name: Str = col.resolve(|| Value::Nil, cx)?.try_into()?;
+    TableRow::col_idx(table, &name, &col.tag).map(|c| (c, name))
+}
+
+/// Append a column of entries **(including the header)** onto the table.
+fn append_col(mut table: Table, col: Vec<Entry<Value>>) -> Table {
+    if let Some(t) = table.get_mut() {
+        t.add_col(col.into_iter());
+    } else {
+        let mut t = table.clone_with_col_capacity(table.cols_len() + 1);
+        t.add_col(col.into_iter());
+        table = t.into();
+    }
+
+    table
+}
+
+fn lag_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "append a column with the entry from a previous row
+the entry is `n` rows before, defaulting to 1, and is nil if there is no such row
+the column is named `lag-<col-name>`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("col-name".into()),
+            HelpParameter::Optional("n".into()),
+        ],
+        flags: vec![PARTITION_FLAG],
+        examples: vec![
+            HelpExample {
+                desc: "the previous day's price",
+                code: "open prices.csv | lag price",
+            },
+            HelpExample {
+                desc: "the price a week ago for each stock",
+                code: "open prices.csv | lag --partition stock price 7",
+            },
+        ],
+        ..HelpMessage::new("lag")
+    }
+}
+
+fn lag_table_intrinsic(blk: Block) -> Result<Step> {
+    shift_intrinsic(blk, false)
+}
+
+fn lead_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "append a column with the entry from a following row
+the entry is `n` rows after, defaulting to 1, and is nil if there is no such row
+the column is named `lead-<col-name>`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("col-name".into()),
+            HelpParameter::Optional("n".into()),
+        ],
+        flags: vec![PARTITION_FLAG],
+        examples: vec![
+            HelpExample {
+                desc: "the next day's price",
+                code: "open prices.csv | lead price",
+            },
+            HelpExample {
+                desc: "the price in a week for each stock",
+                code: "open prices.csv | lead --partition stock price 7",
+            },
+        ],
+        ..HelpMessage::new("lead")
+    }
+}
+
+fn lead_table_intrinsic(blk: Block) -> Result<Step> {
+    shift_intrinsic(blk, true)
+}
+
+/// Shared `lag` and `lead` implementation.
+fn shift_intrinsic(mut blk: Block, lead: bool) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let part = partition_arg(&mut blk)?;
+    let col = blk
+        .next_arg()?
+        .supplied(Ty::Nil)?
+        .returns(Ty::Str)?
+        .concrete()?;
+    let n = match blk.args_len() {
+        0 => None,
+        _ => Some(
+            blk.next_arg()?
+                .supplied(None)?
+                .returns(Ty::Num)?
+                .concrete()?,
+        ),
+    };
+    let cmd = if lead { "lead" } else { "lag" };
 
-    let binop = Rc::new(RefCell::new(binop));
-    for (idx, value) in values.into_iter().skip(1) {
-        // skip header row
-        let key = GrpByKey {
-            value,
-            cx,
-            binop: Rc::clone(&binop),
+    blk.eval_o(move |table, cx| {
+        let n = match &n {
+            Some(arg) => cnv_num_to_uint::<usize>(arg.resolve(|| table.clone(), &cx)?, &arg.tag)?,
+            None => 1,
         };
-        let t = map.entry(key).or_insert_with(newtable);
-        t.add_row(table.row(idx).expect("row inside table").cloned());
-    }
+        let table = Table::try_from(table)?;
+        let (c, name) = window_col(&table, &col, &cx)?;
+
+        let mut entries = vec![Entry::Nil; table.rows_len()];
+        entries[0] = o(format!("{}-{}", cmd, name));
+        for part in partitions(&table, part.as_ref(), &cx)? {
+            for (i, &row) in part.iter().enumerate() {
+                let j = if lead {
+                    i.checked_add(n)
+                } else {
+                    i.checked_sub(n)
+                };
+                if let Some(&r) = j.and_then(|j| part.get(j)) {
+                    entries[row] = TableRow::entry_at(&table, r, c).clone();
+                }
+            }
+        }
 
-    build_table_from_map(map, |k| k.value)
+        cx.done_o(append_col(table, entries))
+    })
 }
 
-// ------ Map ------------------------------------------------------------------
-fn map_table_help() -> HelpMessage {
+fn rolling_table_help() -> HelpMessage {
     HelpMessage {
-        desc: "replace entry in column with result of an expression
-`map` provides the variable `$row` which is the TableRow
-the input into the expression is the value of the entry"
+        desc: "append a column with the result of an expression over a rolling window of rows
+the expression is supplied a table of the current row and up to `n - 1` previous rows
+the column is named `rolling`"
             .into(),
         params: vec![
-            HelpParameter::Required("col-name".into()),
-            HelpParameter::Required("value".into()),
-        ],
-        flags: vec![
-            ("<type>", "the type that entry has"),
-            ("force", "ignore entry types"),
+            HelpParameter::Required("n".into()),
+            HelpParameter::Required("expr".into()),
         ],
+        flags: vec![PARTITION_FLAG],
         examples: vec![
             HelpExample {
-                desc: "scale 'size' by dividing by one million",
-                code: "ls | map size / 1e6",
+                desc: "the 7 day moving average price",
+                code: "open prices.csv | rolling 7 { mean price }",
             },
             HelpExample {
-                desc: "use a different column and result type",
-                code: "ls | map type --Str { \\$row.size | + 100 }",
+                desc: "the highest price in the last 30 days for each stock",
+                code: "open prices.csv | rolling --partition stock 30 { max price }",
             },
         ],
-        ..HelpMessage::new("map")
+        ..HelpMessage::new("rolling")
     }
 }
 
-fn map_table_intrinsic(mut blk: Block) -> Result<Step> {
+fn rolling_table_intrinsic(mut blk: Block) -> Result<Step> {
     blk.assert_input(&Ty::Tab)?;
     blk.assert_output(Ty::Tab);
-    MapTable::map(blk)
-}
 
-struct MapTable {
-    /// Expecting TableRow.
-    transformation: eng::Argument,
-    colarg: eng::Argument,
-    force: bool,
-    row_var: eng::Variable,
+    let part = partition_arg(&mut blk)?;
+    let n = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    let expr = blk.next_arg()?.supplied(Ty::Tab)?.concrete()?;
+
+    blk.eval_o(move |table, cx| {
+        let n = cnv_num_to_uint::<usize>(n.resolve(|| table.clone(), &cx)?, &n.tag)?;
+        let table = Table::try_from(table)?;
+        let parts = partitions(&table, part.as_ref(), &cx)?;
+
+        // each row is windowed by its partition and position within the partition
+        let mut windows = vec![(0, 0); table.rows_len()];
+        for (p, part) in parts.iter().enumerate() {
+            for (i, &row) in part.iter().enumerate() {
+                windows[row] = (p, i);
+            }
+        }
+
+        let entries = {
+            let expr = expr.resolver_sync(&cx);
+            windows
+                .par_iter()
+                .enumerate()
+                .skip(1)
+                .map(|(_, &(p, i))| {
+                    let rows = &parts[p][(i + 1).saturating_sub(n)..=i];
+                    let mut t = InnerTable::new();
+                    t.add_rows(
+                        once(0)
+                            .chain(rows.iter().copied())
+                            .filter_map(|r| table.row(r).map(|r| r.cloned())),
+                    );
+                    expr(Table::from(t).into()).map(Entry::from)
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let col = once(o("rolling")).chain(entries).collect();
+        cx.done_o(append_col(table, col))
+    })
 }
 
-impl MapTable {
-    fn map(mut blk: Block) -> Result<Step> {
-        blk.assert_adds_vars(true);
+fn cumsum_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "append a column with the cumulative sum of a column
+nil entries are ignored, other non-number entries are an error
+the column is named `cumsum-<col-name>`"
+            .into(),
+        params: vec![HelpParameter::Required("col-name".into())],
+        flags: vec![PARTITION_FLAG],
+        examples: vec![
+            HelpExample {
+                desc: "the running total of file sizes",
+                code: "ls | cumsum size",
+            },
+            HelpExample {
+                desc: "the running total of sales for each region",
+                code: "open sales.csv | cumsum --partition region amount",
+            },
+        ],
+        ..HelpMessage::new("cumsum")
+    }
+}
 
-        let colarg = blk
-            .next_arg()?
-            .decouple_op_seal()
-            .supplied(Ty::Nil)?
-            .returns(Ty::Str)?
-            .concrete()?;
-        let force_flag = blk.get_flag("force").is_some();
-        let ty_flag = (!force_flag)
-            .then(|| type_flag(&mut blk))
-            .transpose()?
-            .flatten();
+fn cumsum_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
 
-        let row_var = blk.inject_manual_var_next_arg("row", Ty::TabRow)?;
-        blk.assert_vars_added();
+    let part = partition_arg(&mut blk)?;
+    let col = blk
+        .next_arg()?
+        .supplied(Ty::Nil)?
+        .returns(Ty::Str)?
+        .concrete()?;
 
-        let transformation = blk.next_arg()?;
-        let transformation = match (force_flag, ty_flag) {
-            (true, _) => transformation.supplied(Ty::Nil)?,
-            (false, Some(tyflag)) => transformation.supplied(tyflag)?,
-            _ => transformation,
+    blk.eval_o(move |table, cx| {
+        let table = Table::try_from(table)?;
+        let (c, name) = window_col(&table, &col, &cx)?;
+
+        let mut entries = vec![Entry::Nil; table.rows_len()];
+        entries[0] = o(format!("cumsum-{}", name));
+        for part in partitions(&table, part.as_ref(), &cx)? {
+            let mut sum = 0.0;
+            for row in part {
+                match TableRow::entry_at(&table, row, c) {
+                    Entry::Nil | Entry::Obj(Value::Nil) => (),
+                    Entry::Num(n) | Entry::Obj(Value::Num(n)) => sum += n.as_f64(),
+                    e => {
+                        return Err(Error::unexp_entry_ty(
+                            &Ty::Num,
+                            &Value::from(e).ty(),
+                            row,
+                            &name,
+                            &col.tag,
+                        ))
+                    }
+                }
+                entries[row] = n(sum);
+            }
         }
-        .concrete()?;
 
-        let mp = Box::new(Self {
-            transformation,
-            colarg,
-            force: force_flag,
-            row_var,
-        });
+        cx.done_o(append_col(table, entries))
+    })
+}
 
-        blk.eval_o::<_, Table>(move |table, cx| {
-            mp.doit(table.try_into()?, &cx).and_then(|x| cx.done_o(x))
-        })
+fn rank_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "append a column with the rank of each row, ordered by a column
+equal entries share the same rank, leaving a gap in the ranks which follow
+the column is named `rank-<col-name>`"
+            .into(),
+        params: vec![HelpParameter::Required("col-name".into())],
+        flags: vec![
+            PARTITION_FLAG,
+            ("desc", "rank in descending order, the largest entry is 1"),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "rank files by size, the largest file is 1",
+                code: "ls | rank --desc size",
+            },
+            HelpExample {
+                desc: "rank the sales within each region",
+                code: "open sales.csv | rank --desc --partition region amount",
+            },
+        ],
+        ..HelpMessage::new("rank")
     }
+}
 
-    fn doit(&self, mut table: Table, cx: &Context) -> Result<Table> {
-        let colname: Str = self.colarg.resolve(|| Value::Nil, cx)?.try_into()?;
-        let ctag = &self.colarg.tag;
-        let colidx = TableRow::col_idx(&table, &colname, ctag)?;
+fn rank_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
 
-        let mut replace_with: Vec<Value> =
-            repeat_with(|| Value::Nil).take(table.rows_len()).collect();
-        let tf = &self.transformation;
-        let var = &self.row_var;
-        let force = self.force;
-        let in_ty = tf.in_ty();
+    let desc = blk.get_flag("desc").is_some();
+    let part = partition_arg(&mut blk)?;
+    let col = blk
+        .next_arg()?
+        .supplied(Ty::Nil)?
+        .returns(Ty::Str)?
+        .concrete()?;
 
-        par_over_tablerows(&mut replace_with, &table, cx, |v, cx, trow| {
-            let trowidx = trow.idx;
-            var.set_data(&mut cx.env, trow.into());
-            let outv = match force {
-                true => tf.resolve(|| Value::Nil, cx),
-                false => {
-                    let e = TableRow::entry_at(&table, trowidx, colidx);
-                    let e = TableRow::cnv_value(e, in_ty, trowidx, &colname, ctag)?;
-                    tf.resolve(|| e, cx)
+    blk.eval_o(move |table, cx| {
+        let table = Table::try_from(table)?;
+        let (c, name) = window_col(&table, &col, &cx)?;
+        let entry = |r| TableRow::entry_at(&table, r, c);
+        let ord = |&a: &usize, &b: &usize| match desc {
+            true => cmp_table_entries(entry(b), entry(a)),
+            false => cmp_table_entries(entry(a), entry(b)),
+        };
+
+        let mut entries = vec![Entry::Nil; table.rows_len()];
+        entries[0] = o(format!("rank-{}", name));
+        for mut part in partitions(&table, part.as_ref(), &cx)? {
+            part.par_sort_by(&ord);
+            let mut rank = 1;
+            for (i, &row) in part.iter().enumerate() {
+                if i > 0 && ord(&part[i - 1], &row) != cmp::Ordering::Equal {
+                    rank = i + 1;
                 }
-            };
-            *v = outv?;
-            Ok(())
-        })?;
+                entries[row] = n(rank as u32);
+            }
+        }
 
-        table
-            .make_mut()
-            .col_mut(colidx)
-            .expect("col should exist")
-            .zip(replace_with.into_iter())
-            .skip(1) // for header
-            .for_each(|(e1, e2)| *e1 = e2.into());
+        cx.done_o(append_col(table, entries))
+    })
+}
 
-        Ok(table)
+fn rownumber_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "append a column with the row number, starting at 1
+the column is named `row-number`"
+            .into(),
+        flags: vec![PARTITION_FLAG],
+        examples: vec![
+            HelpExample {
+                desc: "number the files",
+                code: "ls | row-number",
+            },
+            HelpExample {
+                desc: "number the sales within each region",
+                code: "open sales.csv | row-number --partition region",
+            },
+        ],
+        ..HelpMessage::new("row-number")
     }
 }
 
+fn rownumber_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+    blk.assert_output(Ty::Tab);
+
+    let part = partition_arg(&mut blk)?;
+
+    blk.eval_o(move |table, cx| {
+        let table = Table::try_from(table)?;
+        if table.rows_len() == 0 {
+            return cx.done_o(table);
+        }
+
+        let mut entries = vec![Entry::Nil; table.rows_len()];
+        entries[0] = o("row-number");
+        for part in partitions(&table, part.as_ref(), &cx)? {
+            for (i, row) in part.into_iter().enumerate() {
+                entries[row] = n(i as u32 + 1);
+            }
+        }
+
+        cx.done_o(append_col(table, entries))
+    })
+}
+
 // ------ Pick -----------------------------------------------------------------
 fn pick_table_help() -> HelpMessage {
     HelpMessage {
@@ -975,6 +2810,10 @@ fn pick_table_intrinsic(mut blk: Block) -> Result<Step> {
     let trailflag = blk.get_flag("trail").is_some();
     //
     let colnames = ColNameArgs::build(&mut blk)?;
+    let plan = (!trailflag)
+        .then(|| colnames.literals())
+        .flatten()
+        .map(|cols| Plan::Pick { cols });
     blk.eval_o::<_, Table>(move |input, cx| {
         let table = Table::try_from(input)?;
         let mut colidxs = if addflag {
@@ -1009,6 +2848,7 @@ fn pick_table_intrinsic(mut blk: Block) -> Result<Step> {
 
         cx.done_o(::table::Table::from(v).into())
     })
+    .map(|x| x.with_plan(plan))
 }
 
 // ------ Ren ------------------------------------------------------------------
@@ -1325,6 +3165,68 @@ fn skip_table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+fn skip_stream_help() -> HelpMessage {
+    HelpMessage {
+        desc: "skip the first n rows of a stream".into(),
+        params: vec![HelpParameter::Required("count".into())],
+        examples: vec![HelpExample {
+            desc: "skip the first 10 rows of a stream",
+            code: "skip 10",
+        }],
+        ..HelpMessage::new("skip")
+    }
+}
+
+fn skip_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Stream); // stream -> stream
+
+    let count = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    blk.eval_o(move |stream, cx| {
+        let count = count
+            .resolve(|| stream.clone(), &cx)
+            .and_then(|v| cnv_num_to_uint::<usize>(v, &count.tag))?;
+        let stream = slice_stream(Stream::try_from(stream)?, count, None);
+        cx.done_o(stream)
+    })
+}
+
+/// Keep the rows of a stream from `skip`, up to `take` rows.
+///
+/// Chunks stop being pulled once `take` rows have been reached.
+fn slice_stream(stream: Stream, skip: usize, take: Option<usize>) -> Stream {
+    let end = take.map(|x| x.saturating_add(skip));
+    stream.then(move |mut chunks| {
+        let mut pos = 0; // the number of rows before the chunk
+        let mut first = true; // the first chunk is always kept, so the header is kept
+        Box::new(from_fn(move || {
+            if !first && end.map(|e| pos >= e).unwrap_or_default() {
+                return None;
+            }
+
+            let chunk = match chunks.next()? {
+                Ok(x) => x,
+                Err(e) => return Some(Err(e)),
+            };
+            first = false;
+
+            let start = pos;
+            pos += chunk.rows_len().saturating_sub(1);
+            let keep = (0..chunk.rows_len())
+                .map(|i| {
+                    let row = start + i;
+                    i == 0 || (row > skip && end.map(|e| row <= e).unwrap_or(true))
+                })
+                .collect::<Vec<_>>();
+            Some(Ok(FilterTable::retain_keep_rows(chunk, &keep)))
+        }))
+    })
+}
+
 // ------ Sorting --------------------------------------------------------------
 fn sort_table_help() -> HelpMessage {
     HelpMessage {
@@ -1352,11 +3254,31 @@ fn sort_table_intrinsic(mut blk: Block) -> Result<Step> {
     blk.assert_input(&Ty::Tab)?;
     blk.assert_output(Ty::Tab);
 
-    let colnames = ColNameArgs::build(&mut blk)?;
+    let colnames = Arc::new(ColNameArgs::build(&mut blk)?);
+    let plan = Plan::Sort {
+        cols: colnames.literals(),
+        top: {
+            let colnames = Arc::clone(&colnames);
+            Arc::new(move |table: Table, n: usize, cx: &Context| {
+                let cols = colnames.resolve_indices(&table, cx)?;
+                Ok(sort_top(table, &cols, n))
+            })
+        },
+    };
+
     blk.eval_o(move |table, cx| {
         let mut table: Table = table.try_into()?;
         let cols = colnames.resolve_indices(&table, &cx)?;
 
+        // typed columns are sorted in a single pass over the row indices
+        if let Some(keys) = typed_columns(&table, &cols) {
+            let mut order = (0..table.rows_len()).collect::<Vec<_>>();
+            if let Some(rows) = order.get_mut(1..) {
+                rows.sort_by(|&a, &b| cmp_typed_rows(&keys, a, b));
+            }
+            return cx.done_o(reorder_table(table, order));
+        }
+
         let t = table.make_mut();
         for (col, _) in cols {
             t.sort(col, cmp_table_entries);
@@ -1364,11 +3286,113 @@ fn sort_table_intrinsic(mut blk: Block) -> Result<Step> {
 
         cx.done_o(table)
     })
+    .map(|x| x.with_plan(Some(plan)))
+}
+
+/// Select the first `n` rows of `table` as if sorted by `cols`, without sorting the whole table.
+///
+/// Sorting by each column in turn is stable, so the last column takes precedence, and ties keep
+/// their order.
+fn sort_top(table: Table, cols: &[(usize, Str)], n: usize) -> Table {
+    let keys = typed_columns(&table, cols);
+    let cmp = |&a: &usize, &b: &usize| match &keys {
+        Some(keys) => cmp_typed_rows(keys, a, b),
+        None => cols
+            .iter()
+            .rev()
+            .map(|&(c, _)| {
+                cmp_table_entries(
+                    TableRow::entry_at(&table, a, c),
+                    TableRow::entry_at(&table, b, c),
+                )
+            })
+            .find(|x| x.is_ne())
+            .unwrap_or_else(|| a.cmp(&b)),
+    };
+
+    let mut idxs = (1..table.rows_len()).collect::<Vec<_>>();
+    if n < idxs.len() {
+        if n > 0 {
+            idxs.select_nth_unstable_by(n - 1, cmp);
+        }
+        idxs.truncate(n);
+    }
+    idxs.sort_unstable_by(cmp);
+
+    let mut t = ::table::Table::new();
+    t.add_rows(
+        once(0)
+            .chain(idxs)
+            .filter_map(|i| table.row(i).map(|x| x.cloned())),
+    );
+    t.into()
+}
+
+/// The typed columns of `cols`, if each column is typed.
+fn typed_columns(table: &Table, cols: &[(usize, Str)]) -> Option<Vec<Arc<types::ColumnView>>> {
+    cols.iter()
+        .map(|&(c, _)| Some(table.column_view(c)).filter(|x| x.nils().is_some()))
+        .collect()
+}
+
+/// Compare rows `a` and `b` on typed columns, as [`sort_top`] does.
+fn cmp_typed_rows(keys: &[Arc<types::ColumnView>], a: usize, b: usize) -> cmp::Ordering {
+    keys.iter()
+        .rev()
+        .map(|k| cmp_typed_entries(k, a, b))
+        .find(|x| x.is_ne())
+        .unwrap_or_else(|| a.cmp(&b))
+}
+
+/// Compare the entries of rows `a` and `b` in a typed column. Nils order last, as they do in
+/// [`cmp_table_entries`].
+fn cmp_typed_entries(col: &types::ColumnView, a: usize, b: usize) -> cmp::Ordering {
+    use cmp::Ordering::*;
+    use types::ColumnView as C;
+
+    let nils = col.nils().expect("only typed columns are compared");
+    match (nils.is_nil(a), nils.is_nil(b)) {
+        (true, true) => Equal,
+        (true, false) => Greater,
+        (false, true) => Less,
+        (false, false) => match col {
+            C::Num(xs, _) => xs[a].partial_cmp(&xs[b]).unwrap_or(Equal),
+            C::Str(xs, _) => xs[a].cmp(&xs[b]),
+            C::Mixed => unreachable!("only typed columns are compared"),
+        },
+    }
+}
+
+fn sort_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "sort a list lowest to highest in a canonical fashion
+this sorts different value types, but NOT user-defined types"
+            .into(),
+        examples: vec![HelpExample {
+            desc: "sort a list of numbers",
+            code: "\\ [3 1 2] | sort",
+        }],
+        ..HelpMessage::new("sort")
+    }
+}
+
+fn sort_list_intrinsic(mut blk: Block) -> Result<Step> {
+    list_elem_ty(&blk)?;
+    let ty = blk.in_ty().clone();
+    blk.assert_output(ty.clone());
+
+    blk.eval(ty, |list, cx| {
+        let mut list = List::try_from(list)?;
+        list.make_mut()
+            .sort_by(|a, b| cmp_table_entries(&Entry::from(a.clone()), &Entry::from(b.clone())));
+        cx.done(list)
+    })
 }
 
 /// Ogma data types are compared on name. OgmaData with the same types are considered 'equal'.
 fn cmp_table_entries(a: &Entry<Value>, b: &Entry<Value>) -> std::cmp::Ordering {
-    // overall order of variants: Bool, Num, Str, OgmaData, Nil, Table, TableRow
+    // overall order of variants: Bool, Num, Str, DateTime, Duration, OgmaData, Nil, Table, TableRow,
+    // List
     let (ai, bi) = (entry_discriminant(a), entry_discriminant(b));
     if ai != bi {
         return ai.cmp(&bi);
@@ -1388,24 +3412,39 @@ fn cmp_table_entries(a: &Entry<Value>, b: &Entry<Value>) -> std::cmp::Ordering {
         | (O(V::Num(lhs)), O(V::Num(rhs))) => lhs.cmp(rhs),
         (O(V::Bool(lhs)), O(V::Bool(rhs))) => lhs.cmp(rhs),
         (O(V::Str(lhs)), O(V::Str(rhs))) => lhs.cmp(rhs),
+        (O(V::DateTime(lhs)), O(V::DateTime(rhs))) => lhs.cmp(rhs),
+        (O(V::Duration(lhs)), O(V::Duration(rhs))) => lhs.cmp(rhs),
         (O(V::Tab(_)), _) => Equal,
         (O(V::TabRow(_)), _) => Equal,
+        (O(V::Stream(_)), _) => Equal,
         (O(V::Ogma(lhs)), O(V::Ogma(rhs))) => lhs.ty().name().str().cmp(rhs.ty().name().str()),
+        // lists are compared lexicographically
+        (O(V::List(lhs)), O(V::List(rhs))) => lhs
+            .iter()
+            .zip(rhs.iter())
+            .map(|(a, b)| cmp_table_entries(&Entry::from(a.clone()), &Entry::from(b.clone())))
+            .find(|x| x.is_ne())
+            .unwrap_or_else(|| lhs.len().cmp(&rhs.len())),
         _ => unreachable!("should not reach here given all equal cases are done"),
     }
 }
 
 /// Assigns a number to each variant of Entry<Value> combo.
-/// `Bool = 0, Num = 1, Str = 2, OgmaData = 3, Nil = 4, Table = 5, TableRow = 6`
+/// `Bool = 0, Num = 1, Str = 2, DateTime = 3, Duration = 4, OgmaData = 5, Nil = 6, Table = 7,
+/// TableRow = 8, List = 9, Stream = 10`
 fn entry_discriminant(e: &Entry<Value>) -> u8 {
     match e {
         Entry::Obj(Value::Bool(_)) => 0,
         Entry::Num(_) | Entry::Obj(Value::Num(_)) => 1,
         Entry::Obj(Value::Str(_)) => 2,
-        Entry::Obj(Value::Ogma(_)) => 3,
-        Entry::Nil | Entry::Obj(Value::Nil) => 4,
-        Entry::Obj(Value::Tab(_)) => 5,
-        Entry::Obj(Value::TabRow(_)) => 6,
+        Entry::Obj(Value::DateTime(_)) => 3,
+        Entry::Obj(Value::Duration(_)) => 4,
+        Entry::Obj(Value::Ogma(_)) => 5,
+        Entry::Nil | Entry::Obj(Value::Nil) => 6,
+        Entry::Obj(Value::Tab(_)) => 7,
+        Entry::Obj(Value::TabRow(_)) => 8,
+        Entry::Obj(Value::List(_)) => 9,
+        Entry::Obj(Value::Stream(_)) => 10,
     }
 }
 
@@ -1451,7 +3490,7 @@ fn sortby_table_intrinsic(mut blk: Block) -> Result<Step> {
             .enumerate()
             .collect();
         let sorted_values = sort_values(sorting_values, &mut cmpr.pin_env(), &cx)?;
-        cx.done_o(reorder_table(table, sorted_values))
+        cx.done_o(reorder_table(table, sorted_values.into_iter().map(|x| x.0)))
     })
 }
 
@@ -1488,15 +3527,19 @@ where
     }
 }
 
-fn reorder_table(mut table: Table, order: SortIdx) -> Table {
+/// Reorder the rows of `table` by row index, `order` should include the header.
+fn reorder_table<I>(mut table: Table, order: I) -> Table
+where
+    I: IntoIterator<Item = usize>,
+{
     let mut x = Vec::with_capacity(table.rows_len());
     if let Some(table) = table.get_mut() {
         let mut table = mem::take(table).into_raw();
-        for (idx, _) in order {
+        for idx in order {
             x.push(mem::take(&mut table[idx]));
         }
     } else {
-        for (idx, _) in order {
+        for idx in order {
             x.push(table.row(idx).unwrap().cloned().collect());
         }
     }
@@ -1563,6 +3606,11 @@ fn take_table_intrinsic(mut blk: Block) -> Result<Step> {
         .supplied(None)?
         .returns(Ty::Num)?
         .concrete()?;
+    let plan = count
+        .extract_literal::<Number>()
+        .ok()
+        .and_then(|n| cnv_num_to_uint(n.clone().into(), &count.tag).ok())
+        .map(Plan::Take);
     blk.eval_o(move |table, cx| {
         let count = count
             .resolve(|| table.clone(), &cx)
@@ -1582,4 +3630,37 @@ fn take_table_intrinsic(mut blk: Block) -> Result<Step> {
         }
         cx.done_o(table)
     })
+    .map(|x| x.with_plan(plan))
+}
+
+fn take_stream_help() -> HelpMessage {
+    HelpMessage {
+        desc: "take the first n rows of a stream
+only the chunks needed are pulled from the stream"
+            .into(),
+        params: vec![HelpParameter::Required("count".into())],
+        examples: vec![HelpExample {
+            desc: "take the first 10 rows of a stream",
+            code: "take 10",
+        }],
+        ..HelpMessage::new("take")
+    }
+}
+
+fn take_stream_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Stream)?;
+    blk.assert_output(Ty::Stream); // stream -> stream
+
+    let count = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    blk.eval_o(move |stream, cx| {
+        let count = count
+            .resolve(|| stream.clone(), &cx)
+            .and_then(|v| cnv_num_to_uint::<usize>(v, &count.tag))?;
+        let stream = slice_stream(Stream::try_from(stream)?, 0, Some(count));
+        cx.done_o(stream)
+    })
 }
@@ -14,23 +14,41 @@ pub fn add_intrinsics(impls: &mut Implementations) {
             ast::DotOperatorBlock::help
         )
         ("\\", in, Pipeline)
+        (
+            "[",
+            None,
+            ast::ListBlock::intrinsic,
+            Pipeline,
+            ast::ListBlock::help
+        )
 
         ("len", Str, len_str, Pipeline)
         ("len", Table, len_table, Pipeline)
+        ("len", len_list, Pipeline)
 
         (let, Pipeline)
 
         ("nth", Str, nth_str, Pipeline)
         ("nth", Table, nth_table, Pipeline)
+        ("nth", nth_list, Pipeline)
+
+        ("prev", TableRow, prev_tabrow, Pipeline)
 
         (rand, Pipeline)
         (range, Pipeline)
         ("Table", table, Pipeline)
 
+        ("to-bool", Str, to_bool_str, Pipeline)
+        ("to-list", Table, to_list_table, Pipeline)
+        ("to-num", Str, to_num_str, Pipeline)
+
         ("to-str", bool, to_str_bool, Pipeline)
         ("to-str", Number, to_str_num, Pipeline)
         ("to-str", Str, to_str_str, Pipeline)
+        ("to-str", ::time::OffsetDateTime, to_str_datetime, Pipeline)
+        ("to-str", ::time::Duration, to_str_duration, Pipeline)
         ("to-str", to_str, Pipeline)
+        ("to-table", to_table_list, Pipeline)
 
         ("Tuple", tuple, Pipeline)
     };
@@ -258,6 +276,79 @@ impl ast::DotOperatorBlock {
     }
 }
 
+// ------ List Literal ---------------------------------------------------------
+impl ast::ListBlock {
+    fn help() -> HelpMessage {
+        HelpMessage {
+            desc: "construct a list of the result of each expression
+lists are homogeneous, each item must have the same type
+an empty list requires a type annotation"
+                .into(),
+            params: vec![HelpParameter::Required("=> [item-1 item-2 ...]".into())],
+            examples: vec![
+                HelpExample {
+                    desc: "create a list of numbers. type: List<Num>",
+                    code: "[1 2 3]",
+                },
+                HelpExample {
+                    desc: "items are supplied the input",
+                    code: "\\ 3 | [{+ 1} {+ 2} {+ 3}]",
+                },
+                HelpExample {
+                    desc: "create an empty list of strings",
+                    code: "[]:List<Str>",
+                },
+            ],
+            ..HelpMessage::new("[")
+        }
+    }
+
+    /// The element type is taken from the first item, or the output type if there are no items.
+    fn intrinsic(mut blk: Block) -> Result<Step> {
+        let len = blk.args_len();
+        let mut items = Vec::with_capacity(len);
+        let mut elem = None;
+
+        for _ in 0..len {
+            let arg = blk.next_arg()?.supplied(None)?;
+            let arg = match &elem {
+                Some(ty) => arg.returns(Type::clone(ty))?,
+                None => arg,
+            }
+            .concrete()?;
+            elem.get_or_insert_with(|| arg.out_ty().clone());
+            items.push(arg);
+        }
+
+        let elem = match elem {
+            Some(ty) => ty,
+            None => blk
+                .output_ty()
+                .and_then(|ty| ty.list_elem().cloned())
+                .ok_or_else(|| {
+                    let mut err = Error::unknown_blk_output_type(blk.blk_tag());
+                    err.help_msg =
+                        Some("empty lists need a type annotation, such as `[]:List<Num>`".into());
+                    err
+                })?,
+        };
+
+        let oty = Type::list(elem.clone());
+
+        blk.insert_anon_type_into_compiler(oty.clone());
+
+        blk.assert_output(oty.clone());
+
+        blk.eval(oty, move |input, cx| {
+            let mut values = Vec::with_capacity(items.len());
+            for item in &items {
+                values.push(item.resolve(|| input.clone(), &cx)?);
+            }
+            cx.done(List::new(elem.clone(), values))
+        })
+    }
+}
+
 // ------ Input ----------------------------------------------------------------
 fn in_help() -> HelpMessage {
     HelpMessage {
@@ -348,6 +439,29 @@ fn len_table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+fn len_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the number of items in a list".into(),
+        examples: vec![HelpExample {
+            desc: "length of a list",
+            code: "\\ [1 2 3] | len",
+        }],
+        ..HelpMessage::new("len")
+    }
+}
+
+fn len_list_intrinsic(mut blk: Block) -> Result<Step> {
+    list_elem_ty(&blk)?;
+    blk.assert_output(Ty::Num);
+
+    blk.eval_o(|i, cx| {
+        List::try_from(i)
+            .map(|l| l.len())
+            .map(Number::from)
+            .and_then(|x| cx.done_o(x))
+    })
+}
+
 // ------ Let ------------------------------------------------------------------
 fn let_help() -> HelpMessage {
     HelpMessage {
@@ -551,6 +665,93 @@ fn nth_table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+fn nth_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "retrieves the nth item of a list".into(),
+        params: vec![HelpParameter::Required("index".into())],
+        examples: vec![
+            HelpExample {
+                desc: "get the first item of a list",
+                code: "\\ [1 2 3] | nth 0",
+            },
+            HelpExample {
+                desc: "get the last item of a list",
+                code: "\\ [1 2 3] | nth {len | - 1}",
+            },
+        ],
+        ..HelpMessage::new("nth")
+    }
+}
+
+fn nth_list_intrinsic(mut blk: Block) -> Result<Step> {
+    let elem = list_elem_ty(&blk)?;
+    blk.assert_output(elem.clone());
+
+    let n = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    blk.eval(elem, move |list, cx| {
+        let nth = n
+            .resolve(|| list.clone(), &cx)
+            .and_then(|v| cnv_num_to_uint::<usize>(v, &n.tag))?;
+        List::try_from(list)
+            .and_then(|l| {
+                l.get(nth).cloned().ok_or_else(|| {
+                    Error::eval(
+                        &n.tag,
+                        "index is outside list bounds",
+                        format!("this resolves to `{}`", nth),
+                        None,
+                    )
+                })
+            })
+            .and_then(|x| cx.done(x))
+    })
+}
+
+// ------ Prev -----------------------------------------------------------------
+fn prev_tabrow_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return a table of the `n` rows before the table row
+a table is built for each row, so the cost grows with `n`
+prefer the window commands for running aggregates over many rows"
+            .into(),
+        params: vec![HelpParameter::Required("n".into())],
+        examples: vec![HelpExample {
+            desc: "the total size of the previous 3 files",
+            code: "ls | append { prev 3 | sum size }",
+        }],
+        ..HelpMessage::new("prev")
+    }
+}
+
+fn prev_tabrow_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::TabRow)?;
+    blk.assert_output(Ty::Tab);
+
+    let n = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+
+    blk.eval_o(move |trow, cx| {
+        let n = cnv_num_to_uint::<usize>(n.resolve(|| trow.clone(), &cx)?, &n.tag)?;
+        let trow = TableRow::try_from(trow)?;
+        let from = trow.idx.saturating_sub(n).max(1);
+
+        let mut t = InnerTable::new();
+        t.add_rows(
+            once(0)
+                .chain(from..trow.idx)
+                .filter_map(|r| trow.table.row(r).map(|r| r.cloned())),
+        );
+        cx.done_o(Table::from(t))
+    })
+}
+
 // ------ Rand -----------------------------------------------------------------
 fn rand_help() -> HelpMessage {
     HelpMessage {
@@ -763,6 +964,246 @@ fn table_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+// ------ To Bool --------------------------------------------------------------
+fn to_bool_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "parse a string as a boolean
+accepts `true`/`false`, `yes`/`no`, `y`/`n`, and `1`/`0`, ignoring case and surrounding whitespace"
+            .into(),
+        examples: vec![
+            HelpExample {
+                desc: "parse a string as true",
+                code: "\\ 'True' | to-bool",
+            },
+            HelpExample {
+                desc: "filter on a yes/no column",
+                code: "open survey.csv | filter { get subscribed --Str | to-bool }",
+            },
+        ],
+        ..HelpMessage::new("to-bool")
+    }
+}
+
+fn to_bool_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Bool);
+
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |s, cx| {
+        let s = Str::try_from(s)?;
+        match parse_bool(&s) {
+            Some(b) => cx.done_o(b),
+            None => Err(Error::eval(
+                &blktag,
+                format!("failed to parse `{}` as a boolean", s),
+                String::from("within this block"),
+                "expecting `true`, `false`, `yes`, `no`, `y`, `n`, `1`, or `0`".to_string(),
+            )),
+        }
+    })
+}
+
+/// Parse a boolean, accepting `true`/`false`, `yes`/`no`, `y`/`n`, and `1`/`0`, ignoring case
+/// and surrounding whitespace.
+pub(super) fn parse_bool(s: &str) -> Option<bool> {
+    match s.trim().to_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Some(true),
+        "false" | "no" | "n" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+// ------ To List --------------------------------------------------------------
+fn to_list_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a table column into a list
+the column entries must all have the list's item type
+the item type can be specified with a type flag, or inferred from the output type"
+            .into(),
+        params: vec![HelpParameter::Required("col-name".into())],
+        examples: vec![
+            HelpExample {
+                desc: "list the file names in the directory",
+                code: "ls | to-list name --Str",
+            },
+            HelpExample {
+                desc: "specify the type using an annotation",
+                code: "ls | to-list:List<Num> size",
+            },
+        ],
+        ..HelpMessage::new("to-list")
+    }
+}
+
+fn to_list_table_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+
+    let colarg = blk
+        .next_arg()?
+        .supplied(Type::Nil)?
+        .returns(Ty::Str)?
+        .concrete()?;
+    let elem = type_flag(&mut blk).and_then(|ty| {
+        ty.map(Ok).unwrap_or_else(|| {
+            blk.output_ty()
+                .and_then(|ty| ty.list_elem().cloned())
+                .ok_or_else(|| Error::unknown_blk_output_type(blk.blk_tag()))
+        })
+    })?;
+
+    let oty = Type::list(elem.clone());
+    blk.insert_anon_type_into_compiler(oty.clone());
+    blk.assert_output(oty.clone());
+
+    blk.eval(oty, move |table, cx| {
+        let colname: Str = colarg.resolve(|| Value::Nil, &cx)?.try_into()?;
+        let table = Table::try_from(table)?;
+        let col = TableRow::col_idx(&table, colname.as_str(), &colarg.tag)?;
+        let items = (1..table.rows_len())
+            .map(|row| {
+                let e = TableRow::entry_at(&table, row, col);
+                TableRow::cnv_value(e, &elem, row, &colname, &colarg.tag)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        cx.done(List::new(elem.clone(), items))
+    })
+}
+
+// ------ To Num ---------------------------------------------------------------
+fn to_num_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "parse a string as a number
+surrounding whitespace, thousands separators, and currency symbols are ignored
+a trailing `%` divides the number by 100, and an amount in parentheses is negative"
+            .into(),
+        flags: vec![(
+            "decimal-comma",
+            "use `,` as the decimal point and `.` as the thousands separator",
+        )],
+        examples: vec![
+            HelpExample {
+                desc: "parse a currency amount",
+                code: "\\ '$1,234.50' | to-num",
+            },
+            HelpExample {
+                desc: "parse a percentage",
+                code: "\\ '12.5%' | to-num",
+            },
+            HelpExample {
+                desc: "parse a number using a decimal comma",
+                code: "\\ '1.234,5' | to-num --decimal-comma",
+            },
+        ],
+        ..HelpMessage::new("to-num")
+    }
+}
+
+fn to_num_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Num);
+
+    let decimal_comma = blk.get_flag("decimal-comma").is_some();
+    let blktag = blk.blk_tag().clone();
+    blk.eval_o(move |s, cx| {
+        let s = Str::try_from(s)?;
+        match parse_num(&s, decimal_comma) {
+            Some(n) => cx.done_o(n),
+            None => Err(Error::eval(
+                &blktag,
+                format!("failed to parse `{}` as a number", s),
+                String::from("within this block"),
+                if decimal_comma {
+                    "expecting a number such as `1.234,5`, `€1.234`, or `12,5%`"
+                } else {
+                    "expecting a number such as `1234.5`, `$1,234`, or `12.5%`
+          a decimal comma can be used with the `--decimal-comma` flag"
+                }
+                .to_string(),
+            )),
+        }
+    })
+}
+
+/// Parse a number, ignoring surrounding whitespace, thousands separators, and currency symbols.
+///
+/// Thousands separators must group the integer digits in threes, otherwise the string is not a
+/// number. A trailing `%` divides the number by 100, and an amount wrapped in parentheses is
+/// negative. Integers are kept exact.
+pub(super) fn parse_num(s: &str, decimal_comma: bool) -> Option<Number> {
+    const CURRENCY: &[char] = &['$', '€', '£', '¥', '₹', '₩'];
+
+    fn sign(s: &str) -> (bool, &str) {
+        match s.strip_prefix('-') {
+            Some(s) => (true, s.trim_start()),
+            None => (false, s.strip_prefix('+').unwrap_or(s).trim_start()),
+        }
+    }
+
+    fn grouped(s: &str, sep: char) -> bool {
+        let digits = |g: &str| g.chars().all(|c| c.is_ascii_digit());
+        let mut groups = s.split(sep);
+        let first = groups.next().unwrap_or_default();
+        (1..=3).contains(&first.len()) && digits(first) && groups.all(|g| g.len() == 3 && digits(g))
+    }
+
+    let (thousands, decimal) = if decimal_comma {
+        ('.', ',')
+    } else {
+        (',', '.')
+    };
+
+    let s = s.trim();
+    let (paren, s) = match s.strip_prefix('(').and_then(|s| s.strip_suffix(')')) {
+        Some(s) => (true, s.trim()),
+        None => (false, s),
+    };
+    let (pct, s) = match s.strip_suffix('%') {
+        Some(s) => (true, s.trim_end()),
+        None => (false, s),
+    };
+    // the sign can come before or after the currency symbol
+    let (neg1, s) = sign(s);
+    let s = s.trim_start_matches(CURRENCY).trim_start();
+    let (neg2, s) = sign(s);
+    let s = s.trim_end_matches(CURRENCY).trim_end();
+    let neg = paren ^ neg1 ^ neg2;
+
+    if !s.starts_with(|c: char| c.is_ascii_digit() || c == decimal) {
+        return None;
+    }
+
+    let int = s.split(decimal).next().unwrap_or_default();
+    if int.contains(thousands) && !grouped(int, thousands) {
+        return None;
+    }
+
+    let mut x = String::with_capacity(s.len() + 1);
+    if neg {
+        x.push('-');
+    }
+    let mut frac = false;
+    for c in s.chars() {
+        match c {
+            c if c == thousands && !frac => (),
+            c if c == decimal => {
+                frac = true;
+                x.push('.');
+            }
+            c => x.push(c),
+        }
+    }
+
+    match x.parse::<i64>() {
+        Ok(i) if !pct => Some(Number::from(i)),
+        _ => x
+            .parse::<f64>()
+            .ok()
+            .map(|f| if pct { f / 100.0 } else { f })
+            .map(Number::from),
+    }
+}
+
 // ------ To Str ---------------------------------------------------------------
 fn to_str_bool_help() -> HelpMessage {
     HelpMessage {
@@ -839,6 +1280,69 @@ fn to_str_str_intrinsic(mut blk: Block) -> Result<Step> {
     blk.eval_o(|v, c| c.done_o(Str::try_from(v)?))
 }
 
+fn to_str_datetime_help() -> HelpMessage {
+    HelpMessage {
+        desc: "format the datetime as a string
+defaults to RFC 3339, such as `2022-03-04T12:30:00Z`
+formatting syntax can be found at <https://time-rs.github.io/book/api/format-description.html>"
+            .into(),
+        params: vec![HelpParameter::Optional("fmt".into())],
+        examples: vec![
+            HelpExample {
+                desc: "format the current date",
+                code: "now | to-str '[day]/[month]/[year]'",
+            },
+            HelpExample {
+                desc: "get the name of the current day",
+                code: "now | to-str '[weekday]'",
+            },
+        ],
+        ..HelpMessage::new("to-str")
+    }
+}
+
+fn to_str_datetime_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::DateTime)?;
+    blk.assert_output(Ty::Str);
+
+    let fmt = if blk.args_len() == 0 {
+        None
+    } else {
+        Some(super::datetime::datetime_fmt_arg(&mut blk)?)
+    };
+
+    blk.eval_o(move |v, cx| {
+        let dt = ::time::OffsetDateTime::try_from(v)?;
+        let s = match &fmt {
+            Some(fmt) => {
+                let items =
+                    ::time::format_description::parse(fmt).expect("checked at compile time");
+                // formatting only fails if the datetime lacks a component, which it does not
+                dt.format(&items).unwrap_or_default()
+            }
+            None => print::fmt_datetime(&dt),
+        };
+        cx.done_o(Str::from(s))
+    })
+}
+
+fn to_str_duration_help() -> HelpMessage {
+    HelpMessage {
+        desc: "format the duration as a string, such as `1d 2h 30m 4.5s`".into(),
+        ..HelpMessage::new("to-str")
+    }
+}
+
+fn to_str_duration_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Duration)?;
+    blk.assert_output(Ty::Str);
+
+    blk.eval_o(|v, c| {
+        let d = ::time::Duration::try_from(v)?;
+        c.done_o(Str::from(print::fmt_duration(&d)))
+    })
+}
+
 fn to_str_help() -> HelpMessage {
     HelpMessage {
         desc: "convert the input into a string".into(),
@@ -857,6 +1361,56 @@ fn to_str_intrinsic(mut blk: Block) -> Result<Step> {
     })
 }
 
+// ------ To Table -------------------------------------------------------------
+fn to_table_list_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a list into a single column table
+the column header defaults to `list`"
+            .into(),
+        params: vec![HelpParameter::Optional("header".into())],
+        examples: vec![
+            HelpExample {
+                desc: "convert a list of numbers into a table",
+                code: "\\ [1 2 3] | to-table",
+            },
+            HelpExample {
+                desc: "use a column header of `x`",
+                code: "\\ [1 2 3] | to-table x",
+            },
+        ],
+        ..HelpMessage::new("to-table")
+    }
+}
+
+fn to_table_list_intrinsic(mut blk: Block) -> Result<Step> {
+    list_elem_ty(&blk)?;
+    blk.assert_output(Ty::Tab);
+
+    let header = match blk.args_len() {
+        0 => None,
+        _ => Some(
+            blk.next_arg()?
+                .supplied(None)?
+                .returns(Ty::Str)?
+                .concrete()?,
+        ),
+    };
+
+    blk.eval_o(move |list, cx| {
+        let header: Str = match &header {
+            Some(h) => h.resolve(|| list.clone(), &cx)?.try_into()?,
+            None => Str::new("list"),
+        };
+        let list = List::try_from(list)?;
+
+        let mut t = InnerTable::new();
+        t.add_row(once(Entry::from(Value::Str(header))));
+        t.add_rows(list.into_items().into_iter().map(|x| once(Entry::from(x))));
+
+        cx.done_o(Table::from(t))
+    })
+}
+
 // ------ Tuple ----------------------------------------------------------------
 fn tuple_help() -> HelpMessage {
     variadic_help(
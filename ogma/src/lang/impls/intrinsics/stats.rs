@@ -0,0 +1,436 @@
+use super::*;
+
+pub fn add_intrinsics(impls: &mut Implementations) {
+    add! { impls,
+        ("count", Table, count_table, Arithmetic)
+        ("max", Table, max_table, Arithmetic)
+        ("mean", Table, mean_table, Arithmetic)
+        ("median", Table, median_table, Arithmetic)
+        ("min", Table, min_table, Arithmetic)
+        ("pctl", Table, pctl_table, Arithmetic)
+        ("stdev", Table, stdev_table, Arithmetic)
+        ("sum", Table, sum_table, Arithmetic)
+        ("var", Table, var_table, Arithmetic)
+    };
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Agg {
+    Count,
+    Max,
+    Mean,
+    Median,
+    Min,
+    Pctl,
+    Stdev,
+    Sum,
+    Var,
+}
+
+impl Agg {
+    fn cmd(self) -> &'static str {
+        use Agg::*;
+        match self {
+            Count => "count",
+            Max => "max",
+            Mean => "mean",
+            Median => "median",
+            Min => "min",
+            Pctl => "pctl",
+            Stdev => "stdev",
+            Sum => "sum",
+            Var => "var",
+        }
+    }
+
+    /// Aggregate the column `col` of `table`, in parallel over the rows.
+    ///
+    /// `p` is the percentile, only used with [`Agg::Pctl`].
+    fn eval(self, table: &Table, col: &str, coltag: &Tag, p: f64) -> Result<f64> {
+        let c = TableRow::col_idx(table, col, coltag)?;
+        let column = table.column_view(c);
+        let rows = (1..table.rows_len()).into_par_iter();
+
+        if self == Agg::Count {
+            if let Some(nils) = column.nils() {
+                return Ok((table.rows_len() - nils.count()) as f64);
+            }
+
+            let n = rows
+                .filter(|&r| {
+                    !matches!(
+                        TableRow::entry_at(table, r, c),
+                        Entry::Nil | Entry::Obj(Value::Nil)
+                    )
+                })
+                .count();
+            return Ok(n as f64);
+        }
+
+        // a number column is read without matching each entry
+        let mut xs = match &*column {
+            types::ColumnView::Num(xs, nils) => rows
+                .filter(|&r| !nils.is_nil(r))
+                .map(|r| xs[r])
+                .collect::<Vec<_>>(),
+            _ => rows
+                .filter_map(|r| match TableRow::entry_at(table, r, c) {
+                    Entry::Nil | Entry::Obj(Value::Nil) => None,
+                    Entry::Num(n) | Entry::Obj(Value::Num(n)) => Some(Ok(n.as_f64())),
+                    e => Some(Err(Error::unexp_entry_ty(
+                        &Ty::Num,
+                        &Value::from(e).ty(),
+                        r,
+                        col,
+                        coltag,
+                    ))),
+                })
+                .collect::<Result<Vec<_>>>()?,
+        };
+
+        Ok(match self {
+            Agg::Count => unreachable!("count is returned early"),
+            Agg::Max => xs
+                .par_iter()
+                .copied()
+                .reduce_with(f64::max)
+                .unwrap_or(f64::NAN),
+            Agg::Mean => mean(&xs),
+            Agg::Median => percentile(&mut xs, 0.5),
+            Agg::Min => xs
+                .par_iter()
+                .copied()
+                .reduce_with(f64::min)
+                .unwrap_or(f64::NAN),
+            Agg::Pctl => percentile(&mut xs, p),
+            Agg::Stdev => variance(&xs).sqrt(),
+            Agg::Sum => xs.par_iter().sum(),
+            Agg::Var => variance(&xs),
+        })
+    }
+}
+
+fn mean(xs: &[f64]) -> f64 {
+    xs.par_iter().sum::<f64>() / xs.len() as f64
+}
+
+/// The _sample_ variance.
+fn variance(xs: &[f64]) -> f64 {
+    if xs.len() < 2 {
+        return f64::NAN;
+    }
+
+    let m = mean(xs);
+    xs.par_iter().map(|x| (x - m).powi(2)).sum::<f64>() / (xs.len() as f64 - 1.0)
+}
+
+/// Linearly interpolates between the closest ranks. `p` is expected to be within `0..=1`.
+fn percentile(xs: &mut [f64], p: f64) -> f64 {
+    if xs.is_empty() {
+        return f64::NAN;
+    }
+
+    xs.par_sort_unstable_by(f64::total_cmp);
+    let i = p * (xs.len() - 1) as f64;
+    let (lo, hi) = (xs[i.floor() as usize], xs[i.ceil() as usize]);
+    lo + (hi - lo) * i.fract()
+}
+
+/// Builds an aggregation over a column of the input table.
+///
+/// With the `--by` flag, the input is expected to be a grouped table (such as from `grp`) and each
+/// `value` table is aggregated, appending the results as a column.
+fn agg_intrinsic(mut blk: Block, agg: Agg) -> Result<Step> {
+    blk.assert_input(&Ty::Tab)?;
+
+    let by = blk.get_flag("by").is_some();
+    let col = blk
+        .next_arg()?
+        .supplied(Ty::Nil)?
+        .returns(Ty::Str)?
+        .concrete()?;
+    let p = match agg {
+        Agg::Pctl => Some(
+            blk.next_arg()?
+                .supplied(None)?
+                .returns(Ty::Num)?
+                .concrete()?,
+        ),
+        _ => None,
+    };
+    let blktag = blk.blk_tag().clone();
+
+    let out_ty = if by { Ty::Tab } else { Ty::Num };
+    blk.assert_output(out_ty.clone());
+
+    blk.eval(out_ty, move |table, cx| {
+        let colname: Str = col.resolve(|| Value::Nil, &cx)?.try_into()?;
+        let p = match &p {
+            Some(p) => {
+                let x = Number::try_from(p.resolve(|| table.clone(), &cx)?)?.as_f64();
+                check_percentile(x, &p.tag)?;
+                x
+            }
+            None => 0.0,
+        };
+        let mut table = Table::try_from(table)?;
+
+        if !by {
+            let x = agg.eval(&table, &colname, &col.tag, p)?;
+            return cx.done(Value::Num(x.into()));
+        }
+
+        let vcol = TableRow::col_idx(&table, "value", &blktag)?;
+        let mut aggs = vec![Entry::Nil; table.rows_len()];
+        if let Some(hdr) = aggs.get_mut(0) {
+            *hdr = o(format!("{}-{}", agg.cmd(), colname));
+        }
+        aggs.par_iter_mut()
+            .enumerate()
+            .skip(1) // skip header row
+            .try_for_each(|(row, x)| {
+                let t = TableRow::entry_at(&table, row, vcol);
+                let t = TableRow::cnv_value(t, &Ty::Tab, row, "value", &blktag)
+                    .and_then(Table::try_from)?;
+                *x = n(agg.eval(&t, &colname, &col.tag, p)?);
+                Ok(())
+            })?;
+
+        table.make_mut().add_cols(once(aggs.into_iter()));
+        cx.done(Value::Tab(table))
+    })
+}
+
+fn check_percentile(p: f64, tag: &Tag) -> Result<()> {
+    if (0.0..=1.0).contains(&p) {
+        Ok(())
+    } else {
+        Err(Error::eval(
+            tag,
+            "percentile must be between 0 and 1",
+            format!("{} is outside 0..=1", p),
+            "for example, the 95th percentile is `0.95`".to_string(),
+        ))
+    }
+}
+
+fn agg_help(cmd: &'static str, desc: &str, examples: Vec<HelpExample>) -> HelpMessage {
+    HelpMessage {
+        desc: format!(
+            "{}\nnil entries are ignored, other non-number entries are an error",
+            desc
+        )
+        .into(),
+        params: vec![HelpParameter::Required("col-name".into())],
+        flags: vec![(
+            "by",
+            "aggregate each `value` table of a grouped table, appending a column",
+        )],
+        examples,
+        ..HelpMessage::new(cmd)
+    }
+}
+
+// ------ Count ----------------------------------------------------------------
+fn count_table_help() -> HelpMessage {
+    HelpMessage {
+        desc: "count the non-nil entries in a column".into(),
+        params: vec![HelpParameter::Required("col-name".into())],
+        flags: vec![(
+            "by",
+            "aggregate each `value` table of a grouped table, appending a column",
+        )],
+        examples: vec![
+            HelpExample {
+                desc: "count the entries of the size column",
+                code: "ls | count size",
+            },
+            HelpExample {
+                desc: "count the diamonds of each cut with a price",
+                code: "open diamonds.csv | grp cut | count --by price",
+            },
+        ],
+        ..HelpMessage::new("count")
+    }
+}
+
+fn count_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Count)
+}
+
+// ------ Max ------------------------------------------------------------------
+fn max_table_help() -> HelpMessage {
+    agg_help(
+        "max",
+        "return the maximum number in a column",
+        vec![HelpExample {
+            desc: "the largest file size",
+            code: "ls | max size",
+        }],
+    )
+}
+
+fn max_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Max)
+}
+
+// ------ Mean -----------------------------------------------------------------
+fn mean_table_help() -> HelpMessage {
+    agg_help(
+        "mean",
+        "return the arithmetic mean of the numbers in a column",
+        vec![
+            HelpExample {
+                desc: "the average file size",
+                code: "ls | mean size",
+            },
+            HelpExample {
+                desc: "the average price of each cut",
+                code: "open diamonds.csv | grp cut | mean --by price",
+            },
+        ],
+    )
+}
+
+fn mean_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Mean)
+}
+
+// ------ Median ---------------------------------------------------------------
+fn median_table_help() -> HelpMessage {
+    agg_help(
+        "median",
+        "return the median of the numbers in a column",
+        vec![HelpExample {
+            desc: "the median file size",
+            code: "ls | median size",
+        }],
+    )
+}
+
+fn median_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Median)
+}
+
+// ------ Min ------------------------------------------------------------------
+fn min_table_help() -> HelpMessage {
+    agg_help(
+        "min",
+        "return the minimum number in a column",
+        vec![HelpExample {
+            desc: "the smallest file size",
+            code: "ls | min size",
+        }],
+    )
+}
+
+fn min_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Min)
+}
+
+// ------ Percentile -----------------------------------------------------------
+fn pctl_table_help() -> HelpMessage {
+    HelpMessage {
+        params: vec![
+            HelpParameter::Required("col-name".into()),
+            HelpParameter::Required("percentile:Num".into()),
+        ],
+        ..agg_help(
+            "pctl",
+            "return the percentile of the numbers in a column
+the percentile is between 0 and 1, interpolating linearly between the closest ranks",
+            vec![
+                HelpExample {
+                    desc: "the 95th percentile of file sizes",
+                    code: "ls | pctl size 0.95",
+                },
+                HelpExample {
+                    desc: "the lower quartile price of each cut",
+                    code: "open diamonds.csv | grp cut | pctl --by price 0.25",
+                },
+            ],
+        )
+    }
+}
+
+fn pctl_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Pctl)
+}
+
+// ------ Stdev ----------------------------------------------------------------
+fn stdev_table_help() -> HelpMessage {
+    agg_help(
+        "stdev",
+        "return the sample standard deviation of the numbers in a column",
+        vec![HelpExample {
+            desc: "the standard deviation of file sizes",
+            code: "ls | stdev size",
+        }],
+    )
+}
+
+fn stdev_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Stdev)
+}
+
+// ------ Sum ------------------------------------------------------------------
+fn sum_table_help() -> HelpMessage {
+    agg_help(
+        "sum",
+        "return the sum of the numbers in a column",
+        vec![
+            HelpExample {
+                desc: "the total size of files",
+                code: "ls | sum size",
+            },
+            HelpExample {
+                desc: "the total carats of each cut",
+                code: "open diamonds.csv | grp cut | sum --by carat",
+            },
+        ],
+    )
+}
+
+fn sum_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Sum)
+}
+
+// ------ Var ------------------------------------------------------------------
+fn var_table_help() -> HelpMessage {
+    agg_help(
+        "var",
+        "return the sample variance of the numbers in a column",
+        vec![HelpExample {
+            desc: "the variance of file sizes",
+            code: "ls | var size",
+        }],
+    )
+}
+
+fn var_table_intrinsic(blk: Block) -> Result<Step> {
+    agg_intrinsic(blk, Agg::Var)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn percentile_testing() {
+        let xs = &mut [4.0, 1.0, 3.0, 2.0];
+        assert_eq!(percentile(xs, 0.0), 1.0);
+        assert_eq!(percentile(xs, 1.0), 4.0);
+        assert_eq!(percentile(xs, 0.5), 2.5);
+        assert_eq!(percentile(xs, 0.25), 1.75);
+        assert!(percentile(&mut [], 0.5).is_nan());
+    }
+
+    #[test]
+    fn variance_testing() {
+        let xs = &[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0];
+        assert_eq!(mean(xs), 5.0);
+        assert_eq!(variance(xs), 32.0 / 7.0);
+        assert!(variance(&[1.0]).is_nan());
+        assert!(variance(&[]).is_nan());
+    }
+}
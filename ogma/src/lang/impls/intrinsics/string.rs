@@ -0,0 +1,545 @@
+use super::*;
+use ::libs::regex::Regex;
+use std::borrow::Cow;
+
+pub fn add_intrinsics(impls: &mut Implementations) {
+    add! { impls,
+        ("captures", Str, captures_str, Pipeline)
+        ("contains", Str, contains_str, Pipeline)
+        ("ends-with", Str, endswith_str, Pipeline)
+        ("index-of", Str, indexof_str, Pipeline)
+        ("lower", Str, lower_str, Pipeline)
+        ("match", Str, match_str, Pipeline)
+        ("pad", Str, pad_str, Pipeline)
+        ("replace", Str, replace_str, Pipeline)
+        ("replace-re", Str, replacere_str, Pipeline)
+        ("split", Str, split_str, Pipeline)
+        ("starts-with", Str, startswith_str, Pipeline)
+        ("trim", Str, trim_str, Pipeline)
+        ("upper", Str, upper_str, Pipeline)
+    };
+}
+
+/// The next argument, supplied the input string and returning a string.
+fn str_arg(blk: &mut Block) -> Result<eng::Argument> {
+    blk.next_arg()?.supplied(None)?.returns(Ty::Str)?.concrete()
+}
+
+/// Builds an intrinsic which tests the input string against a string argument.
+fn str_test_intrinsic<F>(mut blk: Block, f: F) -> Result<Step>
+where
+    F: Fn(&str, &str) -> bool + Send + Sync + 'static,
+{
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Bool);
+
+    let pat = str_arg(&mut blk)?;
+    blk.eval_o(move |string, cx| {
+        let p: Str = pat.resolve(|| string.clone(), &cx)?.try_into()?;
+        let s = Str::try_from(string)?;
+        cx.done_o(f(&s, &p))
+    })
+}
+
+/// Builds an intrinsic which transforms the input string.
+fn str_map_intrinsic<F>(mut blk: Block, f: F) -> Result<Step>
+where
+    F: Fn(&str) -> String + Send + Sync + 'static,
+{
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Str);
+
+    blk.eval_o(move |string, cx| {
+        let s = Str::try_from(string)?;
+        cx.done_o(Str::from(f(&s)))
+    })
+}
+
+/// A regular expression argument.
+///
+/// Literals are compiled once at compile time, otherwise the expression is compiled on each
+/// evaluation.
+struct RegexArg {
+    arg: eng::Argument,
+    re: Option<Regex>,
+}
+
+impl RegexArg {
+    fn build(blk: &mut Block) -> Result<Self> {
+        let arg = str_arg(blk)?;
+        let re = arg
+            .extract_literal::<Str>()
+            .ok()
+            .map(|s| compile_regex(s, &arg.tag))
+            .transpose()?;
+        Ok(Self { arg, re })
+    }
+
+    fn resolve(&self, input: &Value, cx: &Context) -> Result<Cow<Regex>> {
+        match &self.re {
+            Some(re) => Ok(Cow::Borrowed(re)),
+            None => {
+                let s: Str = self.arg.resolve(|| input.clone(), cx)?.try_into()?;
+                compile_regex(&s, &self.arg.tag).map(Cow::Owned)
+            }
+        }
+    }
+}
+
+/// Compile a regular expression, the regex error is condensed to its final line.
+fn compile_regex(s: &str, tag: &Tag) -> Result<Regex> {
+    Regex::new(s).map_err(|e| {
+        let e = e.to_string();
+        let e = e.lines().last().unwrap_or_default();
+        Error {
+            cat: err::Category::Parsing,
+            desc: format!(
+                "invalid regular expression: {}",
+                e.strip_prefix("error: ").unwrap_or(e)
+            ),
+            traces: err::trace(tag, Some("invalid regex".into())),
+            help_msg: Some(
+                "regex syntax can be found at
+          <https://docs.rs/regex/latest/regex/#syntax>"
+                    .into(),
+            ),
+            hard: true,
+        }
+    })
+}
+
+// ------ Captures -------------------------------------------------------------
+fn captures_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return a list of the regular expression capture groups of the first match
+the first item is the whole match, groups which do not participate in the match are empty
+strings. the list is empty if there is no match"
+            .into(),
+        params: vec![HelpParameter::Required("regex".into())],
+        examples: vec![
+            HelpExample {
+                desc: "capture the number in a string",
+                code: "\\ 'a1 b22 c333' | captures '[a-z](\\d+)' | nth 1",
+            },
+            HelpExample {
+                desc: "capture the year and month of a date",
+                code: "\\ '2022-03-04' | captures '(\\d{4})-(\\d{2})'",
+            },
+        ],
+        ..HelpMessage::new("captures")
+    }
+}
+
+fn captures_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    let oty = Ty::list(Ty::Str);
+    blk.insert_anon_type_into_compiler(oty.clone());
+    blk.assert_output(oty.clone());
+
+    let re = RegexArg::build(&mut blk)?;
+    blk.eval(oty, move |string, cx| {
+        let re = re.resolve(&string, &cx)?;
+        let s = Str::try_from(string)?;
+
+        let groups = re
+            .captures(&s)
+            .map(|caps| {
+                caps.iter()
+                    .map(|m| str_value(m.map(|m| m.as_str()).unwrap_or_default()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        cx.done(List::new(Ty::Str, groups))
+    })
+}
+
+// ------ Contains -------------------------------------------------------------
+fn contains_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "test if the input string contains a substring".into(),
+        params: vec![HelpParameter::Required("substring".into())],
+        examples: vec![HelpExample {
+            desc: "test if a string contains 'world'",
+            code: "\\ 'Hello, world!' | contains world",
+        }],
+        ..HelpMessage::new("contains")
+    }
+}
+
+fn contains_str_intrinsic(blk: Block) -> Result<Step> {
+    str_test_intrinsic(blk, |s, p| s.contains(p))
+}
+
+// ------ Ends-with ------------------------------------------------------------
+fn endswith_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "test if the input string ends with a substring".into(),
+        params: vec![HelpParameter::Required("substring".into())],
+        examples: vec![HelpExample {
+            desc: "filter ls to csv files",
+            code: "ls | filter name --Str ends-with '.csv'",
+        }],
+        ..HelpMessage::new("ends-with")
+    }
+}
+
+fn endswith_str_intrinsic(blk: Block) -> Result<Step> {
+    str_test_intrinsic(blk, |s, p| s.ends_with(p))
+}
+
+// ------ Index-of -------------------------------------------------------------
+fn indexof_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "return the character index of the first occurrence of a substring
+returns -1 if the substring is not found"
+            .into(),
+        params: vec![HelpParameter::Required("substring".into())],
+        examples: vec![HelpExample {
+            desc: "find the index of the comma",
+            code: "\\ 'Hello, world!' | index-of ','",
+        }],
+        ..HelpMessage::new("index-of")
+    }
+}
+
+fn indexof_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Num);
+
+    let pat = str_arg(&mut blk)?;
+    blk.eval_o(move |string, cx| {
+        let p: Str = pat.resolve(|| string.clone(), &cx)?.try_into()?;
+        let s = Str::try_from(string)?;
+        let i = s
+            .find(p.as_str())
+            .map(|b| s[..b].chars().count() as f64)
+            .unwrap_or(-1.0);
+        cx.done_o(Number::from(i))
+    })
+}
+
+// ------ Lower ----------------------------------------------------------------
+fn lower_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a string to lowercase".into(),
+        examples: vec![HelpExample {
+            desc: "lowercase a string",
+            code: "\\ 'Hello, world!' | lower",
+        }],
+        ..HelpMessage::new("lower")
+    }
+}
+
+fn lower_str_intrinsic(blk: Block) -> Result<Step> {
+    str_map_intrinsic(blk, str::to_lowercase)
+}
+
+// ------ Match ----------------------------------------------------------------
+fn match_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "test if the input string matches a regular expression
+the match can occur anywhere in the string, use `^` and `$` to match the whole string"
+            .into(),
+        params: vec![HelpParameter::Required("regex".into())],
+        examples: vec![
+            HelpExample {
+                desc: "test if a string contains a number",
+                code: "\\ 'abc123' | match '\\d+'",
+            },
+            HelpExample {
+                desc: "filter ls to names which are a date",
+                code: "ls | filter name --Str match '^\\d{4}-\\d{2}-\\d{2}'",
+            },
+        ],
+        ..HelpMessage::new("match")
+    }
+}
+
+fn match_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Bool);
+
+    let re = RegexArg::build(&mut blk)?;
+    blk.eval_o(move |string, cx| {
+        let re = re.resolve(&string, &cx)?;
+        let s = Str::try_from(string)?;
+        cx.done_o(re.is_match(&s))
+    })
+}
+
+// ------ Pad ------------------------------------------------------------------
+fn pad_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "pad a string to a character width
+the padding defaults to a space and is added to the end of the string
+strings wider than the width are returned unchanged"
+            .into(),
+        params: vec![
+            HelpParameter::Required("width".into()),
+            HelpParameter::Optional("char".into()),
+        ],
+        flags: vec![
+            ("start", "pad the start of the string"),
+            ("end", "pad the end of the string (default)"),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "pad a string to 10 characters",
+                code: "\\ 'Hello' | pad 10",
+            },
+            HelpExample {
+                desc: "pad a number with leading zeros",
+                code: "\\ 42 | to-str | pad --start 5 '0'",
+            },
+        ],
+        ..HelpMessage::new("pad")
+    }
+}
+
+fn pad_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Str);
+
+    let start = blk.get_flag("start").is_some();
+    blk.get_flag("end"); // default
+    let width = blk
+        .next_arg()?
+        .supplied(None)?
+        .returns(Ty::Num)?
+        .concrete()?;
+    let ch = match blk.args_len() {
+        0 => None,
+        _ => Some(str_arg(&mut blk)?),
+    };
+
+    blk.eval_o(move |string, cx| {
+        let width = width
+            .resolve(|| string.clone(), &cx)
+            .and_then(|v| cnv_num_to_uint::<usize>(v, &width.tag))?;
+        let ch = match &ch {
+            Some(arg) => {
+                let c: Str = arg.resolve(|| string.clone(), &cx)?.try_into()?;
+                let mut chars = c.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => {
+                        return Err(Error::eval(
+                            &arg.tag,
+                            "padding must be a single character",
+                            format!("this resolves to `{}`", c),
+                            None,
+                        ))
+                    }
+                }
+            }
+            None => ' ',
+        };
+
+        let s = Str::try_from(string)?;
+        let padding = repeat(ch)
+            .take(width.saturating_sub(s.chars().count()))
+            .collect::<String>();
+        let s = if start {
+            padding + s.as_str()
+        } else {
+            s.to_string() + &padding
+        };
+        cx.done_o(Str::from(s))
+    })
+}
+
+// ------ Replace --------------------------------------------------------------
+fn replace_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "replace all occurrences of a substring with another string
+to replace using a regular expression see `replace-re`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("from".into()),
+            HelpParameter::Required("to".into()),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "replace the world",
+                code: "\\ 'Hello, world!' | replace world 🌎",
+            },
+            HelpExample {
+                desc: "remove thousands separators",
+                code: "\\ '1,234,567' | replace ',' ''",
+            },
+        ],
+        ..HelpMessage::new("replace")
+    }
+}
+
+fn replace_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Str);
+
+    let from = str_arg(&mut blk)?;
+    let to = str_arg(&mut blk)?;
+    blk.eval_o(move |string, cx| {
+        let from: Str = from.resolve(|| string.clone(), &cx)?.try_into()?;
+        let to: Str = to.resolve(|| string.clone(), &cx)?.try_into()?;
+        let s = Str::try_from(string)?;
+        cx.done_o(Str::from(s.replace(from.as_str(), &to)))
+    })
+}
+
+// ------ Replace-re -----------------------------------------------------------
+fn replacere_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "replace all matches of a regular expression with another string
+capture groups can be referenced in the replacement with `$1` or `${name}`"
+            .into(),
+        params: vec![
+            HelpParameter::Required("regex".into()),
+            HelpParameter::Required("to".into()),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "collapse whitespace",
+                code: "\\ 'Hello,    world!' | replace-re '\\s+' ' '",
+            },
+            HelpExample {
+                desc: "reorder a date",
+                code: "\\ '04/03/2022' | replace-re '(\\d+)/(\\d+)/(\\d+)' '$3-$2-$1'",
+            },
+        ],
+        ..HelpMessage::new("replace-re")
+    }
+}
+
+fn replacere_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    blk.assert_output(Ty::Str);
+
+    let re = RegexArg::build(&mut blk)?;
+    let to = str_arg(&mut blk)?;
+    blk.eval_o(move |string, cx| {
+        let re = re.resolve(&string, &cx)?;
+        let to: Str = to.resolve(|| string.clone(), &cx)?.try_into()?;
+        let s = Str::try_from(string)?;
+        cx.done_o(Str::from(re.replace_all(&s, to.as_str()).into_owned()))
+    })
+}
+
+// ------ Split ----------------------------------------------------------------
+fn split_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "split a string into a list of strings
+splits on whitespace if no separator is specified"
+            .into(),
+        params: vec![HelpParameter::Optional("separator".into())],
+        examples: vec![
+            HelpExample {
+                desc: "split a string on whitespace",
+                code: "\\ 'Hello, world!' | split",
+            },
+            HelpExample {
+                desc: "split a string on commas",
+                code: "\\ 'a,b,c' | split ','",
+            },
+        ],
+        ..HelpMessage::new("split")
+    }
+}
+
+fn split_str_intrinsic(mut blk: Block) -> Result<Step> {
+    blk.assert_input(&Ty::Str)?;
+    let oty = Ty::list(Ty::Str);
+    blk.insert_anon_type_into_compiler(oty.clone());
+    blk.assert_output(oty.clone());
+
+    let sep = match blk.args_len() {
+        0 => None,
+        _ => Some(str_arg(&mut blk)?),
+    };
+    blk.eval(oty, move |string, cx| {
+        let sep = match &sep {
+            Some(sep) => Some(Str::try_from(sep.resolve(|| string.clone(), &cx)?)?),
+            None => None,
+        };
+        let s = Str::try_from(string)?;
+
+        let items = match &sep {
+            Some(sep) => s.split(sep.as_str()).map(str_value).collect(),
+            None => s.split_whitespace().map(str_value).collect(),
+        };
+
+        cx.done(List::new(Ty::Str, items))
+    })
+}
+
+fn str_value(s: &str) -> Value {
+    Value::Str(Str::new(s))
+}
+
+// ------ Starts-with ----------------------------------------------------------
+fn startswith_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "test if the input string starts with a substring".into(),
+        params: vec![HelpParameter::Required("substring".into())],
+        examples: vec![HelpExample {
+            desc: "filter ls to hidden files",
+            code: "ls | filter name --Str starts-with '.'",
+        }],
+        ..HelpMessage::new("starts-with")
+    }
+}
+
+fn startswith_str_intrinsic(blk: Block) -> Result<Step> {
+    str_test_intrinsic(blk, |s, p| s.starts_with(p))
+}
+
+// ------ Trim -----------------------------------------------------------------
+fn trim_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "trim whitespace from the start and end of a string".into(),
+        flags: vec![
+            ("start", "only trim the start of the string"),
+            ("end", "only trim the end of the string"),
+        ],
+        examples: vec![
+            HelpExample {
+                desc: "trim whitespace",
+                code: "\\ '  Hello, world!  ' | trim",
+            },
+            HelpExample {
+                desc: "trim the end of the string",
+                code: "\\ '  Hello, world!  ' | trim --end",
+            },
+        ],
+        ..HelpMessage::new("trim")
+    }
+}
+
+fn trim_str_intrinsic(mut blk: Block) -> Result<Step> {
+    let start = blk.get_flag("start").is_some();
+    let end = blk.get_flag("end").is_some();
+    str_map_intrinsic(blk, move |s| {
+        match (start, end) {
+            (true, false) => s.trim_start(),
+            (false, true) => s.trim_end(),
+            _ => s.trim(),
+        }
+        .to_string()
+    })
+}
+
+// ------ Upper ----------------------------------------------------------------
+fn upper_str_help() -> HelpMessage {
+    HelpMessage {
+        desc: "convert a string to uppercase".into(),
+        examples: vec![HelpExample {
+            desc: "uppercase a string",
+            code: "\\ 'Hello, world!' | upper",
+        }],
+        ..HelpMessage::new("upper")
+    }
+}
+
+fn upper_str_intrinsic(blk: Block) -> Result<Step> {
+    str_map_intrinsic(blk, str::to_uppercase)
+}
@@ -2,7 +2,7 @@ mod intrinsics;
 
 use crate::prelude::*;
 use ast::{Location, Tag};
-use eng::{Block, Step};
+use eng::{Block, RecursiveCall, Step};
 use lang::help::*;
 use libs::divvy::Str;
 use std::{fmt, iter::*};
@@ -129,26 +129,7 @@ impl Implementations {
 
     /// **Builds** the help message which concatenates all applicable input types.
     pub fn get_help_all(&self, op: &str) -> Option<Error> {
-        let mut xs = self
-            .0
-            .get(op)?
-            .iter()
-            .map(|(t, (_, _, x))| (t, x))
-            .collect::<Vec<_>>();
-        xs.sort_unstable_by_key(|a| a.0.map(ToString::to_string));
-
-        xs.into_iter()
-            .map(|(t, x)| err::help_as_error(x, t))
-            .reduce(|mut acc, x| {
-                if let Some((a, b)) = acc.traces.get_mut(0).zip(x.traces.get(0)) {
-                    if !a.source.ends_with('\n') {
-                        a.source += "\n";
-                    }
-                    a.source += "\n";
-                    a.source += &b.source;
-                }
-                acc
-            })
+        help_all(self.iter_op(op))
     }
 
     /// **Builds** the help message which concatenates all applicable input types.
@@ -295,6 +276,32 @@ impl Implementations {
     }
 }
 
+/// **Builds** the help message of the implementations of an op, which concatenates all applicable
+/// input types. Returns `None` if there are no implementations.
+pub fn help_all<'a, I>(impls: I) -> Option<Error>
+where
+    I: IntoIterator<Item = ImplEntry<'a>>,
+{
+    let mut xs = impls
+        .into_iter()
+        .map(|x| (x.ty, x.help))
+        .collect::<Vec<_>>();
+    xs.sort_unstable_by_key(|a| a.0.map(ToString::to_string));
+
+    xs.into_iter()
+        .map(|(t, x)| err::help_as_error(x, t))
+        .reduce(|mut acc, x| {
+            if let Some((a, b)) = acc.traces.get_mut(0).zip(x.traces.get(0)) {
+                if !a.source.ends_with('\n') {
+                    a.source += "\n";
+                }
+                a.source += "\n";
+                a.source += &b.source;
+            }
+            acc
+        })
+}
+
 // ------ Expr Impl ------------------------------------------------------------
 pub fn usr_impl_help(def: &ast::DefinitionImpl) -> HelpMessage {
     let desc: Str = format!("user defined implementation in {}\n`{}`", def.loc, def.src).into();
@@ -303,12 +310,29 @@ pub fn usr_impl_help(def: &ast::DefinitionImpl) -> HelpMessage {
         .params
         .iter()
         .map(|p| {
+            if p.flag {
+                return HelpParameter::Optional(format!("--{}", p.ident).into());
+            }
+
             let mut s = p.ident.to_string();
+            if p.rest {
+                s += "..";
+            }
             if let Some(ty) = &p.ty {
                 s.push(':');
                 s += ty.str();
             }
-            HelpParameter::Required(Str::from(s))
+            if p.rest {
+                return HelpParameter::Optional(Str::from(s));
+            }
+            match &p.default {
+                Some(default) => {
+                    s.push('=');
+                    s += default.tag().str();
+                    HelpParameter::Optional(Str::from(s))
+                }
+                None => HelpParameter::Required(Str::from(s)),
+            }
         })
         .collect();
 
@@ -319,6 +343,41 @@ pub fn usr_impl_help(def: &ast::DefinitionImpl) -> HelpMessage {
     }
 }
 
+// ------ Recursive Def --------------------------------------------------------
+/// A recursive definition (`def-rec`) is not expanded inline, instead it is compiled as an
+/// intrinsic which evaluates the definition body at runtime.
+pub fn recursive_def(def: &ast::DefinitionImpl) -> IntrinsicFn {
+    let def = Arc::new(def.clone());
+    Arc::new(move |blk| recursive_def_call(blk, &def))
+}
+
+fn recursive_def_call(mut blk: Block, def: &ast::DefinitionImpl) -> Result<Step> {
+    let tys = blk.defs().types();
+    let out_ty = tys.get_using_tag(
+        def.out_ty
+            .as_ref()
+            .expect("recursive definitions have an output type annotation"),
+    )?;
+    insert_anon_field_ty(&mut blk, &out_ty);
+    blk.assert_output(out_ty.clone());
+
+    let mut params = Vec::with_capacity(def.params.len());
+    for p in &def.params {
+        let ty = p.ty.as_ref().map(|t| tys.get_using_tag(t)).transpose()?;
+        if let Some(ty) = &ty {
+            insert_anon_field_ty(&mut blk, ty);
+        }
+        params.push((Str::new(p.ident.str()), ty));
+    }
+
+    let call = RecursiveCall::compile(&mut blk, def, params, &out_ty)?;
+
+    blk.eval(out_ty, move |input, cx| {
+        let x = call.eval(input, &cx)?;
+        cx.done(x)
+    })
+}
+
 // ------ TypeDef Init ---------------------------------------------------------
 pub fn add_typedef_init_impls(impls: &mut Implementations, tydef: Arc<types::TypeDef>) {
     fn insert_intrinsic(
@@ -371,6 +430,7 @@ fn typedef_init(
 ) -> Result<Step> {
     let mut value_places = Vec::with_capacity(fields.len());
     for field in fields {
+        insert_anon_field_ty(&mut blk, field.ty());
         let arg = blk
             .next_arg()?
             .supplied(None)?
@@ -389,6 +449,14 @@ fn typedef_init(
     })
 }
 
+/// Instantiated generic types and list types are not part of the compiler's initial types set, so
+/// field types need to be inserted for the arguments to be able to return them.
+fn insert_anon_field_ty(blk: &mut Block, ty: &Type) {
+    if matches!(ty, Type::List(_)) || matches!(ty, Type::Def(x) if x.generic().is_some()) {
+        blk.insert_anon_type_into_compiler(ty.clone());
+    }
+}
+
 fn typedef_init_help(ty: &types::TypeDef) -> HelpMessage {
     let desc = format!("initialise a `{}`", ty.name()).into();
     let map_field =
@@ -420,3 +488,150 @@ fn typedef_init_help(ty: &types::TypeDef) -> HelpMessage {
         }
     }
 }
+
+// ------ Generic TypeDef Init -------------------------------------------------
+pub fn add_generic_init_impls(impls: &mut Implementations, gen: Arc<types::GenericTypeDef>) {
+    let mut insert_intrinsic = |op: Str, variant_idx: usize| {
+        let help = generic_init_help(&gen);
+        let loc = gen.loc().clone();
+        let gen = gen.clone();
+        impls.insert_intrinsic(
+            op,
+            None,
+            move |blk| generic_init(blk, &gen, variant_idx),
+            loc,
+            OperationCategory::Init,
+            help,
+        );
+    };
+
+    match gen.structure() {
+        ast::TypeVariant::Sum(variants) => {
+            for (idx, variant) in variants.iter().enumerate() {
+                insert_intrinsic(format!("{}::{}", gen.name(), variant.name).into(), idx);
+            }
+        }
+        ast::TypeVariant::Product(_) => insert_intrinsic(Str::new(gen.name()), 0),
+    }
+}
+
+fn generic_init(
+    mut blk: Block,
+    gen: &Arc<types::GenericTypeDef>,
+    variant_idx: usize,
+) -> Result<Step> {
+    let fields = gen.fields(variant_idx);
+    let mut args = vec![None; gen.params().len()];
+    let mut value_places = Vec::with_capacity(fields.len());
+    for field in fields {
+        // if the field type is already known, we can oblige the argument to return it
+        let ty = gen.field_ty(field, &args, blk.defs().types())?;
+        if let Some(ty) = &ty {
+            insert_anon_field_ty(&mut blk, ty);
+        }
+
+        let arg = blk.next_arg()?.supplied(None)?;
+        let arg = match ty {
+            Some(ty) => arg.returns(ty)?,
+            None => arg,
+        }
+        .concrete()?;
+
+        if !gen.unify(field, arg.out_ty(), &mut args) {
+            return Err(Error::unexp_arg_output_ty(
+                format!("{}:{}", field.name, field.ty),
+                arg.out_ty(),
+                &arg.tag,
+            ));
+        }
+
+        value_places.push(arg);
+    }
+
+    // any type parameters not bound by the arguments need to be inferred from the output type
+    if args.iter().any(Option::is_none) {
+        let inferred = blk.output_ty().and_then(|ty| match ty {
+            Type::Def(x) => x
+                .generic()
+                .filter(|(g, _)| g.name() == gen.name())
+                .map(|(_, targs)| targs.to_vec()),
+            _ => None,
+        });
+
+        match inferred {
+            Some(targs) => args = targs.into_iter().map(Some).collect(),
+            None => {
+                let mut err = Error::unknown_blk_output_type(blk.blk_tag());
+                err.help_msg = Some(format!(
+                    "try annotating the type, such as `{}:{}`",
+                    blk.blk_tag(),
+                    gen.signature()
+                ));
+                return Err(err);
+            }
+        }
+    }
+
+    let args = args.into_iter().map(Option::unwrap).collect();
+    let tydef = types::GenericTypeDef::instantiate(gen, args, blk.defs().types())?;
+    let oty = Type::Def(tydef.clone());
+
+    blk.insert_anon_type_into_compiler(oty.clone());
+
+    blk.assert_output(oty.clone());
+
+    blk.eval(oty, move |input, cx| {
+        let mut values = Vec::with_capacity(value_places.len());
+        for value in &value_places {
+            values.push(value.resolve(|| input.clone(), &cx)?);
+        }
+
+        cx.done(OgmaData::new(tydef.clone(), variant_idx, values))
+    })
+}
+
+fn generic_init_help(gen: &types::GenericTypeDef) -> HelpMessage {
+    let desc = format!("initialise a `{}`", gen.signature()).into();
+    let field = |f: &ast::Field| {
+        let mut s = format!("{}:{}", f.name, f.ty);
+        if !f.params.is_empty() {
+            let params = f.params.iter().map(|p| p.str()).collect::<Vec<_>>();
+            s = format!("{}<{}>", s, params.join(" "));
+        }
+        s
+    };
+
+    match gen.structure() {
+        ast::TypeVariant::Product(fields) => HelpMessage {
+            desc,
+            params: fields
+                .iter()
+                .map(|f| HelpParameter::Required(field(f).into()))
+                .collect(),
+            ..HelpMessage::new(Str::new(gen.name()))
+        },
+        ast::TypeVariant::Sum(variants) => {
+            let mut params = Vec::new();
+            for variant in variants {
+                params.push(HelpParameter::Custom(format!("::{}", variant.name).into()));
+                // fields need their own spacing since the variant name is not spaced
+                params.extend(
+                    variant
+                        .fields
+                        .iter()
+                        .flatten()
+                        .map(|f| HelpParameter::Custom(format!(" {}", field(f)).into())),
+                );
+                params.push(HelpParameter::Break);
+            }
+            params.pop(); // get rid of last break
+
+            HelpMessage {
+                desc,
+                params,
+                no_space: true,
+                ..HelpMessage::new(Str::new(gen.name()))
+            }
+        }
+    }
+}
@@ -1,11 +1,11 @@
 //! Language characteristics.
 
 pub(crate) mod defs;
-#[allow(dead_code)] // TODO remove once changed over
 pub(crate) mod defs2;
 pub(crate) mod help;
 pub(crate) mod impls;
 pub(crate) mod syntax;
+pub(crate) mod traits;
 pub(crate) mod types;
 
 // Public API
@@ -13,4 +13,4 @@ pub(crate) mod types;
 pub use defs::{construct_def_table, process_definition, recognise_definition, Definitions};
 pub use impls::ImplEntry;
 pub use syntax::{ast, parse};
-pub use types::{AsType, OgmaData, Table, Type, Value};
+pub use types::{AsType, List, OgmaData, Stream, Table, Type, Value};
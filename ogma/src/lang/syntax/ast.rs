@@ -135,7 +135,7 @@ impl fmt::Display for Tag {
 }
 
 /// A location indicator.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum Location {
     /// Defined within `ogma`'s core.
     Ogma,
@@ -269,10 +269,7 @@ pub struct PrefixBlock {
 
 impl IBlock for PrefixBlock {
     fn op(&self) -> CTag {
-        match self.op.is_op() {
-            Some(x) => Cow::Borrowed(x),
-            None => panic!("partitions are not yet supported"),
-        }
+        self.op.tag()
     }
 
     fn terms(&self) -> Cow<[Term]> {
@@ -408,6 +405,124 @@ impl IBlock for DotOperatorBlock {
     }
 }
 
+/// List literal, `[a b c]`, which has the list items as terms.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ListBlock {
+    /// The opening bracket tag (`[`), which is used as the op.
+    pub op: Tag,
+    /// The list items.
+    pub items: Vec<Argument>,
+    /// The closing bracket tag (`]`).
+    pub close: Tag,
+    /// An optional annotated output type constraint.
+    pub out_ty: Option<Tag>,
+}
+
+impl ListBlock {
+    fn terms(&self) -> Terms {
+        self.items.iter().cloned().map(Term::Arg).collect()
+    }
+}
+
+impl IBlock for ListBlock {
+    fn op(&self) -> CTag {
+        Cow::Borrowed(&self.op)
+    }
+
+    fn terms(&self) -> Cow<[Term]> {
+        ListBlock::terms(self).into()
+    }
+
+    fn in_ty(&self) -> Option<CTag> {
+        None
+    }
+
+    fn out_ty(&self) -> Option<CTag> {
+        self.out_ty.as_ref().map(Cow::Borrowed)
+    }
+
+    fn block_tag(&self) -> Tag {
+        let mut tag = self.op.clone();
+        tag.make_mut().end = self.close.end;
+
+        if let Some(t) = &self.out_ty {
+            tag.make_mut().end = t.end;
+        }
+
+        tag
+    }
+
+    fn parts(self: Box<Self>) -> BlockParts {
+        let terms = self.terms();
+        let ListBlock {
+            op,
+            items: _,
+            close: _,
+            out_ty,
+        } = *self;
+
+        BlockParts {
+            op: Op::Single(op),
+            terms,
+            in_ty: None,
+            out_ty,
+        }
+    }
+
+    fn clone(&self) -> Block {
+        Box::new(Clone::clone(self))
+    }
+}
+
+/// A block built by the parser rather than written in the source, such as the ops of an
+/// interpolated string.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthBlock {
+    /// The synthesised op, which does not reference the source.
+    pub op: Tag,
+    /// The terms.
+    pub terms: Terms,
+    /// The source the block is built from, used as the block tag.
+    pub src: Tag,
+}
+
+impl IBlock for SynthBlock {
+    fn op(&self) -> CTag {
+        Cow::Borrowed(&self.op)
+    }
+
+    fn terms(&self) -> Cow<[Term]> {
+        self.terms.as_slice().into()
+    }
+
+    fn in_ty(&self) -> Option<CTag> {
+        None
+    }
+
+    fn out_ty(&self) -> Option<CTag> {
+        None
+    }
+
+    fn block_tag(&self) -> Tag {
+        self.src.clone()
+    }
+
+    fn parts(self: Box<Self>) -> BlockParts {
+        let SynthBlock { op, terms, src: _ } = *self;
+
+        BlockParts {
+            op: Op::Single(op),
+            terms,
+            in_ty: None,
+            out_ty: None,
+        }
+    }
+
+    fn clone(&self) -> Block {
+        Box::new(Clone::clone(self))
+    }
+}
+
 // ###### TERM #################################################################
 /// A expression block term: `bar` in `foo bar`.
 #[derive(Debug, PartialEq, Clone)]
@@ -429,6 +544,8 @@ pub enum Argument {
     Pound(char, Tag),
     /// Recognised as a variable reference.
     Var(Tag),
+    /// Recognised as a splatted variable reference, `$x..`.
+    Splat(Tag),
     /// Recognised as an expression.
     Expr(Expression),
 }
@@ -437,11 +554,11 @@ impl Argument {
     /// Get the tag which represents the argument.
     ///
     /// For expressions this spans the whole expression.
-    /// For variables this _does not_ include the `$` character.
+    /// For variables this _does not_ include the `$` character (or the `..` of a splat).
     pub fn tag(&self) -> &Tag {
         use Argument::*;
         match self {
-            Ident(x) | Pound(_, x) | Num(_, x) | Var(x) => x,
+            Ident(x) | Pound(_, x) | Num(_, x) | Var(x) | Splat(x) => x,
             Expr(expr) => &expr.tag,
         }
     }
@@ -449,12 +566,18 @@ impl Argument {
 
 // ###### DEFINITIONS ##########################################################
 /// A implementation definition's parameters.
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct Parameter {
     /// The name ident of the parameter.
     pub ident: Tag,
     /// Optional type specifier, eg `x:Num`. This is used to label `expressions`.
     pub ty: Option<Tag>,
+    /// Optional default argument, eg `x:Num=0`, used if the argument is not supplied.
+    pub default: Option<Argument>,
+    /// The parameter is a flag, eg `--x`, which is read as a boolean.
+    pub flag: bool,
+    /// The parameter is a rest parameter, eg `x..`, which takes the remaining arguments.
+    pub rest: bool,
 }
 
 /// A implementation definition.
@@ -468,14 +591,52 @@ pub struct DefinitionImpl {
     pub name: Tag,
     /// An optional input type.
     pub in_ty: Option<Tag>,
+    /// The annotated output type, required for recursive definitions.
+    pub out_ty: Option<Tag>,
+    /// Type parameters, which can be bound by traits: `name<T: Ord>`.
+    pub ty_params: Vec<TypeParameter>,
     /// The implementation's required parameters.
     pub params: Vec<Parameter>,
     /// The implementation's evaluation expression.
     pub expr: Expression,
+    /// Defined with `def-rec`, the implementation is called at runtime rather than being expanded
+    /// inline.
+    pub recursive: bool,
+}
+
+/// A implementation definition's type parameter, eg `T: Ord`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct TypeParameter {
+    /// The name ident of the type parameter.
+    pub ident: Tag,
+    /// The traits the type must implement.
+    pub traits: Vec<Tag>,
+}
+
+/// A trait definition, a named set of operations a type must implement.
+#[derive(Debug, PartialEq, Clone)]
+pub struct DefinitionTrait {
+    /// The location where this trait is defined.
+    pub loc: Location,
+    /// The source code.
+    pub src: String,
+    /// The name ident of the trait.
+    pub name: Tag,
+    /// The operations which are required.
+    pub ops: Vec<TraitOp>,
+}
+
+/// An operation required by a trait, eg `cmp (rhs)`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TraitOp {
+    /// The name ident of the operation.
+    pub name: Tag,
+    /// The operation's parameters.
+    pub params: Vec<Parameter>,
 }
 
 /// A type definition.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DefinitionType {
     /// The location where this type is defined.
     pub loc: Location,
@@ -483,12 +644,14 @@ pub struct DefinitionType {
     pub src: String,
     /// The name ident of the type.
     pub name: Tag,
+    /// Type parameters: Name<A B C>
+    pub params: Vec<Tag>,
     /// The type's flavour (and structure).
     pub ty: TypeVariant,
 }
 
 /// Types are either `Sum` (enum) or `Product` (struct).
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub enum TypeVariant {
     /// A 'sum' type, a type composed of mutually exclusive variants.
     Sum(Vec<Variant>),
@@ -497,7 +660,7 @@ pub enum TypeVariant {
 }
 
 /// A sum type variant.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Variant {
     /// The name ident.
     pub name: Tag,
@@ -506,7 +669,7 @@ pub struct Variant {
 }
 
 /// A product type field.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct Field {
     /// The name ident.
     pub name: Tag,
@@ -3,6 +3,9 @@ use super::*;
 /// File constituents.
 #[derive(Debug, Clone)]
 pub struct File {
+    /// The location of the file.
+    pub loc: Location,
+
     /// A document string.
     pub doc: Option<String>,
 
@@ -14,6 +17,11 @@ pub struct File {
     /// Each type is keyed with it's name.
     pub types: Vec<(String, Item)>,
 
+    /// All trait definitions.
+    ///
+    /// Each trait is keyed with it's name.
+    pub traits: Vec<(String, Item)>,
+
     /// All implementation definitions.
     ///
     /// Each impl is keyed with it's name.
@@ -78,9 +86,11 @@ impl File {
     /// Creates an empty file.
     pub fn empty() -> Self {
         Self {
+            loc: Location::default(),
             doc: None,
             directives: vec![],
             types: vec![],
+            traits: vec![],
             impls: vec![],
             exprs: vec![],
         }
@@ -156,6 +166,7 @@ pub fn file(text: &str, loc: Location) -> Result<File, err::Error> {
 
     let mut impls = Vec::new();
     let mut types = Vec::new();
+    let mut traits = Vec::new();
     let mut exprs = Vec::new();
 
     let line_num = |i| {
@@ -191,6 +202,17 @@ pub fn file(text: &str, loc: Location) -> Result<File, err::Error> {
                     line: line_num(code),
                 },
             ));
+        } else if code.starts_with("def-trait") {
+            let (_, name) = ws(preceded(tag("def-trait "), op_ident(&line)))(code)
+                .map_err(|e| convert_parse_error(e, &line.line, loc.clone()).0)?;
+            traits.push((
+                name.to_string(),
+                Item {
+                    doc,
+                    code: code.to_owned(),
+                    line: line_num(code),
+                },
+            ));
         } else if code.starts_with("def") {
             let (_, name) = def_op(&line)(code)
                 .map_err(|e| convert_parse_error(e, &line.line, loc.clone()).0)?;
@@ -212,10 +234,12 @@ pub fn file(text: &str, loc: Location) -> Result<File, err::Error> {
     }
 
     Ok(File {
+        loc,
         doc,
         directives,
         impls,
         types,
+        traits,
         exprs,
     })
 }
@@ -571,6 +595,8 @@ def bar-zog () { }
 
 an xpr | zog
 
+def-trait Ord { cmp (rhs) }
+
 ";
 
         let f = file(f, Location::Shell).unwrap();
@@ -590,6 +616,17 @@ an xpr | zog
                 }
             )]
         );
+        assert_eq!(
+            f.traits,
+            vec![(
+                "Ord".to_string(),
+                Item {
+                    doc: None,
+                    code: "def-trait Ord { cmp (rhs) }".to_string(),
+                    line: 16
+                }
+            )]
+        );
         assert_eq!(
             f.impls,
             vec![
@@ -44,6 +44,17 @@ impl Line {
         .into()
     }
 
+    /// Create a tag of synthesised source, which is not a slice of the line.
+    fn synth_tag(&self, src: &str) -> Tag {
+        Tag_ {
+            anchor: self.loc.clone(),
+            line: Arc::from(src),
+            start: 0,
+            end: src.len(),
+        }
+        .into()
+    }
+
     #[cfg(test)]
     fn from(i: &str) -> Self {
         Self {
@@ -60,6 +71,8 @@ pub enum ParseSuccess {
     Impl(DefinitionImpl),
     /// Parsed as a type definition (`def-ty`).
     Ty(DefinitionType),
+    /// Parsed as a trait definition (`def-trait`).
+    Trait(DefinitionTrait),
     /// Parsed as an expression.
     Expr(Expression),
 }
@@ -69,10 +82,12 @@ pub enum ParseSuccess {
 /// Uses `Location::Shell`.
 pub fn parse(input: &str, defs: &Definitions) -> Result<ParseSuccess, ParseFail> {
     let loc = Location::Shell;
-    if input.starts_with("def ") {
+    if input.starts_with("def ") || input.starts_with("def-rec ") {
         definition_impl(input, loc, defs).map(ParseSuccess::Impl)
     } else if input.starts_with("def-ty ") {
         definition_type(input, loc).map(ParseSuccess::Ty)
+    } else if input.starts_with("def-trait ") {
+        definition_trait(input, loc, defs).map(ParseSuccess::Trait)
     } else {
         expression(input, loc, defs).map(ParseSuccess::Expr)
     }
@@ -127,6 +142,22 @@ pub fn definition_type<S: Into<Arc<str>>>(
         .map_err(|e| convert_parse_error(e, &line.line, location))
 }
 
+/// Parse a definition trait (`def-trait`).
+pub fn definition_trait<S: Into<Arc<str>>>(
+    def: S,
+    location: Location,
+    definitions: &Definitions,
+) -> Result<DefinitionTrait, ParseFail> {
+    let line = Line {
+        loc: location.clone(),
+        line: def.into(),
+    };
+
+    self::def_trait_inner(&line.line, &line, location.clone(), definitions)
+        .map(|x| x.1)
+        .map_err(|e| convert_parse_error(e, &line.line, location))
+}
+
 /// Failure to parse results in the parse [`Error`], and an expecting AST node.
 pub type ParseFail = (err::Error, Expecting);
 
@@ -327,7 +358,7 @@ fn opt_ty(line: &Line) -> impl FnMut(&str) -> IResult<&str, Option<Tag>, Parsing
         opt(preceded(
             char(':'),
             exp(
-                cut(context("expecting a type identifier", op_ident(line))),
+                cut(context("expecting a type identifier", ty_ident(line))),
                 Expecting::TYPE,
             ),
         ))(i)
@@ -366,7 +397,7 @@ fn expr<'f>(
 ) -> impl for<'a> Fn(&'a str) -> IResult<&'a str, Expression, ParsingError<'a>> + 'f {
     move |input| {
         let mut blocks: Vec<Block> = Vec::new();
-        let mut blkparse = maybe_cx("no command", ws(map(block(line, defs), Box::new)));
+        let mut blkparse = maybe_cx("no command", ws(any_block(line, defs)));
 
         // first -- try block parsing
         let (mut i, blk) = blkparse(input)?;
@@ -406,6 +437,20 @@ fn expr<'f>(
     }
 }
 
+/// A block is either a list literal (`[a b c]`), or a prefix block.
+fn any_block<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl for<'a> Fn(&'a str) -> IResult<&'a str, Block, ParsingError<'a>> + 'f {
+    move |i| {
+        if i.starts_with('[') {
+            map(list(line, defs), Block::from)(i)
+        } else {
+            map(block(line, defs), Block::from)(i)
+        }
+    }
+}
+
 fn block<'f>(
     line: &'f Line,
     defs: &'f Definitions,
@@ -546,12 +591,24 @@ fn op_ident(line: &Line) -> impl Fn(&str) -> IResult<&str, Tag, ParsingError> +
     }
 }
 
+/// A type identifier, which can be parameterised with other types: `Ty<A B<C>>`.
+fn ty_ident(line: &Line) -> impl Fn(&str) -> IResult<&str, Tag, ParsingError> + '_ {
+    move |i| map(|i| ty_ident_str(line, i), |ty| line.create_tag(ty))(i)
+}
+
+fn ty_ident_str<'a>(line: &Line, i: &'a str) -> IResult<&'a str, &'a str, ParsingError<'a>> {
+    recognize(pair(
+        op_ident(line),
+        opt(preceded(
+            char('<'),
+            cut(terminated(many1(ws(|i| ty_ident_str(line, i))), char('>'))),
+        )),
+    ))(i)
+}
+
 fn known_op<'a>(line: &'a Line, defs: &'a Definitions) -> impl Fn(&str) -> bool + 'a {
     move |i| match op(line)(i) {
-        Ok((_, op)) => match op.is_op() {
-            Some(t) => defs.impls().contains_op(t.str()),
-            None => false, // TODO handle this once `defs` can have path queries
-        },
+        Ok((_, op)) => defs.contains_op(&op.tag()).unwrap_or_default(),
         _ => false,
     }
 }
@@ -636,7 +693,9 @@ fn dot_infixed(
 
 /// Argument is expression, variable, number, or ident.
 /// - starts with `{`: parse as expression
-/// - starts with `$`: parse as variable
+/// - starts with `[`: parse as list literal (but return as Expression)
+/// - starts with `f'` or `f"`: parse as interpolated string (but return as Expression)
+/// - starts with `$`: parse as variable, or a splatted variable if suffixed with `..`
 /// - starts with `#`: parse as boolean or special input
 /// - starts with `:`: return **Failure** -- unexpected type identifier
 /// - parses as number: return Num
@@ -679,6 +738,18 @@ fn arg<'f>(
 
                 Ok((i, Argument::Expr(e)))
             }
+        } else if i.starts_with('[') {
+            let (i, blk) = list(line, defs)(i)?;
+            Ok((
+                i,
+                Argument::Expr(Expression {
+                    tag: blk.block_tag(),
+                    blocks: vec![Box::new(blk)],
+                    out_ty: None,
+                }),
+            ))
+        } else if i.starts_with("f'") || i.starts_with("f\"") {
+            fstring(line, defs)(i)
         } else if let Some(stripped) = i.strip_prefix(':') {
             // adjust some of the error to capture just the type identifier section
             let (_, ident) = take_till(breakon)(stripped)?;
@@ -693,7 +764,11 @@ fn arg<'f>(
         } else if breakon_s(i) {
             Err(nom::Err::Error(make_error(i, ErrorKind::IsA)))
         } else if i.starts_with('$') {
-            map(var(line), Argument::Var)(i)
+            let (i, var) = var(line)(i)?;
+            match i.strip_prefix("..") {
+                Some(i) => Ok((i, Argument::Splat(var))),
+                None => Ok((i, Argument::Var(var))),
+            }
         } else if let Some(s) = i.strip_prefix('#') {
             let (ii, mut c) = cut(exp(op_ident(line), Expecting::SPECLITERAL))(s)?;
             if c.str().len() != 1 {
@@ -726,6 +801,222 @@ fn arg<'f>(
     }
 }
 
+/// List literal, `[a b c]`, where the items are arguments.
+/// The closing bracket can be followed by a type annotation: `[]:List<Num>`.
+fn list<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, ListBlock, ParsingError> + 'f {
+    move |i| {
+        let (i, op) = tag("[")(i)?;
+        let (i, items) = many0(ws(|i| {
+            let (i, arg) = arg(line, defs)(i)?;
+            maybe_infix(line, i, arg)
+        }))(i)?;
+        let (i, close) = cut(exp(
+            preceded(multispace0, recognize(char(']'))),
+            Expecting::TERM,
+        ))(i)?;
+        let (i, out_ty) = opt_ty(line)(i)?;
+        let blk = ListBlock {
+            op: line.create_tag(op),
+            items,
+            close: line.create_tag(close),
+            out_ty,
+        };
+
+        Ok((i, blk))
+    }
+}
+
+/// Interpolated string literal, `f'Total: {$x:[.2]} units'`.
+///
+/// Embedded expressions are wrapped in braces and can be suffixed with a number format spec
+/// (`:[.2]`). Braces are escaped by doubling them (`{{` and `}}`).
+/// The literal is built into the `+` and `to-str` ops, so the example is equivalent to
+/// `\ 'Total: ' | + {\ $x | to-str '[.2]'} ' units'`.
+/// Embedded expressions which are not a plain variable are evaluated with the _input_ of the
+/// literal, which is bound to a hidden variable (`$fstr-input`).
+///
+/// The literal parts and embedded expressions are tagged in the source line, the built ops are
+/// [`SynthBlock`]s tagged with the interpolation (or literal) they are built from.
+fn fstring<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, Argument, ParsingError> + 'f {
+    move |i| {
+        let q = if i.starts_with("f'") { '\'' } else { '"' };
+        let body = &i[2..];
+
+        let block = |op: &str, src: &str, args: Vec<Argument>| -> Block {
+            SynthBlock {
+                op: line.synth_tag(op),
+                terms: args.into_iter().map(Term::Arg).collect(),
+                src: line.create_tag(src),
+            }
+            .into()
+        };
+        let input_var = || Argument::Var(line.synth_tag("fstr-input"));
+
+        let mut args = Vec::new();
+        let mut input = false;
+        // the start of the literal part being read
+        let mut lit: Option<usize> = None;
+        let push_lit = |args: &mut Vec<Argument>, lit: &mut Option<usize>, end: usize| {
+            if let Some(start) = lit.take() {
+                args.push(Argument::Ident(line.create_tag(&body[start..end])));
+            }
+        };
+        let mut chars = body.char_indices().peekable();
+
+        let end = loop {
+            let (idx, c) = match chars.next() {
+                Some(x) => x,
+                None => {
+                    return Err(ParsingError::failure(
+                        i,
+                        "unterminated interpolated string",
+                        Expecting::NONE,
+                    ))
+                }
+            };
+
+            match c {
+                c if c == q => break idx,
+                // an escaped brace ends the literal part with the first brace
+                '{' | '}' if chars.next_if(|x| x.1 == c).is_some() => {
+                    lit.get_or_insert(idx);
+                    push_lit(&mut args, &mut lit, idx + 1);
+                }
+                '}' => {
+                    return Err(ParsingError::failure(
+                        &body[idx..=idx],
+                        "unmatched brace, use `}}` to escape a brace",
+                        Expecting::NONE,
+                    ))
+                }
+                '{' => {
+                    let close = interp_close(&body[idx..]).ok_or_else(|| {
+                        ParsingError::failure(
+                            &body[idx..=idx],
+                            "unclosed interpolation brace",
+                            Expecting::TERM,
+                        )
+                    })? + idx;
+                    while chars.next_if(|x| x.0 <= close).is_some() {}
+
+                    push_lit(&mut args, &mut lit, idx);
+
+                    let braced = &body[idx..=close];
+                    let (e, fmt) = interp(line, defs, braced)?;
+                    let mut blocks = match e {
+                        Argument::Expr(e) => {
+                            input = true;
+                            let mut blocks = vec![block("\\", braced, vec![input_var()])];
+                            blocks.extend(e.blocks);
+                            blocks
+                        }
+                        var => vec![block("\\", braced, vec![var])],
+                    };
+                    blocks.push(block("to-str", braced, fmt.into_iter().collect()));
+
+                    args.push(Argument::Expr(Expression {
+                        tag: line.create_tag(braced),
+                        blocks,
+                        out_ty: None,
+                    }));
+                }
+                _ => {
+                    lit.get_or_insert(idx);
+                }
+            }
+        };
+
+        push_lit(&mut args, &mut lit, end);
+
+        let (fstr, i) = i.split_at(2 + end + 1);
+
+        let mut blocks = Vec::new();
+        if input {
+            blocks.push(block("let", fstr, vec![input_var()]));
+        }
+        let mut args = args.into_iter();
+        let first = args
+            .next()
+            .unwrap_or_else(|| Argument::Ident(line.create_tag(&body[..0])));
+        blocks.push(block("\\", fstr, vec![first]));
+        let rest = args.collect::<Vec<_>>();
+        if !rest.is_empty() {
+            blocks.push(block("+", fstr, rest));
+        }
+
+        let e = Expression {
+            tag: line.create_tag(fstr),
+            blocks,
+            out_ty: None,
+        };
+
+        Ok((i, Argument::Expr(e)))
+    }
+}
+
+/// Parse an interpolation, `{expr:[fmt]}`, returning the embedded variable (as
+/// [`Argument::Var`]) or expression (as [`Argument::Expr`]), and the optional format spec (as
+/// [`Argument::Ident`]).
+/// The expression is parsed in place such that its tags, and errors, reference the original source.
+fn interp<'a>(
+    line: &Line,
+    defs: &Definitions,
+    braced: &'a str,
+) -> Result<(Argument, Option<Argument>), nom::Err<ParsingError<'a>>> {
+    let inner = braced[1..braced.len() - 1].trim();
+    if inner.is_empty() {
+        return Err(ParsingError::failure(
+            braced,
+            "empty interpolation",
+            Expecting::TERM,
+        ));
+    }
+
+    let (e, fmt) = match inner.strip_suffix(']').and_then(|_| inner.rfind(":[")) {
+        Some(x) => (inner[..x].trim_end(), Some(&inner[x + 1..])),
+        None => (inner, None),
+    };
+    let fmt = fmt.map(|x| Argument::Ident(line.create_tag(x)));
+
+    if let Ok((_, v)) = all_consuming(var(line))(e) {
+        return Ok((Argument::Var(v), fmt));
+    }
+
+    let (_, e) = cut(no_trailing_input(expr(line, defs)))(e)?;
+
+    Ok((Argument::Expr(e), fmt))
+}
+
+/// Returns the index of the brace which closes the leading brace in `s`.
+/// Quoted sections are skipped.
+fn interp_close(s: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut quote = None;
+    for (idx, c) in s.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => (),
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '{') => depth += 1,
+            (None, '}') => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(idx);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    None
+}
+
 fn var(line: &Line) -> impl Fn(&str) -> IResult<&str, Tag, ParsingError> + '_ {
     move |i| preceded(char('$'), op_ident(line))(i) // can use the same op_ident parser
 }
@@ -778,7 +1069,7 @@ fn num(line: &Line) -> impl Fn(&str) -> IResult<&str, (Number, Tag), ()> + '_ {
 }
 
 fn breakon(ch: char) -> bool {
-    ch == '|' || ch == '{' || ch == '}' || ch == ':' || ch.is_whitespace()
+    ch == '|' || ch == '{' || ch == '}' || ch == ']' || ch == ':' || ch.is_whitespace()
 }
 
 fn breakon_s(s: &str) -> bool {
@@ -792,14 +1083,34 @@ fn def_impl_inner<'a>(
     loc: Location,
     definitions: &Definitions,
 ) -> IResult<&'a str, DefinitionImpl, ParsingError<'a>> {
+    let recursive = i.trim_start().starts_with("def-rec ");
     let (i, name) = def_op(line)(i)?;
-    let (i, in_ty) = ws(opt(op_ident(line)))(i)?;
+    let (i, ty_params) = def_ty_params(line)(i)?;
+    if recursive && !ty_params.is_empty() {
+        return Err(ParsingError::failure(
+            ty_params[0].ident.clone(),
+            "recursive definitions do not support type parameters",
+            Expecting::NONE,
+        ));
+    }
+    // recursive definitions must annotate the output type, `def-rec name:Ty`
+    let (i, out_ty) = if recursive {
+        let msg = "recursive definitions require an output type annotation, such as `name:Num`";
+        let (ii, out_ty) = opt_ty(line)(i)?;
+        match out_ty {
+            Some(t) => (ii, Some(t)),
+            None => return Err(ParsingError::failure(i, msg, Expecting::TYPE)),
+        }
+    } else {
+        (i, None)
+    };
+    let (i, in_ty) = ws(opt(ty_ident(line)))(i)?;
     let x = if in_ty.is_some() {
         Expecting::NONE
     } else {
         Expecting::TYPE
     };
-    let (i, params) = exp(def_params(line), x)(i)?;
+    let (i, params) = exp(def_params(line, definitions), x)(i)?;
     let (i, expr) = no_trailing_input(ws(delimited(
         char('{'),
         ws(expr(line, definitions)),
@@ -811,8 +1122,11 @@ fn def_impl_inner<'a>(
         src: line.line.clone(),
         name,
         in_ty,
+        out_ty,
+        ty_params,
         params,
         expr,
+        recursive,
     };
 
     Ok((i, def))
@@ -820,7 +1134,7 @@ fn def_impl_inner<'a>(
 
 fn def_op(line: &Line) -> impl FnMut(&str) -> IResult<&str, Tag, ParsingError> + '_ {
     move |i| {
-        let (i, name) = ws(preceded(tag("def "), op(line)))(i)?;
+        let (i, name) = ws(preceded(alt((tag("def "), tag("def-rec "))), op(line)))(i)?;
         let name = match name.is_op() {
             Some(t) => Ok(t.clone()),
             None => Err(ParsingError::failure(
@@ -834,13 +1148,83 @@ fn def_op(line: &Line) -> impl FnMut(&str) -> IResult<&str, Tag, ParsingError> +
     }
 }
 
-fn def_params(line: &Line) -> impl Fn(&str) -> IResult<&str, Vec<Parameter>, ParsingError> + '_ {
+/// Optional type parameters, bounded by traits: `<T: Ord U: Eq + Ord>`.
+fn def_ty_params(
+    line: &Line,
+) -> impl FnMut(&str) -> IResult<&str, Vec<TypeParameter>, ParsingError> + '_ {
     move |i| {
-        let (i, params) = delimited(char('('), many0(ws(def_param(line))), char(')'))(i)?;
+        let (i, params) = opt(ws(preceded(
+            char('<'),
+            cut(terminated(many1(ws(def_ty_param(line))), char('>'))),
+        )))(i)?;
+
+        // check that the type parameters are distinct
+        let params = params.unwrap_or_default();
+        let mut set = HashSet::default();
+        for param in &params {
+            if !set.insert(param.ident.str()) {
+                return Err(ParsingError::failure(
+                    param.ident.clone(),
+                    format!(
+                        "type parameters must be distinct: `{}` has been previously defined",
+                        param.ident
+                    ),
+                    Expecting::NONE,
+                ));
+            }
+        }
+
+        Ok((i, params))
+    }
+}
+
+fn def_ty_param(line: &Line) -> impl Fn(&str) -> IResult<&str, TypeParameter, ParsingError> + '_ {
+    move |i| {
+        let (i, ident) = op_ident(line)(i)?;
+        let (i, traits) = opt(preceded(
+            ws(char(':')),
+            cut(context(
+                "expecting a trait identifier: `T: Trait`",
+                separated_list1(ws(char('+')), op_ident(line)),
+            )),
+        ))(i)?;
+        let traits = traits.unwrap_or_default();
+        Ok((i, TypeParameter { ident, traits }))
+    }
+}
+
+fn def_params<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, Vec<Parameter>, ParsingError> + 'f {
+    move |i| {
+        let (i, params) = delimited(char('('), many0(ws(def_param(line, defs))), char(')'))(i)?;
         // check that there is only one remainder and it is at the end.
         // also check that parameters are distinct
         let mut set = HashSet::default();
+        let mut defaulted = false;
+        let mut rest = false;
         for param in &params {
+            // defaults must trail the required parameters, flags can be anywhere
+            if !param.flag {
+                if rest {
+                    return Err(ParsingError::err(
+                        param.ident.clone(),
+                        "a rest parameter must be the last parameter",
+                        Expecting::NONE,
+                    ));
+                }
+                if defaulted && param.default.is_none() && !param.rest {
+                    return Err(ParsingError::err(
+                        param.ident.clone(),
+                        "parameters with default values must come after the required parameters",
+                        Expecting::NONE,
+                    ));
+                }
+                defaulted |= param.default.is_some();
+                rest |= param.rest;
+            }
+
             if set.contains(param.ident.str()) {
                 return Err(ParsingError::err(
                     param.ident.clone(),
@@ -858,15 +1242,128 @@ fn def_params(line: &Line) -> impl Fn(&str) -> IResult<&str, Vec<Parameter>, Par
     }
 }
 
-fn def_param(line: &Line) -> impl Fn(&str) -> IResult<&str, Parameter, ParsingError> + '_ {
+fn def_param<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, Parameter, ParsingError> + 'f {
     move |i| {
+        if let Some(ii) = i.strip_prefix("--") {
+            let (i, ident) = op_ident(line)(ii)?;
+            let param = Parameter {
+                ident,
+                ty: None,
+                default: None,
+                flag: true,
+                rest: false,
+            };
+            return Ok((i, param));
+        }
+
         let (i, ident) = op_ident(line)(i)?;
+        let (i, rest) = match i.strip_prefix("..") {
+            Some(i) => (i, true),
+            None => (i, false),
+        };
         let (i, ty) = if i.starts_with(':') {
-            map(preceded(char(':'), op_ident(line)), Some)(i)?
+            map(preceded(char(':'), ty_ident(line)), Some)(i)?
         } else {
             (i, None)
         };
-        Ok((i, Parameter { ident, ty }))
+        if let Some(ty) = ty.as_ref().filter(|t| rest && t.str() == "Expr") {
+            return Err(ParsingError::failure(
+                ty.clone(),
+                "rest parameters cannot be expressions",
+                Expecting::NONE,
+            ));
+        }
+        let (i, default) = if rest && i.starts_with('=') {
+            return Err(ParsingError::failure(
+                &i[..1],
+                "a rest parameter cannot have a default value",
+                Expecting::NONE,
+            ));
+        } else if i.starts_with('=') {
+            map(
+                preceded(char('='), cut(def_param_default(line, defs))),
+                Some,
+            )(i)?
+        } else {
+            (i, None)
+        };
+        let param = Parameter {
+            ident,
+            ty,
+            default,
+            flag: false,
+            rest,
+        };
+        Ok((i, param))
+    }
+}
+
+/// A parameter's default is a single argument.
+/// Unwrapped arguments stop at the closing parenthesis of the parameters.
+fn def_param_default<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, Argument, ParsingError> + 'f {
+    move |i| {
+        if i.starts_with(|c| matches!(c, '{' | '\'' | '"')) {
+            arg(line, defs)(i)
+        } else {
+            let (i, x) = take_till1(|c: char| c == ')' || c.is_whitespace())(i)?;
+            let (_, arg) = all_consuming(arg(line, defs))(x)?;
+            Ok((i, arg))
+        }
+    }
+}
+
+// ------ Traits ---------------------------------------------------------------
+fn def_trait_inner<'a>(
+    i: &'a str,
+    line: &Line,
+    loc: Location,
+    definitions: &Definitions,
+) -> IResult<&'a str, DefinitionTrait, ParsingError<'a>> {
+    let (i, name) = ws(preceded(tag("def-trait "), op_ident(line)))(i)?;
+    let (i, ops) = cut(delimited(
+        char('{'),
+        many0(ws(deftrait_op(line, definitions))),
+        char('}'),
+    ))(i)?;
+
+    // no more characters after this!
+    all_consuming(multispace0)(i)?;
+
+    let def = DefinitionTrait {
+        loc,
+        src: line.line.to_string(),
+        name,
+        ops,
+    };
+
+    Ok((i, def))
+}
+
+fn deftrait_op<'f>(
+    line: &'f Line,
+    defs: &'f Definitions,
+) -> impl Fn(&str) -> IResult<&str, TraitOp, ParsingError> + 'f {
+    move |i| {
+        let (i, name) = op(line)(i)?;
+        let name = match name.is_op() {
+            Some(t) => Ok(t.clone()),
+            None => Err(ParsingError::failure(
+                name.tag().into_owned(),
+                "paths cannot be used to define a trait operation",
+                Expecting::NONE,
+            )),
+        }?;
+        let (i, params) = ws(cut(context(
+            "a trait operation requires parameters: `op (param1 param2)`",
+            def_params(line, defs),
+        )))(i)?;
+        Ok((i, TraitOp { name, params }))
     }
 }
 
@@ -876,9 +1373,7 @@ fn def_type_inner<'a>(
     line: &Line,
     loc: Location,
 ) -> IResult<&'a str, DefinitionType, ParsingError<'a>> {
-    // this does not handle paramterisation _after_ type name definition. This might be lifted
-    // in the future.
-    let (i, name) = defty_name(line)(i)?;
+    let (i, (name, params)) = defty_name(line)(i)?;
     let (i, ty) = match ws::<_, _, ()>(tag("::"))(i) {
         Ok((i, _)) => map(defty_variants(line), TypeVariant::Sum)(i),
         Err(_) => map(defty_fields(line), TypeVariant::Product)(i),
@@ -891,14 +1386,24 @@ fn def_type_inner<'a>(
         loc,
         src: line.line.to_string(),
         name,
+        params,
         ty,
     };
 
     Ok((i, def))
 }
 
-fn defty_name(line: &Line) -> impl FnMut(&str) -> IResult<&str, Tag, ParsingError> + '_ {
-    move |i| ws(preceded(tag("def-ty "), op_ident(line)))(i)
+fn defty_name(
+    line: &Line,
+) -> impl FnMut(&str) -> IResult<&str, (Tag, Vec<Tag>), ParsingError> + '_ {
+    move |i| {
+        let (i, name) = ws(preceded(tag("def-ty "), op_ident(line)))(i)?;
+        let (i, params) = opt(ws(preceded(
+            char('<'),
+            cut(terminated(many1(ws(op_ident(line))), char('>'))),
+        )))(i)?;
+        Ok((i, (name, params.unwrap_or_default())))
+    }
 }
 
 fn defty_variants(line: &Line) -> impl Fn(&str) -> IResult<&str, Vec<Variant>, ParsingError> + '_ {
@@ -932,7 +1437,7 @@ fn defty_field(line: &Line) -> impl Fn(&str) -> IResult<&str, Field, ParsingErro
         if i.starts_with('<') {
             let (ii, p) = cut(delimited(
                 char('<'),
-                many1(ws(op_ident(line))),
+                many1(ws(ty_ident(line))),
                 ws(char('>')),
             ))(i)?;
             params = p;
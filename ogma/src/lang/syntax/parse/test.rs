@@ -333,6 +333,12 @@ fn variable_term() {
         term(&l, &Definitions::new())(&l.line),
         Ok(("", Arg(Var(tt("in")))))
     );
+
+    let l = line("$in.. rem");
+    assert_eq!(
+        term(&l, &Definitions::new())(&l.line),
+        Ok((" rem", Arg(Splat(tt("in")))))
+    );
 }
 
 #[test]
@@ -601,8 +607,9 @@ fn known_op_test() {
 
 #[test]
 fn def_param_test() {
-    let l = line("--not");
-    let x = def_param(&l)(&l.line);
+    let d = &Definitions::new();
+    let l = line("-not");
+    let x = def_param(&l, d)(&l.line);
     assert_eq!(
         x,
         Err(ParsingError::err(
@@ -613,68 +620,210 @@ fn def_param_test() {
     );
 
     let x = line("var rem");
-    let x = def_param(&x)(&x.line);
+    let x = def_param(&x, d)(&x.line);
     assert_eq!(
         x,
         Ok((
             " rem",
             Parameter {
                 ident: tt("var"),
-                ty: None
+                ty: None,
+                default: None,
+                flag: false,
+                rest: false,
             }
         ))
     );
 
     let x = line("var:Num rem");
-    let x = def_param(&x)(&x.line);
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Ok((
+            " rem",
+            Parameter {
+                ident: tt("var"),
+                ty: Some(tt("Num")),
+                default: None,
+                flag: false,
+                rest: false,
+            }
+        ))
+    );
+
+    let x = line("var:Result<Num Option<Str>> rem");
+    let x = def_param(&x, d)(&x.line);
     assert_eq!(
         x,
         Ok((
             " rem",
             Parameter {
                 ident: tt("var"),
-                ty: Some(tt("Num"))
+                ty: Some(tt("Result<Num Option<Str>>")),
+                default: None,
+                flag: false,
+                rest: false,
             }
         ))
     );
+
+    let x = line("--not rem");
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Ok((
+            " rem",
+            Parameter {
+                ident: tt("not"),
+                ty: None,
+                default: None,
+                flag: true,
+                rest: false,
+            }
+        ))
+    );
+
+    let x = line("var:Num=0) rem");
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Ok((
+            ") rem",
+            Parameter {
+                ident: tt("var"),
+                ty: Some(tt("Num")),
+                default: Some(Argument::Num(0.into(), tt("0"))),
+                flag: false,
+                rest: false,
+            }
+        ))
+    );
+
+    let x = line("var='a b' rem");
+    let x = def_param(&x, d)(&x.line).unwrap();
+    assert_eq!(x.0, " rem");
+    assert_eq!(x.1.default, Some(Argument::Ident(tt("a b"))));
+
+    let x = line("var..:Str rem");
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Ok((
+            " rem",
+            Parameter {
+                ident: tt("var"),
+                ty: Some(tt("Str")),
+                default: None,
+                flag: false,
+                rest: true,
+            }
+        ))
+    );
+
+    let x = line("var..:Expr rem");
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Err(ParsingError::failure(
+            tt("Expr"),
+            "rest parameters cannot be expressions",
+            Expecting::NONE
+        ))
+    );
+
+    let x = line("var..=1 rem");
+    let x = def_param(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Err(ParsingError::failure(
+            "=",
+            "a rest parameter cannot have a default value",
+            Expecting::NONE
+        ))
+    );
 }
 
 #[test]
 fn def_params_test() {
+    let d = &Definitions::new();
     let x = line("asdf");
-    let x = def_params(&x)(&x.line);
+    let x = def_params(&x, d)(&x.line);
     assert_eq!(
         x,
         Err(ParsingError::err("asdf", "expected `(`", Expecting::NONE,))
     );
 
     let x = line("(asdf ");
-    let x = def_params(&x)(&x.line);
+    let x = def_params(&x, d)(&x.line);
     assert_eq!(
         x,
         Err(ParsingError::err("", "expected `)`", Expecting::NONE,))
     );
 
     let x = line("( asdf )");
-    let x = def_params(&x)(&x.line).unwrap().1;
+    let x = def_params(&x, d)(&x.line).unwrap().1;
     assert_eq!(x.len(), 1);
     assert!(tag_eq(&x[0].ident, "asdf"));
 
     let x = line("( asdf test )");
-    let x = def_params(&x)(&x.line).unwrap().1;
+    let x = def_params(&x, d)(&x.line).unwrap().1;
     assert_eq!(x.len(), 2);
     assert!(tag_eq(&x[0].ident, "asdf"));
     assert!(tag_eq(&x[1].ident, "test"));
 
     let x = line("( asdf test )");
-    let x = def_params(&x)(&x.line).unwrap().1;
+    let x = def_params(&x, d)(&x.line).unwrap().1;
     assert_eq!(x.len(), 2);
     assert!(tag_eq(&x[0].ident, "asdf"));
     assert!(tag_eq(&x[1].ident, "test"));
 
+    let x = line("(asdf --flag test=1 --x test2:Num={ + 2 })");
+    let x = def_params(&x, d)(&x.line).unwrap().1;
+    assert_eq!(x.len(), 5);
+    assert!(x[1].flag && x[3].flag);
+    assert!(x[2].default.is_some() && x[4].default.is_some());
+
+    let x = line("(asdf test=1 rest.. --flag)");
+    let x = def_params(&x, d)(&x.line).unwrap().1;
+    assert_eq!(x.len(), 4);
+    assert!(x[2].rest && x[3].flag);
+
     // errors
+    let x = line("(rest.. asdf)");
+    let x = def_params(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Err(ParsingError::err(
+            tt("asdf"),
+            "a rest parameter must be the last parameter",
+            Expecting::NONE,
+        ))
+    );
+
+    let x = line("(test=1 asdf)");
+    let x = def_params(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Err(ParsingError::err(
+            tt("asdf"),
+            "parameters with default values must come after the required parameters",
+            Expecting::NONE,
+        ))
+    );
+
+    let x = line("(test --test)");
+    let x = def_params(&x, d)(&x.line);
+    assert_eq!(
+        x,
+        Err(ParsingError::err(
+            tt("test"),
+            "parameters must be distinct: `test` has been previously defined",
+            Expecting::NONE,
+        ))
+    );
+
     let x = line("(test asdf test)");
-    let x = def_params(&x)(&x.line);
+    let x = def_params(&x, d)(&x.line);
     assert_eq!(
         x,
         Err(ParsingError::err(
@@ -724,6 +873,7 @@ fn def_ty_testing() {
             loc: Location::Shell,
             src: "def-ty Ord::Lt|Eq|Gt".to_string(),
             name: tt("Ord"),
+            params: vec![],
             ty: TypeVariant::Sum(vec![
                 Variant {
                     name: tt("Lt"),
@@ -748,6 +898,7 @@ fn def_ty_testing() {
             loc: Location::Shell,
             src: "def-ty Ord :: Lt | Eq | Gt".to_string(),
             name: tt("Ord"),
+            params: vec![],
             ty: TypeVariant::Sum(vec![
                 Variant {
                     name: tt("Lt"),
@@ -772,6 +923,7 @@ fn def_ty_testing() {
             loc: Location::Shell,
             src: "def-ty Point { x:Num y:Num }".to_string(),
             name: tt("Point"),
+            params: vec![],
             ty: TypeVariant::Product(vec![
                 Field {
                     name: tt("x"),
@@ -794,6 +946,7 @@ fn def_ty_testing() {
             loc: Location::Shell,
             src: "def-ty Eg :: N { v:Num } | S { s:Str }".to_string(),
             name: tt("Eg"),
+            params: vec![],
             ty: TypeVariant::Sum(vec![
                 Variant {
                     name: tt("N"),
@@ -825,6 +978,7 @@ fn def_ty_testing() {
             loc: Location::Shell,
             src: "def-ty Eg :: N { v:Num<A B C> } | S { s:Str }".to_string(),
             name: tt("Eg"),
+            params: vec![],
             ty: TypeVariant::Sum(vec![
                 Variant {
                     name: tt("N"),
@@ -847,6 +1001,71 @@ fn def_ty_testing() {
     );
 }
 
+#[test]
+fn generic_def_ty_testing() {
+    let x = definition_type("def-ty Maybe<T> :: Some { v:T } | None", Location::Shell);
+    assert_eq!(
+        x,
+        Ok(DefinitionType {
+            loc: Location::Shell,
+            src: "def-ty Maybe<T> :: Some { v:T } | None".to_string(),
+            name: tt("Maybe"),
+            params: vec![tt("T")],
+            ty: TypeVariant::Sum(vec![
+                Variant {
+                    name: tt("Some"),
+                    fields: Some(vec![Field {
+                        name: tt("v"),
+                        ty: tt("T"),
+                        params: vec![]
+                    },])
+                },
+                Variant {
+                    name: tt("None"),
+                    fields: None
+                }
+            ])
+        })
+    );
+
+    let x = definition_type("def-ty Pair<A B> { a:A b:Maybe<B> }", Location::Shell);
+    assert_eq!(
+        x,
+        Ok(DefinitionType {
+            loc: Location::Shell,
+            src: "def-ty Pair<A B> { a:A b:Maybe<B> }".to_string(),
+            name: tt("Pair"),
+            params: vec![tt("A"), tt("B")],
+            ty: TypeVariant::Product(vec![
+                Field {
+                    name: tt("a"),
+                    ty: tt("A"),
+                    params: vec![]
+                },
+                Field {
+                    name: tt("b"),
+                    ty: tt("Maybe"),
+                    params: vec![tt("B")]
+                },
+            ])
+        })
+    );
+
+    let x = definition_type("def-ty Maybe<> :: Some { v:T } | None", Location::Shell);
+    assert!(x.is_err());
+}
+
+#[test]
+fn def_impl_on_generic_ty() {
+    let y = "def unwrap Option<Num> () { in }";
+    let x = definition_impl(y, Location::Shell, &Definitions::new());
+    assert_eq!(x.map(|x| x.in_ty), Ok(Some(tt("Option<Num>"))));
+
+    let y = "def unwrap Result<Num Str> () { in }";
+    let x = definition_impl(y, Location::Shell, &Definitions::new());
+    assert_eq!(x.map(|x| x.in_ty), Ok(Some(tt("Result<Num Str>"))));
+}
+
 #[test]
 fn no_type_on_field() {
     let x = definition_type("def-ty Point { x y }", Location::Shell)
@@ -908,6 +1127,8 @@ fn def_impl_on_ty() {
             src: Arc::from(y),
             name: tt("add"),
             in_ty: Some(tt("Point")),
+            out_ty: None,
+            ty_params: vec![],
             params: vec![],
             expr: Expression {
                 blocks: vec![PrefixBlock {
@@ -919,7 +1140,8 @@ fn def_impl_on_ty() {
                 .into()],
                 tag: tt("in "),
                 out_ty: None
-            }
+            },
+            recursive: false,
         })
     );
 
@@ -932,6 +1154,38 @@ fn def_impl_on_ty() {
             src: Arc::from(y),
             name: tt("+"),
             in_ty: Some(tt("Point")),
+            out_ty: None,
+            ty_params: vec![],
+            params: vec![],
+            expr: Expression {
+                blocks: vec![PrefixBlock {
+                    op: ops("in"),
+                    terms: vec![],
+                    in_ty: None,
+                    out_ty: None
+                }
+                .into()],
+                tag: tt("in "),
+                out_ty: None
+            },
+            recursive: false,
+        })
+    );
+}
+
+#[test]
+fn def_rec_impl() {
+    let y = "def-rec fact:Num Num () { in }";
+    let x = definition_impl(y, Location::Shell, &Definitions::new());
+    assert_eq!(
+        x,
+        Ok(DefinitionImpl {
+            loc: Location::Shell,
+            src: Arc::from(y),
+            name: tt("fact"),
+            in_ty: Some(tt("Num")),
+            out_ty: Some(tt("Num")),
+            ty_params: vec![],
             params: vec![],
             expr: Expression {
                 blocks: vec![PrefixBlock {
@@ -943,9 +1197,140 @@ fn def_impl_on_ty() {
                 .into()],
                 tag: tt("in "),
                 out_ty: None
+            },
+            recursive: true,
+        })
+    );
+
+    let x = definition_impl(
+        "def-rec tree:List<Str> (x) { in }",
+        Location::Shell,
+        &Definitions::new(),
+    )
+    .unwrap();
+    assert_eq!(x.out_ty, Some(tt("List<Str>")));
+    assert_eq!(x.in_ty, None);
+    assert!(x.recursive);
+
+    let x = definition_impl(
+        "def-rec fact Num () { in }",
+        Location::Shell,
+        &Definitions::new(),
+    )
+    .unwrap_err()
+    .0
+    .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions require an output type annotation"));
+}
+
+#[test]
+fn def_impl_ty_params() {
+    let defs = &Definitions::new();
+    let x = definition_impl("def top<T: Ord> T (n:Num) { in }", Location::Shell, defs).unwrap();
+    assert_eq!(x.name, tt("top"));
+    assert_eq!(x.in_ty, Some(tt("T")));
+    assert_eq!(
+        x.ty_params,
+        vec![TypeParameter {
+            ident: tt("T"),
+            traits: vec![tt("Ord")]
+        }]
+    );
+
+    let x = definition_impl(
+        "def max<T:Ord+Eq U> (a:T b:U) { in }",
+        Location::Shell,
+        defs,
+    )
+    .unwrap();
+    assert_eq!(
+        x.ty_params,
+        vec![
+            TypeParameter {
+                ident: tt("T"),
+                traits: vec![tt("Ord"), tt("Eq")]
+            },
+            TypeParameter {
+                ident: tt("U"),
+                traits: vec![]
             }
+        ]
+    );
+
+    // spec ops are not confused with type parameters
+    let x = definition_impl("def < (rhs) { in }", Location::Shell, defs).unwrap();
+    assert_eq!(x.name, tt("<"));
+    assert!(x.ty_params.is_empty());
+
+    let x = definition_impl("def foo<T T> () { in }", Location::Shell, defs)
+        .unwrap_err()
+        .0
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("type parameters must be distinct: `T` has been previously defined"));
+
+    let x = definition_impl("def foo<T:> () { in }", Location::Shell, defs)
+        .unwrap_err()
+        .0
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("expecting a trait identifier"));
+
+    let x = definition_impl("def-rec foo<T>:Num () { in }", Location::Shell, defs)
+        .unwrap_err()
+        .0
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions do not support type parameters"));
+}
+
+#[test]
+fn def_trait_test() {
+    let defs = &Definitions::new();
+    let y = "def-trait Ord { cmp (rhs) }";
+    let x = definition_trait(y, Location::Shell, defs);
+    assert_eq!(
+        x,
+        Ok(DefinitionTrait {
+            loc: Location::Shell,
+            src: y.to_string(),
+            name: tt("Ord"),
+            ops: vec![TraitOp {
+                name: tt("cmp"),
+                params: vec![Parameter {
+                    ident: tt("rhs"),
+                    ty: None,
+                    default: None,
+                    flag: false,
+                    rest: false,
+                }]
+            }]
         })
     );
+
+    let x = definition_trait(
+        "def-trait Num-like { + (rhs:Num) neg () }",
+        Location::Shell,
+        defs,
+    )
+    .unwrap();
+    assert_eq!(x.name, tt("Num-like"));
+    assert_eq!(x.ops.len(), 2);
+    assert_eq!(x.ops[0].name, tt("+"));
+    assert_eq!(x.ops[0].params[0].ty, Some(tt("Num")));
+    assert_eq!(x.ops[1].name, tt("neg"));
+    assert!(x.ops[1].params.is_empty());
+
+    let x = definition_trait("def-trait Empty { }", Location::Shell, defs).unwrap();
+    assert!(x.ops.is_empty());
+
+    let x = definition_trait("def-trait Ord { cmp }", Location::Shell, defs)
+        .unwrap_err()
+        .0
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("a trait operation requires parameters"));
 }
 
 #[test]
@@ -1170,6 +1555,7 @@ fn no_padding_defty() {
             DefinitionType {
                 loc: Location::Shell,
                 name: tt("Ord"),
+                params: vec![],
                 src: "def-ty Ord::Lt|Eq{x:Num y:Num}|Gt".to_string(),
                 ty: TypeVariant::Sum(vec![
                     Variant {
@@ -1868,3 +2254,171 @@ fn path_parsing_errs() {
         })
     );
 }
+
+#[test]
+fn list_literal() {
+    let defs = &Definitions::new();
+
+    let l = line("foo [1 bar [ ]:List<Num>]");
+    assert_eq!(
+        block(&l, defs)(&l.line),
+        Ok((
+            "",
+            PrefixBlock {
+                op: ops("foo"),
+                terms: vec![Arg(Expr(Expression {
+                    tag: tt("[1 bar [ ]:List<Num>]"),
+                    blocks: vec![ListBlock {
+                        op: tt("["),
+                        items: vec![
+                            Num(1.into(), tt("1")),
+                            Ident(tt("bar")),
+                            Expr(Expression {
+                                tag: tt("[ ]:List<Num>"),
+                                blocks: vec![ListBlock {
+                                    op: tt("["),
+                                    items: vec![],
+                                    close: tt("]"),
+                                    out_ty: Some(tt("List<Num>")),
+                                }
+                                .into()],
+                                out_ty: None
+                            })
+                        ],
+                        close: tt("]"),
+                        out_ty: None,
+                    }
+                    .into()],
+                    out_ty: None
+                }))],
+                in_ty: None,
+                out_ty: None,
+            }
+        ))
+    );
+
+    let l = line("foo [1 2");
+    let x = block(&l, defs)(&l.line);
+    let (_, exp) = convert_parse_error(x.unwrap_err(), &l.line, Location::Ogma);
+    assert_eq!(exp, Expecting::TERM);
+}
+
+#[test]
+fn fstring_literal() {
+    let defs = &Definitions::new();
+    let parse = |s: &str| {
+        let l = line(s);
+        match arg(&l, defs)(&l.line) {
+            Ok(("", Expr(e))) => e,
+            x => panic!("expecting an expression: {:?}", x),
+        }
+    };
+    // nested expressions are rendered in braces, and identifiers are quoted
+    fn render(e: &Expression) -> String {
+        let term = |t: &Term| match t {
+            Arg(Expr(e)) => format!("{{{}}}", render(e)),
+            Arg(Var(v)) => format!("${}", v.str()),
+            Arg(a) => format!("{:?}", a.tag().str()),
+            Flag(f) => format!("--{}", f.str()),
+        };
+        e.blocks
+            .iter()
+            .map(|b| {
+                std::iter::once(b.op().str().to_string())
+                    .chain(b.terms().iter().map(term))
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    let x = parse("f'Total: {$x:[.2]} units'");
+    assert_eq!(x.tag, tt("f'Total: {$x:[.2]} units'"));
+    assert_eq!(
+        render(&x),
+        r#"\ "Total: " | + {\ $x | to-str "[.2]"} " units""#
+    );
+
+    // the literal parts and embedded expressions are tagged in the source
+    assert_eq!(x.blocks[0].terms()[0], Arg(Ident(tt("Total: "))));
+    match &x.blocks[1].terms()[0] {
+        Arg(Expr(e)) => {
+            assert_eq!(e.tag, tt("{$x:[.2]}"));
+            assert_eq!(e.tag.range(), 9..18);
+            match &e.blocks[0].terms()[0] {
+                Arg(Var(v)) => {
+                    assert_eq!(v.line(), "f'Total: {$x:[.2]} units'");
+                    assert_eq!(v.range(), 11..12);
+                }
+                x => panic!("expecting a variable: {:?}", x),
+            }
+        }
+        x => panic!("expecting an expression: {:?}", x),
+    }
+
+    // non-variable expressions use the literal's input
+    let x = parse("f\"{get name}'s size is {get size}\"");
+    assert_eq!(
+        render(&x),
+        r#"let $fstr-input | \ {\ $fstr-input | get "name" | to-str} | + "'s size is " {\ $fstr-input | get "size" | to-str}"#
+    );
+
+    // escaped braces and quoted sections in expressions
+    let x = parse("f'{{{\\ $x | + '}'}}}'");
+    assert_eq!(
+        render(&x),
+        r#"let $fstr-input | \ "{" | + {\ $fstr-input | \ $x | + "}" | to-str} "}""#
+    );
+
+    let x = parse("f''");
+    assert_eq!(render(&x), r#"\ """#);
+}
+
+#[test]
+fn fstring_literal_errs() {
+    let defs = &Definitions::new();
+
+    let l = line("f'abc");
+    let x = arg(&l, defs)(&l.line).unwrap_err();
+    assert_eq!(
+        x,
+        E::Failure(ParsingError {
+            expecting: Expecting::NONE,
+            locs: vec![("f'abc".into(), "unterminated interpolated string".into())]
+        })
+    );
+
+    let l = line("f'a } b'");
+    let x = arg(&l, defs)(&l.line).unwrap_err();
+    assert_eq!(
+        x,
+        E::Failure(ParsingError {
+            expecting: Expecting::NONE,
+            locs: vec![(
+                "}".into(),
+                "unmatched brace, use `}}` to escape a brace".into()
+            )]
+        })
+    );
+
+    let l = line("f'a {$x'");
+    let x = arg(&l, defs)(&l.line).unwrap_err();
+    assert_eq!(
+        x,
+        E::Failure(ParsingError {
+            expecting: Expecting::TERM,
+            locs: vec![("{".into(), "unclosed interpolation brace".into())]
+        })
+    );
+
+    let l = line("f'a { }'");
+    let x = arg(&l, defs)(&l.line).unwrap_err();
+    assert_eq!(
+        x,
+        E::Failure(ParsingError {
+            expecting: Expecting::TERM,
+            locs: vec![("{ }".into(), "empty interpolation".into())]
+        })
+    );
+}
@@ -0,0 +1,51 @@
+//! Traits, named sets of operations which a type must implement.
+use crate::prelude::*;
+use ast::{DefinitionTrait, Location, TraitOp};
+
+/// The working set of traits.
+#[derive(Clone, Default)]
+pub struct Traits(HashMap<Str, DefinitionTrait>);
+
+impl Traits {
+    /// Get the trait using the name in `trait_name`.
+    pub fn get_using_tag(&self, trait_name: &Tag) -> Result<&DefinitionTrait> {
+        self.0
+            .get(trait_name.str())
+            .ok_or_else(|| Error::trait_not_found(trait_name))
+    }
+
+    /// Insert a trait, replacing any trait with the same name.
+    pub fn insert(&mut self, def: DefinitionTrait) {
+        self.0.insert(Str::new(def.name.str()), def);
+    }
+
+    /// Remove any loaded traits. If `only_files` is `true`, traits defined in the shell are
+    /// retained.
+    pub fn clear(&mut self, only_files: bool) {
+        self.0.retain(|_, x| match x.loc {
+            Location::Ogma => true,
+            Location::Shell => only_files,
+            Location::File(_, _) => false,
+        });
+    }
+
+    /// Iterate over the traits.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &DefinitionTrait> {
+        self.0.values()
+    }
+}
+
+/// Returns the operations of `trait_` which do not have an implementation for `ty`.
+///
+/// Operations with an input agnostic implementation are considered implemented.
+pub fn missing_ops<'a>(
+    trait_: &'a DefinitionTrait,
+    ty: &Type,
+    impls: &Implementations,
+) -> Vec<&'a TraitOp> {
+    trait_
+        .ops
+        .iter()
+        .filter(|op| impls.get_impl(op.name.str(), ty).is_none())
+        .collect()
+}
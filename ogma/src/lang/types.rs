@@ -7,38 +7,96 @@ use ::libs::{
 };
 use ast::Location;
 use lang::help::HelpMessage;
-use std::{convert::TryFrom, fmt, hash, ops, sync::Arc};
+use std::{borrow::Cow, convert::TryFrom, fmt, hash, ops, sync::Arc};
 use table::Entry;
 
 // ###### TABLE ################################################################
 type TrTable = ::table::Table<Value>;
 
 /// Wrapper of a table, used to specialise drop and cloning behaviour.
+///
+/// Tables are stored row-major, the rows remain the only storage. Typed [`ColumnView`]s are
+/// read-only copies of a column, built lazily on request and shared by clones of the table, until
+/// the table is mutated.
 #[derive(Clone, PartialEq, Debug, Default)]
-pub struct Table(pub Arc<TrTable>);
+pub struct Table(Arc<TableData>);
+
+#[derive(Default)]
+struct TableData {
+    table: TrTable,
+    /// The typed column views built for the table, keyed on column index.
+    columns: Mutex<HashMap<usize, Arc<ColumnView>>>,
+}
 
 impl Table {
     /// If this table reference is unique, get a mutable alias to it.
+    ///
+    /// The typed columns are dropped.
     pub fn get_mut(&mut self) -> Option<&mut TrTable> {
-        Arc::get_mut(&mut self.0)
+        Arc::get_mut(&mut self.0).map(TableData::table_mut)
     }
 
     /// Make a unique reference to this table by cloning if required.
+    ///
+    /// The typed columns are dropped.
     pub fn make_mut(&mut self) -> &mut TrTable {
-        Arc::make_mut(&mut self.0)
+        Arc::make_mut(&mut self.0).table_mut()
+    }
+
+    /// Get the typed view of column `col`, building it if required.
+    pub fn column_view(&self, col: usize) -> Arc<ColumnView> {
+        let TableData { table, columns } = &*self.0;
+        let mut columns = columns.lock();
+        let c = columns
+            .entry(col)
+            .or_insert_with(|| Arc::new(ColumnView::build(table, col)));
+        Arc::clone(c)
+    }
+}
+
+impl TableData {
+    fn table_mut(&mut self) -> &mut TrTable {
+        self.columns.get_mut().clear();
+        &mut self.table
     }
 }
 
 impl From<TrTable> for Table {
     fn from(table: TrTable) -> Self {
-        Table(Arc::new(table))
+        Table(Arc::new(TableData {
+            table,
+            columns: Default::default(),
+        }))
     }
 }
 
 impl ops::Deref for Table {
     type Target = TrTable;
     fn deref(&self) -> &TrTable {
-        self.0.deref()
+        &self.0.table
+    }
+}
+
+/// Cloning does not clone the typed columns.
+impl Clone for TableData {
+    fn clone(&self) -> Self {
+        TableData {
+            table: self.table.clone(),
+            columns: Default::default(),
+        }
+    }
+}
+
+/// Tables are equal on their entries.
+impl PartialEq for TableData {
+    fn eq(&self, other: &Self) -> bool {
+        self.table == other.table
+    }
+}
+
+impl fmt::Debug for TableData {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.table.fmt(f)
     }
 }
 
@@ -55,6 +113,218 @@ impl Drop for Table {
     }
 }
 
+// ###### COLUMN VIEW ##########################################################
+/// A typed, read-only copy of a table column, used to speed up column scans.
+///
+/// Entries are indexed by row, the header row is marked as nil. Number and string columns hold
+/// their entries contiguously, so scanning a column need not match each [`Entry`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnView {
+    /// Numbers (and nils). Numbers are stored as `f64`, so a column is only a number column if
+    /// each number converts to `f64` without loss.
+    Num(Vec<f64>, Nils),
+    /// Strings (and nils).
+    Str(Vec<Str>, Nils),
+    /// Entries of mixed or other types, which are read through the table rows.
+    Mixed,
+}
+
+impl ColumnView {
+    /// Build column `col` of `table`. A column of only nils is a number column.
+    pub fn build(table: &TrTable, col: usize) -> Self {
+        Self::build_typed(table, col).unwrap_or(ColumnView::Mixed)
+    }
+
+    /// Build a typed column, returning `None` if the column is mixed.
+    fn build_typed(table: &TrTable, col: usize) -> Option<Self> {
+        enum B {
+            Num(Vec<f64>),
+            Str(Vec<Str>),
+        }
+
+        let len = table.rows_len();
+        let mut nils = Nils::new(len);
+        if len > 0 {
+            nils.set(0);
+        }
+        let mut b: Option<B> = None;
+
+        for (idx, mut row) in table.rows().enumerate().skip(1) {
+            match (row.nth(col), &mut b) {
+                (None | Some(Entry::Nil | Entry::Obj(Value::Nil)), _) => nils.set(idx),
+                (Some(Entry::Num(n) | Entry::Obj(Value::Num(n))), None) => {
+                    let mut xs = vec![0.0; len];
+                    xs[idx] = lossless_f64(n)?;
+                    b = Some(B::Num(xs));
+                }
+                (Some(Entry::Num(n) | Entry::Obj(Value::Num(n))), Some(B::Num(xs))) => {
+                    xs[idx] = lossless_f64(n)?
+                }
+                (Some(Entry::Obj(Value::Str(s))), None) => {
+                    let mut xs = vec![Str::default(); len];
+                    xs[idx] = s.clone();
+                    b = Some(B::Str(xs));
+                }
+                (Some(Entry::Obj(Value::Str(s))), Some(B::Str(xs))) => xs[idx] = s.clone(),
+                _ => return None,
+            }
+        }
+
+        Some(match b {
+            Some(B::Str(xs)) => ColumnView::Str(xs, nils),
+            Some(B::Num(xs)) => ColumnView::Num(xs, nils),
+            None => ColumnView::Num(vec![0.0; len], nils),
+        })
+    }
+
+    /// The nils of a typed column.
+    pub fn nils(&self) -> Option<&Nils> {
+        match self {
+            ColumnView::Num(_, nils) | ColumnView::Str(_, nils) => Some(nils),
+            ColumnView::Mixed => None,
+        }
+    }
+}
+
+/// The number as an `f64`, if it converts without loss (integers beyond 2^53 might not).
+fn lossless_f64(n: &Number) -> Option<f64> {
+    let x = n.as_f64();
+    let exact = match (n.as_i128(), n.as_u128()) {
+        (Ok(i), _) => x < i128::MAX as f64 && x as i128 == i,
+        (_, Ok(u)) => x < u128::MAX as f64 && x as u128 == u,
+        _ => true,
+    };
+    exact.then_some(x)
+}
+
+/// A bitmap marking the nil entries of a [`ColumnView`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Nils(Vec<u64>);
+
+impl Nils {
+    fn new(len: usize) -> Self {
+        Nils(vec![0; len.div_ceil(64)])
+    }
+
+    fn set(&mut self, idx: usize) {
+        self.0[idx / 64] |= 1 << (idx % 64);
+    }
+
+    /// The entry at row `idx` is nil.
+    pub fn is_nil(&self, idx: usize) -> bool {
+        self.0[idx / 64] & (1 << (idx % 64)) != 0
+    }
+
+    /// The number of nil entries, including the header.
+    pub fn count(&self) -> usize {
+        self.0.iter().map(|x| x.count_ones() as usize).sum()
+    }
+}
+
+// ###### STREAM ###############################################################
+/// The chunks of a [`Stream`]. Each chunk is a table which includes the header row.
+pub type Chunks = Box<dyn Iterator<Item = Result<Table>>>;
+
+/// A lazily evaluated table, pulled from its source in chunks.
+///
+/// Row-wise operations are applied chunk by chunk, so the whole table is never materialised.
+/// Each iteration of the stream pulls from the source again.
+#[derive(Clone)]
+pub struct Stream(Arc<dyn Fn() -> Result<Chunks> + Send + Sync>);
+
+impl Stream {
+    /// Create a stream from a function which opens the source chunks.
+    pub fn new<F>(open: F) -> Self
+    where
+        F: Fn() -> Result<Chunks> + Send + Sync + 'static,
+    {
+        Stream(Arc::new(open))
+    }
+
+    /// Open the stream, pulling chunks from the source.
+    ///
+    /// Each chunk must have the same header row as the first chunk, otherwise the chunk is an
+    /// error.
+    pub fn chunks(&self) -> Result<Chunks> {
+        let mut hdr: Option<Vec<Entry<Value>>> = None;
+        let chunks = (self.0)()?.map(move |chunk| {
+            chunk.and_then(|chunk| {
+                let row = || chunk.row(0).into_iter().flatten();
+                match &hdr {
+                    Some(h) if !row().eq(h.iter()) => return Err(Error::stream_header_changed()),
+                    Some(_) => (),
+                    None => hdr = Some(row().cloned().collect()),
+                }
+                Ok(chunk)
+            })
+        });
+        Ok(Box::new(chunks))
+    }
+
+    /// Transform the chunks iterator, creating a new stream.
+    pub fn then<F>(&self, f: F) -> Self
+    where
+        F: Fn(Chunks) -> Chunks + Send + Sync + 'static,
+    {
+        let src = self.clone();
+        Stream::new(move || src.chunks().map(&f))
+    }
+
+    /// Map each chunk, creating a new stream.
+    pub fn map_chunks<F>(&self, f: F) -> Self
+    where
+        F: Fn(Table) -> Result<Table> + Send + Sync + 'static,
+    {
+        let f = Arc::new(f);
+        self.then(move |chunks| {
+            let f = Arc::clone(&f);
+            Box::new(chunks.map(move |x| x.and_then(&*f)))
+        })
+    }
+
+    /// Materialise the stream into a table.
+    ///
+    /// The header row is taken from the first chunk.
+    pub fn collect(&self) -> Result<Table> {
+        let mut table: Option<Table> = None;
+        for chunk in self.chunks()? {
+            let chunk = chunk?;
+            match &mut table {
+                Some(t) => t
+                    .make_mut()
+                    .add_rows(chunk.rows().skip(1).map(|r| r.cloned())),
+                None => table = Some(chunk),
+            }
+        }
+
+        Ok(table.unwrap_or_default())
+    }
+
+    /// A stream with a single chunk, the materialised stream.
+    ///
+    /// This is used by operations which read rows in earlier chunks (such as `prev`).
+    pub fn whole(&self) -> Self {
+        let src = self.clone();
+        Stream::new(move || {
+            let chunks: Chunks = Box::new(std::iter::once(src.collect()));
+            Ok(chunks)
+        })
+    }
+}
+
+impl fmt::Debug for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Stream")
+    }
+}
+
+/// Streams are equal if they share the same source.
+impl PartialEq for Stream {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
 // ###### TYPE #################################################################
 /// Ogma data types.
 ///
@@ -73,6 +343,14 @@ pub enum Type {
     Tab,
     /// A table row type.
     TabRow,
+    /// A lazily evaluated table type.
+    Stream,
+    /// A date and time type, with a UTC offset.
+    DateTime,
+    /// A span of time type.
+    Duration,
+    /// A homogeneous list type, with the element type.
+    List(Arc<Type>),
     /// An ogma-defined type.
     Def(Arc<TypeDef>),
 }
@@ -88,6 +366,18 @@ impl Type {
             Str => "string value".into(),
             Tab => "table value".into(),
             TabRow => "table row".into(),
+            Stream => "lazily evaluated table, pulled from a file in chunks
+create using `open --Stream`"
+                .into(),
+            DateTime => "date and time value, with a UTC offset
+create using `to-datetime` or `now`"
+                .into(),
+            Duration => "duration value, a span of time
+create using `to-duration` or by subtracting datetimes"
+                .into(),
+            List(_) => "list value, a collection of values with the same type
+create using `[a b c]` or `to-list`"
+                .into(),
             Def(tydef) => return tydef.help(),
         };
 
@@ -104,6 +394,30 @@ impl Type {
             _ => false,
         }
     }
+
+    /// Construct a list type with elements of type `elem`.
+    pub fn list(elem: Type) -> Self {
+        Type::List(Arc::new(elem))
+    }
+
+    /// The element type if this is a list type.
+    pub fn list_elem(&self) -> Option<&Type> {
+        match self {
+            Type::List(x) => Some(x),
+            _ => None,
+        }
+    }
+
+    /// The type of the element at `idx` if this is a tuple type.
+    pub fn tuple_elem(&self, idx: usize) -> Option<&Type> {
+        match self {
+            Type::Def(x) if x.is_tuple() => match x.structure() {
+                TypeVariant::Product(fields) => fields.get(idx).map(Field::ty),
+                TypeVariant::Sum(_) => None,
+            },
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Type {
@@ -115,6 +429,10 @@ impl fmt::Display for Type {
             Type::Str => "String",
             Type::Tab => "Table",
             Type::TabRow => "TableRow",
+            Type::Stream => "Stream",
+            Type::DateTime => "DateTime",
+            Type::Duration => "Duration",
+            Type::List(_) => return write!(f, "{}", Tuple::tyname(self)),
             Type::Def(x) => x.name.str(),
         };
 
@@ -145,6 +463,14 @@ pub enum Value {
     Tab(Table),
     /// Table row.
     TabRow(TableRow),
+    /// Lazily evaluated table.
+    Stream(Stream),
+    /// Date and time value.
+    DateTime(::time::OffsetDateTime),
+    /// Duration value.
+    Duration(::time::Duration),
+    /// List value.
+    List(List),
     /// User defined data value.
     Ogma(OgmaData),
 }
@@ -160,6 +486,10 @@ impl Value {
             Str(_) => Type::Str,
             Tab(_) => Type::Tab,
             TabRow(_) => Type::TabRow,
+            Stream(_) => Type::Stream,
+            DateTime(_) => Type::DateTime,
+            Duration(_) => Type::Duration,
+            List(x) => x.ty(),
             Ogma(x) => Type::Def(Arc::clone(x.ty())),
         }
     }
@@ -230,7 +560,16 @@ impl From<$type> for Value {
     };
 }
 
-prim_type_impls!(bool=>Bool, Number=>Num, Str=>Str, Table=>Tab, TableRow=>TabRow);
+prim_type_impls!(
+    bool=>Bool,
+    Number=>Num,
+    Str=>Str,
+    Table=>Tab,
+    TableRow=>TabRow,
+    Stream=>Stream,
+    ::time::OffsetDateTime=>DateTime,
+    ::time::Duration=>Duration
+);
 
 // ----- additional that don't fit pattern -----
 impl AsType for () {
@@ -273,19 +612,49 @@ impl From<OgmaData> for Value {
         Value::Ogma(x)
     }
 }
+impl TryFrom<Value> for List {
+    type Error = Error;
+    fn try_from(v: Value) -> Result<Self> {
+        match v {
+            Value::List(x) => Ok(x),
+            x => Err(Error {
+                cat: err::Category::Evaluation,
+                desc: format!(
+                    "converting value into `List` failed, value has type `{}`",
+                    x.ty()
+                ),
+                ..Error::default()
+            }),
+        }
+    }
+}
+impl From<List> for Value {
+    fn from(x: List) -> Self {
+        Value::List(x)
+    }
+}
 // ----- end -----
 
 // ###### USER TYPES ###########################################################
 #[derive(Clone)]
 pub struct Types {
     map: HashMap<Str, Type>,
+    generics: HashMap<Str, Arc<GenericTypeDef>>,
+}
+
+/// A newly defined type, which needs to have its initialisation impls added.
+pub enum NewType {
+    /// See [`lang::impls::add_typedef_init_impls`].
+    Def(Arc<TypeDef>),
+    /// See [`lang::impls::add_generic_init_impls`].
+    Generic(Arc<GenericTypeDef>),
 }
 
 /// Ctor methods.
 impl Types {
     /// Initialise the standard types, returning the types and a vector of typedefs that need to
-    /// have their initialisation impls added ([`lang::impls::add_typedef_init_impls`]).
-    pub(super) fn init_std() -> (Self, Vec<Arc<TypeDef>>) {
+    /// have their initialisation impls added.
+    pub(super) fn init_std() -> (Self, Vec<NewType>) {
         let mut map = HashMap::default();
 
         map.insert(Str::from("Nil"), Type::Nil);
@@ -294,8 +663,14 @@ impl Types {
         map.insert(Str::from("Str"), Type::Str);
         map.insert(Str::from("Table"), Type::Tab);
         map.insert(Str::from("TableRow"), Type::TabRow);
+        map.insert(Str::from("Stream"), Type::Stream);
+        map.insert(Str::from("DateTime"), Type::DateTime);
+        map.insert(Str::from("Duration"), Type::Duration);
 
-        let mut types = Self { map };
+        let mut types = Self {
+            map,
+            generics: HashMap::default(),
+        };
 
         // we add in the ogma primitive types, each one adding onto defs.
         // we can unwrap here as these should always parse just fine (and is tested)
@@ -316,51 +691,151 @@ impl Types {
         }
 
         init! {
-            ORD, "Ord", "def-ty Ord :: Lt | Eq | Gt"
+            ORD, "Ord", "def-ty Ord :: Lt | Eq | Gt" |
+            ERROR, "Error", "def-ty Error { cat:Str desc:Str trace:Str }"
         };
 
+        // the generic types are initialised in a similar fashion
+        for (id, code) in [
+            (&OPTION, "def-ty Option<T> :: Some { v:T } | None"),
+            (&RESULT, "def-ty Result<T E> :: Ok { v:T } | Err { e:E }"),
+        ] {
+            match tys.insert_inner(code, Location::Ogma, None).unwrap() {
+                NewType::Generic(x) => {
+                    id.set(x.clone());
+                    xs.push(NewType::Generic(x));
+                }
+                NewType::Def(_) => unreachable!("will always be a GenericTypeDef"),
+            }
+        }
+
         (types, xs)
     }
 
-    fn insert_inner(
-        &mut self,
-        def: &str,
-        loc: Location,
-        help: Option<String>,
-    ) -> Result<Arc<TypeDef>> {
+    fn insert_inner(&mut self, def: &str, loc: Location, help: Option<String>) -> Result<NewType> {
         let def = lang::syntax::parse::definition_type(def, loc).map_err(|e| e.0)?;
-        let ty = TypeDef::from_parsed_def(def, help, self)?;
-        if self.contains_type(ty.name().str()) {
+        let name = def.name.clone();
+        if self.contains_type(name.str()) {
             // TODO allow overwriting type definitions. It will require:
             // a. Error on primitive type defs.
             // b. Relinking any type references previously map to this types name.
             // If no mapping is done, other types defined off this one will still have old
             // reference to them!
             // c. The associated init impl needs to be redone to match the new typedef signature
-            let name = ty.name();
             Err(Error {
                 cat: err::Category::Definitions,
                 desc: format!("can not redefine type `{}`", name),
                 traces: vec![err::Trace::from_tag(
-                    name,
+                    &name,
                     format!("`{}` already defined", name),
                 )],
                 help_msg: Some("try defining your type with a different name".into()),
                 hard: true,
             })
+        } else if def.params.is_empty() {
+            let ty = Arc::new(TypeDef::from_parsed_def(def, help, self)?);
+            self.map.insert(Str::new(&name), Type::Def(ty.clone()));
+            Ok(NewType::Def(ty))
         } else {
-            let ty = Arc::new(ty);
-            let name = Str::new(&ty.name);
-            self.map.insert(name, Type::Def(ty.clone()));
-            Ok(ty)
+            let params = def.params.len();
+            let gen = Arc::new(GenericTypeDef { def, help });
+            // check the field types resolve by instantiating with placeholder arguments
+            GenericTypeDef::instantiate(&gen, vec![Type::Nil; params], self)?;
+            self.generics.insert(Str::new(&name), gen.clone());
+            Ok(NewType::Generic(gen))
         }
     }
 }
 
 impl Types {
-    pub fn get_using_tag(&self, type_name: &Tag) -> Result<&Type> {
-        self.get_using_str(type_name.str())
-            .ok_or_else(|| Error::type_not_found(type_name))
+    /// Get the type using the name in `type_name`.
+    ///
+    /// Generic types are instantiated with the type arguments, such as `Option<Num>`.
+    pub fn get_using_tag(&self, type_name: &Tag) -> Result<Type> {
+        self.resolve_tag(type_name, &[])
+    }
+
+    /// Resolve the type name `ty`, substituting the bound type parameters.
+    ///
+    /// Returns `None` if `ty` mentions a type parameter which is not bound yet.
+    pub fn resolve_params(
+        &self,
+        ty: &Tag,
+        params: &[&str],
+        args: &[Option<Type>],
+    ) -> Result<Option<Type>> {
+        let mut subs = Vec::with_capacity(args.len());
+        for (&p, arg) in params.iter().zip(args) {
+            match arg {
+                Some(arg) => subs.push((p, arg.clone())),
+                None if mentions_params(ty, &[p]) => return Ok(None),
+                None => (),
+            }
+        }
+
+        self.resolve_tag(ty, &subs).map(Some)
+    }
+
+    fn resolve_tag(&self, type_name: &Tag, subs: &Subs) -> Result<Type> {
+        match TyName::parse(type_name.str()) {
+            Some(name) => self.resolve_name(&name, type_name, subs),
+            None => Err(Error::type_not_found(type_name)),
+        }
+    }
+
+    fn resolve_name(&self, name: &TyName, tag: &Tag, subs: &Subs) -> Result<Type> {
+        use ::nom::Offset;
+
+        let start = tag.start + tag.str().offset(name.name);
+        let ty_tag: Tag = ast::Tag_ {
+            start,
+            end: start + name.name.len(),
+            ..ast::Tag_::clone(tag)
+        }
+        .into();
+
+        if let Some((_, x)) = subs
+            .iter()
+            .find(|(p, _)| name.args.is_empty() && *p == name.name)
+        {
+            Ok(x.clone())
+        } else if name.args.is_empty() {
+            match (self.map.get(name.name), self.generics.get(name.name)) {
+                (Some(ty), _) => Ok(ty.clone()),
+                (None, Some(gen)) => Err(Error::type_params_mismatch(&ty_tag, gen.params(), 0)),
+                (None, None) if name.name == LIST => {
+                    Err(Error::type_params_mismatch(&ty_tag, &LIST_PARAMS, 0))
+                }
+                (None, None) => Err(Error::type_not_found(&ty_tag)),
+            }
+        } else {
+            let args = name
+                .args
+                .iter()
+                .map(|x| self.resolve_name(x, tag, subs))
+                .collect::<Result<Vec<_>>>()?;
+            self.instantiate(&ty_tag, args)
+        }
+    }
+
+    /// Instantiate the generic type `ty` with the type arguments.
+    ///
+    /// The builtin `List` type is also instantiated here.
+    pub fn instantiate(&self, ty: &Tag, mut args: Vec<Type>) -> Result<Type> {
+        match self.generics.get(ty.str()) {
+            Some(gen) if gen.params().len() == args.len() => {
+                GenericTypeDef::instantiate(gen, args, self).map(Type::Def)
+            }
+            Some(gen) => Err(Error::type_params_mismatch(ty, gen.params(), args.len())),
+            None if ty.str() == LIST && args.len() == 1 => Ok(Type::list(args.remove(0))),
+            None if ty.str() == LIST => {
+                Err(Error::type_params_mismatch(ty, &LIST_PARAMS, args.len()))
+            }
+            None if self.map.contains_key(ty.str()) => {
+                Err(Error::type_params_mismatch::<&str>(ty, &[], args.len()))
+            }
+            None => Err(Error::type_not_found(ty)),
+        }
     }
 
     pub fn get_using_str(&self, type_name: &str) -> Option<&Type> {
@@ -368,7 +843,9 @@ impl Types {
     }
 
     pub fn contains_type(&self, type_name: &str) -> bool {
-        self.map.contains_key(type_name)
+        type_name == LIST
+            || self.map.contains_key(type_name)
+            || self.generics.contains_key(type_name)
     }
 
     pub fn insert(
@@ -378,24 +855,32 @@ impl Types {
         help: Option<String>,
         impls: &mut Implementations,
     ) -> Result<()> {
-        let ty = self.insert_inner(def, loc, help)?;
-        lang::impls::add_typedef_init_impls(impls, ty);
+        match self.insert_inner(def, loc, help)? {
+            NewType::Def(ty) => lang::impls::add_typedef_init_impls(impls, ty),
+            NewType::Generic(gen) => lang::impls::add_generic_init_impls(impls, gen),
+        }
         Ok(())
     }
 
     pub fn clear(&mut self, only_files: bool) {
+        let keep = |loc: &Location| match loc {
+            Location::Ogma => true,
+            Location::Shell => only_files,
+            Location::File(_, _) => false,
+        };
+
         self.map.retain(|_, x| match x {
-            Type::Def(x) => match &x.loc {
-                Location::Ogma => true,
-                Location::Shell => only_files,
-                Location::File(_, _) => false,
-            },
+            Type::Def(x) => keep(&x.loc),
             _ => true,
-        })
+        });
+        self.generics.retain(|_, x| keep(x.loc()));
     }
 
     pub fn help_iter(&self) -> impl Iterator<Item = (&Str, HelpMessage)> {
-        self.map.iter().map(|(n, t)| (n, t.help()))
+        self.map
+            .iter()
+            .map(|(n, t)| (n, t.help()))
+            .chain(self.generics.iter().map(|(n, g)| (n, g.help())))
     }
 
     pub fn iter(&self) -> impl ExactSizeIterator<Item = (&str, &Type)> {
@@ -411,6 +896,8 @@ pub struct TypeDef {
     help: Option<String>,
     name: Tag,
     ty: TypeVariant,
+    /// The generic type and type arguments this type is instantiated from.
+    inst: Option<(Arc<GenericTypeDef>, Vec<Type>)>,
 }
 
 #[derive(Debug, Clone)]
@@ -445,25 +932,14 @@ impl TypeDef {
         help: Option<String>,
         types: &Types,
     ) -> Result<Self> {
-        let ast::DefinitionType { loc, src, name, ty } = def;
-        let ty: TypeVariant = match ty {
-            ast::TypeVariant::Sum(variants) => {
-                let mut v = Vec::with_capacity(variants.len());
-                for var in variants {
-                    let ast::Variant { name, fields } = var;
-                    let fields = if let Some(fields) = fields {
-                        Some(from_parsed_fields(fields, types)?)
-                    } else {
-                        None
-                    };
-                    v.push(Variant { name, fields });
-                }
-                TypeVariant::Sum(v)
-            }
-            ast::TypeVariant::Product(fields) => {
-                TypeVariant::Product(from_parsed_fields(fields, types)?)
-            }
-        };
+        let ast::DefinitionType {
+            loc,
+            src,
+            name,
+            params: _,
+            ty,
+        } = def;
+        let ty = from_parsed_variant(ty, types, &[])?;
 
         Ok(Self {
             loc,
@@ -471,6 +947,7 @@ impl TypeDef {
             help,
             name,
             ty,
+            inst: None,
         })
     }
 
@@ -500,18 +977,48 @@ impl TypeDef {
     pub fn is_tuple(&self) -> bool {
         self.name.str().starts_with("U_")
     }
+
+    /// If this type is an instantiation of a generic type, returns the generic type and the type
+    /// arguments.
+    pub fn generic(&self) -> Option<(&Arc<GenericTypeDef>, &[Type])> {
+        self.inst.as_ref().map(|(g, args)| (g, args.as_slice()))
+    }
+}
+
+/// Type parameter substitutions.
+type Subs<'a> = [(&'a str, Type)];
+
+fn from_parsed_variant(ty: ast::TypeVariant, types: &Types, subs: &Subs) -> Result<TypeVariant> {
+    Ok(match ty {
+        ast::TypeVariant::Sum(variants) => {
+            let mut v = Vec::with_capacity(variants.len());
+            for var in variants {
+                let ast::Variant { name, fields } = var;
+                let fields = if let Some(fields) = fields {
+                    Some(from_parsed_fields(fields, types, subs)?)
+                } else {
+                    None
+                };
+                v.push(Variant { name, fields });
+            }
+            TypeVariant::Sum(v)
+        }
+        ast::TypeVariant::Product(fields) => {
+            TypeVariant::Product(from_parsed_fields(fields, types, subs)?)
+        }
+    })
 }
 
-fn from_parsed_fields(fields: Vec<ast::Field>, types: &Types) -> Result<Vec<Field>> {
+fn from_parsed_fields(fields: Vec<ast::Field>, types: &Types, subs: &Subs) -> Result<Vec<Field>> {
     let mut v = Vec::with_capacity(fields.len());
     for field in fields {
         let ast::Field { name, ty, params } = field;
         let typedef = ty;
-        let ty = types.get_using_tag(&typedef)?.clone();
+        let ty = resolve_field_ty(&typedef, &params, types, subs)?;
         let x = params;
         let mut params = Vec::with_capacity(x.len());
         for param in x {
-            params.push(types.get_using_tag(&param)?.clone());
+            params.push(resolve_field_ty(&param, &[], types, subs)?);
         }
 
         v.push(Field {
@@ -524,6 +1031,19 @@ fn from_parsed_fields(fields: Vec<ast::Field>, types: &Types) -> Result<Vec<Fiel
     Ok(v)
 }
 
+/// Resolve a field type, substituting any type parameters.
+fn resolve_field_ty(ty: &Tag, params: &[Tag], types: &Types, subs: &Subs) -> Result<Type> {
+    if params.is_empty() {
+        types.resolve_tag(ty, subs)
+    } else {
+        let args = params
+            .iter()
+            .map(|p| types.resolve_tag(p, subs))
+            .collect::<Result<Vec<_>>>()?;
+        types.instantiate(ty, args)
+    }
+}
+
 impl PartialEq for TypeDef {
     fn eq(&self, rhs: &Self) -> bool {
         self.name.str() == rhs.name.str()
@@ -551,20 +1071,247 @@ impl Field {
     }
 }
 
+// ###### GENERICS #############################################################
+/// A type definition which is parameterised over types, such as `Option<T>`.
+///
+/// A generic type is not a [`Type`] itself. It is _instantiated_ into a [`TypeDef`] once the type
+/// arguments are known, with the name reflecting the arguments (`Option<Num>`).
+#[derive(Debug)]
+pub struct GenericTypeDef {
+    def: ast::DefinitionType,
+    help: Option<String>,
+}
+
+impl GenericTypeDef {
+    pub fn name(&self) -> &Tag {
+        &self.def.name
+    }
+
+    pub fn loc(&self) -> &Location {
+        &self.def.loc
+    }
+
+    /// The type parameters.
+    pub fn params(&self) -> &[Tag] {
+        &self.def.params
+    }
+
+    pub fn structure(&self) -> &ast::TypeVariant {
+        &self.def.ty
+    }
+
+    /// The fields of a variant (`variant_idx` is ignored for product types).
+    pub fn fields(&self, variant_idx: usize) -> &[ast::Field] {
+        match &self.def.ty {
+            ast::TypeVariant::Sum(vars) => vars[variant_idx].fields.as_deref().unwrap_or_default(),
+            ast::TypeVariant::Product(fields) => fields,
+        }
+    }
+
+    /// The name with the type parameters, such as `Result<T E>`.
+    pub fn signature(&self) -> String {
+        let params = self
+            .params()
+            .iter()
+            .map(|p| p.str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{}<{}>", self.name(), params)
+    }
+
+    pub fn help(&self) -> HelpMessage {
+        let cmd = self.signature();
+        let desc = if let Some(help) = &self.help {
+            format!("{}\n`{}`\n\n{}", self.def.loc, self.def.src, help)
+        } else {
+            format!("{}\n`{}`", self.def.loc, self.def.src)
+        }
+        .into();
+        HelpMessage {
+            desc,
+            ..HelpMessage::new(cmd)
+        }
+    }
+
+    /// Instantiate the generic type with the type arguments.
+    ///
+    /// # Panics
+    /// Panics if the number of arguments does not match the number of type parameters.
+    pub fn instantiate(gen: &Arc<Self>, args: Vec<Type>, types: &Types) -> Result<Arc<TypeDef>> {
+        assert_eq!(gen.params().len(), args.len(), "type arguments mismatch");
+
+        let name = {
+            let args = args.iter().map(Tuple::tyname).collect::<Vec<_>>();
+            format!("{}<{}>", gen.name(), args.join(" "))
+        };
+        let name = ast::Tag_ {
+            anchor: gen.def.loc.clone(),
+            line: Arc::from(name.as_str()),
+            start: 0,
+            end: name.len(),
+        };
+
+        let subs = gen
+            .params()
+            .iter()
+            .map(|p| p.str())
+            .zip(args.iter().cloned())
+            .collect::<Vec<_>>();
+        let ty = from_parsed_variant(gen.def.ty.clone(), types, &subs)?;
+
+        Ok(Arc::new(TypeDef {
+            loc: gen.def.loc.clone(),
+            src: gen.def.src.clone(),
+            help: gen.help.clone(),
+            name: name.into(),
+            ty,
+            inst: Some((gen.clone(), args)),
+        }))
+    }
+
+    /// Resolve the type of `field`, using the known type arguments.
+    ///
+    /// Returns `None` if the field uses a type parameter which is not known yet.
+    pub fn field_ty(
+        &self,
+        field: &ast::Field,
+        args: &[Option<Type>],
+        types: &Types,
+    ) -> Result<Option<Type>> {
+        let mut subs = Vec::with_capacity(args.len());
+        for (p, arg) in self.params().iter().zip(args) {
+            match arg {
+                Some(arg) => subs.push((p.str(), arg.clone())),
+                None if field_name(field).mentions(p.str()) => return Ok(None),
+                None => (),
+            }
+        }
+
+        resolve_field_ty(&field.ty, &field.params, types, &subs).map(Some)
+    }
+
+    /// Unify the type of `field` with the type `ty`, binding any unknown type arguments.
+    ///
+    /// Returns `false` if the types cannot be unified.
+    pub fn unify(&self, field: &ast::Field, ty: &Type, args: &mut [Option<Type>]) -> bool {
+        self.unify_name(&field_name(field), ty, args)
+    }
+
+    fn unify_name(&self, name: &TyName, ty: &Type, args: &mut [Option<Type>]) -> bool {
+        let params = self.params().iter().map(|p| p.str()).collect::<Vec<_>>();
+        unify_name(name, &params, ty, args)
+    }
+}
+
+/// Unify the type name with the type `ty`, binding any unknown type arguments of `params`.
+///
+/// Concrete types within the name are not checked, they are obliged once the name is resolved.
+fn unify_name(name: &TyName, params: &[&str], ty: &Type, args: &mut [Option<Type>]) -> bool {
+    let param = params.iter().position(|&p| p == name.name);
+
+    match (param, ty) {
+        (Some(idx), ty) if name.args.is_empty() => match &args[idx] {
+            Some(x) => x == ty,
+            None => {
+                args[idx] = Some(ty.clone());
+                true
+            }
+        },
+        // concrete types are obliged through `field_ty`
+        (None, _) if name.args.is_empty() => true,
+        (_, Type::List(x)) => {
+            name.name == LIST && name.args.len() == 1 && unify_name(&name.args[0], params, x, args)
+        }
+        (_, Type::Def(x)) => match x.generic() {
+            Some((gen, targs)) if gen.name().str() == name.name => name
+                .args
+                .iter()
+                .zip(targs)
+                .all(|(n, t)| unify_name(n, params, t, args)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// The full type name of a field, including the type arguments.
+fn field_name(field: &ast::Field) -> TyName {
+    TyName {
+        name: field.ty.str(),
+        args: field
+            .params
+            .iter()
+            .filter_map(|p| TyName::parse(p.str()))
+            .collect(),
+    }
+}
+
+/// A parsed type name, such as `Result<Num Option<Str>>`.
+#[derive(Debug, PartialEq)]
+struct TyName<'a> {
+    name: &'a str,
+    args: Vec<TyName<'a>>,
+}
+
+impl<'a> TyName<'a> {
+    fn parse(s: &'a str) -> Option<Self> {
+        use ::nom::{
+            bytes::complete::*, character::complete::*, combinator::*, multi::*, sequence::*, *,
+        };
+        type R<'a, T> = IResult<&'a str, T, ()>;
+
+        fn ty_name(i: &str) -> R<TyName> {
+            let (i, name) = take_till1(|c: char| c == '<' || c == '>' || c.is_whitespace())(i)?;
+            let (i, args) = opt(delimited(
+                char('<'),
+                many1(delimited(multispace0, ty_name, multispace0)),
+                char('>'),
+            ))(i)?;
+            let args = args.unwrap_or_default();
+            Ok((i, TyName { name, args }))
+        }
+
+        all_consuming(ty_name)(s).ok().map(|(_, x)| x)
+    }
+
+    /// The type name is, or contains, `ty`.
+    fn mentions(&self, ty: &str) -> bool {
+        self.name == ty || self.args.iter().any(|x| x.mentions(ty))
+    }
+}
+
+// ###### TYPE PARAMETERS ######################################################
+/// Returns if the type name `ty` is, or contains, one of the type parameters, such as `Option<T>`.
+pub fn mentions_params(ty: &Tag, params: &[&str]) -> bool {
+    TyName::parse(ty.str())
+        .map(|name| params.iter().any(|p| name.mentions(p)))
+        .unwrap_or_default()
+}
+
+/// Unify the type name `ty` with the concrete type `with`, binding the type parameters in `args`.
+///
+/// Returns `false` if the types cannot be unified. Concrete types within the name are not
+/// checked, compare `with` against [`Types::resolve_params`] once bound.
+pub fn unify_params(ty: &Tag, params: &[&str], with: &Type, args: &mut [Option<Type>]) -> bool {
+    TyName::parse(ty.str())
+        .map(|name| unify_name(&name, params, with, args))
+        .unwrap_or_default()
+}
+
 // ###### PRIM #################################################################
-pub struct PrimTyDef(RwLock<Option<Arc<TypeDef>>>);
+pub struct PrimTyDef<T = TypeDef>(RwLock<Option<Arc<T>>>);
 
-impl PrimTyDef {
+impl<T> PrimTyDef<T> {
     const fn new() -> Self {
         PrimTyDef(const_rwlock(None))
     }
 
-    fn set(&self, data: Arc<TypeDef>) {
+    fn set(&self, data: Arc<T>) {
         let mut lock = self.0.write();
         *lock = Some(data);
     }
 
-    pub fn get(&self) -> Arc<TypeDef> {
+    pub fn get(&self) -> Arc<T> {
         self.0
             .read()
             .clone()
@@ -653,21 +1400,33 @@ fn add_kserd_fields(
 
     for (idx, value) in values.into_iter().enumerate() {
         let field = &fields[idx];
-        let value = match value {
-            Value::Nil => Kserd::new_unit(),
-            Value::Bool(b) => Kserd::new_bool(b),
-            Value::Num(n) => Kserd::new(KValue::Num(n)),
-            Value::Str(s) => Kserd::new_string(s.to_string()),
-            Value::Tab(_) => Kserd::new_str("<table>"),
-            Value::TabRow(_) => Kserd::new_str("<table row>"),
-            Value::Ogma(data) => data.into_kserd()?,
-        };
-        map.insert(field.name.to_string().into(), value);
+        map.insert(field.name.to_string().into(), value_into_kserd(value)?);
     }
 
     Ok(KValue::Cntr(map))
 }
 
+fn value_into_kserd(value: Value) -> std::result::Result<Kserd<'static>, ToKserdErr> {
+    Ok(match value {
+        Value::Nil => Kserd::new_unit(),
+        Value::Bool(b) => Kserd::new_bool(b),
+        Value::Num(n) => Kserd::new(KValue::Num(n)),
+        Value::Str(s) => Kserd::new_string(s.to_string()),
+        Value::Tab(_) => Kserd::new_str("<table>"),
+        Value::TabRow(_) => Kserd::new_str("<table row>"),
+        Value::Stream(_) => Kserd::new_str("<stream>"),
+        Value::DateTime(dt) => Kserd::new_string(print::fmt_datetime(&dt)),
+        Value::Duration(d) => Kserd::new_string(print::fmt_duration(&d)),
+        Value::List(x) => Kserd::new(KValue::Seq(
+            x.into_items()
+                .into_iter()
+                .map(value_into_kserd)
+                .collect::<std::result::Result<_, _>>()?,
+        )),
+        Value::Ogma(data) => data.into_kserd()?,
+    })
+}
+
 // ###### ORD ##################################################################
 pub static ORD: PrimTyDef = PrimTyDef::new();
 
@@ -708,6 +1467,93 @@ impl From<std::cmp::Ordering> for Value {
     }
 }
 
+// ###### ERROR ################################################################
+/// A caught runtime error, such as the fallback input of `try`.
+pub static ERROR: PrimTyDef = PrimTyDef::new();
+
+impl From<&Error> for Value {
+    fn from(err: &Error) -> Self {
+        let data = vec![
+            Value::Str(err.cat.to_string().into()),
+            Value::Str(Str::from(err.desc.as_str())),
+            Value::Str(err.print_traces().into()),
+        ];
+        Value::Ogma(OgmaData::new(ERROR.get(), None, data))
+    }
+}
+
+// ###### OPTION + RESULT ######################################################
+pub static OPTION: PrimTyDef<GenericTypeDef> = PrimTyDef::new();
+pub static RESULT: PrimTyDef<GenericTypeDef> = PrimTyDef::new();
+
+// ###### LIST #################################################################
+/// The builtin list type name.
+const LIST: &str = "List";
+const LIST_PARAMS: [&str; 1] = ["T"];
+
+/// A homogeneous list of values.
+///
+/// The element type is kept with the list, so an empty list is still typed.
+#[derive(Debug, PartialEq, Clone)]
+pub struct List {
+    elem: Arc<Type>,
+    items: Arc<Vec<Value>>,
+}
+
+impl List {
+    /// Build a new [`List`] with elements of type `elem`.
+    ///
+    /// # Panics
+    /// For debug builds, panics if any of the items are not of type `elem`.
+    pub fn new(elem: Type, items: Vec<Value>) -> Self {
+        debug_assert!(
+            items.iter().all(|x| x.ty() == elem),
+            "all list items should have the element type"
+        );
+
+        Self {
+            elem: Arc::new(elem),
+            items: Arc::new(items),
+        }
+    }
+
+    /// The list type, `List<T>`.
+    pub fn ty(&self) -> Type {
+        Type::List(self.elem.clone())
+    }
+
+    /// The element type.
+    pub fn elem_ty(&self) -> &Type {
+        &self.elem
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&Value> {
+        self.items.get(idx)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<Value> {
+        self.items.iter()
+    }
+
+    /// Make a mutable reference to the items by clone-on-write.
+    pub fn make_mut(&mut self) -> &mut Vec<Value> {
+        Arc::make_mut(&mut self.items)
+    }
+
+    /// Take the items, cloning if the list is shared.
+    pub fn into_items(self) -> Vec<Value> {
+        Arc::try_unwrap(self.items).unwrap_or_else(|x| x.as_ref().clone())
+    }
+}
+
 // ###### TABLE ROW ############################################################
 pub type TableRowColMap = Arc<Mutex<HashMap<String, usize>>>;
 
@@ -804,7 +1650,7 @@ impl PartialEq for TableRow {
 // tuples are not defined in the types, instead they are defined in the HIR phase.
 pub struct Tuple;
 impl Tuple {
-    fn tyname(ty: &Type) -> &str {
+    fn tyname(ty: &Type) -> Cow<str> {
         match ty {
             Type::Nil => "Nil",
             Type::Bool => "Bool",
@@ -812,8 +1658,13 @@ impl Tuple {
             Type::Str => "Str",
             Type::Tab => "Table",
             Type::TabRow => "TableRow",
+            Type::Stream => "Stream",
+            Type::DateTime => "DateTime",
+            Type::Duration => "Duration",
+            Type::List(x) => return format!("{}<{}>", LIST, Self::tyname(x)).into(),
             Type::Def(x) => x.name.str(),
         }
+        .into()
     }
 
     fn name(args: &[Type]) -> String {
@@ -898,6 +1749,7 @@ impl Tuple {
             help: None,
             name: name.into(),
             ty: TypeVariant::Product(fields),
+            inst: None,
         }
     }
 
@@ -957,14 +1809,107 @@ mod tests {
     use super::*;
     use Type::*;
 
+    #[test]
+    fn table_columns() {
+        let s = |x: &str| Entry::Obj(Value::Str(x.into()));
+        let n = |x: f64| Entry::Num(x.into());
+        let mut t = TrTable::new();
+        t.add_row(vec![s("num"), s("str"), s("mixed")]);
+        t.add_row(vec![n(1.0), s("a"), n(1.0)]);
+        t.add_row(vec![Entry::Nil, Entry::Obj(Value::Nil), s("b")]);
+        t.add_row(vec![n(3.0), s("c"), Entry::Nil]);
+        let mut t = Table::from(t);
+
+        match &*t.column_view(0) {
+            ColumnView::Num(xs, nils) => {
+                assert_eq!((xs[1], xs[3]), (1.0, 3.0));
+                assert_eq!(
+                    (nils.is_nil(0), nils.is_nil(1), nils.is_nil(2)),
+                    (true, false, true)
+                );
+                assert_eq!(nils.count(), 2);
+            }
+            c => panic!("expecting a number column: {:?}", c),
+        }
+        match &*t.column_view(1) {
+            ColumnView::Str(xs, nils) => {
+                assert_eq!(xs[3], Str::from("c"));
+                assert_eq!(nils.count(), 2);
+            }
+            c => panic!("expecting a string column: {:?}", c),
+        }
+        assert_eq!(*t.column_view(2), ColumnView::Mixed);
+
+        // clones share the columns until mutated
+        let t2 = t.clone();
+        assert!(Arc::ptr_eq(&t.column_view(0), &t2.column_view(0)));
+        t.make_mut();
+        assert!(!Arc::ptr_eq(&t.column_view(0), &t2.column_view(0)));
+        let c = t.column_view(0);
+        t.make_mut();
+        assert!(!Arc::ptr_eq(&c, &t.column_view(0)));
+
+        // integers which do not convert to f64 exactly
+        let mut t = TrTable::new();
+        t.add_row(vec![s("a"), s("b"), s("c")]);
+        t.add_row(vec![n(1.0), Entry::Num((1u64 << 53).into()), n(1.0)]);
+        t.add_row(vec![
+            Entry::Num(((1u64 << 53) + 1).into()),
+            Entry::Num(((1i64 << 53) + 2).into()),
+            Entry::Num((-(1i64 << 53) - 1).into()),
+        ]);
+        let t = Table::from(t);
+        assert_eq!(*t.column_view(0), ColumnView::Mixed);
+        assert!(matches!(*t.column_view(1), ColumnView::Num(..)));
+        assert_eq!(*t.column_view(2), ColumnView::Mixed);
+    }
+
+    #[test]
+    fn stream_chunk_headers() {
+        let s = |x: &str| Entry::Obj(Value::Str(x.into()));
+        let n = |x: f64| Entry::Num(x.into());
+        let stream = |hdrs: [&'static str; 2]| {
+            Stream::new(move || {
+                let chunks = hdrs.into_iter().map(move |h| {
+                    let t = TrTable::from(vec![vec![s("a"), s(h)], vec![n(1.0), n(2.0)]]);
+                    Ok(Table::from(t))
+                });
+                Ok(Box::new(chunks) as Chunks)
+            })
+        };
+
+        let t = stream(["b", "b"]).collect().unwrap();
+        assert_eq!(t.rows_len(), 3);
+
+        let e = stream(["b", "c"]).collect().unwrap_err();
+        assert_eq!(
+            &e.desc,
+            "a stream chunk has a different header to the first chunk"
+        );
+    }
+
     #[test]
     fn display_impl() {
-        let ty = vec![Nil, Bool, Num, Str, Tab, TabRow, Def(ORD.get())]
-            .iter()
-            .map(|x| x.to_string())
-            .collect::<Vec<_>>()
-            .join(", ");
-        assert_eq!(&ty, "Nil, Bool, Number, String, Table, TableRow, Ord");
+        let ty = vec![
+            Nil,
+            Bool,
+            Num,
+            Str,
+            Tab,
+            TabRow,
+            DateTime,
+            Duration,
+            Def(ORD.get()),
+            Type::list(Num),
+        ]
+        .iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+        assert_eq!(
+            &ty,
+            "Nil, Bool, Number, String, Table, TableRow, DateTime, Duration, Ord, List<Num>"
+        );
     }
 
     fn x(t: Type) -> String {
@@ -1059,6 +2004,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn error_ty_help_msg() {
+        assert_eq!(
+            &x(Def(ERROR.get())),
+            "Help: `Error`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | <ogma>
+ | `def-ty Error { cat:Str desc:Str trace:Str }`
+ | 
+ | Usage:
+ |  => Error
+"
+        );
+    }
+
     #[test]
     fn tuple_type_testing() {
         let args = [Type::Nil, Type::Num, Type::Str];
@@ -1115,6 +2076,81 @@ mod tests {
         )
     }
 
+    #[test]
+    fn ty_name_parse_testing() {
+        let f = TyName::parse;
+        let t = |name| TyName { name, args: vec![] };
+
+        assert_eq!(f(""), None);
+        assert_eq!(f("Num"), Some(t("Num")));
+        assert_eq!(f("Num Str"), None);
+        assert_eq!(f("Option<>"), None);
+        assert_eq!(f("Option<Num"), None);
+        assert_eq!(
+            f("Option<Num>"),
+            Some(TyName {
+                name: "Option",
+                args: vec![t("Num")]
+            })
+        );
+        assert_eq!(
+            f("Result< Num  Option<Str> >"),
+            Some(TyName {
+                name: "Result",
+                args: vec![
+                    t("Num"),
+                    TyName {
+                        name: "Option",
+                        args: vec![t("Str")]
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn generic_instantiation_testing() {
+        let defs = crate::prelude::Definitions::new();
+        let tys = defs.types();
+        let tag = |s: &str| -> Tag {
+            ast::Tag_ {
+                anchor: Location::Shell,
+                line: Arc::from(s),
+                start: 0,
+                end: s.len(),
+            }
+            .into()
+        };
+
+        let ty = tys.get_using_tag(&tag("Result<Num Option<Str>>")).unwrap();
+        assert_eq!(ty.to_string(), "Result<Num Option<Str>>");
+        let tydef = match &ty {
+            Def(x) => x,
+            _ => panic!(),
+        };
+        let (gen, args) = tydef.generic().unwrap();
+        assert_eq!(gen.signature(), "Result<T E>");
+        assert_eq!(args[0], Num);
+        assert_eq!(args[1].to_string(), "Option<Str>");
+        match tydef.structure() {
+            TypeVariant::Sum(x) => {
+                assert_eq!(x[0].fields.as_ref().unwrap()[0].ty(), &Num);
+                assert_eq!(x[1].fields.as_ref().unwrap()[0].ty(), &args[1]);
+            }
+            _ => panic!(),
+        }
+
+        // same instantiations are equal
+        assert_eq!(
+            ty,
+            tys.get_using_tag(&tag("Result<Num Option<Str>>")).unwrap()
+        );
+        assert!(tys.get_using_tag(&tag("Option")).is_err());
+        assert!(tys.get_using_tag(&tag("Option<Num Str>")).is_err());
+        assert!(tys.get_using_tag(&tag("Num<Str>")).is_err());
+        assert!(tys.get_using_tag(&tag("Option<Foo>")).is_err());
+    }
+
     #[test]
     fn split_parse_testing() {
         use Split::*;
@@ -4,4 +4,6 @@ pub(crate) mod print;
 
 // Public API
 
-pub use print::{print_error, print_ogma_data, print_table};
+pub use print::{
+    fmt_datetime, fmt_duration, fmt_list, print_error, print_ogma_data, print_stream, print_table,
+};
@@ -78,6 +78,43 @@ pub fn print_table(table: &Table, wtr: &mut dyn Write) -> io::Result<()> {
     writeln!(wtr, "{}", out)
 }
 
+/// Print the first rows of the [`Stream`] as a text formatted table, see [`print_table`].
+/// Only the chunks needed to fill the printed rows are pulled from the stream.
+pub fn print_stream(stream: &Stream, wtr: &mut dyn Write) -> io::Result<()> {
+    let mut rows: Vec<Vec<Entry<Value>>> = Vec::new();
+    let mut more = false;
+
+    let chunks = match stream.chunks() {
+        Ok(x) => x,
+        Err(e) => return print_error(&e, wtr),
+    };
+    for chunk in chunks {
+        let chunk = match chunk {
+            Ok(x) => x,
+            Err(e) => return print_error(&e, wtr),
+        };
+        // the header is taken from the first chunk
+        let skip = usize::from(!rows.is_empty());
+        rows.extend(chunk.rows().skip(skip).map(|r| r.cloned().collect()));
+        if rows.len() > ROWS_LIM {
+            rows.truncate(ROWS_LIM);
+            more = true;
+            break;
+        }
+    }
+
+    print_table(&Table::from(::table::Table::from(rows)), wtr)?;
+    if more {
+        writeln!(
+            wtr,
+            "{}",
+            "stream has more rows, use `save` or `collect` to consume it".bright_yellow()
+        )?;
+    }
+
+    Ok(())
+}
+
 /// Prints the processing error. Uses colour and assumes printing is to the terminal.
 /// Use [`Error::print`] if this is not the case.
 pub fn print_error(err: &Error, wtr: &mut dyn Write) -> io::Result<()> {
@@ -129,10 +166,64 @@ pub fn fmt_cell(entry: &Entry<Value>, numfmtr: &mut Formatter) -> Str {
         Obj(V::Str(s)) => s.clone(),
         Obj(V::Tab(t)) => format!("<table [{},{}]>", t.rows_len(), t.cols_len()).into(),
         Obj(V::TabRow(_)) => Str::from("<table row>"), // this should not be reachable.
+        Obj(V::Stream(_)) => Str::from("<stream>"),
+        Obj(V::DateTime(dt)) => fmt_datetime(dt).into(),
+        Obj(V::Duration(d)) => fmt_duration(d).into(),
+        Obj(V::List(x)) => fmt_list(x, numfmtr).into(),
         Obj(V::Ogma(x)) => print_ogma_data(x.clone()).into(),
     }
 }
 
+/// Formats a list as its items separated by spaces, such as `[1 2 3]`.
+pub fn fmt_list(list: &List, numfmtr: &mut Formatter) -> String {
+    let items = list
+        .iter()
+        .map(|x| fmt_cell(&Entry::from(x.clone()), numfmtr))
+        .collect::<Vec<_>>();
+    format!("[{}]", items.join(" "))
+}
+
+/// Formats a datetime as RFC 3339, such as `2022-03-04T12:34:56Z`.
+pub fn fmt_datetime(dt: &::time::OffsetDateTime) -> String {
+    use ::time::format_description::well_known::Rfc3339;
+    // years outside 0..=9999 are not supported by RFC 3339
+    dt.format(&Rfc3339).unwrap_or_else(|_| dt.to_string())
+}
+
+/// Formats a duration using the largest units, such as `1d 2h 30m 4.5s`.
+pub fn fmt_duration(d: &::time::Duration) -> String {
+    use std::fmt::Write;
+
+    if d.is_zero() {
+        return "0s".to_string();
+    }
+
+    let mut s = String::new();
+    if d.is_negative() {
+        s.push('-');
+    }
+
+    let d = d.abs();
+    let parts = [
+        (d.whole_days(), 'd'),
+        (d.whole_hours() % 24, 'h'),
+        (d.whole_minutes() % 60, 'm'),
+    ];
+    for (x, unit) in parts.into_iter().filter(|x| x.0 != 0) {
+        write!(&mut s, "{}{} ", x, unit).ok();
+    }
+
+    let (secs, nanos) = (d.whole_seconds() % 60, d.subsec_nanoseconds());
+    if nanos != 0 {
+        let frac = format!("{:09}", nanos);
+        write!(&mut s, "{}.{}s", secs, frac.trim_end_matches('0')).ok();
+    } else if secs != 0 {
+        write!(&mut s, "{}s", secs).ok();
+    }
+
+    s.trim_end().to_string()
+}
+
 /// Serialises `OgmaData` into [`::kserd::Kserd`] and the formats it into a string.
 pub fn print_ogma_data(data: types::OgmaData) -> String {
     use kserd::ToKserd;
@@ -246,4 +337,31 @@ mod tests {
 ",
         );
     }
+
+    #[test]
+    fn fmt_datetime_test() {
+        use ::time::macros::datetime;
+        assert_eq!(
+            &fmt_datetime(&datetime!(2022-03-04 12:34:56 UTC)),
+            "2022-03-04T12:34:56Z"
+        );
+        assert_eq!(
+            &fmt_datetime(&datetime!(2022-03-04 12:34:56.5 +10:00)),
+            "2022-03-04T12:34:56.5+10:00"
+        );
+    }
+
+    #[test]
+    fn fmt_duration_test() {
+        use ::time::Duration as D;
+        assert_eq!(&fmt_duration(&D::ZERO), "0s");
+        assert_eq!(&fmt_duration(&D::seconds(4)), "4s");
+        assert_eq!(&fmt_duration(&D::milliseconds(4500)), "4.5s");
+        assert_eq!(&fmt_duration(&D::minutes(90)), "1h 30m");
+        assert_eq!(
+            &fmt_duration(&(D::days(1) + D::hours(2) + D::seconds(3))),
+            "1d 2h 3s"
+        );
+        assert_eq!(&fmt_duration(&D::hours(-3)), "-3h");
+    }
 }
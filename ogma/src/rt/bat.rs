@@ -30,6 +30,8 @@ pub struct Batch {
     pub parallelise: bool,
     /// Stop processing upon encountering an error in one of the items.
     pub fail_fast: bool,
+    /// The `import` directives, resolved against the definitions' partitions.
+    pub imports: Vec<lang::parse::Import>,
 }
 
 /// An item to process.
@@ -86,6 +88,8 @@ pub enum ItemType {
     Impl,
     /// A type definition.
     Type,
+    /// A trait definition.
+    Trait,
 }
 
 // ------ Parsing --------------------------------------------------------------
@@ -94,9 +98,11 @@ pub enum ItemType {
 /// This can be thought of as parsing a code file, with multiple items separated by a blank line.
 pub fn parse_str(s: &str, loc: Location) -> Result<Batch> {
     let File {
+        loc: _,
         doc: _,
         directives,
         types,
+        traits,
         impls,
         exprs,
     } = lang::parse::file(s, loc)?;
@@ -110,6 +116,17 @@ pub fn parse_str(s: &str, loc: Location) -> Result<Batch> {
             line,
             ty: ItemType::Type,
         })
+        .chain(
+            traits
+                .into_iter()
+                .map(|(_, Item { doc, code, line })| BatchItem {
+                    code,
+                    comment: doc,
+                    file: Path::new(".").into(),
+                    line,
+                    ty: ItemType::Trait,
+                }),
+        )
         .chain(
             impls
                 .into_iter()
@@ -133,6 +150,14 @@ pub fn parse_str(s: &str, loc: Location) -> Result<Batch> {
     Ok(Batch {
         parallelise: !directives.iter().any(|x| x == &Directive::NoParallelise),
         fail_fast: directives.iter().any(|x| x == &Directive::FailFast),
+        imports: directives
+            .into_iter()
+            .filter_map(|x| match x {
+                Directive::Import(x) => Some(x),
+                _ => None,
+            })
+            .flatten()
+            .collect(),
         items,
     })
 }
@@ -205,6 +230,16 @@ pub fn process(
             .collect(),
     );
 
+    // imports are resolved from the root partition, if they fail no items are processed
+    if let Err(e) = add_imports(batch, &mut definitions) {
+        let r = Outcome::Failed(e);
+        if !items.is_empty() {
+            report_progress(progress, &reporter, 0, &r);
+            results[0].0 = r;
+        }
+        return results;
+    }
+
     // anything that is not an expression must be processed in sequence first
     let (defs, xprs) =
         items
@@ -286,6 +321,22 @@ pub fn process(
     results
 }
 
+fn add_imports(batch: &Batch, definitions: &mut Definitions) -> Result<()> {
+    if batch.imports.is_empty() {
+        return Ok(());
+    }
+
+    let mut files = batch.items.iter().map(|x| &x.file).collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+
+    for file in files {
+        definitions.add_imports(file.clone(), &batch.imports)?;
+    }
+
+    Ok(())
+}
+
 // ------ Progress -------------------------------------------------------------
 type ProgressResults = Mutex<BatchProgress>;
 /// Serialisable progress of a [`Batch`].
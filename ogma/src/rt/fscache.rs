@@ -20,8 +20,9 @@ const LIFESPAN: Duration = Duration::from_secs(60 * 3); // 3 minutes
 const DEBOUNCE: Duration = Duration::from_millis(5); // 5ms fs watching
 static INIT: Once = Once::new();
 
+/// Keyed on the path, the value type, and a format discriminant (such as `csv` or `json`).
 #[derive(PartialEq, Eq, Hash)]
-struct Key(String, Type);
+struct Key(String, Type, &'static str);
 type Value = (Instant, types::Value);
 type Map = HashMap<Key, Value>;
 
@@ -35,8 +36,8 @@ pub struct FsCache {
 }
 
 impl Key {
-    fn from<T: AsType>(path: &Path) -> Self {
-        Key(path_to_str(path), T::as_type())
+    fn from<T: AsType>(path: &Path, fmt: &'static str) -> Self {
+        Key(path_to_str(path), T::as_type(), fmt)
     }
 }
 
@@ -45,13 +46,25 @@ impl FsCache {
     ///
     /// If a cache hit occurs it resets the use timer to keep this item alive.
     pub fn get<T>(&self, path: &Path) -> Option<T>
+    where
+        T: AsType,
+        T: TryFrom<types::Value>,
+    {
+        self.get_fmt(path, "")
+    }
+
+    /// Fetch a value from the cache along `path` which was parsed using the format `fmt`.
+    ///
+    /// The same path can be cached for differing formats, for example, a file opened as both
+    /// comma separated and tab separated values.
+    pub fn get_fmt<T>(&self, path: &Path, fmt: &'static str) -> Option<T>
     where
         T: AsType,
         T: TryFrom<types::Value>,
     {
         std::thread::sleep(DEBOUNCE * 5); // we sleep for the 5 x debounce duration to give time for the fs watcher to catch up
 
-        let key = Key::from::<T>(path);
+        let key = Key::from::<T>(path, fmt);
         let mut lock = self.map.lock();
         lock.get_mut(&key)
             .map(|x| {
@@ -67,7 +80,16 @@ impl FsCache {
         T: AsType,
         T: Into<types::Value>,
     {
-        let key = Key::from::<T>(path);
+        self.insert_fmt(path, "", value)
+    }
+
+    /// Insert the value `T` along path, which was parsed using the format `fmt`.
+    pub fn insert_fmt<T>(&self, path: &Path, fmt: &'static str, value: T)
+    where
+        T: AsType,
+        T: Into<types::Value>,
+    {
+        let key = Key::from::<T>(path, fmt);
         self.map.lock().insert(key, (Instant::now(), value.into()));
     }
 
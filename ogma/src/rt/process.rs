@@ -22,11 +22,21 @@ where
 {
     rt::fscache::ensure_init(root); // initialise the cache
 
-    let expr = lang::syntax::parse::expression(expr, loc, defs).map_err(|e| e.0)?;
-    handle_help(&expr, defs)?;
-    let eng::FullCompilation { eval_stack, env } = eng::compile(expr, defs, I::as_type())?;
-    let cx = eng::Context { root, wd, env };
-    let output = eval_stack.eval(seed.into(), cx)?.0;
+    let expr: Arc<str> = expr.into();
+    let in_ty = I::as_type();
+    let compiled = defs.compiled(&expr, &loc, &in_ty, || {
+        let expr = lang::syntax::parse::expression(Arc::clone(&expr), loc.clone(), defs)
+            .map_err(|e| e.0)?;
+        handle_help(&expr, defs)?;
+        eng::compile(expr, defs, in_ty.clone())
+    })?;
+    let cx = eng::Context {
+        root,
+        wd,
+        env: compiled.env.clone(),
+        depth: 0,
+    };
+    let output = compiled.eval_stack.eval(seed.into(), cx)?.0;
 
     Ok(output)
 }
@@ -40,7 +50,7 @@ pub fn handle_help(expr: &ast::Expression, definitions: &Definitions) -> Result<
             .any(|x| matches!(x, ast::Term::Flag(f) if f.str() == "help"));
 
         if help_flagged {
-            let x = definitions.impls().get_help_with_err(&block.op())?;
+            let x = definitions.get_help_with_err(&block.op())?;
             Err(x)
         } else {
             Ok(())
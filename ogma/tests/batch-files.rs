@@ -107,3 +107,66 @@ def-ty Foo { x:Num y: }"#;
 "#
     );
 }
+
+#[test]
+fn batch_imports_testing() {
+    use rt::bat::*;
+    use Outcome::*;
+
+    let p = &ProgressTx::dummy();
+    let (root, wd) = paths();
+
+    let mut defs = ogma::lang::Definitions::default();
+    defs.add_from_dir("partitions-structure-tests/test06", None)
+        .unwrap_err(); // imports from plugins
+    defs.add_from_dir(
+        "partitions-structure-tests/test06",
+        Some(Path::new("partitions-structure-tests/test06-plugins")),
+    )
+    .unwrap();
+
+    let code = r#"[import(strings/shout nums/*)]
+
+\ 'a' | shout
+
+\ 3 | double
+
+\ 3 | nums/shout"#;
+
+    let batch = parse_str(code, Location::Shell).unwrap();
+    let x = process(&batch, root, wd, p, defs.clone())
+        .into_iter()
+        .map(|x| x.0)
+        .collect::<Vec<_>>();
+    assert_eq!(x.len(), 3);
+    let x = match &x[0] {
+        Failed(e) => e.to_string(),
+        _ => panic!(),
+    };
+    println!("{x}");
+    assert!(x.contains("operation `shout` is imported from multiple partitions"));
+
+    let code = r#"[import(strings/shout nums/double)]
+
+\ 'a' | shout
+
+\ 3 | double"#;
+
+    let batch = parse_str(code, Location::Shell).unwrap();
+    let x = process(&batch, root, wd, p, defs)
+        .into_iter()
+        .map(|x| x.0)
+        .collect::<Vec<_>>();
+    assert_eq!(x, vec![Success, Success]);
+
+    // imports require the partitions to be loaded
+    let batch = parse_str(code, Location::Shell).unwrap();
+    let mut x = process(&batch, root, wd, p, Default::default()).into_iter();
+    let y = match x.next() {
+        Some((Failed(e), _)) => e.to_string(),
+        _ => panic!(),
+    };
+    println!("{y}");
+    assert!(y.contains("imports require partitions to be loaded"));
+    assert!(matches!(x.next(), Some((Outstanding, _))));
+}
@@ -38,6 +38,31 @@ fn add_help_msg() {
         &x,
         r#"Help: `+`
 --> shell:0
+ | ---- Input Type: DateTime ----
+ | add durations to a datetime
+ | -variadic-: more than one argument can be specified
+ | 
+ | Usage:
+ |  => + args..
+ | 
+ | Examples:
+ |  the time in an hour
+ |  => now | + {\ 1 | to-duration --hours}
+ | 
+ |  add multiple durations
+ |  => now | + {\ 1 | to-duration --days} {\ 30 | to-duration --mins}
+ | 
+ | ---- Input Type: Duration ----
+ | add durations together
+ | -variadic-: more than one argument can be specified
+ | 
+ | Usage:
+ |  => + args..
+ | 
+ | Examples:
+ |  add 30 minutes to an hour
+ |  => \ 1 | to-duration --hours | + {\ 30 | to-duration --mins}
+ | 
  | ---- Input Type: Number ----
  | add numbers together
  | -variadic-: more than one argument can be specified
@@ -543,6 +568,32 @@ fn sub_help_msg() {
         &x,
         "Help: `-`
 --> shell:0
+ | ---- Input Type: DateTime ----
+ | subtract durations from a datetime
+ | if a single datetime argument is given, the duration between the datetimes is returned
+ | -variadic-: more than one argument can be specified
+ | 
+ | Usage:
+ |  => - args..
+ | 
+ | Examples:
+ |  the time an hour ago
+ |  => now | - {\\ 1 | to-duration --hours}
+ | 
+ |  the time since the start of 2000
+ |  => now | - {\\ '2000-01-01' | to-datetime}
+ | 
+ | ---- Input Type: Duration ----
+ | subtract durations from one another
+ | -variadic-: more than one argument can be specified
+ | 
+ | Usage:
+ |  => - args..
+ | 
+ | Examples:
+ |  subtract 30 minutes from an hour
+ |  => \\ 1 | to-duration --hours | - {\\ 30 | to-duration --mins}
+ | 
  | ---- Input Type: Number ----
  | subtract arguments from one another
  | -variadic-: more than one argument can be specified
@@ -570,3 +621,173 @@ fn sub_testing() {
     let x = process_w_num("let $x | \\ 1 | - 1 2 $x", defs);
     assert_eq!(x, Ok(Value::Num((-5).into()))); // 1 - 1 - 2 - 3
 }
+
+// ------ Maths ----------------------------------------------------------------
+#[test]
+fn pow_help_msg() {
+    let src = "pow --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `pow`
+--> shell:0
+ | ---- Input Type: Number ----
+ | raise a number to a power
+ | integers raised to non-negative integer powers are exact
+ | 
+ | Usage:
+ |  => pow exponent
+ | 
+ | Examples:
+ |  2 to the power of 10
+ |  => \\ 2 | pow 10
+ | 
+ |  the square root of 2
+ |  => \\ 2 | pow 0.5
+"
+    );
+}
+
+#[test]
+fn round_help_msg() {
+    let src = "round --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `round`
+--> shell:0
+ | ---- Input Type: Number ----
+ | round a number to a number of decimal places, defaulting to 0
+ | halfway values are rounded away from zero
+ | negative places round to the left of the decimal point
+ | 
+ | Usage:
+ |  => round [places]
+ | 
+ | Examples:
+ |  round to the nearest integer
+ |  => \\ 2.5 | round
+ | 
+ |  round to 2 decimal places
+ |  => \\ 3.14159 | round 2
+ | 
+ |  round to the nearest hundred
+ |  => \\ 1234 | round -2
+"
+    );
+}
+
+#[test]
+fn pow_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_nil(src, defs);
+
+    assert_eq!(t("\\ 2 | pow 10"), Ok(Value::Num(1024.into())));
+    assert_eq!(t("\\ -3 | pow 3"), Ok(Value::Num((-27).into())));
+    assert_eq!(t("\\ 2 | pow -1"), Ok(Value::Num(0.5.into())));
+    assert_eq!(t("\\ 4 | pow 0.5"), Ok(Value::Num(2.into())));
+    assert_eq!(t("\\ 1.5 | pow 2"), Ok(Value::Num(2.25.into())));
+    // integers stay exact
+    assert_eq!(
+        t("\\ 2 | pow 100 | to-str"),
+        Ok(Value::Str("1267650600228229401496703205376".into()))
+    );
+}
+
+#[test]
+fn exp_and_log_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_nil(src, defs);
+
+    assert_eq!(t("\\ 0 | exp"), Ok(Value::Num(1.into())));
+    assert_eq!(t("e | ln | - 1 | abs | < 1e-10"), Ok(Value::Bool(true)));
+    assert_eq!(
+        t("\\ 2 | exp | ln | - 2 | abs | < 1e-10"),
+        Ok(Value::Bool(true))
+    );
+    assert_eq!(t("\\ 1000 | log 10"), Ok(Value::Num(3.into())));
+    assert_eq!(t("\\ 8 | log 2"), Ok(Value::Num(3.into())));
+    assert_eq!(
+        t("\\ 81 | log 3 | - 4 | abs | < 1e-10"),
+        Ok(Value::Bool(true))
+    );
+    assert_eq!(t("\\ 0 | ln | is-finite"), Ok(Value::Bool(false)));
+    assert_eq!(t("\\ 3 | hypot 4"), Ok(Value::Num(5.into())));
+}
+
+#[test]
+fn rounding_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_nil(src, defs);
+
+    assert_eq!(t("\\ 2.5 | round"), Ok(Value::Num(3.into())));
+    assert_eq!(t("\\ -2.5 | round"), Ok(Value::Num((-3).into())));
+    assert_eq!(t("\\ 1.23456 | round 2"), Ok(Value::Num(1.23.into())));
+    assert_eq!(t("\\ 1234 | round -2"), Ok(Value::Num(1200.into())));
+    assert_eq!(t("\\ -1250 | round -2"), Ok(Value::Num((-1300).into())));
+    assert_eq!(t("\\ 1234.5 | round -2"), Ok(Value::Num(1200.into())));
+    assert_eq!(t("\\ 7 | round 2"), Ok(Value::Num(7.into())));
+
+    assert_eq!(t("\\ -3.7 | trunc"), Ok(Value::Num((-3).into())));
+    assert_eq!(t("\\ 3.7 | trunc"), Ok(Value::Num(3.into())));
+
+    assert_eq!(t("\\ -5 | sign"), Ok(Value::Num((-1).into())));
+    assert_eq!(t("\\ 0 | sign"), Ok(Value::Num(0.into())));
+    assert_eq!(t("\\ 2.5 | sign"), Ok(Value::Num(1.into())));
+
+    assert_eq!(t("\\ 1.2 | clamp 0 1"), Ok(Value::Num(1.into())));
+    assert_eq!(t("\\ -3 | clamp 0 10"), Ok(Value::Num(0.into())));
+    assert_eq!(t("\\ 5 | clamp 0 10"), Ok(Value::Num(5.into())));
+    assert_eq!(t("\\ 0.5 | clamp 0 1"), Ok(Value::Num(0.5.into())));
+}
+
+#[test]
+fn rounding_errs() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("\\ 3.5 | round 1.5", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: decimal places must be an integer
+--> shell:14
+ | \\ 3.5 | round 1.5
+ |               ^^^ this resolves to 1.5
+"
+    );
+
+    let x = process_w_nil("\\ 5 | clamp 10 0", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: clamp minimum is greater than the maximum
+--> shell:6
+ | \\ 5 | clamp 10 0
+ |       ^^^^^^^^^^ min is 10 and max is 0
+"
+    );
+}
+
+#[test]
+fn trig_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_nil(src, defs);
+    let close = |src: &str, x: f64| {
+        let src = format!("{src} | - {x} | abs | < 1e-10");
+        assert_eq!(t(&src), Ok(Value::Bool(true)), "{src}");
+    };
+
+    close("pi", std::f64::consts::PI);
+    close("e", std::f64::consts::E);
+    close("pi | / 2 | sin", 1.0);
+    close("pi | cos", -1.0);
+    close("pi | / 4 | tan", 1.0);
+    close("\\ 1 | asin | * 2", std::f64::consts::PI);
+    close("\\ -1 | acos", std::f64::consts::PI);
+    close("\\ 1 | atan | * 4", std::f64::consts::PI);
+    assert_eq!(t("\\ 2 | asin | is-finite"), Ok(Value::Bool(false)));
+}
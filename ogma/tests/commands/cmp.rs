@@ -22,6 +22,27 @@ fn cmp_help_test() {
  | Usage:
  |  => cmp rhs:Bool
  | 
+ | ---- Input Type: DateTime ----
+ | compare <rhs> to input.
+ | datetimes are compared by their instant in time, regardless of UTC offset
+ | 
+ | Usage:
+ |  => cmp rhs:DateTime
+ | 
+ | Examples:
+ |  compare now to the start of 2000
+ |  => now | cmp {\\ '2000-01-01' | to-datetime}
+ | 
+ | ---- Input Type: Duration ----
+ | compare <rhs> to input.
+ | 
+ | Usage:
+ |  => cmp rhs:Duration
+ | 
+ | Examples:
+ |  compare 90 minutes to 1 hour
+ |  => \\ 90 | to-duration --mins | cmp {\\ 1 | to-duration --hours}
+ | 
  | ---- Input Type: Nil ----
  | compare <rhs> to input. Nil types are always equal.
  | 
@@ -223,6 +244,23 @@ fn eq_help_test() {
  | Usage:
  |  => eq rhs:Bool
  | 
+ | ---- Input Type: DateTime ----
+ | returns if <rhs> is equal to input
+ | datetimes are equal if they are the same instant in time, regardless of UTC offset
+ | 
+ | Usage:
+ |  => eq rhs:DateTime
+ | 
+ | Examples:
+ |  the same instant in different offsets
+ |  => \ '2022-01-01T10:00:00+10:00' | to-datetime | eq {\ '2022-01-01' | to-datetime}
+ | 
+ | ---- Input Type: Duration ----
+ | returns if <rhs> is equal to input
+ | 
+ | Usage:
+ |  => eq rhs:Duration
+ | 
  | ---- Input Type: Nil ----
  | returns if <rhs> is equal to input
  | 
@@ -371,6 +409,20 @@ fn min_help_msg() {
  | 
  |  minimum of multiple args
  |  => min 1 2 3 4 5
+ | 
+ | ---- Input Type: Table ----
+ | return the minimum number in a column
+ | nil entries are ignored, other non-number entries are an error
+ | 
+ | Usage:
+ |  => min col-name
+ | 
+ | Flags:
+ |  --by: aggregate each `value` table of a grouped table, appending a column
+ | 
+ | Examples:
+ |  the smallest file size
+ |  => ls | min size
 "
     );
 }
@@ -396,6 +448,20 @@ fn max_help_msg() {
  | 
  |  maximum of multiple args
  |  => max 1 2 3 4 5
+ | 
+ | ---- Input Type: Table ----
+ | return the maximum number in a column
+ | nil entries are ignored, other non-number entries are an error
+ | 
+ | Usage:
+ |  => max col-name
+ | 
+ | Flags:
+ |  --by: aggregate each `value` table of a grouped table, appending a column
+ | 
+ | Examples:
+ |  the largest file size
+ |  => ls | max size
 "
     );
 }
@@ -0,0 +1,136 @@
+use super::*;
+
+// ------ To DateTime ----------------------------------------------------------
+#[test]
+fn to_datetime_and_back() {
+    let defs = &Definitions::new();
+    let x = process_w_nil(
+        "\\ '2022-03-04T12:30:00+10:00' | to-datetime | to-str",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Str("2022-03-04T12:30:00+10:00".into())));
+
+    let x = process_w_nil("\\ '2022-03-04' | to-datetime | to-str", defs);
+    assert_eq!(x, Ok(Value::Str("2022-03-04T00:00:00Z".into())));
+
+    let x = process_w_nil(
+        "\\ '04/03/2022' | to-datetime '[day]/[month]/[year]' | to-str '[year]-[month]-[day]'",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Str("2022-03-04".into())));
+}
+
+#[test]
+fn to_datetime_err() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ 'foo' | to-datetime", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: failed to parse `foo` as a datetime
+--> shell:10
+ | \\ 'foo' | to-datetime
+ |           ^^^^^^^^^^^ within this block
+--> help: expecting an ISO 8601 format such as `2022-03-04T12:30:00Z`
+          a custom format can be specified, for example `to-datetime '[day]/[month]/[year]'`
+"
+    );
+}
+
+#[test]
+fn datetime_components() {
+    let defs = &Definitions::new();
+    let dt = "\\ '2022-03-04T12:30:45Z' | to-datetime";
+    let x = process_w_nil(&format!("{dt} | year"), defs);
+    assert_eq!(x, Ok(Value::Num(2022.into())));
+    let x = process_w_nil(&format!("{dt} | month"), defs);
+    assert_eq!(x, Ok(Value::Num(3.into())));
+    let x = process_w_nil(&format!("{dt} | day"), defs);
+    assert_eq!(x, Ok(Value::Num(4.into())));
+    let x = process_w_nil(&format!("{dt} | hour"), defs);
+    assert_eq!(x, Ok(Value::Num(12.into())));
+    let x = process_w_nil(&format!("{dt} | minute"), defs);
+    assert_eq!(x, Ok(Value::Num(30.into())));
+    let x = process_w_nil(&format!("{dt} | second"), defs);
+    assert_eq!(x, Ok(Value::Num(45.into())));
+    let x = process_w_nil(&format!("{dt} | weekday"), defs);
+    assert_eq!(x, Ok(Value::Num(5.into())));
+}
+
+#[test]
+fn ls_modified_is_datetime() {
+    let defs = &Definitions::new();
+    let x = process_w_nil(
+        "ls ls-test | nth 0 {get:DateTime modified | year | > 2000}",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Bool(true)));
+}
+
+// ------ Duration -------------------------------------------------------------
+#[test]
+fn to_duration_and_back() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ 5400 | to-duration | to-str", defs);
+    assert_eq!(x, Ok(Value::Str("1h 30m".into())));
+
+    let x = process_w_nil("\\ 2 | to-duration --days | to-num --hours", defs);
+    assert_eq!(x, Ok(Value::Num(48.into())));
+
+    let x = process_w_nil("\\ 1500 | to-duration --ms | to-str", defs);
+    assert_eq!(x, Ok(Value::Str("1.5s".into())));
+}
+
+// ------ Arithmetic -----------------------------------------------------------
+#[test]
+fn datetime_arithmetic() {
+    let defs = &Definitions::new();
+    let x = process_w_nil(
+        "\\ '2022-03-04' | to-datetime | + {\\ 1 | to-duration --days} | to-str",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Str("2022-03-05T00:00:00Z".into())));
+
+    let x = process_w_nil(
+        "\\ '2022-03-04' | to-datetime | - {\\ 1 | to-duration --hours} | to-str",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Str("2022-03-03T23:00:00Z".into())));
+
+    let x = process_w_nil(
+        "\\ '2022-03-04' | to-datetime | let $a | \\ '2022-03-05T06:00:00Z' | to-datetime | - $a | to-num --hours",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Num(30.into())));
+
+    let x = process_w_nil(
+        "\\ 90 | to-duration --mins | + {\\ 30 | to-duration} | to-str",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Str("1h 30m 30s".into())));
+}
+
+// ------ Cmp ------------------------------------------------------------------
+#[test]
+fn datetime_cmp() {
+    let defs = &Definitions::new();
+    let x = process_w_nil(
+        "\\ '2022-03-04' | to-datetime | < {\\ '2022-03-05' | to-datetime}",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Bool(true)));
+
+    let x = process_w_nil(
+        "\\ '2022-01-01T10:00:00+10:00' | to-datetime | = {\\ '2022-01-01' | to-datetime}",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Bool(true)));
+
+    let x = process_w_nil(
+        "\\ 90 | to-duration --mins | > {\\ 1 | to-duration --hours}",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Bool(true)));
+}
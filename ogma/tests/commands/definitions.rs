@@ -18,7 +18,7 @@ fn defs_help_msg() {
  | def has specialised syntax which takes variable params: ( )
  | 
  | Usage:
- |  => def name [Ty] ([param1[:Ty|Expr]] [param2[:Ty|Expr]] ...) { expr }
+ |  => def name [<T: Trait ...>] [Ty] ([param1[:Ty|Expr][=default]] [--flag] ... [rest..[:Ty]]) { expr }
  | 
  | Flags:
  |  --list: list the definitions as a table
@@ -34,6 +34,18 @@ fn defs_help_msg() {
  | 
  |  use types to better define use
  |  => def sum Table (seed:Num value:Expr) { fold $seed + $acc $value }
+ | 
+ |  parameters can have default values, and flags are read as booleans
+ |  => def add Num (x:Num=1 --neg) { if $neg { - $x } { + $x } }
+ | 
+ |  rest parameters take the remaining arguments and can be splatted
+ |  => def keep Table (cols..:Str) { pick --trail $cols.. }
+ | 
+ |  type parameters can be bound by traits, see `def-trait --help`
+ |  => def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }
+ | 
+ |  recursive commands use `def-rec` and annotate the output type
+ |  => def-rec fact:Num Num () { if {<= 1} 1 { * {- 1 | fact} } }
 "
     );
 }
@@ -114,21 +126,21 @@ fn list_defs() {
 
         assert_eq!(
             s,
-            "┌────────────────┬─────────────┬────────┬──────────┬──────┬────────────────────────────┐
-│ name           ┆ category    ┆ input  ┆ location ┆ line ┆ code                       │
-╞════════════════╪═════════════╪════════╪══════════╪══════╪════════════════════════════╡
-│ !=             ┆ cmp         ┆ -      ┆ <ogma>   ┆ -    ┆ != (rhs) { eq $rhs | not } │
-│ *              ┆ arithmetic  ┆ Number ┆ <ogma>   ┆ -    ┆ -                          │
-│ +              ┆ arithmetic  ┆ Number ┆ <ogma>   ┆ -    ┆ -                          │
-│ +              ┆ arithmetic  ┆ String ┆ <ogma>   ┆ -    ┆ -                          │
-│ +              ┆ arithmetic  ┆ Table  ┆ <ogma>   ┆ -    ┆ -                          │
-│ 85 rows elided ┆ ...         ┆ ...    ┆ ...      ┆ ...  ┆ ...                        │
-│ to-str         ┆ pipeline    ┆ Number ┆ <ogma>   ┆ -    ┆ -                          │
-│ to-str         ┆ pipeline    ┆ String ┆ <ogma>   ┆ -    ┆ -                          │
-│ typify         ┆ diagnostics ┆ -      ┆ <ogma>   ┆ -    ┆ -                          │
-│ ×              ┆ arithmetic  ┆ Number ┆ <ogma>   ┆ -    ┆ -                          │
-│ ÷              ┆ arithmetic  ┆ Number ┆ <ogma>   ┆ -    ┆ -                          │
-└────────────────┴─────────────┴────────┴──────────┴──────┴────────────────────────────┘
+            "┌─────────────────┬────────────┬──────────┬──────────┬──────┬────────────────────────────┐
+│ name            ┆ category   ┆ input    ┆ location ┆ line ┆ code                       │
+╞═════════════════╪════════════╪══════════╪══════════╪══════╪════════════════════════════╡
+│ !=              ┆ cmp        ┆ -        ┆ <ogma>   ┆ -    ┆ != (rhs) { eq $rhs | not } │
+│ *               ┆ arithmetic ┆ Number   ┆ <ogma>   ┆ -    ┆ -                          │
+│ +               ┆ arithmetic ┆ DateTime ┆ <ogma>   ┆ -    ┆ -                          │
+│ +               ┆ arithmetic ┆ Duration ┆ <ogma>   ┆ -    ┆ -                          │
+│ +               ┆ arithmetic ┆ Number   ┆ <ogma>   ┆ -    ┆ -                          │
+│ 175 rows elided ┆ ...        ┆ ...      ┆ ...      ┆ ...  ┆ ...                        │
+│ var             ┆ arithmetic ┆ Table    ┆ <ogma>   ┆ -    ┆ -                          │
+│ weekday         ┆ pipeline   ┆ DateTime ┆ <ogma>   ┆ -    ┆ -                          │
+│ year            ┆ pipeline   ┆ DateTime ┆ <ogma>   ┆ -    ┆ -                          │
+│ ×               ┆ arithmetic ┆ Number   ┆ <ogma>   ┆ -    ┆ -                          │
+│ ÷               ┆ arithmetic ┆ Number   ┆ <ogma>   ┆ -    ┆ -                          │
+└─────────────────┴────────────┴──────────┴──────────┴──────┴────────────────────────────┘
 "
         );
     } else {
@@ -402,6 +414,350 @@ fn passing_invalid_arg_to_def() {
     );
 }
 
+#[test]
+fn default_params() {
+    let defs = &mut Definitions::new();
+    process_definition(
+        "def add Num (x:Num=1 y=2) { + $x | + $y }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+
+    let x = process_w_num("add", defs);
+    assert_eq!(x, Ok(Value::Num(6.into())));
+    let x = process_w_num("add 10", defs);
+    assert_eq!(x, Ok(Value::Num(15.into())));
+    let x = process_w_num("add 10 20", defs);
+    assert_eq!(x, Ok(Value::Num(33.into())));
+
+    // defaults are evaluated as if supplied at the callsite
+    process_definition(
+        "def add-to Num (x:Num=#i) { + $x }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+    let x = process_w_num("add-to", defs);
+    assert_eq!(x, Ok(Value::Num(6.into())));
+    let x = process_w_num("add-to 1", defs);
+    assert_eq!(x, Ok(Value::Num(4.into())));
+
+    // expression defaults
+    process_definition(
+        "def adj Num (f:Expr={ * 2 }) { + $f }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+    let x = process_w_num("adj", defs);
+    assert_eq!(x, Ok(Value::Num(9.into())));
+    let x = process_w_num("adj { + 1 }", defs);
+    assert_eq!(x, Ok(Value::Num(7.into())));
+}
+
+#[test]
+fn flag_params() {
+    let defs = &mut Definitions::new();
+    process_definition(
+        "def pick-one (a b=2 --last) { if $last $b $a }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+
+    let x = process_w_nil("pick-one 1", defs);
+    assert_eq!(x, Ok(Value::Num(1.into())));
+    let x = process_w_nil("pick-one 1 --last", defs);
+    assert_eq!(x, Ok(Value::Num(2.into())));
+    let x = process_w_nil("pick-one --last 1 3", defs);
+    assert_eq!(x, Ok(Value::Num(3.into())));
+
+    let x = print_help("pick-one --help", defs);
+    assert_eq!(
+        &x,
+        "Help: `pick-one`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | user defined implementation in shell
+ | `def pick-one (a b=2 --last) { if $last $b $a }`
+ | 
+ | Usage:
+ |  => pick-one a [b=2] [--last]
+"
+    );
+
+    let x = process_w_nil("pick-one 1 --first", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: not expecting flags: `first`
+--> shell:13
+ | pick-one 1 --first
+ |              ^^^^^ flag not supported
+--> help: try using the `--help` flag to view requirements
+"
+    );
+
+    let x = process_w_nil("pick-one --last", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: expecting more than 0 arguments
+--> shell:0
+ | pick-one --last
+ | ^^^^^^^^^^^^^^^ expecting additional argument(s)
+--> help: try using the `--help` flag to view requirements.
+          `pick-one` is defined to accept parameters `(a b=2 --last)`
+"
+    );
+}
+
+#[test]
+fn rest_params() {
+    let defs = &mut Definitions::new();
+    let def = |src, defs: &mut Definitions| {
+        process_definition(src, Location::Shell, None, defs).unwrap();
+    };
+
+    def("def sum Num (xs..:Num) { + $xs.. }", defs);
+    let x = process_w_num("sum 1 2 3", defs);
+    assert_eq!(x, Ok(Value::Num(9.into())));
+    let x = process_w_num("sum { * 2 }", defs);
+    assert_eq!(x, Ok(Value::Num(9.into())));
+
+    // splats can be forwarded through other defs
+    def("def sum-twice Num (ys..:Num) { sum $ys.. $ys.. }", defs);
+    let x = process_w_num("sum-twice 1 2", defs);
+    assert_eq!(x, Ok(Value::Num(9.into())));
+
+    // flags can follow the rest parameter
+    def(
+        "def sum-neg Num (xs..:Num --neg) { if $neg { - $xs.. } { + $xs.. } }",
+        defs,
+    );
+    let x = process_w_num("sum-neg 1 2", defs);
+    assert_eq!(x, Ok(Value::Num(6.into())));
+    let x = process_w_num("sum-neg --neg 1 2", defs);
+    assert_eq!(x, Ok(Value::Num(0.into())));
+
+    // the rest parameter can take no arguments
+    def("def fst (x xs..) { \\ $x }", defs);
+    let x = process_w_nil("fst 1", defs);
+    assert_eq!(x, Ok(Value::Num(1.into())));
+
+    // wrapping a variadic intrinsic
+    def(
+        "def cols Table (first names..) { pick $first $names.. }",
+        defs,
+    );
+    let x = process_w_table("cols snd first", defs);
+    check_is_table(
+        x,
+        vec![
+            vec![o("snd"), o("first")],
+            vec![n(3), n(0)],
+            vec![n(20), n(1)],
+            vec![n(100), n(-30)],
+        ],
+    );
+
+    // the rest parameter is bound to a tuple
+    def("def snd (xs..) { \\ $xs | get t1 }", defs);
+    let x = process_w_nil("snd 1 'a' #t", defs);
+    assert_eq!(x, Ok(Value::Str("a".into())));
+
+    let x = print_help("sum-neg --help", defs);
+    assert_eq!(
+        &x,
+        "Help: `sum-neg`
+--> shell:0
+ | ---- Input Type: Number ----
+ | user defined implementation in shell
+ | `def sum-neg Num (xs..:Num --neg) { if $neg { - $xs.. } { + $xs.. } }`
+ | 
+ | Usage:
+ |  => sum-neg [xs..:Num] [--neg]
+"
+    );
+}
+
+#[test]
+fn rest_params_errs() {
+    let defs = &mut Definitions::new();
+    let def = |src, defs: &mut Definitions| {
+        process_definition(src, Location::Shell, None, defs).unwrap();
+    };
+
+    // each argument is type checked
+    def("def sum Num (xs..:Num) { + $xs.. }", defs);
+    assert!(process_w_num("sum 1 'a'", defs).is_err());
+
+    // including each splatted element
+    def("def add-all Num (xs..) { + $xs.. }", defs);
+    let x = process_w_num("add-all 1 'a'", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("expecting argument with output type `Number`, found `String`"));
+
+    def("def bad Num (x) { + $x.. }", defs);
+    let x = process_w_num("bad 1", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.starts_with(
+        "Semantics Error: cannot splat `$x`, it is not a rest parameter
+--> shell:21
+ | def bad Num (x) { + $x.. }
+ |                      ^ splats must reference a rest parameter
+"
+    ));
+
+    let x = process_w_num("+ $x..", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("cannot splat `$x`, it is not a rest parameter"));
+
+    let x = process_definition("def foo (xs.. y) { \\ 1 }", Location::Shell, None, defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("a rest parameter must be the last parameter"));
+
+    let x = process_definition("def foo (xs..:Expr) { \\ 1 }", Location::Shell, None, defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.contains("rest parameters cannot be expressions"));
+}
+
+#[test]
+fn def_trait_help_msg() {
+    let src = "def-trait --help";
+    let defs = &mut Definitions::new();
+    let x = process_definition(src, Location::Shell, None, defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Help: `def-trait`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | define a trait, a named set of commands that a type must implement
+ | a def's type parameters can be bound by traits, which are checked when the def is used
+ | 
+ | Usage:
+ |  => def-trait name { op1 ([param1 ...]) op2 ([param1 ...]) ... }
+ | 
+ | Examples:
+ |  define a trait for types that can be ordered
+ |  => def-trait Ord { cmp (rhs) }
+ | 
+ |  bound the input type of a def
+ |  => def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }
+"
+    );
+}
+
+#[test]
+fn trait_bounds() {
+    let defs = &mut Definitions::new();
+    let def = |src, defs: &mut Definitions| {
+        process_definition(src, Location::Shell, None, defs).unwrap();
+    };
+
+    def("def-trait Ord { cmp (rhs) }", defs);
+    def("def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }", defs);
+    let x = process_w_num("max 5", defs);
+    assert_eq!(x, Ok(Value::Num(5.into())));
+    let x = process_w_num("max 1", defs);
+    assert_eq!(x, Ok(Value::Num(3.into())));
+    let x = process_w_str("max 'World'", defs);
+    assert_eq!(x, Ok(Value::Str("World".into())));
+
+    // user types implement a trait by defining the ops
+    def("def-ty Point { x:Num y:Num }", defs);
+    def(
+        "def cmp Point (rhs) { get x | cmp { \\ $rhs | get x } }",
+        defs,
+    );
+    let x = process_w_nil("Point 1 2 | max {Point 3 4} | get y", defs);
+    assert_eq!(x, Ok(Value::Num(4.into())));
+
+    // bounds on parameters
+    def("def ord-of<T: Ord> (a:T b:T) { \\ $a | cmp $b }", defs);
+    let x = process_w_nil("ord-of {Point 5 0} {Point 3 4} | = Ord::Gt", defs);
+    assert_eq!(x, Ok(Value::Bool(true)));
+
+    // traits can be defined in files
+    let x = defs.add_from_str(
+        "def-trait Show { to-str () }
+
+def show<T: Show> T () { to-str }",
+        std::path::Path::new("traits.ogma"),
+    );
+    assert_eq!(x, Ok(2));
+    let x = process_w_num("show", defs);
+    assert_eq!(x, Ok(Value::Str("3".into())));
+}
+
+#[test]
+fn trait_bounds_errs() {
+    let defs = &mut Definitions::new();
+    let def = |src, defs: &mut Definitions| {
+        process_definition(src, Location::Shell, None, defs).unwrap();
+    };
+
+    // traits must be defined
+    let x = process_definition("def foo<T: Ord> T () { \\ 1 }", Location::Shell, None, defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: trait `Ord` not defined
+--> shell:11
+ | def foo<T: Ord> T () { \\ 1 }
+ |            ^^^ `Ord` not defined
+--> help: traits are defined with `def-trait`, such as `def-trait Ord { cmp (rhs) }`
+"
+    );
+
+    def("def-trait Ord { cmp (rhs) }", defs);
+    def("def-ty Point { x:Num y:Num }", defs);
+    def("def max<T: Ord> T (rhs:T) { if {< $rhs} $rhs #i }", defs);
+
+    // the missing ops are listed before the expression is compiled
+    let x = process_w_nil("Point 1 2 | max {Point 3 4}", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.starts_with("Semantics Error: type `Point` does not implement trait `Ord`"));
+    assert!(x.contains("^^^ `Point` does not implement `Ord`"));
+    assert!(x.contains("^^^ required by this bound"));
+    assert!(x.contains("--> help: `Point` is missing implementations for: `cmp (rhs)`"));
+
+    def("def ord-of<T: Ord> (a:T b:T) { \\ $a | cmp $b }", defs);
+    let x = process_w_nil("ord-of {Point 5 0} {Point 3 4}", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.starts_with("Semantics Error: type `Point` does not implement trait `Ord`"));
+
+    // each use of a type parameter must be the same type
+    let x = process_w_nil("ord-of 1 'a'", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.starts_with("Semantics Error: type parameter `T` is bound to different types"));
+    assert!(x.contains("`T` is bound to `Number` and `String`"));
+}
+
 #[test]
 fn not_defined_err_msg() {
     let defs = &mut Definitions::new();
@@ -489,3 +845,228 @@ fn variable_not_defined_in_def() {
 "#
     );
 }
+
+#[test]
+fn recursive_defs() {
+    let defs = &mut Definitions::new();
+    let mut def = |s| process_definition(s, Location::Shell, None, defs).unwrap();
+    def("def-rec fact:Num Num () { if {<= 1} 1 { * {- 1 | fact} } }");
+    def("def-rec pwr:Num (n:Num x:Num) { if {\\ $x | = 0} 1 { \\ $n | * { pwr $n {\\ $x | - 1} } } }");
+    def("def-rec fib:Num Num () { if {<= 1} #i { let $n | - 1 | fib | + {\\ $n | - 2 | fib} } }");
+
+    assert_eq!(
+        process_w_nil("\\ 5 | fact", defs),
+        Ok(Value::Num(120.into()))
+    );
+    assert_eq!(process_w_nil("\\ 1 | fact", defs), Ok(Value::Num(1.into())));
+    assert_eq!(process_w_nil("pwr 2 10", defs), Ok(Value::Num(1024.into())));
+    assert_eq!(process_w_nil("pwr 2 0", defs), Ok(Value::Num(1.into())));
+    assert_eq!(
+        process_w_nil("\\ 10 | fib", defs),
+        Ok(Value::Num(55.into()))
+    );
+
+    let x = process_w_nil("range 1 6 | append --'i!' { get i | fact }", defs);
+    check_is_table(
+        x,
+        vec![
+            vec![o("i"), o("i!")],
+            vec![n(1), n(1)],
+            vec![n(2), n(2)],
+            vec![n(3), n(6)],
+            vec![n(4), n(24)],
+            vec![n(5), n(120)],
+        ],
+    );
+}
+
+#[test]
+fn recursive_def_over_tree() {
+    let defs = &mut Definitions::new();
+    process_definition(
+        "def-rec headcount:Num Table (name:Str) { let $org
+    | filter manager --Str = $name
+    | fold 0 { + 1 | + { \\ $org | headcount $row.name:Str } } }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+
+    let org = "Table name manager
+    | append-row b a | append-row c a | append-row d b | append-row e d | append-row f c";
+    let x = process_w_nil(&format!("{org} | headcount a"), defs);
+    assert_eq!(x, Ok(Value::Num(5.into())));
+    let x = process_w_nil(&format!("{org} | headcount b"), defs);
+    assert_eq!(x, Ok(Value::Num(2.into())));
+    let x = process_w_nil(&format!("{org} | headcount e"), defs);
+    assert_eq!(x, Ok(Value::Num(0.into())));
+}
+
+#[test]
+fn recursion_limit_err() {
+    let defs = &mut Definitions::new();
+    defs.set_recursion_limit(5);
+    process_definition(
+        "def-rec forever:Num Num () { + 1 | forever }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+
+    let x = process_w_num("forever", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: recursion limit of 5 reached
+--> shell:35
+ | def-rec forever:Num Num () { + 1 | forever }
+ |                                    ^^^^^^^ `forever` exceeded the recursion limit
+--> help: check that the recursive definition has a base case which stops recursing
+"
+    );
+
+    // the limit is in terms of depth, so a shallow recursion succeeds
+    defs.set_recursion_limit(10);
+    process_definition(
+        "def-rec count-down:Num Num () { if {<= 0} 0 { - 1 | count-down } }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+    assert_eq!(
+        process_w_nil("\\ 9 | count-down", defs),
+        Ok(Value::Num(0.into()))
+    );
+    assert!(process_w_nil("\\ 10 | count-down", defs).is_err());
+}
+
+#[test]
+fn recursive_def_errs() {
+    let defs = &mut Definitions::new();
+
+    let x = process_definition(
+        "def-rec fact Num () { if {<= 1} 1 { * {- 1 | fact} } }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions require an output type annotation"));
+
+    let x = process_definition(
+        "def-rec foo:Num (x:Expr) { \\ 1 }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions do not support `Expr` parameters"));
+
+    let x = process_definition(
+        "def-rec foo:Num (x:Num=1) { \\ 1 }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions do not support default, flag, or rest parameters"));
+
+    let x = process_definition(
+        "def-rec foo:Num (xs..) { \\ 1 }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{x}");
+    assert!(x.contains("recursive definitions do not support default, flag, or rest parameters"));
+
+    process_definition(
+        "def-rec foo:Str Num () { + 1 }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+    let x = process_w_num("foo", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("expecting recursive definition with output type `String`, found `Number`"));
+}
+
+#[test]
+fn partitions_from_dir() {
+    let defs = &mut Definitions::new();
+    let n = defs
+        .add_from_dir(
+            "partitions-structure-tests/test06",
+            Some(Path::new("partitions-structure-tests/test06-plugins")),
+        )
+        .unwrap();
+    assert_eq!(n, 8);
+
+    // exported items are reachable with the partition path
+    let x = process_w_nil("app/hello", defs);
+    assert_eq!(x, Ok(Value::Str(Str::new("hello!!]"))));
+    let x = process_w_num("app/quad", defs);
+    assert_eq!(x, Ok(Value::Num(12.into())));
+    let x = process_w_num("app/loud", defs);
+    assert_eq!(x, Ok(Value::Num(31.into())));
+
+    // partition items are qualified
+    let x = process_w_str("shout", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("operation `shout` not defined"));
+
+    let x = process_w_str("strings/bang", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("operation `/strings/bang` is not exported from its partition"));
+
+    // clearing removes the partitions
+    defs.clear(false);
+    let x = process_w_nil("app/hello", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("operation `app/hello` not defined"));
+}
+
+#[test]
+fn compiled_expressions_cache() {
+    let defs = &mut Definitions::new();
+    let def = |code: &str, defs: &mut Definitions| {
+        process_definition(code, Location::Shell, None, defs).unwrap();
+    };
+
+    def("def foo () { \\ 1 }", defs);
+    assert_eq!(process_w_nil("foo", defs), Ok(Value::Num(1.into())));
+    assert_eq!(process_w_nil("foo", defs), Ok(Value::Num(1.into())));
+
+    // the input type keys the compilation
+    assert_eq!(process_w_num("+ 1", defs), Ok(Value::Num(4.into())));
+    assert!(process_w_nil("+ 1", defs).is_err());
+
+    // redefining recompiles
+    def("def foo () { \\ 2 }", defs);
+    assert_eq!(process_w_nil("foo", defs), Ok(Value::Num(2.into())));
+
+    // clearing recompiles
+    defs.clear(false);
+    assert!(process_w_nil("foo", defs).is_err());
+
+    // clones which change separately do not share compilations
+    def("def foo () { \\ 3 }", defs);
+    let other = &mut defs.clone();
+    assert_eq!(process_w_nil("foo", other), Ok(Value::Num(3.into())));
+    def("def foo () { \\ 4 }", other);
+    def("def foo () { \\ 5 }", defs);
+    assert_eq!(process_w_nil("foo", other), Ok(Value::Num(4.into())));
+    assert_eq!(process_w_nil("foo", defs), Ok(Value::Num(5.into())));
+}
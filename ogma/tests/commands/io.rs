@@ -101,3 +101,379 @@ fn ls_err() {
         );
     }
 }
+
+// ------ Open -----------------------------------------------------------------
+fn fruit_table() -> Vec<Vec<Entry<Value>>> {
+    vec![
+        vec![o("name"), o("qty"), o("note")],
+        vec![o("apple"), n(3), o("crisp, red")],
+        vec![o("pear"), n(10), Nil],
+    ]
+}
+
+#[test]
+fn open_fmt_by_extension() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("open fmt-test/fruit.tsv", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open fmt-test/fruit.psv", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open fmt-test/fruit.json", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open fmt-test/fruit.ndjson", defs);
+    check_is_table(x, fruit_table());
+}
+
+#[test]
+fn open_fmt_flag_override() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("open --tsv fmt-test/fruit-tsv.txt", defs);
+    check_is_table(x, fruit_table());
+
+    // flag order does not matter
+    let x = process_w_nil("open fmt-test/fruit-tsv.txt --tsv", defs);
+    check_is_table(x, fruit_table());
+
+    // cached by format, csv does not split on tabs
+    let x = process_w_nil("open --csv fmt-test/fruit-tsv.txt", defs);
+    assert!(
+        matches!(x, Ok(Value::Tab(t)) if t.row(0).unwrap().next() == Some(&o("name\tqty\tnote")))
+    );
+
+    let x = process_w_nil("open --json fmt-test/fruit.tsv", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.starts_with("Evaluation Error: failed to parse `fmt-test/fruit.tsv` as json: "));
+
+    let x = process_w_nil("open --Str --json fmt-test/fruit.json", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: not expecting flags: `json`
+--> shell:13
+ | open --Str --json fmt-test/fruit.json
+ |              ^^^^ flag not supported
+--> help: try using the `--help` flag to view requirements
+"
+    );
+}
+
+#[test]
+fn open_infer_types() {
+    let defs = &Definitions::new();
+    let b = |b| Entry::Obj(Value::Bool(b));
+
+    let x = process_w_nil("open fmt-test/sales.csv", defs);
+    check_is_table(
+        x,
+        vec![
+            vec![o("item"), o("price"), o("discount"), o("active")],
+            vec![o("widget"), o("$1,234.50"), o("10%"), o("true")],
+            vec![o("gadget"), o("$5"), Nil, o("false")],
+            vec![o("gizmo"), o("(12)"), o("2.5%"), o("yes")],
+        ],
+    );
+
+    let x = process_w_nil("open --infer fmt-test/sales.csv", defs);
+    check_is_table(
+        x,
+        vec![
+            vec![o("item"), o("price"), o("discount"), o("active")],
+            vec![o("widget"), n(1234.5), n(0.1), b(true)],
+            vec![o("gadget"), n(5), Nil, b(false)],
+            vec![o("gizmo"), n(-12), n(0.025), b(true)],
+        ],
+    );
+
+    // already typed columns are unchanged
+    let x = process_w_nil("open --infer fmt-test/fruit.tsv", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open --Str --infer fmt-test/sales.csv", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: not expecting flags: `infer`
+--> shell:13
+ | open --Str --infer fmt-test/sales.csv
+ |              ^^^^^ flag not supported
+--> help: try using the `--help` flag to view requirements
+"
+    );
+}
+
+// ------ Save -----------------------------------------------------------------
+#[test]
+fn save_fmt_by_extension_round_trip() {
+    let defs = &Definitions::new();
+
+    for ext in ["csv", "tsv", "psv", "json", "ndjson"] {
+        let x = process_w_nil(
+            &format!(
+                "open fmt-test/fruit.json | save target/save-test/fruit.{}",
+                ext
+            ),
+            defs,
+        );
+        check_is_table(x, fruit_table());
+
+        let x = process_w_nil(&format!("open target/save-test/fruit.{}", ext), defs);
+        check_is_table(x, fruit_table());
+    }
+
+    let s = std::fs::read_to_string("target/save-test/fruit.csv").unwrap();
+    assert_eq!(&s, "name,qty,note\napple,3.0,\"crisp, red\"\npear,10.0,");
+
+    let s = std::fs::read_to_string("target/save-test/fruit.json").unwrap();
+    assert_eq!(
+        &s,
+        r#"[
+{"name":"apple","qty":3,"note":"crisp, red"},
+{"name":"pear","qty":10,"note":null}
+]"#
+    );
+}
+
+#[test]
+fn save_fmt_md_and_flag() {
+    let defs = &Definitions::new();
+
+    process_w_nil(
+        "open fmt-test/fruit.json | save target/save-test/fruit.md",
+        defs,
+    )
+    .unwrap();
+    let s = std::fs::read_to_string("target/save-test/fruit.md").unwrap();
+    assert_eq!(
+        &s,
+        "| name | qty | note |
+| --- | --- | --- |
+| apple | 3.0 | crisp, red |
+| pear | 10.0 |  |
+"
+    );
+
+    // flag overrides extension
+    process_w_nil(
+        "open fmt-test/fruit.json | save --ndjson target/save-test/fruit-nd.txt",
+        defs,
+    )
+    .unwrap();
+    let s = std::fs::read_to_string("target/save-test/fruit-nd.txt").unwrap();
+    assert_eq!(
+        &s,
+        r#"{"name":"apple","qty":3,"note":"crisp, red"}
+{"name":"pear","qty":10,"note":null}
+"#
+    );
+}
+
+#[test]
+fn open_md_as_csv() {
+    let defs = &Definitions::new();
+
+    // markdown tables are only saved, a `.md` file is opened as csv
+    process_w_nil(
+        "open fmt-test/fruit.json | save --csv target/save-test/fruit-csv.md",
+        defs,
+    )
+    .unwrap();
+    let x = process_w_nil("open target/save-test/fruit-csv.md | len", defs);
+    assert_eq!(x, Ok(Value::Num(2.into())));
+
+    let x = process_w_nil("open --md target/save-test/fruit-csv.md", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert!(x.starts_with("Semantics Error: not expecting flags: `md`"));
+}
+
+#[test]
+fn save_json_ogma_data() {
+    let defs = &mut Definitions::new();
+    process_definition("def-ty Point { x:Num y:Num }", Location::Shell, None, defs).unwrap();
+
+    process_w_nil("Point 1 2.5 | save target/save-test/point.json", defs).unwrap();
+    let s = std::fs::read_to_string("target/save-test/point.json").unwrap();
+    assert_eq!(&s, r#"{"x":1,"y":2.5}"#);
+
+    process_w_nil("Tuple 'a' #t | save target/save-test/tuple.json", defs).unwrap();
+    let s = std::fs::read_to_string("target/save-test/tuple.json").unwrap();
+    assert_eq!(&s, r#"{"t0":"a","t1":true}"#);
+
+    process_w_nil("\\ 1 | cmp 2 | save target/save-test/ord.json", defs).unwrap();
+    let s = std::fs::read_to_string("target/save-test/ord.json").unwrap();
+    assert_eq!(&s, r#""Lt""#);
+
+    // within a table
+    process_w_nil(
+        "Table a | append-row {Point 3 4} | save target/save-test/points.ndjson",
+        defs,
+    )
+    .unwrap();
+    let s = std::fs::read_to_string("target/save-test/points.ndjson").unwrap();
+    assert_eq!(&s, "{\"a\":{\"x\":3,\"y\":4}}\n");
+
+    // fields keep their declared order
+    process_definition(
+        "def-ty Item { name:Str qty:Num code:Str avail:Bool }",
+        Location::Shell,
+        None,
+        defs,
+    )
+    .unwrap();
+    process_w_nil(
+        "Item 'apple' 3 'A1' #t | save target/save-test/item.json",
+        defs,
+    )
+    .unwrap();
+    let s = std::fs::read_to_string("target/save-test/item.json").unwrap();
+    assert_eq!(&s, r#"{"name":"apple","qty":3,"code":"A1","avail":true}"#);
+}
+
+// ------ Streams --------------------------------------------------------------
+#[test]
+fn stream_collect() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("open --Stream fmt-test/fruit.tsv | collect", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open --Stream fmt-test/fruit.ndjson | collect", defs);
+    check_is_table(x, fruit_table());
+
+    let x = process_w_nil("open --Stream tests/diamonds.csv | collect | len", defs);
+    assert_eq!(x, Ok(Value::Num(53940.into())));
+}
+
+#[test]
+fn stream_matches_table() {
+    let defs = &Definitions::new();
+
+    // these cross chunk boundaries
+    for expr in [
+        "filter carat > 1",
+        "map price * 2",
+        "append --ppc / #i.price #i.carat",
+        "take 10005",
+        "skip 10001 | take 2",
+        "skip 53939",
+        // prev reaches rows in earlier chunks
+        "append { prev 1 | len } --p",
+        "filter { prev 1 | len | = 0 }",
+        "map carat { \\ $row | prev 1 | len }",
+    ] {
+        let t = process_w_nil(&format!("open tests/diamonds.csv | {}", expr), defs).unwrap();
+        let s = process_w_nil(
+            &format!("open --Stream tests/diamonds.csv | {} | collect", expr),
+            defs,
+        )
+        .unwrap();
+        assert_eq!(t, s, "{}", expr);
+    }
+
+    let x = process_w_nil(
+        "open --Stream tests/diamonds.csv | fold 0 + $row.price",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Num(212135217.into())));
+    let x = process_w_nil(
+        "open --Stream tests/diamonds.csv | reduce 0 { + $row.price } { + $rhs }",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Num(212135217.into())));
+    let x = process_w_nil(
+        "open --Stream tests/diamonds.csv | fold 0 { + { \\ $row | prev 1 | len } }",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Num(53939.into())));
+}
+
+#[test]
+fn stream_materialises() {
+    let defs = &Definitions::new();
+
+    // sort is only implemented for tables
+    let t = process_w_nil("open tests/diamonds.csv | sort price | take 3", defs).unwrap();
+    let s = process_w_nil(
+        "open --Stream tests/diamonds.csv | filter price > 0 | sort price | take 3",
+        defs,
+    )
+    .unwrap();
+    assert!(matches!(&s, Value::Tab(_)));
+    assert_eq!(t, s);
+}
+
+#[test]
+fn stream_save() {
+    let defs = &Definitions::new();
+
+    for ext in ["csv", "json", "ndjson"] {
+        process_w_nil(
+            &format!(
+                "open --Stream fmt-test/fruit.tsv | save target/save-test/fruit-stream.{}",
+                ext
+            ),
+            defs,
+        )
+        .unwrap();
+        process_w_nil(
+            &format!(
+                "open fmt-test/fruit.tsv | save target/save-test/fruit-tab.{}",
+                ext
+            ),
+            defs,
+        )
+        .unwrap();
+
+        let s = std::fs::read_to_string(format!("target/save-test/fruit-stream.{}", ext));
+        let t = std::fs::read_to_string(format!("target/save-test/fruit-tab.{}", ext));
+        assert_eq!(s.unwrap(), t.unwrap(), "{}", ext);
+    }
+
+    process_w_nil(
+        "open --Stream tests/diamonds.csv | filter cut --Str = Fair | save target/save-test/fair.csv",
+        defs,
+    )
+    .unwrap();
+    let x = process_w_nil("open target/save-test/fair.csv | len", defs);
+    assert_eq!(x, Ok(Value::Num(1610.into())));
+}
+
+#[test]
+fn stream_errs() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("open --Stream fmt-test/fruit.json", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: json tables cannot be streamed
+--> shell:0
+ | open --Stream fmt-test/fruit.json
+ | ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^ within this block
+--> help: streams support the csv, tsv, psv, and ndjson formats
+"
+    );
+
+    let x = process_w_nil(
+        "open --Stream fmt-test/fruit.tsv | filter --skip-errors qty > 3",
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{}", x);
+    assert!(x.starts_with("Semantics Error: not expecting flags: `skip-errors`"));
+}
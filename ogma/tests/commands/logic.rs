@@ -135,6 +135,193 @@ fn if_err_testing() {
     );
 }
 
+// ------ Match ----------------------------------------------------------------
+#[test]
+fn match_help_msg() {
+    let src = "match --help";
+    let x = print_help(src, &Definitions::new());
+    println!("{x}");
+    assert!(x.starts_with(
+        "Help: `match`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | evaluate the expression of the arm matching the variant of a sum type
+ | each arm is a variant name followed by an expression
+ | the variant's fields are bound as variables within the arm's expression
+ | input is carried through to each of the expressions
+ | every variant must be matched, use `_` as a catch-all arm for the remaining variants
+ | all arm expressions must evaluate to the same type
+ | 
+ | Usage:
+ |  => match variant-1 expr-1 [variant-2] [expr-2] ...
+ | 
+ | Examples:
+ |  describe the ordering of 3 compared to 5
+ |  => \\ 3 | cmp 5 | match Lt 'less' Eq 'equal' Gt 'greater'
+ | 
+ |  use a catch-all arm
+ |  => \\ 3 | cmp 5 | match Lt 'less' _ 'not less'
+ | 
+ |  bind the fields of a variant
+ |  => Weather::Rainy 5 | match Sunny 0 Rainy $mm
+"
+    ));
+}
+
+fn weather_defs() -> Definitions {
+    let mut defs = Definitions::new();
+    process_definition(
+        "def-ty Weather :: Sunny | Rainy { mm:Num } | Cyclonic { name:Str speed:Num }",
+        Location::Shell,
+        None,
+        &mut defs,
+    )
+    .unwrap();
+    defs
+}
+
+#[test]
+fn match_testing() {
+    let defs = &weather_defs();
+    let x = |src| process_w_nil(src, defs);
+    let st = |s: &str| Ok(Value::Str(s.into()));
+
+    assert_eq!(
+        process_w_num("cmp 5 | match Lt 'less' Eq 'equal' Gt 'greater'", defs),
+        st("less")
+    );
+    assert_eq!(
+        process_w_num("cmp 3 | match Lt 'less' Eq 'equal' Gt 'greater'", defs),
+        st("equal")
+    );
+    assert_eq!(
+        process_w_num("cmp 1 | match Lt 'less' _ 'not less'", defs),
+        st("not less")
+    );
+
+    let src = "match Sunny 0 Rainy $mm Cyclonic { \\ $speed | * 2 }";
+    assert_eq!(
+        x(&format!("Weather::Sunny | {src}")),
+        Ok(Value::Num(0.into()))
+    );
+    assert_eq!(
+        x(&format!("Weather::Rainy 12 | {src}")),
+        Ok(Value::Num(12.into()))
+    );
+    assert_eq!(
+        x(&format!("Weather::Cyclonic 'Marcus' 150 | {src}")),
+        Ok(Value::Num(300.into()))
+    );
+
+    // arm order does not matter, and the input is passed through
+    assert_eq!(
+        x("Weather::Cyclonic 'Marcus' 150 | match _ 'calm' Cyclonic $name"),
+        st("Marcus")
+    );
+    assert_eq!(
+        x("Weather::Rainy 3 | match _ { eq Weather::Sunny } Rainy { = 3 }"),
+        Ok(Value::Bool(true))
+    );
+    // field variables shadow outer variables
+    assert_eq!(
+        x("\\ 5 | let $mm | Weather::Rainy 3 | match Rainy $mm _ $mm"),
+        Ok(Value::Num(3.into()))
+    );
+    assert_eq!(
+        x("\\ 5 | let $mm | Weather::Sunny | match Rainy $mm _ $mm"),
+        Ok(Value::Num(5.into()))
+    );
+}
+
+#[test]
+fn match_err_testing() {
+    let defs = &weather_defs();
+
+    let x = process_w_nil("Weather::Sunny | match Sunny 0 Rainy $mm", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: match on `Weather` does not cover every variant
+--> shell:17
+ | Weather::Sunny | match Sunny 0 Rainy $mm
+ |                  ^^^^^^^^^^^^^^^^^^^^^^^ missing arms for: Cyclonic
+--> help: add an arm for each missing variant, or use `_` as a catch-all
+"
+    );
+
+    let x = process_w_nil("Weather::Sunny | match Sunny 0 Snowy 1 _ 2", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: `Weather` does not contain variant `Snowy`
+--> shell:31
+ | Weather::Sunny | match Sunny 0 Snowy 1 _ 2
+ |                                ^^^^^ `Snowy` not found
+--> help: `Weather` has the following variants: Sunny, Rainy, Cyclonic
+"
+    );
+
+    let x = process_w_nil("Weather::Sunny | match Sunny 0 Rainy 'wet' _ 2", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: match arms do not have matching output types
+--> shell:38
+ | Weather::Sunny | match Sunny 0 Rainy 'wet' _ 2
+ |                                       ^^^ this arm has a different output type
+--> help: branching impls require consistent output types
+"
+    );
+
+    let x = process_w_nil("Weather::Sunny | match Sunny 0 _ 1 Sunny 2", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: `Sunny` is matched more than once
+--> shell:35
+ | Weather::Sunny | match Sunny 0 _ 1 Sunny 2
+ |                                    ^^^^^ duplicate arm
+--> help: each variant can only have one arm
+"
+    );
+
+    let x = process_w_nil("Weather::Sunny | match Sunny 0 Rainy", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: expecting more than 3 arguments
+--> shell:17
+ | Weather::Sunny | match Sunny 0 Rainy
+ |                  ^^^^^^^^^^^^^^^^^^^ expecting additional argument(s)
+--> help: `match` requires pairs of variant names and expressions
+"
+    );
+
+    let x = process_w_num("match Sunny 0", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: `match` does not support `Number` input data
+--> shell:0
+ | match Sunny 0
+ | ^^^^^
+--> help: `match` works on the variants of sum types
+"
+    );
+}
+
 // ------ Or -------------------------------------------------------------------
 #[test]
 fn or_help_msg() {
@@ -177,3 +364,80 @@ fn or_test() {
     ];
     check_is_table(x, exp);
 }
+
+// ------ Try ------------------------------------------------------------------
+#[test]
+fn try_help_msg() {
+    let src = "try --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `try`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | evaluate an expression, evaluating the fallback if the expression errors
+ | input is carried through to the expression
+ | the fallback's input is the `Error`, which has the fields `cat`, `desc`, and `trace`
+ | `expr` and `fallback` must evaluate to the same type
+ | 
+ | Usage:
+ |  => try expr fallback
+ | 
+ | Examples:
+ |  return -1 if a string is not a number
+ |  => \\ 'abc' | try {to-num} -1
+ | 
+ |  return the error description
+ |  => \\ 'abc' | try {to-num | to-str} {get desc}
+"
+    );
+}
+
+#[test]
+fn try_testing() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ 'abc' | try {to-num} -1", defs);
+    assert_eq!(x, Ok(Value::Num((-1).into())));
+    let x = process_w_nil("\\ '12' | try {to-num} -1", defs);
+    assert_eq!(x, Ok(Value::Num(12.into())));
+    let x = process_w_nil("\\ 'abc' | try {to-num | to-str} {get desc}", defs);
+    assert_eq!(
+        x,
+        Ok(Value::Str("failed to parse `abc` as a number".into()))
+    );
+    let x = process_w_nil("\\ 'abc' | try {to-num | to-str} {get cat}", defs);
+    assert_eq!(x, Ok(Value::Str("Evaluation Error".into())));
+    let x = process_w_nil("\\ 'abc' | try {to-num | to-str} {get trace}", defs);
+    assert_eq!(
+        x,
+        Ok(Value::Str(
+            "--> shell:15
+ | \\ 'abc' | try {to-num | to-str} {get trace}
+ |                ^^^^^^ within this block
+"
+            .into()
+        ))
+    );
+
+    // nested trys
+    let x = process_w_nil("\\ 'abc' | try {to-num} {try {\\ 'x' | to-num} 2}", defs);
+    assert_eq!(x, Ok(Value::Num(2.into())));
+}
+
+#[test]
+fn try_err_testing() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ 'abc' | try {+ 'd'} 1", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: expecting argument with output type `String`, found `Number`
+--> shell:22
+ | \\ 'abc' | try {+ 'd'} 1
+ |                       ^ this argument returns type `Number`
+--> help: commands may require specific argument types, use `--help` to view requirements
+"
+    );
+}
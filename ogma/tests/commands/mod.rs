@@ -13,6 +13,7 @@ type Result<T> = std::result::Result<T, ogma::Error>;
 mod annotation;
 mod arithmetic;
 mod cmp;
+mod datetime;
 mod definitions;
 mod diagnostics;
 mod errs;
@@ -20,6 +21,8 @@ mod io;
 mod logic;
 mod morphism;
 mod pipeline;
+mod stats;
+mod string;
 mod types;
 
 fn n<N: Into<::kserd::Number>>(n: N) -> Entry<Value> {
@@ -604,7 +607,7 @@ fn forbid_recursion() {
 --> shell:24
  | def test-recursion () { test-recursion }
  |                         ^^^^^^^^^^^^^^ `test-recursion` not defined for input `<any>`
---> help: recursion is not supported.
+--> help: recursion is not supported by `def`, use `def-rec` for a recursive definition.
           for alternatives, please see <https://daedalus.report/d/docs/ogma.book/11%20(no)%20recursion.md?pwd-raw=docs>
 "
     );
@@ -9,6 +9,20 @@ fn append_help_msg() {
         &x,
         "Help: `append`
 --> shell:0
+ | ---- Input Type: Stream ----
+ | add new columns onto a stream using one or more expressions
+ | the stream is processed chunk by chunk, if `prev` is used the stream is collected first
+ | 
+ | Usage:
+ |  => append args..
+ | 
+ | Flags:
+ |  --<col-names>: name each column in order of expression
+ | 
+ | Examples:
+ |  flag slow requests in a large log file
+ |  => open --Stream logs.csv | append { get ms | > 1000 } --slow | save flagged.csv
+ | 
  | ---- Input Type: Table ----
  | add new columns onto a table using one or more expressions
  | each expression adds a new column, populated by row with the result of the expression
@@ -19,6 +33,7 @@ fn append_help_msg() {
  | 
  | Flags:
  |  --<col-names>: name each column in order of expression
+ |  --skip-errors: remove rows which error, outputting a Tuple of the table and a table of the errors
  | 
  | Examples:
  |  flag if a filesystem item is a file
@@ -87,6 +102,36 @@ fn append_encounter_wrong_ty() {
     assert!(x.contains("expected `String`, found `Number`")); // parallel so exact error might chg
 }
 
+#[test]
+fn append_skip_errors() {
+    let defs = &Definitions::new();
+    let src = "append --skip-errors { get 'Heading 3' --Str | if {= b} {to-num} 1 } --x";
+
+    let x = process_w_table(&format!("{src} | get t0"), defs);
+    let exp = vec![
+        vec![o("first"), o("snd"), o("Heading 3"), o("x")],
+        vec![n(0), n(3), o("a"), n(1)],
+        vec![n(-30), n(100), o("z"), n(1)],
+    ];
+    check_is_table(x, exp);
+
+    let x = process_w_table(&format!("{src} | get t1 | pick row cat desc"), defs);
+    let exp = vec![
+        vec![o("row"), o("cat"), o("desc")],
+        vec![
+            n(2),
+            o("Evaluation Error"),
+            o("failed to parse `b` as a number"),
+        ],
+    ];
+    check_is_table(x, exp);
+
+    // without errors, the error table is empty
+    let x = process_w_table("append --skip-errors 1 | get t1", defs);
+    let exp = vec![vec![o("row"), o("cat"), o("desc"), o("trace")]];
+    check_is_table(x, exp);
+}
+
 // ------ Append-Row -----------------------------------------------------------
 #[test]
 fn append_row_help_msg() {
@@ -258,6 +303,31 @@ fn filtering_help_msg() {
         &x,
         r#"Help: `filter`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | filter a list, keeping the items which match a predicate
+ | 
+ | Usage:
+ |  => filter <predicate>
+ | 
+ | Examples:
+ |  keep the numbers greater than 2
+ |  => \ [1 2 3 4] | filter > 2
+ | 
+ | ---- Input Type: Stream ----
+ | filter a stream using a predicate
+ | the stream is processed chunk by chunk, if `prev` is used the stream is collected first
+ | 
+ | Usage:
+ |  => filter [col-name] <predicate>
+ | 
+ | Flags:
+ |  --<type>: only filter entries of type. defaults to Num if not specified
+ |  --cols: filter columns with predicate. predicate is String -> Bool
+ | 
+ | Examples:
+ |  save the server errors of a large log file
+ |  => open --Stream logs.csv | filter status > 499 | save errors.csv
+ | 
  | ---- Input Type: String ----
  | filter a string based on if a character matches a predicate
  | 
@@ -279,6 +349,7 @@ fn filtering_help_msg() {
  | Flags:
  |  --<type>: only filter entries of type. defaults to Num if not specified
  |  --cols: filter columns with predicate. predicate is String -> Bool
+ |  --skip-errors: remove rows which error, outputting a Tuple of the table and a table of the errors
  | 
  | Examples:
  |  filter ls items greater than 5kB
@@ -431,6 +502,50 @@ fn string_filtering() {
     assert_eq!(x, Ok(Value::Str("Ho".into())));
 }
 
+#[test]
+fn list_filtering() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [1 2 3 4] | filter > 2 | to-table", defs);
+    check_is_table(x, vec![vec![o("list")], vec![n(3)], vec![n(4)]]);
+    let x = process_w_nil("\\ ['a' 'bb' 'c'] | filter { len | = 1 } | len", defs);
+    assert_eq!(x, Ok(Value::Num(2.into())));
+}
+
+#[test]
+fn row_filtering_skip_errors() {
+    let defs = &Definitions::new();
+
+    // table row input
+    let src = "filter --skip-errors { get first | if {= 1} {\\ 'x' | to-num} #i | >= 0 }";
+    let x = process_w_table(&format!("{src} | get t0"), defs);
+    let exp = vec![
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(0), n(3), o("a")],
+    ];
+    check_is_table(x, exp);
+    let x = process_w_table(&format!("{src} | get t1 | pick row desc"), defs);
+    let exp = vec![
+        vec![o("row"), o("desc")],
+        vec![n(2), o("failed to parse `x` as a number")],
+    ];
+    check_is_table(x, exp);
+
+    // known column
+    let src = "filter snd --skip-errors { if {= 20} {\\ 'x' | to-num} #i | > 10 }";
+    let x = process_w_table(&format!("{src} | get t0"), defs);
+    let exp = vec![
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(-30), n(100), o("z")],
+    ];
+    check_is_table(x, exp);
+    let x = process_w_table(&format!("{src} | get t1 | pick row desc"), defs);
+    let exp = vec![
+        vec![o("row"), o("desc")],
+        vec![n(2), o("failed to parse `x` as a number")],
+    ];
+    check_is_table(x, exp);
+}
+
 // ------ Folding --------------------------------------------------------------
 #[test]
 fn fold_help_msg() {
@@ -440,6 +555,31 @@ fn fold_help_msg() {
         &x,
         "Help: `fold`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | fold (reduce) a list into single value
+ | fold takes a seed value and an accumulator expression
+ | the variable $item is available to query the list item
+ | 
+ | Usage:
+ |  => fold seed accumulator
+ | 
+ | Examples:
+ |  sum a list of numbers
+ |  => \\ [1 2 3] | fold 0 + $item
+ | 
+ | ---- Input Type: Stream ----
+ | fold (reduce) a stream into single value
+ | fold takes a seed value and an accumulator expression
+ | the variable $row is available to query the table row
+ | the stream is processed chunk by chunk, if `prev` is used the stream is collected first
+ | 
+ | Usage:
+ |  => fold seed accumulator
+ | 
+ | Examples:
+ |  count the rows of a large file
+ |  => open --Stream logs.csv | fold 0 + 1
+ | 
  | ---- Input Type: Table ----
  | fold (reduce) table into single value
  | fold takes a seed value and an accumulator expression
@@ -506,6 +646,103 @@ fn fold_while_test() {
     assert_eq!(x, Ok(Value::Num((21).into())));
 }
 
+#[test]
+fn list_folding() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [1 2 3] | fold 0 + $item", defs);
+    assert_eq!(x, Ok(Value::Num(6.into())));
+    let x = process_w_nil("\\ ['a' 'b' 'c'] | fold '' + $item", defs);
+    assert_eq!(x, Ok(Value::Str("abc".into())));
+    let x = process_w_nil("\\ []:List<Num> | fold 5 + $item", defs);
+    assert_eq!(x, Ok(Value::Num(5.into())));
+}
+
+// ------ Reduce ---------------------------------------------------------------
+#[test]
+fn reduce_help_msg() {
+    let src = "reduce --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `reduce`
+--> shell:0
+ | ---- Input Type: Stream ----
+ | reduce a stream into single value, folding the rows of each chunk in parallel
+ | reduce takes a seed value, an accumulator expression, and a combine expression
+ | the variable $row is available to query the table row in the accumulator
+ | the variable $rhs is the result being merged in the combine expression
+ | the stream is processed chunk by chunk, if `prev` is used the stream is collected first
+ | 
+ | Usage:
+ |  => reduce seed accumulator combine
+ | 
+ | Examples:
+ |  count the rows of a large file
+ |  => open --Stream logs.csv | reduce 0 { + 1 } { + $rhs }
+ | 
+ | ---- Input Type: Table ----
+ | reduce table into single value, folding the rows in parallel
+ | reduce takes a seed value, an accumulator expression, and a combine expression
+ | the rows are split into chunks, each chunk is folded from the seed using the accumulator,
+ | and the chunk results are merged using the combine expression
+ | the variable $row is available to query the table row in the accumulator
+ | the variable $rhs is the result being merged in the combine expression
+ | the seed must not alter a result it is combined with, and the combine expression must be
+ | associative, otherwise the result depends on how the rows are split
+ | 
+ | Usage:
+ |  => reduce seed accumulator combine
+ | 
+ | Examples:
+ |  sum numbers (0,10)
+ |  => range 0 11 | reduce 0 { + $row.i } { + $rhs }
+ | 
+ |  the maximum price of the diamonds
+ |  => open diamonds.csv | reduce -inf { max $row.price } { max $rhs }
+"
+    );
+}
+
+#[test]
+fn reduce_test() {
+    let defs = &Definitions::new();
+    let x = process_w_num("range 0 10 | reduce 0 { + $row.i } { + $rhs }", defs);
+    assert_eq!(x, Ok(Value::Num(45.into())));
+    let x = process_w_num("range 0 1e5 | reduce 0 { + $row.i } { + $rhs }", defs);
+    assert_eq!(x, Ok(Value::Num(4_999_950_000u64.into())));
+    let x = process_w_num("range 0 1e5 | reduce 0 { + 1 } { + $rhs }", defs);
+    assert_eq!(x, Ok(Value::Num(100_000.into())));
+    // no rows reduces to the seed
+    let x = process_w_num("range 0 0 | reduce -100 { + $row.i } { + $rhs }", defs);
+    assert_eq!(x, Ok(Value::Num((-100).into())));
+
+    // matches fold
+    let x = process_w_nil(
+        "open tests/diamonds.csv | reduce -inf { max $row.price } { max $rhs }",
+        defs,
+    );
+    let y = process_w_nil("open tests/diamonds.csv | fold -inf max $row.price", defs);
+    assert_eq!(x, y);
+    let x = process_w_nil(
+        "open tests/diamonds.csv | reduce {Tuple 0 0} { Tuple { get t0 | + 1 } { get t1 | + $row.carat } } { Tuple { get t0 | + { \\$rhs | get t0 } } { get t1 | + { \\$rhs | get t1 } } } | get t0",
+        defs,
+    );
+    assert_eq!(x, Ok(Value::Num(53_940.into())));
+}
+
+#[test]
+fn reduce_errs() {
+    let defs = &Definitions::new();
+    let x = process_w_num("range 0 10 | reduce 0 { + $row.i }", defs)
+        .unwrap_err()
+        .to_string();
+    assert!(x.contains("expecting more than 2 arguments"), "{}", x);
+    // the combine expression returns the seed type
+    assert!(process_w_num("range 0 10 | reduce 0 { + $row.i } { Str $rhs }", defs).is_err());
+    // errors in the accumulator surface
+    assert!(process_w_num("range 0 10 | reduce 0 { + $row.foo } { + $rhs }", defs).is_err());
+}
+
 // ------ Grp ------------------------------------------------------------------
 #[test]
 fn grp_help_msg() {
@@ -617,6 +854,360 @@ fold {range 0 0} { let $acc | \\$row | get value --Table | pick first | let $x |
     check_is_table(x, exp);
 }
 
+// ------ Pivot ----------------------------------------------------------------
+#[test]
+fn pivot_help_msg() {
+    let src = "pivot --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `pivot`
+--> shell:0
+ | ---- Input Type: Table ----
+ | reshape a table into wide form, aggregating the values of each row and column key
+ | a row is made for each distinct row key entry and a column for each distinct column key entry
+ | the aggregation expression is supplied a table of the value columns for the matching rows
+ | cells without any matching rows are nil
+ | to reshape into long form see `unpivot`
+ | 
+ | Usage:
+ |  => pivot row-key col-key value-col.. <agg>
+ | 
+ | Examples:
+ |  total sales by region (rows) and month (columns)
+ |  => open sales.csv | pivot region month sales { fold 0 + $row.sales }
+ | 
+ |  price per carat by clarity and cut
+ |  => open diamonds.csv | pivot clarity cut price carat { let { fold 0 + $row.carat } $c | fold 0 + $row.price | / $c }
+"
+    );
+}
+
+#[test]
+fn pivot_testing() {
+    let defs = &Definitions::new();
+
+    let x = process_w_table(
+        "append --pos { get first | >= 0 } | pivot pos 'Heading 3' snd { fold 0 + $row.snd }",
+        defs,
+    );
+    let exp = vec![
+        vec![o("pos"), o("a"), o("b"), o("z")],
+        vec![Entry::from(Value::Bool(false)), Nil, Nil, n(100)],
+        vec![Entry::from(Value::Bool(true)), n(3), n(20), Nil],
+    ];
+    check_is_table(x, exp);
+
+    // multiple value columns
+    let x = process_w_table(
+        "append --pos { get first | >= 0 } | pivot pos pos first snd { let $t | fold 0 + $row.first | + { \\ $t | fold 0 + $row.snd } }",
+        defs,
+    );
+    let exp = vec![
+        vec![o("pos"), o("false"), o("true")],
+        vec![Entry::from(Value::Bool(false)), n(70), Nil],
+        vec![Entry::from(Value::Bool(true)), Nil, n(24)],
+    ];
+    check_is_table(x, exp);
+}
+
+#[test]
+fn pivot_err_testing() {
+    let defs = &Definitions::new();
+    let x = process_w_table("pivot first snd 'Heading 3'", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: expecting more than 3 arguments
+--> shell:0
+ | pivot first snd 'Heading 3'
+ | ^^^^^^^^^^^^^^^^^^^^^^^^^^^ expecting additional argument(s)
+--> help: try using the `--help` flag to view requirements
+"
+    );
+
+    let x = process_w_table("pivot first snd foo { len }", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: header `foo` not found in table
+--> shell:16
+ | pivot first snd foo { len }
+ |                 ^^^ `foo` resolves to `foo`
+"
+    );
+}
+
+// ------ Unpivot --------------------------------------------------------------
+#[test]
+fn unpivot_help_msg() {
+    let src = "unpivot --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `unpivot`
+--> shell:0
+ | ---- Input Type: Table ----
+ | reshape a table into long form, turning the specified columns into key-value rows
+ | a row is made for each specified column of each row, with the column name in `key`
+ | and the entry in `value`. the unspecified columns are repeated on each row
+ | to reshape into wide form see `pivot`
+ | 
+ | Usage:
+ |  => unpivot col-name..
+ | 
+ | Examples:
+ |  turn monthly sales columns into rows
+ |  => open sales.csv | unpivot Jan Feb Mar
+ | 
+ |  unpivot and then rename the key-value columns
+ |  => open sales.csv | unpivot Jan Feb Mar | ren month sales
+"
+    );
+}
+
+#[test]
+fn unpivot_testing() {
+    let defs = &Definitions::new();
+
+    let exp = || {
+        vec![
+            vec![o("Heading 3"), o("key"), o("value")],
+            vec![o("a"), o("first"), n(0)],
+            vec![o("a"), o("snd"), n(3)],
+            vec![o("b"), o("first"), n(1)],
+            vec![o("b"), o("snd"), n(20)],
+            vec![o("z"), o("first"), n(-30)],
+            vec![o("z"), o("snd"), n(100)],
+        ]
+    };
+    check_is_table(process_w_table("unpivot first snd", defs), exp());
+    check_is_table(process_w_table("melt first snd", defs), exp());
+
+    // round trip
+    let x = process_w_table(
+        "unpivot first snd | pivot 'Heading 3' key value { nth 0 { get value } }",
+        defs,
+    );
+    let exp = vec![
+        vec![o("Heading 3"), o("first"), o("snd")],
+        vec![o("a"), n(0), n(3)],
+        vec![o("b"), n(1), n(20)],
+        vec![o("z"), n(-30), n(100)],
+    ];
+    check_is_table(x, exp);
+}
+
+// ------ Join -----------------------------------------------------------------
+#[test]
+fn join_help_msg() {
+    let src = "join --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `join`
+--> shell:0
+ | ---- Input Type: Table ----
+ | join the input table with another table where the key columns match
+ | the key columns must exist in both tables and only appear once in the result
+ | clashing column names from the other table are suffixed with `_rhs`
+ | to join on a derived value see `join-by`
+ | 
+ | Usage:
+ |  => join table col-name..
+ | 
+ | Flags:
+ |  --inner: only keep rows which match in both tables (default)
+ |  --left: keep all rows of the input table
+ |  --right: keep all rows of the other table
+ |  --outer: keep all rows of both tables
+ | 
+ | Examples:
+ |  join the files of two directories by name
+ |  => ls | join { ls other-dir } name
+ | 
+ |  keep all sales, joining on region and country
+ |  => open sales.csv | join --left { open regions.csv } region country
+"
+    );
+}
+
+#[test]
+fn join_testing() {
+    let defs = &Definitions::new();
+    let rhs = "{ map first + 1 | pick first snd }";
+    let hdr = || vec![o("first"), o("snd"), o("Heading 3"), o("snd_rhs")];
+
+    let x = process_w_table(&format!("join {rhs} first"), defs);
+    let exp = vec![hdr(), vec![n(1), n(20), o("b"), n(3)]];
+    check_is_table(x, exp);
+
+    let x = process_w_table(&format!("join --left {rhs} first"), defs);
+    let exp = vec![
+        hdr(),
+        vec![n(0), n(3), o("a"), Nil],
+        vec![n(1), n(20), o("b"), n(3)],
+        vec![n(-30), n(100), o("z"), Nil],
+    ];
+    check_is_table(x, exp);
+
+    let x = process_w_table(&format!("join --right {rhs} first"), defs);
+    let exp = vec![
+        hdr(),
+        vec![n(1), n(20), o("b"), n(3)],
+        vec![n(2), Nil, Nil, n(20)],
+        vec![n(-29), Nil, Nil, n(100)],
+    ];
+    check_is_table(x, exp);
+
+    let x = process_w_table(&format!("join --outer {rhs} first"), defs);
+    let exp = vec![
+        hdr(),
+        vec![n(0), n(3), o("a"), Nil],
+        vec![n(1), n(20), o("b"), n(3)],
+        vec![n(-30), n(100), o("z"), Nil],
+        vec![n(2), Nil, Nil, n(20)],
+        vec![n(-29), Nil, Nil, n(100)],
+    ];
+    check_is_table(x, exp);
+
+    // multiple keys, all columns are keys
+    let x = process_w_table("join { rev } first snd 'Heading 3'", defs);
+    let exp = vec![
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(0), n(3), o("a")],
+        vec![n(1), n(20), o("b")],
+        vec![n(-30), n(100), o("z")],
+    ];
+    check_is_table(x, exp);
+}
+
+#[test]
+fn join_err_testing() {
+    let defs = &Definitions::new();
+    let x = process_w_table("join { pick snd } first", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: header `first` not found in table
+--> shell:18
+ | join { pick snd } first
+ |                   ^^^^^ `first` resolves to `first`
+"
+    );
+
+    let x = process_w_table("join --left --right { rev } first", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Semantics Error: not expecting flags: `right`
+--> shell:14
+ | join --left --right { rev } first
+ |               ^^^^^ flag not supported
+--> help: try using the `--help` flag to view requirements
+"
+    );
+}
+
+// ------ Join-by --------------------------------------------------------------
+#[test]
+fn joinby_help_msg() {
+    let src = "join-by --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `join-by`
+--> shell:0
+ | ---- Input Type: Table ----
+ | join the input table with another table where the result of an expression matches
+ | the expression is evaluated on the rows of both tables and cannot return a table
+ | all columns of both tables are kept, clashing column names from the other table
+ | are suffixed with `_rhs`
+ | 
+ | Usage:
+ |  => join-by table <expr>
+ | 
+ | Flags:
+ |  --inner: only keep rows which match in both tables (default)
+ |  --left: keep all rows of the input table
+ |  --right: keep all rows of the other table
+ |  --outer: keep all rows of both tables
+ | 
+ | Examples:
+ |  join files modified on the same day
+ |  => ls | join-by { ls other-dir } { get:DateTime modified | to-str '[year]-[month]-[day]' }
+ | 
+ |  join using a user-defined type
+ |  => ls | join-by --outer { ls other-dir } { Point { get size } }
+"
+    );
+}
+
+#[test]
+fn joinby_testing() {
+    let defs = &Definitions::new();
+    let hdr = || {
+        vec![
+            o("first"),
+            o("snd"),
+            o("Heading 3"),
+            o("first_rhs"),
+            o("snd_rhs"),
+            o("Heading 3_rhs"),
+        ]
+    };
+
+    let x = process_w_table("join-by { take 1 } { get first | >= 0 }", defs);
+    let exp = vec![
+        hdr(),
+        vec![n(0), n(3), o("a"), n(0), n(3), o("a")],
+        vec![n(1), n(20), o("b"), n(0), n(3), o("a")],
+    ];
+    check_is_table(x, exp);
+
+    let x = process_w_table("join-by --left { take 1 } { get first | >= 0 }", defs);
+    let exp = vec![
+        hdr(),
+        vec![n(0), n(3), o("a"), n(0), n(3), o("a")],
+        vec![n(1), n(20), o("b"), n(0), n(3), o("a")],
+        vec![n(-30), n(100), o("z"), Nil, Nil, Nil],
+    ];
+    check_is_table(x, exp);
+
+    // user-defined types are matched by value
+    let defs = &with_dummy_defs();
+    let x = process_w_table(
+        "join-by --right { map first * 2 } { Point { get first } { get first } } | pick first snd_rhs",
+        defs,
+    );
+    let exp = vec![
+        vec![o("first"), o("snd_rhs")],
+        vec![n(0), n(3)],
+        vec![Nil, n(20)],
+        vec![Nil, n(100)],
+    ];
+    check_is_table(x, exp);
+}
+
+#[test]
+fn joinby_err_testing() {
+    let defs = &Definitions::new();
+    let x = process_w_table("join-by { rev } { \\Table }", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.starts_with("Semantics Error: join-by key expression cannot return a `Table`"));
+    assert!(x.ends_with("--> help: join keys are matched by value, try returning a table entry\n"));
+}
+
 // ------ Map ------------------------------------------------------------------
 #[test]
 fn map_help_msg() {
@@ -626,6 +1217,37 @@ fn map_help_msg() {
         &x,
         "Help: `map`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | map each item in a list using an expression
+ | the output list has the type returned by the expression
+ | 
+ | Usage:
+ |  => map <expr>
+ | 
+ | Examples:
+ |  double each number
+ |  => \\ [1 2 3] | map * 2
+ | 
+ |  convert numbers to strings
+ |  => \\ [1 2 3] | map to-str
+ | 
+ | ---- Input Type: Stream ----
+ | replace entry in column with result of an expression
+ | `map` provides the variable `$row` which is the TableRow
+ | the input into the expression is the value of the entry
+ | the stream is processed chunk by chunk, if `prev` is used the stream is collected first
+ | 
+ | Usage:
+ |  => map col-name value
+ | 
+ | Flags:
+ |  --<type>: the type that entry has
+ |  --force: ignore entry types
+ | 
+ | Examples:
+ |  convert milliseconds to seconds in a large log file
+ |  => open --Stream logs.csv | map ms / 1000 | save secs.csv
+ | 
  | ---- Input Type: Table ----
  | replace entry in column with result of an expression
  | `map` provides the variable `$row` which is the TableRow
@@ -637,6 +1259,7 @@ fn map_help_msg() {
  | Flags:
  |  --<type>: the type that entry has
  |  --force: ignore entry types
+ |  --skip-errors: remove rows which error, outputting a Tuple of the table and a table of the errors
  | 
  | Examples:
  |  scale 'size' by dividing by one million
@@ -704,12 +1327,56 @@ fn map_testing() {
     // check ignore flag
     let x = process_w_table("map 'Heading 3' --force $row.first:Num", defs);
     let exp = vec![
-        vec![o("first"), o("snd"), o("Heading 3")],
-        vec![n(0), n(3), n(0)],
-        vec![n(1), n(20), n(1)],
-        vec![n(-30), n(100), n(-30)],
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(0), n(3), n(0)],
+        vec![n(1), n(20), n(1)],
+        vec![n(-30), n(100), n(-30)],
+    ];
+    check_is_table(x, exp);
+}
+
+#[test]
+fn map_skip_errors() {
+    let defs = &Definitions::new();
+    let src = "map 'Heading 3' --Str --skip-errors { if {= b} {to-num} 1 }";
+
+    let x = process_w_table(&format!("{src} | get t0"), defs);
+    let exp = vec![
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(0), n(3), n(1)],
+        vec![n(-30), n(100), n(1)],
+    ];
+    check_is_table(x, exp);
+
+    let x = process_w_table(&format!("{src} | get t1"), defs);
+    let exp = vec![
+        vec![o("row"), o("cat"), o("desc"), o("trace")],
+        vec![
+            n(2),
+            o("Evaluation Error"),
+            o("failed to parse `b` as a number"),
+            o("--> shell:48
+ | map 'Heading 3' --Str --skip-errors { if {= b} {to-num} 1 } | get t1
+ |                                                 ^^^^^^ within this block
+"),
+        ],
     ];
     check_is_table(x, exp);
+
+    // errors are still returned without the flag
+    let x = process_w_table("map 'Heading 3' --Str { if {= b} {to-num} 1 }", defs);
+    assert!(x.is_err());
+}
+
+#[test]
+fn list_mapping() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [1 2 3] | map * 2 | to-table", defs);
+    check_is_table(x, vec![vec![o("list")], vec![n(2)], vec![n(4)], vec![n(6)]]);
+    let x = process_w_nil("\\ [1 2 3] | map to-str | nth 2", defs);
+    assert_eq!(x, Ok(Value::Str("3".into())));
+    let x = process_w_nil("\\ [1 2] | map [#t] | nth 0 | len", defs);
+    assert_eq!(x, Ok(Value::Num(1.into())));
 }
 
 // ------ Pick -----------------------------------------------------------------
@@ -1107,6 +1774,16 @@ fn skip_help_msg() {
         &x,
         r#"Help: `skip`
 --> shell:0
+ | ---- Input Type: Stream ----
+ | skip the first n rows of a stream
+ | 
+ | Usage:
+ |  => skip count
+ | 
+ | Examples:
+ |  skip the first 10 rows of a stream
+ |  => skip 10
+ | 
  | ---- Input Type: String ----
  | skip the first n characters of a string
  | 
@@ -1182,6 +1859,17 @@ fn sort_help_msg() {
         &x,
         "Help: `sort`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | sort a list lowest to highest in a canonical fashion
+ | this sorts different value types, but NOT user-defined types
+ | 
+ | Usage:
+ |  => sort
+ | 
+ | Examples:
+ |  sort a list of numbers
+ |  => \\ [3 1 2] | sort
+ | 
  | ---- Input Type: Table ----
  | sort a table by column headers
  | each header sorts the rows lowest to highest in a canonical fashion,
@@ -1239,6 +1927,15 @@ fn empty_sort_err() {
     );
 }
 
+#[test]
+fn list_sorting() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [3 1 2] | sort | to-table", defs);
+    check_is_table(x, vec![vec![o("list")], vec![n(1)], vec![n(2)], vec![n(3)]]);
+    let x = process_w_nil("\\ ['b' 'c' 'a'] | sort | nth 0", defs);
+    assert_eq!(x, Ok(Value::Str("a".into())));
+}
+
 // ------ Sort-by --------------------------------------------------------------
 #[test]
 fn sortby_help_msg() {
@@ -1396,6 +2093,17 @@ fn take_help_msg() {
         &x,
         r#"Help: `take`
 --> shell:0
+ | ---- Input Type: Stream ----
+ | take the first n rows of a stream
+ | only the chunks needed are pulled from the stream
+ | 
+ | Usage:
+ |  => take count
+ | 
+ | Examples:
+ |  take the first 10 rows of a stream
+ |  => take 10
+ | 
  | ---- Input Type: String ----
  | take the first n characters of a string
  | 
@@ -1463,3 +2171,518 @@ fn take_testing() {
     let x = process_w_str("take 5", defs);
     assert_eq!(x, Ok(Value::Str("Hello".into())));
 }
+
+// ------ Windows --------------------------------------------------------------
+#[test]
+fn lag_help_msg() {
+    let src = "lag --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `lag`
+--> shell:0
+ | ---- Input Type: Table ----
+ | append a column with the entry from a previous row
+ | the entry is `n` rows before, defaulting to 1, and is nil if there is no such row
+ | the column is named `lag-<col-name>`
+ | 
+ | Usage:
+ |  => lag col-name [n]
+ | 
+ | Flags:
+ |  --partition: partition the rows by the first argument column, each partition is windowed separately
+ | 
+ | Examples:
+ |  the previous day's price
+ |  => open prices.csv | lag price
+ | 
+ |  the price a week ago for each stock
+ |  => open prices.csv | lag --partition stock price 7
+"
+    );
+}
+
+#[test]
+fn rolling_help_msg() {
+    let src = "rolling --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `rolling`
+--> shell:0
+ | ---- Input Type: Table ----
+ | append a column with the result of an expression over a rolling window of rows
+ | the expression is supplied a table of the current row and up to `n - 1` previous rows
+ | the column is named `rolling`
+ | 
+ | Usage:
+ |  => rolling n expr
+ | 
+ | Flags:
+ |  --partition: partition the rows by the first argument column, each partition is windowed separately
+ | 
+ | Examples:
+ |  the 7 day moving average price
+ |  => open prices.csv | rolling 7 { mean price }
+ | 
+ |  the highest price in the last 30 days for each stock
+ |  => open prices.csv | rolling --partition stock 30 { max price }
+"
+    );
+}
+
+/// The test table with each row appended with `x`.
+fn window_table(name: &str, x: Vec<Entry<Value>>) -> Vec<Vec<Entry<Value>>> {
+    let mut t = vec![
+        vec![o("first"), o("snd"), o("Heading 3")],
+        vec![n(0), n(3), o("a")],
+        vec![n(1), n(20), o("b")],
+        vec![n(-30), n(100), o("z")],
+    ];
+    for (row, x) in t.iter_mut().zip(std::iter::once(o(name)).chain(x)) {
+        row.push(x);
+    }
+    t
+}
+
+#[test]
+fn lag_and_lead_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("lag snd"),
+        window_table("lag-snd", vec![Nil, n(3), n(20)]),
+    );
+    check_is_table(
+        t("lag snd 2"),
+        window_table("lag-snd", vec![Nil, Nil, n(3)]),
+    );
+    check_is_table(
+        t("lag snd 0"),
+        window_table("lag-snd", vec![n(3), n(20), n(100)]),
+    );
+    check_is_table(
+        t("lead 'Heading 3'"),
+        window_table("lead-Heading 3", vec![o("b"), o("z"), Nil]),
+    );
+    check_is_table(
+        t("lead snd 5"),
+        window_table("lead-snd", vec![Nil, Nil, Nil]),
+    );
+}
+
+#[test]
+fn rolling_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("rolling 2 { sum snd }"),
+        window_table("rolling", vec![n(3), n(23), n(120)]),
+    );
+    check_is_table(
+        t("rolling 5 { len }"),
+        window_table("rolling", vec![n(1), n(2), n(3)]),
+    );
+}
+
+#[test]
+fn cumulative_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("cumsum snd"),
+        window_table("cumsum-snd", vec![n(3), n(23), n(123)]),
+    );
+    check_is_table(
+        t("row-number"),
+        window_table("row-number", vec![n(1), n(2), n(3)]),
+    );
+    check_is_table(
+        t("rank snd"),
+        window_table("rank-snd", vec![n(1), n(2), n(3)]),
+    );
+    check_is_table(
+        t("rank --desc first"),
+        window_table("rank-first", vec![n(2), n(1), n(3)]),
+    );
+
+    let x = t("cumsum 'Heading 3'").unwrap_err().to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: table entry for [row:1,col:'Heading 3'] did not have expected type
+expected `Number`, found `String`
+--> shell:8
+ | cumsum 'Heading 3'
+ |         ^^^^^^^^^
+--> help: column entries must have a matching type
+"
+    );
+}
+
+#[test]
+fn partitioned_window_testing() {
+    let defs = &Definitions::new();
+    let t = |src: &str| {
+        process_w_table(
+            &format!("append --grp {{ get first --Num | < 1 }} | {src}"),
+            defs,
+        )
+    };
+    let b = |b| Entry::Obj(Value::Bool(b));
+    let tbl = |name, x: Vec<_>| {
+        let mut t = window_table("grp", vec![b(true), b(false), b(true)]);
+        for (row, x) in t.iter_mut().zip(std::iter::once(o(name)).chain(x)) {
+            row.push(x);
+        }
+        t
+    };
+
+    check_is_table(
+        t("lag --partition grp snd"),
+        tbl("lag-snd", vec![Nil, Nil, n(3)]),
+    );
+    check_is_table(
+        t("lead --partition grp snd"),
+        tbl("lead-snd", vec![n(100), Nil, Nil]),
+    );
+    check_is_table(
+        t("cumsum --partition grp snd"),
+        tbl("cumsum-snd", vec![n(3), n(20), n(103)]),
+    );
+    check_is_table(
+        t("row-number --partition grp"),
+        tbl("row-number", vec![n(1), n(1), n(2)]),
+    );
+    check_is_table(
+        t("rank --partition grp first"),
+        tbl("rank-first", vec![n(2), n(1), n(1)]),
+    );
+    check_is_table(
+        t("rank --desc grp"),
+        tbl("rank-grp", vec![n(1), n(3), n(1)]),
+    );
+    check_is_table(
+        t("rolling --partition grp 2 { sum snd }"),
+        tbl("rolling", vec![n(3), n(20), n(103)]),
+    );
+}
+
+#[test]
+fn prev_rows_testing() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("append --prev { prev 2 | sum snd }"),
+        window_table("prev", vec![n(0), n(3), n(23)]),
+    );
+    check_is_table(
+        t("append --prev { prev 1 | sum snd }"),
+        window_table("prev", vec![n(0), n(3), n(20)]),
+    );
+    let x = t("fold 0 { \\$row | prev 2 | len }");
+    assert_eq!(x, Ok(Value::Num(2.into())));
+
+    // the number of rows is required
+    assert!(t("append --prev { prev | sum snd }").is_err());
+}
+
+// ------ Optimisation ---------------------------------------------------------
+#[test]
+fn optimised_filter_pushdown() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("append --x { get first --Num | + 1 } | filter snd > 10"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3"), o("x")],
+            vec![n(1), n(20), o("b"), n(2)],
+            vec![n(-30), n(100), o("z"), n(-29)],
+        ],
+    );
+
+    // appending from previous rows is not reordered
+    check_is_table(
+        t("append --p { prev 2 | len } | filter snd > 10"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3"), o("p")],
+            vec![n(1), n(20), o("b"), n(1)],
+            vec![n(-30), n(100), o("z"), n(2)],
+        ],
+    );
+
+    check_is_table(
+        t("sort first | filter snd < 50"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(0), n(3), o("a")],
+            vec![n(1), n(20), o("b")],
+        ],
+    );
+
+    // an error on a row which is filtered out is still raised
+    let mixed = "Table a b | append-row x 1 | append-row 2 20";
+    let x = process_w_nil(
+        &format!("{} | append --c {{ get a --Num }} | filter b > 10", mixed),
+        defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{}", x);
+    assert!(x.contains("table entry for [row:1,col:'a'] did not have expected type"));
+
+    let x = process_w_nil(&format!("{} | map a + 1 | filter b > 10", mixed), defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.contains("table entry for [row:1,col:'a'] did not have expected type"));
+}
+
+#[test]
+fn optimised_pick_pruning() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("append --x 1 | sort first | pick snd first"),
+        vec![
+            vec![o("snd"), o("first")],
+            vec![n(100), n(-30)],
+            vec![n(3), n(0)],
+            vec![n(20), n(1)],
+        ],
+    );
+
+    // steps which could error are not removed
+    let x = t("map 'Heading 3' + 1 | pick first")
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.contains("col:'Heading 3'] did not have expected type"));
+
+    let x = t("append --x { get 'Heading 3' --Num } | pick first")
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.contains("col:'Heading 3'] did not have expected type"));
+
+    let x = t("map nope --force 0 | pick first")
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.contains("header `nope` not found in table"));
+}
+
+#[test]
+fn optimised_map_fusion() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("map first + 1 | map first * 2 | map snd - 1 | filter first > 0"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(2), n(2), o("a")],
+            vec![n(4), n(19), o("b")],
+        ],
+    );
+
+    check_is_table(
+        t("map first + 1 | map 'Heading 3' --Str + '!'"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(1), n(3), o("a!")],
+            vec![n(2), n(20), o("b!")],
+            vec![n(-29), n(100), o("z!")],
+        ],
+    );
+
+    let x = t("map first + 1 | map first --Str + '!'")
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.contains("col:'first'] did not have expected type"));
+}
+
+#[test]
+fn optimised_sort_take() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    check_is_table(
+        t("sort snd first | take 2"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(-30), n(100), o("z")],
+            vec![n(0), n(3), o("a")],
+        ],
+    );
+
+    // ties keep their order
+    check_is_table(
+        t("append --k 1 | sort k | take 2"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3"), o("k")],
+            vec![n(0), n(3), o("a"), n(1)],
+            vec![n(1), n(20), o("b"), n(1)],
+        ],
+    );
+
+    check_is_table(
+        t("sort first | take 0"),
+        vec![vec![o("first"), o("snd"), o("Heading 3")]],
+    );
+    check_is_table(
+        t("sort 'Heading 3' | take 10"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(0), n(3), o("a")],
+            vec![n(1), n(20), o("b")],
+            vec![n(-30), n(100), o("z")],
+        ],
+    );
+
+    // skip is not treated as a take
+    check_is_table(
+        t("sort first | take 1"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(-30), n(100), o("z")],
+        ],
+    );
+    check_is_table(
+        t("sort first | skip 1"),
+        vec![
+            vec![o("first"), o("snd"), o("Heading 3")],
+            vec![n(0), n(3), o("a")],
+            vec![n(1), n(20), o("b")],
+        ],
+    );
+
+    let x = process_w_nil(
+        "open tests/diamonds.csv | sort price carat | take 5 | pick carat price",
+        defs,
+    );
+    let y = process_w_nil(
+        "open tests/diamonds.csv | sort price carat | rev | rev | take 5 | pick carat price",
+        defs,
+    );
+    assert_eq!(x, y);
+}
+
+// ------ Typed columns --------------------------------------------------------
+fn nils_table() -> Table {
+    ::table::Table::from(vec![
+        vec![o("num"), o("str"), o("mixed")],
+        vec![n(3), o("b"), n(1)],
+        vec![Nil, Nil, o("a")],
+        vec![n(-1), o("a"), Nil],
+        vec![n(3), o("c"), n(2)],
+    ])
+    .into()
+}
+
+#[test]
+fn typed_column_sorting() {
+    let defs = &Definitions::new();
+    let t = |src| process(nils_table(), src, defs);
+    let hdr = || vec![o("num"), o("str"), o("mixed")];
+
+    // nils sort last, ties keep their order
+    let exp = vec![
+        hdr(),
+        vec![n(-1), o("a"), Nil],
+        vec![n(3), o("b"), n(1)],
+        vec![n(3), o("c"), n(2)],
+        vec![Nil, Nil, o("a")],
+    ];
+    check_is_table(t("sort num"), exp.clone());
+    check_is_table(t("sort num str"), exp.clone());
+    check_is_table(t("sort str num"), exp.clone());
+    check_is_table(t("sort num | take 2"), exp[..3].to_vec());
+
+    // mixed columns sort on their entries
+    check_is_table(
+        t("sort mixed"),
+        vec![
+            hdr(),
+            vec![n(3), o("b"), n(1)],
+            vec![n(3), o("c"), n(2)],
+            vec![Nil, Nil, o("a")],
+            vec![n(-1), o("a"), Nil],
+        ],
+    );
+}
+
+#[test]
+fn typed_column_large_integers() {
+    let defs = &Definitions::new();
+    // 2^53 + 1 is not an f64, the column is sorted on its entries
+    let big = 1u64 << 53;
+    let table: Table = ::table::Table::from(vec![
+        vec![o("x")],
+        vec![n(big + 1)],
+        vec![n(big)],
+        vec![n(big + 3)],
+    ])
+    .into();
+
+    check_is_table(
+        process(table, "sort x", defs),
+        vec![
+            vec![o("x")],
+            vec![n(big)],
+            vec![n(big + 1)],
+            vec![n(big + 3)],
+        ],
+    );
+}
+
+#[test]
+fn typed_column_filtering() {
+    let defs = &Definitions::new();
+    let t = |src| process(nils_table(), src, defs);
+    let hdr = || vec![o("num"), o("str"), o("mixed")];
+
+    check_is_table(
+        t("filter num > 0"),
+        vec![hdr(), vec![n(3), o("b"), n(1)], vec![n(3), o("c"), n(2)]],
+    );
+    check_is_table(
+        t("filter str --Str != b"),
+        vec![hdr(), vec![n(-1), o("a"), Nil], vec![n(3), o("c"), n(2)]],
+    );
+    check_is_table(t("filter num --Str != b"), vec![hdr()]);
+    check_is_table(t("filter mixed > 1"), vec![hdr(), vec![n(3), o("c"), n(2)]]);
+}
+
+#[test]
+fn typed_column_grouping() {
+    let defs = &Definitions::new();
+    let t = |src| process(nils_table(), src, defs);
+
+    check_is_table(
+        t("grp num str | map value --Table len"),
+        vec![
+            vec![o("key"), o("value")],
+            vec![o("---"), n(1)],
+            vec![o("-1-a"), n(1)],
+            vec![o("3-b"), n(1)],
+            vec![o("3-c"), n(1)],
+        ],
+    );
+    check_is_table(
+        t("grp num | map value --Table len"),
+        vec![
+            vec![o("key"), o("value")],
+            vec![o("-"), n(1)],
+            vec![o("-1"), n(1)],
+            vec![o("3"), n(2)],
+        ],
+    );
+}
@@ -492,6 +492,16 @@ fn len_help_msg() {
         &x,
         r#"Help: `len`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | return the number of items in a list
+ | 
+ | Usage:
+ |  => len
+ | 
+ | Examples:
+ |  length of a list
+ |  => \ [1 2 3] | len
+ | 
  | ---- Input Type: String ----
  | return the number of characters in a string
  | 
@@ -542,6 +552,15 @@ fn len_str_testing() {
     assert_eq!(x, Ok(Value::Num(9.into())));
 }
 
+#[test]
+fn len_list() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [1 2 3] | len", defs);
+    assert_eq!(x, Ok(Value::Num(3.into())));
+    let x = process_w_nil("\\ []:List<Str> | len", defs);
+    assert_eq!(x, Ok(Value::Num(0.into())));
+}
+
 // ------ Let and Variables ----------------------------------------------------
 #[test]
 fn let_help_msg() {
@@ -819,6 +838,19 @@ fn nth_help_msg() {
         &x,
         r#"Help: `nth`
 --> shell:0
+ | ---- Input Type: <any> ----
+ | retrieves the nth item of a list
+ | 
+ | Usage:
+ |  => nth index
+ | 
+ | Examples:
+ |  get the first item of a list
+ |  => \ [1 2 3] | nth 0
+ | 
+ |  get the last item of a list
+ |  => \ [1 2 3] | nth {len | - 1}
+ | 
  | ---- Input Type: String ----
  | retrieves the nth character of a string
  | 
@@ -923,6 +955,15 @@ fn open_bom_test() {
     check_is_table(utf16be, exp.clone());
 }
 
+#[test]
+fn nth_list() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ [1 2 3] | nth 0", defs);
+    assert_eq!(x, Ok(Value::Num(1.into())));
+    let x = process_w_nil("\\ ['a' 'b' 'c'] | nth {len | - 1}", defs);
+    assert_eq!(x, Ok(Value::Str("c".into())));
+}
+
 // ------ Rand -----------------------------------------------------------------
 #[test]
 fn rand_help_msg() {
@@ -1053,6 +1094,183 @@ fn range_test() {
     );
 }
 
+// ------ To-num and To-bool --------------------------------------------------
+#[test]
+fn to_num_help_msg() {
+    let src = "to-num --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        r#"Help: `to-num`
+--> shell:0
+ | ---- Input Type: Duration ----
+ | convert a duration into a number
+ | the unit is specified with a flag and defaults to seconds
+ | 
+ | Usage:
+ |  => to-num
+ | 
+ | Flags:
+ |  --weeks: number of weeks
+ |  --days: number of days
+ |  --hours: number of hours
+ |  --mins: number of minutes
+ |  --secs: number of seconds (default)
+ |  --ms: number of milliseconds
+ | 
+ | Examples:
+ |  the number of days since the start of 2000
+ |  => now | - {\ '2000-01-01' | to-datetime} | to-num --days
+ | 
+ | ---- Input Type: String ----
+ | parse a string as a number
+ | surrounding whitespace, thousands separators, and currency symbols are ignored
+ | a trailing `%` divides the number by 100, and an amount in parentheses is negative
+ | 
+ | Usage:
+ |  => to-num
+ | 
+ | Flags:
+ |  --decimal-comma: use `,` as the decimal point and `.` as the thousands separator
+ | 
+ | Examples:
+ |  parse a currency amount
+ |  => \ '$1,234.50' | to-num
+ | 
+ |  parse a percentage
+ |  => \ '12.5%' | to-num
+ | 
+ |  parse a number using a decimal comma
+ |  => \ '1.234,5' | to-num --decimal-comma
+"#
+    );
+}
+
+#[test]
+fn to_num_testing() {
+    let defs = &Definitions::new();
+    let t = |s: &str| process_w_nil(&format!("\\ '{s}' | to-num"), defs);
+    let num = |n: f64| Ok(Value::Num(n.into()));
+
+    assert_eq!(t("123"), num(123.0));
+    assert_eq!(t(" -1.5 "), num(-1.5));
+    assert_eq!(t("1,234,567"), num(1234567.0));
+    assert_eq!(t("$1,234.50"), num(1234.5));
+    assert_eq!(t("-$5"), num(-5.0));
+    assert_eq!(t("$-5"), num(-5.0));
+    assert_eq!(t("(12)"), num(-12.0));
+    assert_eq!(t("€ 10"), num(10.0));
+    assert_eq!(t("10 €"), num(10.0));
+    assert_eq!(t("12.5%"), num(0.125));
+    assert_eq!(t("1e3"), num(1000.0));
+    assert_eq!(t(".5"), num(0.5));
+
+    // integers are exact
+    let x = process_w_nil("\\ '9007199254740993' | to-num | to-str", defs);
+    assert_eq!(x, Ok(Value::Str("9007199254740993".into())));
+
+    let x = process_w_nil("\\ '1.234,5' | to-num --decimal-comma", defs);
+    assert_eq!(x, num(1234.5));
+    let x = process_w_nil("\\ '€1.234' | to-num --decimal-comma", defs);
+    assert_eq!(x, num(1234.0));
+}
+
+#[test]
+fn to_num_err() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("\\ 'foo' | to-num", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: failed to parse `foo` as a number
+--> shell:10
+ | \\ 'foo' | to-num
+ |           ^^^^^^ within this block
+--> help: expecting a number such as `1234.5`, `$1,234`, or `12.5%`
+          a decimal comma can be used with the `--decimal-comma` flag
+"
+    );
+
+    // thousands separators must group digits in threes
+    for s in [
+        "", "-", "$", "1.2.3", "inf", "12%%", "1,5", "1,2,3", "1234,567", ",123",
+    ] {
+        let x = process_w_nil(&format!("\\ '{s}' | to-num"), defs);
+        assert!(x.is_err(), "expecting `{s}` to fail");
+    }
+}
+
+#[test]
+fn to_bool_testing() {
+    let defs = &Definitions::new();
+    let t = |s: &str| process_w_nil(&format!("\\ '{s}' | to-bool"), defs);
+
+    for s in ["true", "True", " TRUE ", "yes", "y", "1"] {
+        assert_eq!(t(s), Ok(Value::Bool(true)));
+    }
+    for s in ["false", "False", "no", "N", "0"] {
+        assert_eq!(t(s), Ok(Value::Bool(false)));
+    }
+
+    let x = t("maybe").unwrap_err().to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: failed to parse `maybe` as a boolean
+--> shell:12
+ | \\ 'maybe' | to-bool
+ |             ^^^^^^^ within this block
+--> help: expecting `true`, `false`, `yes`, `no`, `y`, `n`, `1`, or `0`
+"
+    );
+}
+
+// ------ To-list and To-table ------------------------------------------------
+#[test]
+fn to_list_table() {
+    let defs = &Definitions::new();
+    let nums = |xs: &[i32]| {
+        let items = xs.iter().map(|&x| Value::Num(x.into())).collect();
+        Ok(Value::List(List::new(Type::Num, items)))
+    };
+
+    assert_eq!(
+        process_w_table("to-list snd --Num", defs),
+        nums(&[3, 20, 100])
+    );
+    assert_eq!(
+        process_w_table("to-list:List<Num> first", defs),
+        nums(&[0, 1, -30])
+    );
+
+    let x = process_w_table("to-list 'Heading 3' --Num", defs)
+        .unwrap_err()
+        .to_string();
+    assert!(x.starts_with(
+        "Evaluation Error: table entry for [row:1,col:'Heading 3'] did not have expected type"
+    ));
+}
+
+#[test]
+fn to_table_list() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("\\ [1 2 3] | to-table", defs);
+    check_is_table(x, vec![vec![o("list")], vec![n(1)], vec![n(2)], vec![n(3)]]);
+
+    let x = process_w_nil("\\ ['a' 'b'] | to-table x", defs);
+    check_is_table(x, vec![vec![o("x")], vec![o("a")], vec![o("b")]]);
+
+    // round trip
+    let x = process_w_table("to-list snd --Num | to-table snd", defs);
+    check_is_table(
+        x,
+        vec![vec![o("snd")], vec![n(3)], vec![n(20)], vec![n(100)]],
+    );
+}
+
 // ------ To-str ---------------------------------------------------------------
 #[test]
 fn to_str_help_msg() {
@@ -1074,6 +1292,27 @@ fn to_str_help_msg() {
  | Usage:
  |  => to-str
  | 
+ | ---- Input Type: DateTime ----
+ | format the datetime as a string
+ | defaults to RFC 3339, such as `2022-03-04T12:30:00Z`
+ | formatting syntax can be found at <https://time-rs.github.io/book/api/format-description.html>
+ | 
+ | Usage:
+ |  => to-str [fmt]
+ | 
+ | Examples:
+ |  format the current date
+ |  => now | to-str '[day]/[month]/[year]'
+ | 
+ |  get the name of the current day
+ |  => now | to-str '[weekday]'
+ | 
+ | ---- Input Type: Duration ----
+ | format the duration as a string, such as `1d 2h 30m 4.5s`
+ | 
+ | Usage:
+ |  => to-str
+ | 
  | ---- Input Type: Number ----
  | format the number as a string
  | 
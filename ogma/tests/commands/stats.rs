@@ -0,0 +1,143 @@
+use super::*;
+
+// ------ Help -----------------------------------------------------------------
+#[test]
+fn sum_help_msg() {
+    let src = "sum --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `sum`
+--> shell:0
+ | ---- Input Type: Table ----
+ | return the sum of the numbers in a column
+ | nil entries are ignored, other non-number entries are an error
+ | 
+ | Usage:
+ |  => sum col-name
+ | 
+ | Flags:
+ |  --by: aggregate each `value` table of a grouped table, appending a column
+ | 
+ | Examples:
+ |  the total size of files
+ |  => ls | sum size
+ | 
+ |  the total carats of each cut
+ |  => open diamonds.csv | grp cut | sum --by carat
+"
+    );
+}
+
+#[test]
+fn pctl_help_msg() {
+    let src = "pctl --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `pctl`
+--> shell:0
+ | ---- Input Type: Table ----
+ | return the percentile of the numbers in a column
+ | the percentile is between 0 and 1, interpolating linearly between the closest ranks
+ | nil entries are ignored, other non-number entries are an error
+ | 
+ | Usage:
+ |  => pctl col-name percentile:Num
+ | 
+ | Flags:
+ |  --by: aggregate each `value` table of a grouped table, appending a column
+ | 
+ | Examples:
+ |  the 95th percentile of file sizes
+ |  => ls | pctl size 0.95
+ | 
+ |  the lower quartile price of each cut
+ |  => open diamonds.csv | grp cut | pctl --by price 0.25
+"
+    );
+}
+
+// ------ Aggregations ---------------------------------------------------------
+#[test]
+fn column_aggregations() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_table(src, defs);
+
+    assert_eq!(t("sum first"), Ok(Value::Num((-29).into())));
+    assert_eq!(t("sum snd"), Ok(Value::Num(123.into())));
+    assert_eq!(t("mean snd"), Ok(Value::Num(41.into())));
+    assert_eq!(t("median snd"), Ok(Value::Num(20.into())));
+    assert_eq!(t("min first"), Ok(Value::Num((-30).into())));
+    assert_eq!(t("max snd"), Ok(Value::Num(100.into())));
+    assert_eq!(t("count 'Heading 3'"), Ok(Value::Num(3.into())));
+    assert_eq!(t("var snd"), Ok(Value::Num(2683.into())));
+    assert_eq!(t("stdev snd"), Ok(Value::Num(2683f64.sqrt().into())));
+    assert_eq!(t("pctl snd 0.25"), Ok(Value::Num(11.5.into())));
+    assert_eq!(t("pctl snd 0.75"), Ok(Value::Num(60.into())));
+    assert_eq!(t("pctl snd 1"), Ok(Value::Num(100.into())));
+
+    // nils are ignored
+    assert_eq!(
+        t("append-row #n #n #n | count snd"),
+        Ok(Value::Num(3.into()))
+    );
+    assert_eq!(
+        t("append-row #n #n #n | mean snd"),
+        Ok(Value::Num(41.into()))
+    );
+}
+
+#[test]
+fn by_aggregations() {
+    let defs = &Definitions::new();
+    let x = process_w_table(
+        "grp-by { get first | >= 0 } | sum --by snd | mean --by first | pick key sum-snd mean-first",
+        defs,
+    );
+    let exp = vec![
+        vec![o("key"), o("sum-snd"), o("mean-first")],
+        vec![Entry::from(Value::Bool(false)), n(100), n(-30)],
+        vec![Entry::from(Value::Bool(true)), n(23), n(0.5)],
+    ];
+    check_is_table(x, exp);
+}
+
+#[test]
+fn aggregation_errs() {
+    let defs = &Definitions::new();
+    let x = process_w_table("take 1 | sum 'Heading 3'", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: table entry for [row:1,col:'Heading 3'] did not have expected type
+expected `Number`, found `String`
+--> shell:14
+ | take 1 | sum 'Heading 3'
+ |               ^^^^^^^^^
+--> help: column entries must have a matching type
+"
+    );
+
+    let x = process_w_table("pctl snd 95", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert_eq!(
+        &x,
+        "Evaluation Error: percentile must be between 0 and 1
+--> shell:9
+ | pctl snd 95
+ |          ^^ 95 is outside 0..=1
+--> help: for example, the 95th percentile is `0.95`
+"
+    );
+
+    let x = process_w_table("sum --by snd", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{}", x);
+    assert!(x.starts_with("Evaluation Error: header `value` not found in table"));
+}
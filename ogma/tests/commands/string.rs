@@ -0,0 +1,223 @@
+use super::*;
+
+// ------ Help -----------------------------------------------------------------
+#[test]
+fn pad_help_msg() {
+    let src = "pad --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `pad`
+--> shell:0
+ | ---- Input Type: String ----
+ | pad a string to a character width
+ | the padding defaults to a space and is added to the end of the string
+ | strings wider than the width are returned unchanged
+ | 
+ | Usage:
+ |  => pad width [char]
+ | 
+ | Flags:
+ |  --start: pad the start of the string
+ |  --end: pad the end of the string (default)
+ | 
+ | Examples:
+ |  pad a string to 10 characters
+ |  => \\ 'Hello' | pad 10
+ | 
+ |  pad a number with leading zeros
+ |  => \\ 42 | to-str | pad --start 5 '0'
+"
+    );
+}
+
+#[test]
+fn captures_help_msg() {
+    let src = "captures --help";
+    let x = print_help(src, &Definitions::new());
+    assert_eq!(
+        &x,
+        "Help: `captures`
+--> shell:0
+ | ---- Input Type: String ----
+ | return a list of the regular expression capture groups of the first match
+ | the first item is the whole match, groups which do not participate in the match are empty
+ | strings. the list is empty if there is no match
+ | 
+ | Usage:
+ |  => captures regex
+ | 
+ | Examples:
+ |  capture the number in a string
+ |  => \\ 'a1 b22 c333' | captures '[a-z](\\d+)' | nth 1
+ | 
+ |  capture the year and month of a date
+ |  => \\ '2022-03-04' | captures '(\\d{4})-(\\d{2})'
+"
+    );
+}
+
+// ------ String operations ----------------------------------------------------
+#[test]
+fn string_tests() {
+    let defs = &Definitions::new();
+    let s = |src| process_w_str(src, defs);
+    let st = |x: &str| Ok(Value::Str(x.into()));
+
+    assert_eq!(s("contains ell"), Ok(Value::Bool(true)));
+    assert_eq!(s("contains foo"), Ok(Value::Bool(false)));
+    assert_eq!(s("starts-with He"), Ok(Value::Bool(true)));
+    assert_eq!(s("starts-with lo"), Ok(Value::Bool(false)));
+    assert_eq!(s("ends-with lo"), Ok(Value::Bool(true)));
+    assert_eq!(s("ends-with He"), Ok(Value::Bool(false)));
+    assert_eq!(s("index-of l"), Ok(Value::Num(2.into())));
+    assert_eq!(s("index-of z"), Ok(Value::Num((-1).into())));
+    assert_eq!(
+        process_w_nil("\\ 'héllo' | index-of l", defs),
+        Ok(Value::Num(2.into()))
+    );
+
+    assert_eq!(s("upper"), st("HELLO"));
+    assert_eq!(s("lower"), st("hello"));
+    assert_eq!(s("replace l L"), st("HeLLo"));
+    assert_eq!(s("replace l ''"), st("Heo"));
+
+    let t = |src| process_w_nil(src, defs);
+    assert_eq!(t("\\ '  a b  ' | trim"), st("a b"));
+    assert_eq!(t("\\ '  a b  ' | trim --start"), st("a b  "));
+    assert_eq!(t("\\ '  a b  ' | trim --end"), st("  a b"));
+
+    assert_eq!(s("pad 7"), st("Hello  "));
+    assert_eq!(s("pad --start 7"), st("  Hello"));
+    assert_eq!(s("pad 7 '.'"), st("Hello.."));
+    assert_eq!(s("pad 3"), st("Hello"));
+    assert_eq!(t("\\ 42 | to-str | pad --start 5 '0'"), st("00042"));
+}
+
+#[test]
+fn fstring_tests() {
+    let defs = &Definitions::new();
+    let st = |x: &str| Ok(Value::Str(x.into()));
+
+    assert_eq!(
+        process_w_nil("\\ 3.14159 | let $x | \\ f'Total: {$x:[.2]} units'", defs),
+        st("Total: 3.14 units")
+    );
+    assert_eq!(
+        process_w_num("let $x | \\ f\"{$x} isn't {+ 1}\"", defs),
+        st("3 isn't 4")
+    );
+    // embedded expressions use the input
+    assert_eq!(
+        process_w_str("\\ f'{len} chars in {upper}'", defs),
+        st("5 chars in HELLO")
+    );
+    assert_eq!(process_w_nil("\\ f'{{escaped}}'", defs), st("{escaped}"));
+    assert_eq!(process_w_nil("\\ f''", defs), st(""));
+
+    // built into + and to-str
+    assert_eq!(
+        process_w_num("let $x | typify f'x{$x}'", defs),
+        st("{:Num \\ x |:Str + {:Str \\ $x:Num |:Num to-str }:Str }:Str")
+    );
+
+    // errors reference the interpolated string
+    let x = process_w_nil("\\ f'x {$y}'", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: variable `y` does not exist
+--> shell:8
+ | \\ f'x {$y}'
+ |         ^ `y` not in scope
+--> help: variables must be in scope
+          variables can be defined using the `let` command
+"
+    );
+}
+
+#[test]
+fn str_list(items: &[&str]) -> Result<Value> {
+    let items = items.iter().map(|&x| Value::Str(x.into())).collect();
+    Ok(Value::List(List::new(Type::Str, items)))
+}
+
+#[test]
+fn split_tests() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("\\ ' a b  c ' | split", defs);
+    assert_eq!(x, str_list(&["a", "b", "c"]));
+
+    let x = process_w_nil("\\ 'a,b,,c' | split ','", defs);
+    assert_eq!(x, str_list(&["a", "b", "", "c"]));
+
+    let x = process_w_nil("\\ 'a,b,,c' | split ',' | len", defs);
+    assert_eq!(x, Ok(Value::Num(4.into())));
+}
+
+#[test]
+fn regex_tests() {
+    let defs = &Definitions::new();
+    let t = |src| process_w_nil(src, defs);
+
+    assert_eq!(t("\\ 'abc123' | match '\\d+'"), Ok(Value::Bool(true)));
+    assert_eq!(t("\\ 'abc' | match '\\d+'"), Ok(Value::Bool(false)));
+    assert_eq!(t("\\ 'abc123' | match '^\\d+$'"), Ok(Value::Bool(false)));
+    // non-literal regex
+    assert_eq!(
+        t("\\ 'abc123' | match { \\ '[a-c]' | + '+' }"),
+        Ok(Value::Bool(true))
+    );
+
+    assert_eq!(
+        t("\\ 'Hello,    world!' | replace-re '\\s+' ' '"),
+        Ok(Value::Str("Hello, world!".into()))
+    );
+    assert_eq!(
+        t("\\ '04/03/2022' | replace-re '(\\d+)/(\\d+)/(\\d+)' '$3-$2-$1'"),
+        Ok(Value::Str("2022-03-04".into()))
+    );
+
+    let x = t("\\ 'a1 b22 c333' | captures '[a-z](\\d+)'");
+    assert_eq!(x, str_list(&["a1", "1"]));
+    let x = t("\\ 'a1 b22 c333' | captures '[a-z](\\d+)' | nth 1");
+    assert_eq!(x, Ok(Value::Str("1".into())));
+
+    let x = t("\\ '2023' | captures '(?P<year>\\d{4})(-(?P<month>\\d{2}))?'");
+    assert_eq!(x, str_list(&["2023", "2023", "", ""]));
+
+    let x = t("\\ 'abc' | captures '\\d+'");
+    assert_eq!(x, str_list(&[]));
+}
+
+#[test]
+fn string_errs() {
+    let defs = &Definitions::new();
+
+    let x = process_w_nil("\\ 'abc' | match 'a('", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Parsing Error: invalid regular expression: unclosed group
+--> shell:17
+ | \\ 'abc' | match 'a('
+ |                  ^^ invalid regex
+--> help: regex syntax can be found at
+          <https://docs.rs/regex/latest/regex/#syntax>
+"
+    );
+
+    let x = process_w_str("pad 7 ab", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: padding must be a single character
+--> shell:6
+ | pad 7 ab
+ |       ^^ this resolves to `ab`
+"
+    );
+}
@@ -339,3 +339,249 @@ fn field_does_not_exist() {
 "
     );
 }
+
+// ------ Generic Types --------------------------------------------------------
+fn generic_defs() -> Definitions {
+    let mut defs = Definitions::new();
+    for def in [
+        "def-ty Maybe<T> :: Just { v:T } | Nothing",
+        "def-ty Pair<A B> { a:A b:Maybe<B> }",
+        "def unwrap-or Option<Num> (default) { match Some $v None $default }",
+    ] {
+        process_definition(def, Location::Shell, None, &mut defs).unwrap();
+    }
+    defs
+}
+
+#[test]
+fn option_init_help_msg() {
+    let defs = &Definitions::new();
+    let x = process_w_nil("Option::Some --help", defs)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        &x,
+        "Help: `Option`
+--> shell:0
+ | ---- Input Type: <any> ----
+ | initialise a `Option<T>`
+ | 
+ | Usage:
+ |  => Option::Some v:T
+ |  => Option::None
+"
+    );
+}
+
+#[test]
+fn generic_type_construction() {
+    let defs = &generic_defs();
+    let x = |src| match process_w_nil(src, defs) {
+        Ok(Value::Ogma(x)) => (
+            x.ty().name().str().to_string(),
+            x.variant_idx(),
+            x.data().to_vec(),
+        ),
+        x => panic!("not right variant: {x:?}"),
+    };
+
+    assert_eq!(
+        x("Option::Some 3"),
+        ("Option<Num>".into(), 0, vec![Value::Num(3.into())])
+    );
+    assert_eq!(
+        x("Option::None:Option<Str>"),
+        ("Option<Str>".into(), 1, vec![])
+    );
+    assert_eq!(
+        x("Result::Err:Result<Num Str> 'oops'"),
+        ("Result<Num Str>".into(), 1, vec![Value::Str("oops".into())])
+    );
+    assert_eq!(
+        x("Maybe::Just { Option::Some 'a' }").0,
+        "Maybe<Option<Str>>"
+    );
+    assert_eq!(x("Pair 1 { Maybe::Just #t }").0, "Pair<Num Bool>");
+}
+
+#[test]
+fn generic_type_interactions() {
+    let defs = &generic_defs();
+    let x = |src| process_w_nil(src, defs);
+
+    assert_eq!(
+        x("Pair 1 { Maybe::Just 2 } | get a"),
+        Ok(Value::Num(1.into()))
+    );
+    assert_eq!(
+        x("Pair 1 { Maybe::Nothing:Maybe<Str> } | get b | match Just #t Nothing #f"),
+        Ok(Value::Bool(false))
+    );
+    assert_eq!(
+        x("Option::Some 3 | match Some { \\ $v | + 1 } None 0"),
+        Ok(Value::Num(4.into()))
+    );
+    assert_eq!(x("Option::Some 3 | unwrap-or 5"), Ok(Value::Num(3.into())));
+    assert_eq!(
+        x("Option::None:Option<Num> | unwrap-or 5"),
+        Ok(Value::Num(5.into()))
+    );
+    assert_eq!(
+        x("Option::Some 3 | = { Option::Some 3 }"),
+        Ok(Value::Bool(true))
+    );
+}
+
+#[test]
+fn generic_type_errs() {
+    let mut defs = generic_defs();
+
+    // type arguments can not be inferred
+    assert!(process_w_nil("Option::None", &defs).is_err());
+    // no `unwrap-or` for `Option<Str>`
+    assert!(process_w_nil("Option::Some 'a' | unwrap-or 5", &defs).is_err());
+
+    let x = process_definition("def-ty Foo { x:Option }", Location::Shell, None, &mut defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: type `Option` expects 1 type argument(s), found 0
+--> shell:15
+ | def-ty Foo { x:Option }
+ |                ^^^^^^ expecting 1 type argument(s)
+--> help: `Option` is defined as `Option<T>`
+"
+    );
+
+    let x = process_definition(
+        "def-ty Foo { x:Num<Str> }",
+        Location::Shell,
+        None,
+        &mut defs,
+    )
+    .unwrap_err()
+    .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: type `Num` expects 0 type argument(s), found 1
+--> shell:15
+ | def-ty Foo { x:Num<Str> }
+ |                ^^^ expecting 0 type argument(s)
+--> help: `Num` is not a generic type
+"
+    );
+
+    let x = process_definition(
+        "def-ty Option<A> :: Some { v:A }",
+        Location::Shell,
+        None,
+        &mut defs,
+    )
+    .unwrap_err()
+    .to_string();
+    assert!(x.starts_with("Definition Error: can not redefine type `Option`"));
+}
+
+#[test]
+fn generic_def_signatures() {
+    let mut defs = Definitions::new();
+    for def in [
+        "def unwrap-or<T> Option<T> (default:T) { match Some $v None $default }",
+        "def or-else<T> Option<T> (other:Option<T>) { match Some { Option::Some $v } None $other }",
+    ] {
+        process_definition(def, Location::Shell, None, &mut defs).unwrap();
+    }
+    let x = |src| process_w_nil(src, &defs);
+
+    assert_eq!(x("Option::Some 3 | unwrap-or 5"), Ok(Value::Num(3.into())));
+    assert_eq!(
+        x("Option::Some 'a' | unwrap-or 'b'"),
+        Ok(Value::Str("a".into()))
+    );
+    // the input type is instantiated from the arguments
+    assert_eq!(x("Option::None | unwrap-or 5"), Ok(Value::Num(5.into())));
+    assert_eq!(
+        x("Option::None | or-else { Option::Some 2 } | unwrap-or 0"),
+        Ok(Value::Num(2.into()))
+    );
+
+    let e = x("Option::Some 'a' | unwrap-or 5").unwrap_err().to_string();
+    println!("{e}");
+    assert!(e.starts_with("Semantics Error: type parameter `T` is bound to different types"));
+    assert!(e.contains("`T` is bound to `String` and `Number`"));
+
+    let e = x("\\ 3 | unwrap-or 5").unwrap_err().to_string();
+    println!("{e}");
+    assert!(e.starts_with("Semantics Error: `unwrap-or` does not support `Number` input data"));
+}
+
+// ------ Lists ----------------------------------------------------------------
+fn list(elem: Type, items: Vec<Value>) -> Value {
+    Value::List(List::new(elem, items))
+}
+
+fn nums(xs: &[i32]) -> Vec<Value> {
+    xs.iter().map(|&x| Value::Num(x.into())).collect()
+}
+
+#[test]
+fn list_construction() {
+    let defs = &Definitions::new();
+    let x = |src| process_w_num(src, defs);
+
+    assert_eq!(x("[1 2 3]"), Ok(list(Type::Num, nums(&[1, 2, 3]))));
+    assert_eq!(x("\\ [1 2 3]"), Ok(list(Type::Num, nums(&[1, 2, 3]))));
+    assert_eq!(x("[{+ 1} {* 2}]"), Ok(list(Type::Num, nums(&[4, 6]))));
+    assert_eq!(x("[]:List<Str>"), Ok(list(Type::Str, vec![])));
+    assert_eq!(
+        x("[[1 2] [3]]"),
+        Ok(list(
+            Type::list(Type::Num),
+            vec![list(Type::Num, nums(&[1, 2])), list(Type::Num, nums(&[3]))]
+        ))
+    );
+    assert_eq!(
+        x("[[1 2] []:List<Num>] | nth 1 | len"),
+        Ok(Value::Num(0.into()))
+    );
+}
+
+#[test]
+fn list_errs() {
+    let defs = &Definitions::new();
+
+    // items must have the same type
+    assert!(process_w_nil("[1 'a']", defs).is_err());
+
+    let x = process_w_nil("[]", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert!(x.contains("empty lists need a type annotation, such as `[]:List<Num>`"));
+
+    let x = process_w_nil("\\ 3 | nth 0", defs).unwrap_err().to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Semantics Error: `nth` does not support `Number` input data
+--> shell:6
+ | \\ 3 | nth 0
+ |       ^^^
+--> help: `nth` supports `List` input, use `nth --help` to view the supported input types
+"
+    );
+
+    let x = process_w_nil("\\ [1 2] | nth 2", defs)
+        .unwrap_err()
+        .to_string();
+    println!("{x}");
+    assert_eq!(
+        &x,
+        "Evaluation Error: index is outside list bounds
+--> shell:14
+ | \\ [1 2] | nth 2
+ |               ^ this resolves to `2`
+"
+    );
+}
@@ -276,3 +276,22 @@ fn _11_0_no_recursion_03() {
     let x = process("gcd 255 25", defs);
     assert_eq!(x, Ok(Value::Num(5u8.into())));
 }
+
+#[test]
+fn _11_1_recursive_definitions_01() {
+    let defs = &mut Definitions::new();
+
+    process_def(
+        r#"def-rec fact:Num Num () { if {<= 1} 1 { * {- 1 | fact} } }"#,
+        defs,
+    );
+    process_def(
+        r#"def-rec fib:Num Num () { if {<= 1} #i { let $n | - 1 | fib | + {\ $n | - 2 | fib} } }"#,
+        defs,
+    );
+
+    let x = process("\\ 6 | fact", defs);
+    assert_eq!(x, Ok(Value::Num(720.into())));
+    let x = process("\\ 12 | fib", defs);
+    assert_eq!(x, Ok(Value::Num(144u8.into())));
+}